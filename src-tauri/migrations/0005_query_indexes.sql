@@ -0,0 +1,4 @@
+CREATE INDEX IF NOT EXISTS idx_entries_source_read ON entries(source_id, is_read);
+CREATE INDEX IF NOT EXISTS idx_entries_published_at ON entries(published_at);
+CREATE INDEX IF NOT EXISTS idx_entries_link ON entries(link);
+CREATE INDEX IF NOT EXISTS idx_sources_active_synced ON sources(is_active, last_synced_at);
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS entries_archive (
+  id INTEGER PRIMARY KEY,
+  source_id INTEGER NOT NULL,
+  guid TEXT,
+  link TEXT NOT NULL,
+  title TEXT NOT NULL,
+  translated_title TEXT,
+  summary TEXT,
+  content TEXT,
+  published_at TEXT,
+  is_read INTEGER NOT NULL DEFAULT 0,
+  is_starred INTEGER NOT NULL DEFAULT 0,
+  created_at TEXT NOT NULL,
+  archived_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE(source_id, link),
+  FOREIGN KEY(source_id) REFERENCES sources(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_entries_archive_published_at ON entries_archive(published_at);
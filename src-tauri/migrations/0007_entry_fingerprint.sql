@@ -0,0 +1,2 @@
+ALTER TABLE entries ADD COLUMN fingerprint TEXT;
+ALTER TABLE entries_archive ADD COLUMN fingerprint TEXT;
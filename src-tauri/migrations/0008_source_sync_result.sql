@@ -0,0 +1,3 @@
+ALTER TABLE sources ADD COLUMN last_sync_status TEXT;
+ALTER TABLE sources ADD COLUMN last_sync_upserted INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sources ADD COLUMN last_error TEXT;
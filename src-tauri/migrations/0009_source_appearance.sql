@@ -0,0 +1,2 @@
+ALTER TABLE sources ADD COLUMN color TEXT;
+ALTER TABLE sources ADD COLUMN custom_icon TEXT;
@@ -0,0 +1,3 @@
+ALTER TABLE sources ADD COLUMN last_http_status INTEGER;
+ALTER TABLE sources ADD COLUMN gone_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sources ADD COLUMN parked_domain INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE sources ADD COLUMN replacement_feed_url TEXT;
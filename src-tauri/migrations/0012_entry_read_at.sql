@@ -0,0 +1,4 @@
+ALTER TABLE entries ADD COLUMN read_at TEXT;
+ALTER TABLE entries_archive ADD COLUMN read_at TEXT;
+CREATE INDEX IF NOT EXISTS idx_entries_read_at ON entries(read_at);
+CREATE INDEX IF NOT EXISTS idx_entries_archive_read_at ON entries_archive(read_at);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS read_operations (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  kind TEXT NOT NULL,
+  entry_count INTEGER NOT NULL DEFAULT 0,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS read_operation_entries (
+  operation_id INTEGER NOT NULL,
+  entry_id INTEGER NOT NULL,
+  previous_is_read INTEGER NOT NULL,
+  previous_read_at TEXT,
+  PRIMARY KEY (operation_id, entry_id)
+);
@@ -0,0 +1,13 @@
+ALTER TABLE sources ADD COLUMN notify_mode TEXT NOT NULL DEFAULT 'never';
+ALTER TABLE sources ADD COLUMN notify_keywords TEXT;
+
+CREATE TABLE IF NOT EXISTS pending_notifications (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  source_id INTEGER NOT NULL,
+  entry_id INTEGER NOT NULL,
+  matched_keyword TEXT,
+  delivered_at TEXT,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE (entry_id)
+);
+CREATE INDEX IF NOT EXISTS idx_pending_notifications_delivered ON pending_notifications(delivered_at);
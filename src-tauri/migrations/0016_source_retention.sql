@@ -0,0 +1,2 @@
+ALTER TABLE sources ADD COLUMN retention_days INTEGER;
+ALTER TABLE sources ADD COLUMN retention_max_entries INTEGER;
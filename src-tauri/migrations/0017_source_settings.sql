@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS source_settings (
+  source_id INTEGER PRIMARY KEY,
+  sync_interval_secs INTEGER,
+  timeout_secs INTEGER,
+  retry_count INTEGER,
+  translate_titles INTEGER,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(source_id) REFERENCES sources(id) ON DELETE CASCADE
+);
@@ -0,0 +1,12 @@
+ALTER TABLE source_settings ADD COLUMN open_externally INTEGER;
+
+CREATE TABLE IF NOT EXISTS entry_open_events (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  entry_id INTEGER NOT NULL,
+  source_id INTEGER NOT NULL,
+  external INTEGER NOT NULL,
+  opened_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(source_id) REFERENCES sources(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_entry_open_events_entry ON entry_open_events(entry_id, opened_at DESC);
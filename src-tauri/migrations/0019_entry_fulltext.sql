@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS entry_fulltext (
+  entry_id INTEGER PRIMARY KEY,
+  url TEXT NOT NULL,
+  title TEXT,
+  content_html BLOB NOT NULL,
+  text_length INTEGER NOT NULL DEFAULT 0,
+  fetched_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
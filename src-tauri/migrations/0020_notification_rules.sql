@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS notification_rules (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  keywords TEXT NOT NULL DEFAULT '[]',
+  source_id INTEGER,
+  category TEXT,
+  is_enabled INTEGER NOT NULL DEFAULT 1,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(source_id) REFERENCES sources(id) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+ALTER TABLE sources ADD COLUMN auto_disabled_reason TEXT;
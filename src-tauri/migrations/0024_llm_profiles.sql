@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS llm_profiles (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL UNIQUE,
+  config TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS llm_task_profiles (
+  task TEXT PRIMARY KEY,
+  profile_id INTEGER NOT NULL,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(profile_id) REFERENCES llm_profiles(id) ON DELETE CASCADE
+);
+
+INSERT INTO llm_profiles (name, config)
+SELECT 'default', value FROM app_settings WHERE key = 'llm_config';
+
+DELETE FROM app_settings WHERE key = 'llm_config';
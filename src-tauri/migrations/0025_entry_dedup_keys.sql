@@ -0,0 +1,36 @@
+ALTER TABLE entries ADD COLUMN dedup_key TEXT;
+
+UPDATE entries
+SET is_read = merged.is_read,
+    is_starred = merged.is_starred,
+    read_at = merged.read_at
+FROM (
+  SELECT MIN(id) AS keep_id, MAX(is_read) AS is_read, MAX(is_starred) AS is_starred, MAX(read_at) AS read_at
+  FROM entries
+  WHERE guid IS NOT NULL AND guid NOT IN ('', 'unknown')
+  GROUP BY source_id, guid
+  HAVING COUNT(*) > 1
+) AS merged
+WHERE entries.id = merged.keep_id;
+
+DELETE FROM entries
+WHERE guid IS NOT NULL
+  AND guid NOT IN ('', 'unknown')
+  AND id NOT IN (
+    SELECT MIN(id) FROM entries
+    WHERE guid IS NOT NULL AND guid NOT IN ('', 'unknown')
+    GROUP BY source_id, guid
+  );
+
+DELETE FROM pending_notifications
+WHERE entry_id NOT IN (SELECT id FROM entries)
+  AND entry_id NOT IN (SELECT id FROM entries_archive);
+
+DELETE FROM entry_fulltext
+WHERE entry_id NOT IN (SELECT id FROM entries)
+  AND entry_id NOT IN (SELECT id FROM entries_archive);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_entries_source_dedup_key
+  ON entries(source_id, dedup_key) WHERE dedup_key IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_entries_source_guid
+  ON entries(source_id, guid) WHERE guid IS NOT NULL AND guid NOT IN ('', 'unknown');
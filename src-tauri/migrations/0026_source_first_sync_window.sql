@@ -0,0 +1 @@
+ALTER TABLE source_settings ADD COLUMN first_sync_max_age_days INTEGER;
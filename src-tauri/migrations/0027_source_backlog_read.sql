@@ -0,0 +1 @@
+ALTER TABLE sources ADD COLUMN mark_backlog_read INTEGER NOT NULL DEFAULT 0;
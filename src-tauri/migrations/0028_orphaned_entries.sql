@@ -0,0 +1,4 @@
+ALTER TABLE entries ADD COLUMN orphaned_from_feed_url TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_entries_orphaned_from
+  ON entries(orphaned_from_feed_url) WHERE orphaned_from_feed_url IS NOT NULL;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS idx_entries_source_counts ON entries(source_id, is_read, is_starred);
@@ -0,0 +1,80 @@
+CREATE TABLE entries_rebuilt (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  source_id INTEGER NOT NULL,
+  guid TEXT,
+  link TEXT NOT NULL,
+  title TEXT NOT NULL,
+  summary TEXT,
+  content TEXT,
+  published_at TEXT,
+  is_read INTEGER NOT NULL DEFAULT 0,
+  is_starred INTEGER NOT NULL DEFAULT 0,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  translated_title TEXT,
+  fingerprint TEXT,
+  read_at TEXT,
+  dedup_key TEXT,
+  orphaned_from_feed_url TEXT,
+  FOREIGN KEY(source_id) REFERENCES sources(id) ON DELETE CASCADE
+);
+
+INSERT INTO entries_rebuilt (
+  id, source_id, guid, link, title, summary, content, published_at, is_read, is_starred, created_at,
+  translated_title, fingerprint, read_at, dedup_key, orphaned_from_feed_url
+)
+SELECT
+  id, source_id, guid, link, title, summary, content, published_at, is_read, is_starred, created_at,
+  translated_title, fingerprint, read_at, dedup_key, orphaned_from_feed_url
+FROM entries;
+
+DROP TABLE entries;
+ALTER TABLE entries_rebuilt RENAME TO entries;
+
+CREATE INDEX IF NOT EXISTS idx_entries_source_read ON entries(source_id, is_read);
+CREATE INDEX IF NOT EXISTS idx_entries_published_at ON entries(published_at);
+CREATE INDEX IF NOT EXISTS idx_entries_link ON entries(link);
+CREATE INDEX IF NOT EXISTS idx_entries_read_at ON entries(read_at);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_entries_source_dedup_key
+  ON entries(source_id, dedup_key) WHERE dedup_key IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_entries_source_guid
+  ON entries(source_id, guid) WHERE guid IS NOT NULL AND guid NOT IN ('', 'unknown');
+CREATE INDEX IF NOT EXISTS idx_entries_orphaned_from
+  ON entries(orphaned_from_feed_url) WHERE orphaned_from_feed_url IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_entries_source_counts ON entries(source_id, is_read, is_starred);
+
+CREATE TABLE entries_archive_rebuilt (
+  id INTEGER PRIMARY KEY,
+  source_id INTEGER NOT NULL,
+  guid TEXT,
+  link TEXT NOT NULL,
+  title TEXT NOT NULL,
+  translated_title TEXT,
+  summary TEXT,
+  content TEXT,
+  published_at TEXT,
+  is_read INTEGER NOT NULL DEFAULT 0,
+  is_starred INTEGER NOT NULL DEFAULT 0,
+  created_at TEXT NOT NULL,
+  archived_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  fingerprint TEXT,
+  read_at TEXT,
+  dedup_key TEXT,
+  FOREIGN KEY(source_id) REFERENCES sources(id) ON DELETE CASCADE
+);
+
+INSERT INTO entries_archive_rebuilt (
+  id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read,
+  is_starred, created_at, archived_at, fingerprint, read_at
+)
+SELECT
+  id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read,
+  is_starred, created_at, archived_at, fingerprint, read_at
+FROM entries_archive;
+
+DROP TABLE entries_archive;
+ALTER TABLE entries_archive_rebuilt RENAME TO entries_archive;
+
+CREATE INDEX IF NOT EXISTS idx_entries_archive_published_at ON entries_archive(published_at);
+CREATE INDEX IF NOT EXISTS idx_entries_archive_read_at ON entries_archive(read_at);
+CREATE INDEX IF NOT EXISTS idx_entries_archive_source_dedup_key
+  ON entries_archive(source_id, dedup_key);
@@ -0,0 +1,6 @@
+CREATE INDEX IF NOT EXISTS idx_entries_listing_order
+  ON entries(COALESCE(published_at, created_at), id);
+CREATE INDEX IF NOT EXISTS idx_entries_unread_order
+  ON entries(COALESCE(published_at, created_at), id) WHERE is_read = 0;
+CREATE INDEX IF NOT EXISTS idx_entries_starred_order
+  ON entries(COALESCE(published_at, created_at), id) WHERE is_starred = 1;
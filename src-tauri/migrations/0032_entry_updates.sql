@@ -0,0 +1,4 @@
+ALTER TABLE entries ADD COLUMN updated_at TEXT;
+ALTER TABLE entries ADD COLUMN is_updated INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE entries_archive ADD COLUMN updated_at TEXT;
+ALTER TABLE entries_archive ADD COLUMN is_updated INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+ALTER TABLE entries ADD COLUMN canonical_link TEXT;
+ALTER TABLE entries_archive ADD COLUMN canonical_link TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_entries_source_canonical_link
+  ON entries(source_id, canonical_link);
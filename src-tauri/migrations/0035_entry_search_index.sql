@@ -0,0 +1,42 @@
+CREATE VIRTUAL TABLE IF NOT EXISTS entry_search USING fts5(
+  title,
+  body,
+  pinyin,
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TABLE IF NOT EXISTS entry_search_queue (
+  entry_id INTEGER PRIMARY KEY
+);
+
+INSERT OR IGNORE INTO entry_search_queue (entry_id)
+SELECT id FROM entries
+UNION
+SELECT id FROM entries_archive;
+
+CREATE TRIGGER IF NOT EXISTS entries_search_insert AFTER INSERT ON entries
+BEGIN
+  INSERT OR IGNORE INTO entry_search_queue (entry_id) VALUES (new.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS entries_search_update AFTER UPDATE OF title, translated_title, summary ON entries
+BEGIN
+  INSERT OR IGNORE INTO entry_search_queue (entry_id) VALUES (new.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS entries_search_delete AFTER DELETE ON entries
+WHEN NOT EXISTS (SELECT 1 FROM entries_archive WHERE id = old.id)
+BEGIN
+  DELETE FROM entry_search WHERE rowid = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS entries_archive_search_insert AFTER INSERT ON entries_archive
+BEGIN
+  INSERT OR IGNORE INTO entry_search_queue (entry_id) VALUES (new.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS entries_archive_search_delete AFTER DELETE ON entries_archive
+WHEN NOT EXISTS (SELECT 1 FROM entries WHERE id = old.id)
+BEGIN
+  DELETE FROM entry_search WHERE rowid = old.id;
+END;
@@ -0,0 +1,4 @@
+ALTER TABLE sources ADD COLUMN feed_ttl_minutes INTEGER;
+ALTER TABLE sources ADD COLUMN feed_skip_hours TEXT;
+ALTER TABLE sources ADD COLUMN poll_interval_secs INTEGER;
+ALTER TABLE sources ADD COLUMN next_poll_at TEXT;
@@ -0,0 +1 @@
+ALTER TABLE llm_cache ADD COLUMN key_version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1 @@
+ALTER TABLE sources ADD COLUMN first_sync_cutoff TEXT;
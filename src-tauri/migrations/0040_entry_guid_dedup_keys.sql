@@ -0,0 +1,7 @@
+UPDATE entries
+SET canonical_link = NULL
+WHERE guid LIKE 'http%' AND orphaned_from_feed_url IS NULL;
+
+UPDATE entries_archive
+SET canonical_link = NULL
+WHERE guid LIKE 'http%';
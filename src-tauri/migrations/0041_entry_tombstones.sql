@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS entry_tombstones (
+  source_id INTEGER NOT NULL,
+  dedup_key TEXT NOT NULL,
+  fingerprint TEXT,
+  pruned_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (source_id, dedup_key),
+  FOREIGN KEY(source_id) REFERENCES sources(id) ON DELETE CASCADE
+);
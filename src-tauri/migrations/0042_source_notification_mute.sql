@@ -0,0 +1 @@
+ALTER TABLE sources ADD COLUMN notify_muted INTEGER NOT NULL DEFAULT 0;
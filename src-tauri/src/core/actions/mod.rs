@@ -0,0 +1,1131 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgKind {
+    String,
+    Integer,
+    Boolean,
+    Object,
+    IntegerList,
+    ObjectList,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ActionArg {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ActionDescriptor {
+    pub name: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+    pub args: &'static [ActionArg],
+    pub destructive: bool,
+}
+
+const fn arg(name: &'static str, kind: ArgKind, required: bool) -> ActionArg {
+    ActionArg {
+        name,
+        kind,
+        required,
+    }
+}
+
+const fn action(
+    name: &'static str,
+    category: &'static str,
+    description: &'static str,
+    args: &'static [ActionArg],
+    destructive: bool,
+) -> ActionDescriptor {
+    ActionDescriptor {
+        name,
+        category,
+        description,
+        args,
+        destructive,
+    }
+}
+
+pub const ACTIONS: &[ActionDescriptor] = &[
+    action(
+        "app_health",
+        "system",
+        "Report backend service health",
+        &[],
+        false,
+    ),
+    action(
+        "list_actions",
+        "system",
+        "List available backend actions",
+        &[],
+        false,
+    ),
+    action(
+        "get_network_status",
+        "system",
+        "Show offline mode and queued requests",
+        &[],
+        false,
+    ),
+    action(
+        "set_offline_mode",
+        "system",
+        "Pause or resume all network activity",
+        &[arg("offline", ArgKind::Boolean, true)],
+        false,
+    ),
+    action(
+        "list_sources",
+        "sources",
+        "List all subscriptions",
+        &[],
+        false,
+    ),
+    action(
+        "search_sources",
+        "sources",
+        "Search subscriptions by keyword and filters",
+        &[
+            arg("query", ArgKind::String, false),
+            arg("filters", ArgKind::Object, false),
+        ],
+        false,
+    ),
+    action(
+        "get_source_details",
+        "sources",
+        "Show details and stats for a subscription",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "get_source_sync_history",
+        "sources",
+        "List recent sync attempts for a source with status, HTTP code and error",
+        &[
+            arg("sourceId", ArgKind::Integer, true),
+            arg("limit", ArgKind::Integer, false),
+        ],
+        false,
+    ),
+    action(
+        "list_failing_sources",
+        "sources",
+        "List sources with failures, their last error and when they last failed",
+        &[arg("limit", ArgKind::Integer, false)],
+        false,
+    ),
+    action(
+        "list_problem_sources",
+        "sources",
+        "List failing sources grouped by problem class with a suggested remediation",
+        &[],
+        false,
+    ),
+    action(
+        "upsert_source",
+        "sources",
+        "Add or update a subscription",
+        &[arg("request", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "delete_source",
+        "sources",
+        "Delete a subscription and its entries, keeping starred ones",
+        &[arg("id", ArgKind::Integer, true)],
+        true,
+    ),
+    action(
+        "set_sources_active",
+        "sources",
+        "Enable or disable subscriptions",
+        &[
+            arg("sourceIds", ArgKind::IntegerList, true),
+            arg("isActive", ArgKind::Boolean, true),
+        ],
+        false,
+    ),
+    action(
+        "reactivate_source",
+        "sources",
+        "Re-enable a source and clear its failure count and backoff",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "reset_source_failures",
+        "sources",
+        "Clear failure counts and re-enable sources that were disabled after repeated failures",
+        &[arg("sourceIds", ArgKind::IntegerList, true)],
+        false,
+    ),
+    action(
+        "get_stale_sources",
+        "sources",
+        "List stale or broken subscriptions",
+        &[arg("thresholdDays", ArgKind::Integer, false)],
+        false,
+    ),
+    action(
+        "unsubscribe_sources",
+        "sources",
+        "Unsubscribe from several subscriptions and delete their entries",
+        &[arg("sourceIds", ArgKind::IntegerList, true)],
+        true,
+    ),
+    action(
+        "list_orphaned_entries",
+        "sources",
+        "List starred entries kept after their subscription was deleted",
+        &[],
+        false,
+    ),
+    action(
+        "rehome_orphaned_entries",
+        "sources",
+        "Move kept starred entries back under a re-added subscription",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "discover_feeds",
+        "sources",
+        "Find feed URLs advertised by or common to a website",
+        &[arg("url", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "probe_feed",
+        "sources",
+        "Fetch a feed URL and report redirects, headers, detected format and parsed entries",
+        &[arg("url", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "rediscover_source_feed",
+        "sources",
+        "Look for a moved feed URL",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "suggest_source_metadata",
+        "sources",
+        "Ask the LLM for a display title and an existing category for a feed URL",
+        &[arg("url", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "accept_feed_replacement",
+        "sources",
+        "Replace a subscription's feed URL",
+        &[
+            arg("sourceId", ArgKind::Integer, true),
+            arg("feedUrl", ArgKind::String, true),
+        ],
+        true,
+    ),
+    action(
+        "dismiss_feed_replacement",
+        "sources",
+        "Dismiss a suggested feed replacement",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "set_source_color",
+        "sources",
+        "Set a subscription color",
+        &[
+            arg("sourceId", ArgKind::Integer, true),
+            arg("color", ArgKind::String, false),
+        ],
+        false,
+    ),
+    action(
+        "set_source_custom_icon",
+        "sources",
+        "Set a subscription icon",
+        &[
+            arg("sourceId", ArgKind::Integer, true),
+            arg("customIcon", ArgKind::String, false),
+        ],
+        false,
+    ),
+    action(
+        "recommend_sources",
+        "discovery",
+        "Recommend new subscriptions",
+        &[arg("limit", ArgKind::Integer, false)],
+        false,
+    ),
+    action(
+        "run_source_discovery",
+        "discovery",
+        "Discover feeds from subscribed sites",
+        &[],
+        false,
+    ),
+    action(
+        "get_discovery_suggestions",
+        "discovery",
+        "Show discovered feed suggestions",
+        &[],
+        false,
+    ),
+    action(
+        "list_starter_packs",
+        "discovery",
+        "List starter packs",
+        &[],
+        false,
+    ),
+    action(
+        "preview_starter_pack",
+        "discovery",
+        "Preview a starter pack import",
+        &[arg("id", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "import_starter_pack",
+        "discovery",
+        "Import a starter pack",
+        &[arg("id", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "preview_import",
+        "import_export",
+        "Preview an OPML or URL list import",
+        &[arg("request", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "import_sources",
+        "import_export",
+        "Import subscriptions",
+        &[arg("request", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "export_opml",
+        "import_export",
+        "Export subscriptions as OPML",
+        &[
+            arg("path", ArgKind::String, true),
+            arg("request", ArgKind::Object, false),
+        ],
+        false,
+    ),
+    action(
+        "get_snapshot_target",
+        "backup",
+        "Read the WebDAV or S3 location used for encrypted profile snapshots",
+        &[],
+        false,
+    ),
+    action(
+        "save_snapshot_target",
+        "backup",
+        "Configure the WebDAV or S3 location for encrypted profile snapshots",
+        &[arg("target", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "push_snapshot",
+        "backup",
+        "Upload an encrypted snapshot of subscriptions and read state",
+        &[arg("passphrase", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "pull_snapshot",
+        "backup",
+        "Download an encrypted snapshot and merge its subscriptions and read state",
+        &[arg("passphrase", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "export_starred_as_feed",
+        "import_export",
+        "Export starred entries as an RSS feed",
+        &[
+            arg("path", ArgKind::String, true),
+            arg("title", ArgKind::String, false),
+        ],
+        false,
+    ),
+    action(
+        "render_entry",
+        "entries",
+        "Render an entry to a PDF or PNG file for sharing",
+        &[
+            arg("entryId", ArgKind::Integer, true),
+            arg("format", ArgKind::String, true),
+            arg("path", ArgKind::String, true),
+            arg("includeSummary", ArgKind::Boolean, false),
+        ],
+        false,
+    ),
+    action(
+        "get_onboarding_state",
+        "onboarding",
+        "Show first-run onboarding progress",
+        &[],
+        false,
+    ),
+    action(
+        "onboarding_import",
+        "onboarding",
+        "Import starter packs or an OPML file during onboarding",
+        &[arg("request", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "run_onboarding_sync",
+        "onboarding",
+        "Run the first sync with progress events",
+        &[],
+        false,
+    ),
+    action(
+        "complete_onboarding",
+        "onboarding",
+        "Mark onboarding as finished",
+        &[],
+        false,
+    ),
+    action(
+        "list_entries",
+        "entries",
+        "List entries one keyset page at a time, without article content",
+        &[arg("request", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "list_view_entries",
+        "entries",
+        "List entries in a system view: all, today, unread or starred",
+        &[
+            arg("view", ArgKind::String, true),
+            arg("cursor", ArgKind::String, false),
+            arg("limit", ArgKind::Integer, false),
+        ],
+        false,
+    ),
+    action(
+        "get_entry",
+        "entries",
+        "Get one entry including its full content",
+        &[arg("entryId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "get_search_pinyin",
+        "entries",
+        "Show whether search terms also match the pinyin of Chinese titles",
+        &[],
+        false,
+    ),
+    action(
+        "set_search_pinyin",
+        "entries",
+        "Let Latin search terms match Chinese titles by full pinyin or pinyin initials",
+        &[arg("enabled", ArgKind::Boolean, true)],
+        false,
+    ),
+    action(
+        "cancel_query",
+        "entries",
+        "Cancel the running entry query started with a query key",
+        &[arg("queryKey", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "start_triage_session",
+        "entries",
+        "Snapshot a filtered entry list for keyboard triage",
+        &[arg("filter", ArgKind::Object, false)],
+        false,
+    ),
+    action(
+        "triage_next",
+        "entries",
+        "Move the triage cursor by an offset",
+        &[
+            arg("sessionId", ArgKind::String, true),
+            arg("offset", ArgKind::Integer, false),
+        ],
+        false,
+    ),
+    action(
+        "triage_action",
+        "entries",
+        "Apply read, unread, star, unstar or skip to the current triage entry and advance",
+        &[
+            arg("sessionId", ArgKind::String, true),
+            arg("action", ArgKind::String, true),
+        ],
+        false,
+    ),
+    action(
+        "end_triage_session",
+        "entries",
+        "Discard a triage session",
+        &[arg("sessionId", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "get_entry_date_histogram",
+        "entries",
+        "Count entries per day for grouping and heatmaps",
+        &[arg("filter", ArgKind::Object, false)],
+        false,
+    ),
+    action(
+        "get_trending_terms",
+        "entries",
+        "Show trending keywords from recent titles",
+        &[
+            arg("days", ArgKind::Integer, false),
+            arg("limit", ArgKind::Integer, false),
+        ],
+        false,
+    ),
+    action(
+        "get_catchup_selection",
+        "entries",
+        "Pick unread entries that fit in the given minutes by read time and importance",
+        &[arg("minutesAvailable", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "skip_entries",
+        "entries",
+        "Leave unread entries out of future catch-up selections",
+        &[arg("entryIds", ArgKind::IntegerList, true)],
+        false,
+    ),
+    action(
+        "reset_skipped_entries",
+        "entries",
+        "Return every skipped entry to the catch-up queue",
+        &[],
+        false,
+    ),
+    action(
+        "mark_entry_read",
+        "entries",
+        "Mark an entry read or unread",
+        &[
+            arg("entryId", ArgKind::Integer, true),
+            arg("isRead", ArgKind::Boolean, true),
+        ],
+        false,
+    ),
+    action(
+        "undo_last_read_operation",
+        "entries",
+        "Undo the last bulk mark-read",
+        &[],
+        false,
+    ),
+    action(
+        "list_recently_read",
+        "entries",
+        "Show recently read entries",
+        &[arg("limit", ArgKind::Integer, false)],
+        false,
+    ),
+    action(
+        "mark_entries_read_up_to",
+        "entries",
+        "Mark entries read down to the last one seen",
+        &[
+            arg("filter", ArgKind::Object, false),
+            arg("lastSeenEntryId", ArgKind::Integer, true),
+        ],
+        false,
+    ),
+    action(
+        "report_entries_scrolled_past",
+        "entries",
+        "Mark entries read up to a scroll anchor when the read policy is on_scroll",
+        &[
+            arg("filter", ArgKind::Object, false),
+            arg("lastSeenEntryId", ArgKind::Integer, true),
+        ],
+        false,
+    ),
+    action(
+        "mark_all_read",
+        "entries",
+        "Mark every unread entry of a source as read",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "mark_category_read",
+        "entries",
+        "Mark every unread entry in a category as read",
+        &[arg("category", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "mark_read_older_than",
+        "entries",
+        "Mark unread entries published more than a number of days ago as read",
+        &[arg("days", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "get_flagged_entry_protection",
+        "entries",
+        "Show whether starred entries are kept out of bulk operations",
+        &[],
+        false,
+    ),
+    action(
+        "set_flagged_entry_protection",
+        "entries",
+        "Keep starred entries out of bulk mark-read and archiving",
+        &[arg("enabled", ArgKind::Boolean, true)],
+        false,
+    ),
+    action(
+        "fetch_full_content",
+        "entries",
+        "Extract and cache the readable article behind an entry link",
+        &[
+            arg("entryId", ArgKind::Integer, true),
+            arg("refresh", ArgKind::Boolean, false),
+        ],
+        false,
+    ),
+    action(
+        "open_entry_external",
+        "entries",
+        "Open an entry link using its source preference and mark it read",
+        &[arg("entryId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "set_entry_starred",
+        "entries",
+        "Star or unstar an entry",
+        &[
+            arg("entryId", ArgKind::Integer, true),
+            arg("isStarred", ArgKind::Boolean, true),
+        ],
+        false,
+    ),
+    action(
+        "get_counts",
+        "entries",
+        "Unread, starred and total entry counts per source and category",
+        &[],
+        false,
+    ),
+    action(
+        "list_starred_entries",
+        "entries",
+        "List starred entries one page at a time",
+        &[
+            arg("limit", ArgKind::Integer, false),
+            arg("offset", ArgKind::Integer, false),
+        ],
+        false,
+    ),
+    action(
+        "get_source_settings",
+        "sources",
+        "Show a source's sync interval, timeout, retry and translation overrides",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "list_source_settings",
+        "sources",
+        "List every source that has sync or translation overrides",
+        &[],
+        false,
+    ),
+    action(
+        "set_source_settings",
+        "sources",
+        "Override sync interval, timeout, retry count, title translation or first-sync age window for a source",
+        &[arg("settings", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "get_first_sync_max_age_days",
+        "sources",
+        "Show how many days back the first sync of a new source imports",
+        &[],
+        false,
+    ),
+    action(
+        "set_first_sync_max_age_days",
+        "sources",
+        "Skip entries older than N days when a source syncs for the first time (0 keeps all)",
+        &[arg("days", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "get_source_notifications",
+        "sources",
+        "Read the notification preference of a source",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "set_source_notifications",
+        "sources",
+        "Notify on all new entries, keyword matches or never for a source",
+        &[
+            arg("sourceId", ArgKind::Integer, true),
+            arg("prefs", ArgKind::Object, true),
+        ],
+        false,
+    ),
+    action(
+        "list_notification_rules",
+        "notifications",
+        "List desktop notification rules",
+        &[],
+        false,
+    ),
+    action(
+        "create_notification_rule",
+        "notifications",
+        "Notify when new entries match keywords, a source or a category",
+        &[arg("rule", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "update_notification_rule",
+        "notifications",
+        "Change or disable a notification rule",
+        &[arg("rule", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "delete_notification_rule",
+        "notifications",
+        "Delete a notification rule",
+        &[arg("ruleId", ArgKind::Integer, true)],
+        true,
+    ),
+    action(
+        "take_pending_notifications",
+        "entries",
+        "Take queued new-entry notifications",
+        &[],
+        false,
+    ),
+    action(
+        "summarize_entry",
+        "entries",
+        "Summarize an entry with the LLM",
+        &[arg("entryId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "summarize_entry_stream",
+        "entries",
+        "Summarize an entry and stream the text as summary-chunk events",
+        &[arg("entryId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "get_storage_breakdown",
+        "storage",
+        "Show entry counts and stored bytes per source",
+        &[],
+        false,
+    ),
+    action(
+        "set_source_retention",
+        "storage",
+        "Keep a source's read entries for a number of days or a maximum count",
+        &[
+            arg("sourceId", ArgKind::Integer, true),
+            arg("retentionDays", ArgKind::Integer, false),
+            arg("maxEntries", ArgKind::Integer, false),
+        ],
+        true,
+    ),
+    action(
+        "archive_old_entries",
+        "storage",
+        "Archive entries older than a number of days",
+        &[arg("olderThanDays", ArgKind::Integer, true)],
+        true,
+    ),
+    action(
+        "compact_storage",
+        "storage",
+        "Compress stored entry content",
+        &[],
+        true,
+    ),
+    action(
+        "get_sanitizer_allowlist",
+        "storage",
+        "Show the HTML tags, attributes and URL schemes kept when entry content is stored",
+        &[],
+        false,
+    ),
+    action(
+        "save_sanitizer_allowlist",
+        "storage",
+        "Change the HTML tags, attributes and URL schemes kept when entry content is stored",
+        &[arg("allowlist", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "sanitize_stored_entries",
+        "storage",
+        "Re-sanitize the HTML of stored entries with the current allowlist",
+        &[],
+        true,
+    ),
+    action(
+        "export_diagnostics_bundle",
+        "storage",
+        "Export a redacted diagnostics bundle",
+        &[arg("path", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "export_llm_cache",
+        "storage",
+        "Export cached LLM translations and summaries to a JSON file",
+        &[arg("path", ArgKind::String, true)],
+        false,
+    ),
+    action(
+        "import_llm_cache",
+        "storage",
+        "Import cached LLM outputs from an export file, optionally remapping model names",
+        &[
+            arg("path", ArgKind::String, true),
+            arg("modelMap", ArgKind::Object, false),
+            arg("overwrite", ArgKind::Boolean, false),
+        ],
+        false,
+    ),
+    action(
+        "get_migration_status",
+        "storage",
+        "Show database migration status",
+        &[],
+        false,
+    ),
+    #[cfg(feature = "demo-data")]
+    action(
+        "seed_demo_data",
+        "storage",
+        "Insert deterministic demo sources and entries",
+        &[],
+        false,
+    ),
+    action(
+        "sync_source",
+        "sync",
+        "Sync one subscription",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "backfill_source",
+        "sync",
+        "Pull older entries from paginated feed URLs or archived snapshots",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "sync_active_sources",
+        "sync",
+        "Sync all active subscriptions",
+        &[],
+        false,
+    ),
+    action(
+        "get_sync_runtime_status",
+        "sync",
+        "Show background sync status",
+        &[],
+        false,
+    ),
+    action(
+        "cancel_sync",
+        "sync",
+        "Stop the sync batch that is currently running",
+        &[],
+        false,
+    ),
+    action(
+        "pause_background_sync",
+        "sync",
+        "Pause scheduled background sync until resumed",
+        &[],
+        false,
+    ),
+    action(
+        "resume_background_sync",
+        "sync",
+        "Resume scheduled background sync",
+        &[],
+        false,
+    ),
+    action(
+        "get_sync_settings",
+        "settings",
+        "Show sync settings",
+        &[],
+        false,
+    ),
+    action(
+        "save_sync_settings",
+        "settings",
+        "Save sync settings",
+        &[arg("settings", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "get_read_policy",
+        "settings",
+        "Show when entries are marked read automatically",
+        &[],
+        false,
+    ),
+    action(
+        "save_read_policy",
+        "settings",
+        "Choose whether entries are marked read on open, on scroll, after some days or never",
+        &[arg("settings", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "get_llm_budget",
+        "llm",
+        "Show how many title translations and summaries run per cycle",
+        &[],
+        false,
+    ),
+    action(
+        "save_llm_budget",
+        "llm",
+        "Limit title translations and queued summaries per cycle",
+        &[arg("settings", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "get_llm_backlog",
+        "llm",
+        "Count untranslated titles and queued summaries",
+        &[],
+        false,
+    ),
+    action(
+        "translate_source_titles",
+        "llm",
+        "Translate every untranslated title of one source, reporting progress",
+        &[arg("sourceId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "get_advanced_settings",
+        "settings",
+        "Show advanced sync limits",
+        &[],
+        false,
+    ),
+    action(
+        "save_advanced_settings",
+        "settings",
+        "Save advanced sync limits",
+        &[arg("settings", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "get_backup_settings",
+        "settings",
+        "Show backup settings",
+        &[],
+        false,
+    ),
+    action(
+        "save_backup_settings",
+        "settings",
+        "Save backup settings",
+        &[arg("settings", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "run_backup_now",
+        "settings",
+        "Write a backup now",
+        &[],
+        false,
+    ),
+    action(
+        "get_local_api_settings",
+        "settings",
+        "Show local API settings",
+        &[],
+        false,
+    ),
+    action(
+        "save_local_api_settings",
+        "settings",
+        "Save local API settings",
+        &[arg("settings", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "get_local_api_address",
+        "settings",
+        "Show the local API address",
+        &[],
+        false,
+    ),
+    action(
+        "get_llm_config",
+        "settings",
+        "Show LLM configuration",
+        &[],
+        false,
+    ),
+    action(
+        "save_llm_config",
+        "settings",
+        "Save LLM configuration",
+        &[arg("config", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "list_llm_profiles",
+        "settings",
+        "List LLM provider profiles and the tasks they serve",
+        &[],
+        false,
+    ),
+    action(
+        "save_llm_profile",
+        "settings",
+        "Create or update a named LLM provider profile",
+        &[arg("profile", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "activate_llm_profile",
+        "settings",
+        "Make an LLM provider profile the default for tasks without their own mapping",
+        &[arg("profileId", ArgKind::Integer, true)],
+        false,
+    ),
+    action(
+        "delete_llm_profile",
+        "settings",
+        "Delete an LLM provider profile",
+        &[arg("profileId", ArgKind::Integer, true)],
+        true,
+    ),
+    action(
+        "list_glossary_terms",
+        "llm",
+        "List glossary terms and their preferred translations",
+        &[],
+        false,
+    ),
+    action(
+        "save_glossary_term",
+        "llm",
+        "Add a glossary term or change its preferred translation",
+        &[arg("term", ArgKind::Object, true)],
+        false,
+    ),
+    action(
+        "delete_glossary_term",
+        "llm",
+        "Delete a glossary term",
+        &[arg("termId", ArgKind::Integer, true)],
+        true,
+    ),
+    action(
+        "set_task_profile",
+        "settings",
+        "Choose which LLM profile handles summaries, translation or digests",
+        &[
+            arg("task", ArgKind::String, true),
+            arg("profileId", ArgKind::Integer, false),
+        ],
+        false,
+    ),
+    action(
+        "test_llm_connection",
+        "settings",
+        "Test the LLM connection",
+        &[arg("config", ArgKind::Object, false)],
+        false,
+    ),
+    action(
+        "get_automation_hooks",
+        "automation",
+        "List automation hooks",
+        &[],
+        false,
+    ),
+    action(
+        "save_automation_hooks",
+        "automation",
+        "Save automation hooks",
+        &[arg("hooks", ArgKind::ObjectList, true)],
+        false,
+    ),
+    action(
+        "test_automation_hook",
+        "automation",
+        "Run an automation hook with a test event",
+        &[arg("hook", ArgKind::Object, true)],
+        false,
+    ),
+];
+
+pub fn list_actions() -> &'static [ActionDescriptor] {
+    ACTIONS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn action_names_are_unique_and_args_are_camel_case() {
+        let mut names = HashSet::new();
+        for action in list_actions() {
+            assert!(
+                names.insert(action.name),
+                "duplicate action {}",
+                action.name
+            );
+            for arg in action.args {
+                assert!(!arg.name.contains('_'), "{} arg {}", action.name, arg.name);
+            }
+        }
+        assert!(list_actions()
+            .iter()
+            .any(|action| action.name == "delete_source" && action.destructive));
+    }
+}
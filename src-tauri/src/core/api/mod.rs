@@ -0,0 +1,213 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+
+use axum::extract::{Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::sync::oneshot;
+
+use crate::core::storage::repository::{SourceRepository, StorageError};
+
+pub const DEFAULT_LOCAL_API_PORT: u16 = 7878;
+const DEFAULT_ENTRY_LIMIT: i64 = 50;
+const MAX_ENTRY_LIMIT: i64 = 500;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocalApiError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Clone)]
+struct ApiState {
+    repository: SourceRepository,
+    token: Arc<str>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EntriesQuery {
+    source_id: Option<i64>,
+    search: Option<String>,
+    unread_only: bool,
+    limit: Option<i64>,
+}
+
+pub struct LocalApiServer {
+    addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+}
+
+impl LocalApiServer {
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn stop(self) {
+        let _ = self.shutdown.send(());
+    }
+}
+
+pub async fn start_local_api(
+    repository: SourceRepository,
+    port: u16,
+    token: &str,
+) -> Result<LocalApiServer, LocalApiError> {
+    let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+    let addr = listener.local_addr()?;
+    let state = ApiState {
+        repository,
+        token: Arc::from(token),
+    };
+    let app = Router::new()
+        .route("/entries", get(list_entries))
+        .route("/sources", get(list_sources))
+        .route("/unread-counts", get(list_unread_counts))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state);
+    let (shutdown, signal) = oneshot::channel::<()>();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                let _ = signal.await;
+            })
+            .await;
+    });
+    Ok(LocalApiServer { addr, shutdown })
+}
+
+pub fn generate_api_token() -> Option<String> {
+    let mut bytes = [0_u8; 32];
+    SystemRandom::new().fill(&mut bytes).ok()?;
+    Some(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn token_matches(provided: &str, expected: &str) -> bool {
+    let provided = Sha256::digest(provided.as_bytes());
+    let expected = Sha256::digest(expected.as_bytes());
+    provided
+        .iter()
+        .zip(expected.iter())
+        .fold(0_u8, |diff, (left, right)| diff | (left ^ right))
+        == 0
+}
+
+async fn require_token(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    if !provided.is_some_and(|provided| token_matches(provided, &state.token)) {
+        return error_response(StatusCode::UNAUTHORIZED, "missing or invalid token");
+    }
+    next.run(request).await
+}
+
+async fn list_entries(
+    State(state): State<ApiState>,
+    Query(query): Query<EntriesQuery>,
+) -> Response {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ENTRY_LIMIT)
+        .clamp(1, MAX_ENTRY_LIMIT);
+    json_result(
+        state
+            .repository
+            .list_entries(
+                query.source_id,
+                query.search.as_deref(),
+                query.unread_only,
+                limit,
+            )
+            .await,
+    )
+}
+
+async fn list_sources(State(state): State<ApiState>) -> Response {
+    json_result(state.repository.list_sources().await)
+}
+
+async fn list_unread_counts(State(state): State<ApiState>) -> Response {
+    json_result(state.repository.list_unread_counts().await)
+}
+
+fn json_result<T: serde::Serialize>(result: Result<T, StorageError>) -> Response {
+    match result {
+        Ok(value) => Json(value).into_response(),
+        Err(error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &error.to_string()),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::storage::models::NewSource;
+
+    #[tokio::test]
+    async fn serves_sources_only_with_valid_token() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        repository
+            .upsert_source(&NewSource {
+                title: "Example".to_string(),
+                site_url: None,
+                feed_url: "https://example.com/feed.xml".to_string(),
+                category: None,
+                is_active: true,
+            })
+            .await
+            .expect("create source");
+        let token = generate_api_token().expect("token should generate");
+        assert_eq!(token.len(), 64);
+        assert_ne!(generate_api_token(), Some(token.clone()));
+        let server = start_local_api(repository, 0, &token)
+            .await
+            .expect("server should start");
+        let base = format!("http://{}", server.local_addr());
+        let client = reqwest::Client::new();
+
+        let denied = client
+            .get(format!("{base}/sources"))
+            .send()
+            .await
+            .expect("request should complete");
+        assert_eq!(denied.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let sources = client
+            .get(format!("{base}/sources"))
+            .bearer_auth(&token)
+            .send()
+            .await
+            .expect("request should complete")
+            .json::<serde_json::Value>()
+            .await
+            .expect("body should be json");
+        assert_eq!(sources[0]["feed_url"], "https://example.com/feed.xml");
+
+        let counts = client
+            .get(format!("{base}/unread-counts"))
+            .bearer_auth(&token)
+            .send()
+            .await
+            .expect("request should complete")
+            .json::<serde_json::Value>()
+            .await
+            .expect("body should be json");
+        assert_eq!(counts[0]["unread_count"], 0);
+        server.stop();
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde_json::Value;
+
+use crate::core::diagnostics::redact_json_secrets;
+
+const BACKUP_FILE_PREFIX: &str = "rssr-backup-";
+const BACKUP_STAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const BACKUP_LOCATION_FILE: &str = "backup-location";
+pub const DEFAULT_BACKUP_DIR: &str = "backups";
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialize error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupSnapshot {
+    pub opml_path: PathBuf,
+    pub settings_path: PathBuf,
+}
+
+pub fn write_backup_snapshot(
+    dir: &Path,
+    opml: &str,
+    settings: &Value,
+    now: DateTime<Utc>,
+) -> Result<BackupSnapshot, BackupError> {
+    std::fs::create_dir_all(dir)?;
+    let stamp = now.format(BACKUP_STAMP_FORMAT).to_string();
+    let mut settings = settings.clone();
+    redact_json_secrets(&mut settings);
+    let snapshot = BackupSnapshot {
+        opml_path: dir.join(format!("{BACKUP_FILE_PREFIX}{stamp}.opml")),
+        settings_path: dir.join(format!("{BACKUP_FILE_PREFIX}{stamp}.json")),
+    };
+    std::fs::write(&snapshot.opml_path, opml)?;
+    std::fs::write(
+        &snapshot.settings_path,
+        serde_json::to_string_pretty(&settings)?,
+    )?;
+    Ok(snapshot)
+}
+
+pub fn latest_backup_at(dir: &Path) -> Result<Option<DateTime<Utc>>, BackupError> {
+    Ok(backup_stamps(dir)?.into_iter().next_back())
+}
+
+pub fn latest_backup_opml(dir: &Path) -> Result<Option<PathBuf>, BackupError> {
+    Ok(backup_files(dir)?
+        .into_iter()
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "opml")
+        })
+        .max_by_key(|path| parse_stamp(path)))
+}
+
+pub fn remember_backup_dir(data_dir: &Path, directory: Option<&str>) -> Result<(), BackupError> {
+    let location = data_dir.join(BACKUP_LOCATION_FILE);
+    match directory {
+        Some(directory) => std::fs::write(location, directory)?,
+        None if location.exists() => std::fs::remove_file(location)?,
+        None => {}
+    }
+    Ok(())
+}
+
+pub fn recovery_backup_dirs(data_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = std::fs::read_to_string(data_dir.join(BACKUP_LOCATION_FILE))
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .into_iter()
+        .collect::<Vec<_>>();
+    let fallback = data_dir.join(DEFAULT_BACKUP_DIR);
+    if !dirs.contains(&fallback) {
+        dirs.push(fallback);
+    }
+    dirs
+}
+
+pub fn prune_backups(dir: &Path, retention: usize) -> Result<usize, BackupError> {
+    let expired = backup_stamps(dir)?
+        .into_iter()
+        .rev()
+        .skip(retention.max(1))
+        .collect::<BTreeSet<_>>();
+    let mut removed = 0_usize;
+    for path in backup_files(dir)? {
+        if parse_stamp(&path).is_some_and(|stamp| expired.contains(&stamp)) {
+            std::fs::remove_file(path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn backup_stamps(dir: &Path) -> Result<BTreeSet<DateTime<Utc>>, BackupError> {
+    Ok(backup_files(dir)?
+        .iter()
+        .filter_map(|path| parse_stamp(path))
+        .collect())
+}
+
+fn backup_files(dir: &Path) -> Result<Vec<PathBuf>, BackupError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && parse_stamp(&path).is_some() {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn parse_stamp(path: &Path) -> Option<DateTime<Utc>> {
+    let stem = path.file_stem()?.to_str()?;
+    let stamp = stem.strip_prefix(BACKUP_FILE_PREFIX)?;
+    NaiveDateTime::parse_from_str(stamp, BACKUP_STAMP_FORMAT)
+        .ok()
+        .map(|value| value.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn recovery_checks_the_remembered_dir_then_the_default() {
+        let dir = tempfile::tempdir().expect("tempdir should exist");
+        let fallback = dir.path().join(DEFAULT_BACKUP_DIR);
+        assert_eq!(recovery_backup_dirs(dir.path()), vec![fallback.clone()]);
+
+        remember_backup_dir(dir.path(), Some("/mnt/backups")).expect("remember");
+        assert_eq!(
+            recovery_backup_dirs(dir.path()),
+            vec![PathBuf::from("/mnt/backups"), fallback.clone()]
+        );
+
+        remember_backup_dir(dir.path(), None).expect("forget");
+        assert_eq!(recovery_backup_dirs(dir.path()), vec![fallback]);
+    }
+
+    #[test]
+    fn writes_redacted_snapshots_and_prunes_by_retention() {
+        let dir = tempfile::tempdir().expect("tempdir should exist");
+        let settings = serde_json::json!({ "llm_config": { "api_key": "sk-live-123" } });
+        for day in 1..=4 {
+            let now = Utc
+                .with_ymd_and_hms(2026, 3, day, 8, 0, 0)
+                .single()
+                .expect("timestamp should be valid");
+            write_backup_snapshot(dir.path(), "<opml/>", &settings, now)
+                .expect("snapshot should be written");
+        }
+        std::fs::write(dir.path().join("notes.txt"), "keep me").expect("unrelated file");
+
+        let removed = prune_backups(dir.path(), 2).expect("prune should succeed");
+        let latest = latest_backup_at(dir.path()).expect("latest should load");
+        let remaining = std::fs::read_dir(dir.path())
+            .expect("dir should be readable")
+            .count();
+        let settings_json =
+            std::fs::read_to_string(dir.path().join("rssr-backup-20260304T080000Z.json"))
+                .expect("latest settings should exist");
+
+        assert_eq!(removed, 4);
+        assert_eq!(remaining, 5);
+        assert_eq!(latest, Utc.with_ymd_and_hms(2026, 3, 4, 8, 0, 0).single());
+        assert_eq!(
+            latest_backup_opml(dir.path()).expect("latest opml should load"),
+            Some(dir.path().join("rssr-backup-20260304T080000Z.opml"))
+        );
+        assert!(!settings_json.contains("sk-live-123"));
+        assert!(dir
+            .path()
+            .join("rssr-backup-20260303T080000Z.opml")
+            .exists());
+        assert!(!dir
+            .path()
+            .join("rssr-backup-20260302T080000Z.opml")
+            .exists());
+    }
+}
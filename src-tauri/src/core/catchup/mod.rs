@@ -0,0 +1,179 @@
+use serde::Serialize;
+
+use crate::core::search::tokenize::{is_cjk, plain_text};
+use crate::core::storage::models::CatchupCandidate;
+
+pub const CATCHUP_BATCH_SIZE: i64 = 50;
+pub const MAX_CATCHUP_MINUTES: u32 = 8 * 60;
+const WORDS_PER_MINUTE: f64 = 230.0;
+const CJK_CHARS_PER_MINUTE: f64 = 500.0;
+const STARRED_WEIGHT: f64 = 3.0;
+const READ_RATIO_WEIGHT: f64 = 2.0;
+const OPENS_WEIGHT: f64 = 0.1;
+const MAX_OPENS_COUNTED: i64 = 20;
+const NOTIFY_WEIGHT: f64 = 1.5;
+const RECENCY_WEIGHT: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CatchupItem {
+    pub entry_id: i64,
+    pub source_id: i64,
+    pub source_title: String,
+    pub title: String,
+    pub link: String,
+    pub published_at: Option<String>,
+    pub estimated_minutes: u32,
+    pub importance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CatchupSelection {
+    pub minutes_available: u32,
+    pub estimated_minutes: u32,
+    pub entries: Vec<CatchupItem>,
+    pub skipped_entry_ids: Vec<i64>,
+}
+
+pub fn estimate_read_minutes(html: &str) -> u32 {
+    let text = plain_text(html);
+    let cjk_chars = text.chars().filter(|ch| is_cjk(*ch)).count();
+    let words = text
+        .split(|ch: char| ch.is_whitespace() || is_cjk(ch))
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count();
+    let minutes = words as f64 / WORDS_PER_MINUTE + cjk_chars as f64 / CJK_CHARS_PER_MINUTE;
+    (minutes.ceil() as u32).max(1)
+}
+
+pub fn importance_score(candidate: &CatchupCandidate, now: chrono::DateTime<chrono::Utc>) -> f64 {
+    let recency = candidate
+        .published_at
+        .as_deref()
+        .and_then(|published| {
+            chrono::DateTime::parse_from_rfc3339(published)
+                .or_else(|_| chrono::DateTime::parse_from_rfc2822(published))
+                .ok()
+        })
+        .map(|published| {
+            let age_days = (now - published.with_timezone(&chrono::Utc)).num_hours() as f64 / 24.0;
+            RECENCY_WEIGHT / (1.0 + age_days.max(0.0))
+        })
+        .unwrap_or_default();
+    let mut score = 1.0
+        + candidate.source_read_ratio.clamp(0.0, 1.0) * READ_RATIO_WEIGHT
+        + candidate.source_opens.clamp(0, MAX_OPENS_COUNTED) as f64 * OPENS_WEIGHT
+        + recency;
+    if candidate.is_starred {
+        score += STARRED_WEIGHT;
+    }
+    if candidate.source_notifies {
+        score += NOTIFY_WEIGHT;
+    }
+    score
+}
+
+pub fn select_catchup(
+    candidates: Vec<CatchupCandidate>,
+    minutes_available: u32,
+    now: chrono::DateTime<chrono::Utc>,
+) -> CatchupSelection {
+    let mut scored = candidates
+        .into_iter()
+        .map(|candidate| {
+            let minutes = estimate_read_minutes(&candidate.text);
+            let importance = importance_score(&candidate, now);
+            (candidate, minutes, importance)
+        })
+        .collect::<Vec<_>>();
+    scored.sort_by(|left, right| {
+        right
+            .2
+            .total_cmp(&left.2)
+            .then_with(|| left.1.cmp(&right.1))
+            .then_with(|| right.0.id.cmp(&left.0.id))
+    });
+
+    let mut remaining = minutes_available;
+    let mut entries = Vec::new();
+    let mut skipped_entry_ids = Vec::new();
+    for (candidate, minutes, importance) in scored {
+        if minutes > remaining {
+            skipped_entry_ids.push(candidate.id);
+            continue;
+        }
+        remaining -= minutes;
+        entries.push(CatchupItem {
+            entry_id: candidate.id,
+            source_id: candidate.source_id,
+            source_title: candidate.source_title,
+            title: candidate.title,
+            link: candidate.link,
+            published_at: candidate.published_at,
+            estimated_minutes: minutes,
+            importance,
+        });
+    }
+    CatchupSelection {
+        minutes_available,
+        estimated_minutes: minutes_available - remaining,
+        entries,
+        skipped_entry_ids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: i64, words: usize, read_ratio: f64, is_starred: bool) -> CatchupCandidate {
+        CatchupCandidate {
+            id,
+            source_id: 1,
+            source_title: "Source".to_string(),
+            title: format!("Entry {id}"),
+            link: format!("https://example.com/{id}"),
+            published_at: None,
+            text: format!("<p>{}</p>", "word ".repeat(words)),
+            is_starred,
+            source_read_ratio: read_ratio,
+            source_opens: 0,
+            source_notifies: false,
+        }
+    }
+
+    #[test]
+    fn estimates_latin_and_cjk_reading_time() {
+        assert_eq!(estimate_read_minutes(""), 1);
+        assert_eq!(
+            estimate_read_minutes(&format!("<p>{}</p>", "word ".repeat(460))),
+            2
+        );
+        assert_eq!(estimate_read_minutes(&"读".repeat(1200)), 3);
+    }
+
+    #[test]
+    fn picks_most_important_entries_that_fit_and_skips_the_rest() {
+        let now = chrono::Utc::now();
+        let selection = select_catchup(
+            vec![
+                candidate(1, 900, 0.1, false),
+                candidate(2, 1100, 0.9, false),
+                candidate(3, 200, 0.2, true),
+                candidate(4, 200, 0.0, false),
+            ],
+            7,
+            now,
+        );
+
+        assert_eq!(
+            selection
+                .entries
+                .iter()
+                .map(|item| (item.entry_id, item.estimated_minutes))
+                .collect::<Vec<_>>(),
+            vec![(3, 1), (2, 5), (4, 1)]
+        );
+        assert_eq!(selection.estimated_minutes, 7);
+        assert_eq!(selection.skipped_entry_ids, vec![1]);
+    }
+}
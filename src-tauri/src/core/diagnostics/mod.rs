@@ -0,0 +1,128 @@
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+use zip::write::SimpleFileOptions;
+
+use crate::core::redact::{is_secret_key, redact_secrets, REDACTED};
+
+#[derive(Debug, thiserror::Error)]
+pub enum DiagnosticsError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("serialize error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticsBundle {
+    files: Vec<(String, String)>,
+}
+
+impl DiagnosticsBundle {
+    pub fn add_text(&mut self, name: &str, content: &str) {
+        self.files.push((name.to_string(), redact_secrets(content)));
+    }
+
+    pub fn add_json<T: Serialize>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> Result<(), DiagnosticsError> {
+        let mut json = serde_json::to_value(value)?;
+        redact_json_secrets(&mut json);
+        self.files
+            .push((name.to_string(), serde_json::to_string_pretty(&json)?));
+        Ok(())
+    }
+
+    pub fn write_zip(&self, path: &Path) -> Result<(), DiagnosticsError> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(path)?;
+        let mut writer = zip::ZipWriter::new(file);
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, content) in &self.files {
+            writer.start_file(name.as_str(), options)?;
+            writer.write_all(content.as_bytes())?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+pub fn redact_json_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                if is_secret_key(key) && !item.is_null() {
+                    *item = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json_secrets(item);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json_secrets),
+        Value::String(text) => *text = redact_secrets(text),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn redacts_nested_secret_fields() {
+        let mut value = serde_json::json!({
+            "base_url": "https://api.example.com/v1",
+            "api_key": "sk-live-123",
+            "profiles": [{ "name": "local", "access_token": "abc" }],
+            "last_error": "401 for https://example.com/feed?token=abc"
+        });
+        redact_json_secrets(&mut value);
+
+        assert_eq!(value["base_url"], "https://api.example.com/v1");
+        assert_eq!(value["api_key"], REDACTED);
+        assert_eq!(value["profiles"][0]["access_token"], REDACTED);
+        assert_eq!(
+            value["last_error"],
+            "401 for https://example.com/feed?token=[redacted]"
+        );
+    }
+
+    #[test]
+    fn writes_zip_with_redacted_json() {
+        let dir = tempfile::tempdir().expect("tempdir should exist");
+        let path = dir.path().join("bundle").join("diagnostics.zip");
+        let mut bundle = DiagnosticsBundle::default();
+        bundle.add_text("README.txt", "rssr diagnostics");
+        bundle
+            .add_json(
+                "settings.json",
+                &serde_json::json!({ "api_key": "sk-live-123" }),
+            )
+            .expect("json should serialize");
+        bundle.write_zip(&path).expect("zip should be written");
+
+        let file = std::fs::File::open(&path).expect("zip should exist");
+        let mut archive = zip::ZipArchive::new(file).expect("zip should open");
+        assert_eq!(archive.len(), 2);
+        let mut settings = String::new();
+        archive
+            .by_name("settings.json")
+            .expect("settings entry should exist")
+            .read_to_string(&mut settings)
+            .expect("settings should be readable");
+        assert!(!settings.contains("sk-live-123"));
+    }
+}
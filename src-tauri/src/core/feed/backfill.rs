@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+
+use tokio::sync::AcquireError;
+
+use super::fetcher::{fetch_feed, FetchError, FetchStatus};
+use super::parser::parse_feed_with_content_type;
+use super::types::ParsedEntry;
+use crate::core::sync::politeness::SyncHttp;
+
+pub const WAYBACK_BASE_URL: &str = "https://web.archive.org";
+const PAGE_PARAMS: [&str; 2] = ["paged", "page"];
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackfillError {
+    #[error("invalid feed url: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("archive lookup failed: {0}")]
+    Archive(#[from] FetchError),
+    #[error("archive index is malformed: {0}")]
+    ArchiveIndex(#[from] serde_json::Error),
+    #[error("request limiter closed")]
+    LimiterClosed(#[from] AcquireError),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BackfillLimits {
+    pub max_pages: usize,
+    pub max_snapshots: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BackfillReport {
+    pub pages_fetched: usize,
+    pub snapshots_fetched: usize,
+    pub entries: Vec<ParsedEntry>,
+}
+
+pub fn paged_feed_url(feed_url: &str, param: &str, page: usize) -> Result<String, BackfillError> {
+    let mut url = url::Url::parse(feed_url)?;
+    let retained = url
+        .query_pairs()
+        .filter(|(key, _)| key != param)
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    {
+        let mut pairs = url.query_pairs_mut();
+        pairs.clear();
+        for (key, value) in &retained {
+            pairs.append_pair(key, value);
+        }
+        pairs.append_pair(param, &page.to_string());
+    }
+    Ok(url.to_string())
+}
+
+pub fn wayback_snapshot_urls(
+    archive_base: &str,
+    feed_url: &str,
+    cdx_body: &str,
+) -> Result<Vec<String>, BackfillError> {
+    let rows = serde_json::from_str::<Vec<Vec<String>>>(cdx_body)?;
+    let Some((header, rows)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    let Some(column) = header.iter().position(|name| name == "timestamp") else {
+        return Ok(Vec::new());
+    };
+    Ok(rows
+        .iter()
+        .filter_map(|row| row.get(column))
+        .map(|timestamp| {
+            format!(
+                "{}/web/{timestamp}id_/{feed_url}",
+                archive_base.trim_end_matches('/')
+            )
+        })
+        .collect())
+}
+
+pub async fn backfill_feed(
+    http: &SyncHttp,
+    timeout_secs: u64,
+    feed_url: &str,
+    archive_base: &str,
+    known: HashSet<String>,
+    limits: BackfillLimits,
+) -> Result<BackfillReport, BackfillError> {
+    let client = &http.client(timeout_secs).map_err(FetchError::from)?;
+    let mut seen = known;
+    let mut report = BackfillReport::default();
+
+    for param in PAGE_PARAMS {
+        let mut found_any = false;
+        for page in 2..limits.max_pages + 2 {
+            let url = paged_feed_url(feed_url, param, page)?;
+            let Some(entries) = fetch_entries(http, client, &url).await else {
+                break;
+            };
+            report.pages_fetched += 1;
+            let added = collect_new(&mut seen, entries, &mut report.entries);
+            if added == 0 {
+                break;
+            }
+            found_any = true;
+        }
+        if found_any {
+            return Ok(report);
+        }
+    }
+
+    let cdx_url = format!(
+        "{}/cdx/search/cdx?url={}&output=json&fl=timestamp&filter=statuscode:200&collapse=digest",
+        archive_base.trim_end_matches('/'),
+        url::form_urlencoded::byte_serialize(feed_url.as_bytes()).collect::<String>()
+    );
+    let permit = http.acquire(&cdx_url).await?;
+    let cdx_body = match fetch_feed(client, &cdx_url, None, None, None).await? {
+        FetchStatus::Updated(payload) => String::from_utf8_lossy(&payload.body).into_owned(),
+        FetchStatus::NotModified => return Ok(report),
+    };
+    drop(permit);
+    let mut snapshots = wayback_snapshot_urls(archive_base, feed_url, &cdx_body)?;
+    snapshots.reverse();
+    for snapshot in snapshots.into_iter().take(limits.max_snapshots) {
+        if let Some(entries) = fetch_entries(http, client, &snapshot).await {
+            report.snapshots_fetched += 1;
+            collect_new(&mut seen, entries, &mut report.entries);
+        }
+    }
+    Ok(report)
+}
+
+async fn fetch_entries(
+    http: &SyncHttp,
+    client: &reqwest::Client,
+    url: &str,
+) -> Option<Vec<ParsedEntry>> {
+    let _permit = http.acquire(url).await.ok()?;
+    match fetch_feed(client, url, None, None, None).await.ok()? {
+        FetchStatus::Updated(payload) => {
+            parse_feed_with_content_type(&payload.body, payload.content_type.as_deref())
+                .ok()
+                .map(|feed| feed.entries)
+        }
+        FetchStatus::NotModified => None,
+    }
+}
+
+fn collect_new(
+    seen: &mut HashSet<String>,
+    entries: Vec<ParsedEntry>,
+    collected: &mut Vec<ParsedEntry>,
+) -> usize {
+    let mut added = 0;
+    for entry in entries {
+        if seen.insert(entry.link.clone()) {
+            collected.push(entry);
+            added += 1;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+    use axum::http::StatusCode;
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::get;
+    use axum::Router;
+    use std::collections::HashMap;
+
+    fn rss(items: &[usize]) -> String {
+        let items = items
+            .iter()
+            .map(|index| {
+                format!(
+                    "<item><title>Post {index}</title><link>https://blog.example.com/{index}</link><guid>{index}</guid></item>"
+                )
+            })
+            .collect::<String>();
+        format!("<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>Blog</title>{items}</channel></rss>")
+    }
+
+    async fn paged_feed(Query(query): Query<HashMap<String, String>>) -> Response {
+        match query.get("paged").map(String::as_str) {
+            None => rss(&[5, 4]).into_response(),
+            Some("2") => rss(&[3, 2]).into_response(),
+            Some("3") => rss(&[1]).into_response(),
+            Some(_) => StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+
+    async fn cdx() -> &'static str {
+        r#"[["timestamp"],["20240101000000"],["20250101000000"]]"#
+    }
+
+    async fn snapshot() -> String {
+        rss(&[9, 8])
+    }
+
+    #[test]
+    fn builds_paged_and_snapshot_urls() {
+        assert_eq!(
+            paged_feed_url("https://blog.example.com/feed/?lang=en&paged=1", "paged", 3)
+                .expect("url should build"),
+            "https://blog.example.com/feed/?lang=en&paged=3"
+        );
+        assert_eq!(
+            wayback_snapshot_urls(
+                "https://web.archive.org/",
+                "https://blog.example.com/feed",
+                r#"[["timestamp"],["20240101000000"]]"#
+            )
+            .expect("cdx should parse"),
+            vec!["https://web.archive.org/web/20240101000000id_/https://blog.example.com/feed"]
+        );
+    }
+
+    #[tokio::test]
+    async fn backfills_from_pages_then_falls_back_to_archive() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let base = format!("http://{}", listener.local_addr().expect("local addr"));
+        let app = Router::new()
+            .route("/feed", get(paged_feed))
+            .route("/static", get(|| async { rss(&[7]) }))
+            .route("/cdx/search/cdx", get(cdx))
+            .route("/web/{*rest}", get(snapshot));
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        let http = SyncHttp::new(4, 2, std::time::Duration::ZERO);
+        let limits = BackfillLimits {
+            max_pages: 5,
+            max_snapshots: 5,
+        };
+
+        let known = HashSet::from([
+            "https://blog.example.com/5".to_string(),
+            "https://blog.example.com/4".to_string(),
+        ]);
+        let paged = backfill_feed(&http, 5, &format!("{base}/feed"), &base, known, limits)
+            .await
+            .expect("paged backfill should succeed");
+        assert_eq!(paged.entries.len(), 3);
+        assert_eq!(paged.snapshots_fetched, 0);
+
+        let known = HashSet::from(["https://blog.example.com/7".to_string()]);
+        let archived = backfill_feed(&http, 5, &format!("{base}/static"), &base, known, limits)
+            .await
+            .expect("archive backfill should succeed");
+        assert_eq!(archived.snapshots_fetched, 2);
+        assert_eq!(archived.entries.len(), 2);
+
+        server.abort();
+    }
+}
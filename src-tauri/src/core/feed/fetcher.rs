@@ -1,15 +1,22 @@
-use reqwest::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::header::{HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
-#[derive(Debug, Clone)]
+use crate::core::redact::redact_secrets;
+
+const UNKNOWN_BODY_ESTIMATE_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug)]
 pub struct FetchedFeed {
     pub body: Vec<u8>,
     pub content_type: Option<String>,
     pub etag: Option<String>,
     pub last_modified: Option<String>,
+    pub budget_permit: Option<OwnedSemaphorePermit>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum FetchStatus {
     Updated(FetchedFeed),
     NotModified,
@@ -17,18 +24,117 @@ pub enum FetchStatus {
 
 #[derive(Debug, thiserror::Error)]
 pub enum FetchError {
-    #[error("request failed: {0}")]
+    #[error("request failed: {}", redact_secrets(&.0.to_string()))]
     Request(#[from] reqwest::Error),
+    #[error("authentication required (401)")]
+    Unauthorized,
+    #[error("access forbidden (403)")]
+    Forbidden,
+    #[error("feed not found (404)")]
+    NotFound,
+    #[error("feed permanently removed (410)")]
+    Gone,
+    #[error("rate limited (429)")]
+    RateLimited { retry_after_secs: Option<u64> },
     #[error("unexpected status code: {0}")]
     HttpStatus(u16),
+    #[error("memory budget closed")]
+    BudgetClosed,
+    #[error("feed body exceeded the in-flight memory budget")]
+    BudgetExceeded,
 }
 
-pub async fn fetch_feed(
+impl FetchError {
+    pub fn from_status(status: u16, headers: &HeaderMap) -> Self {
+        match status {
+            401 => Self::Unauthorized,
+            403 => Self::Forbidden,
+            404 => Self::NotFound,
+            410 => Self::Gone,
+            429 => Self::RateLimited {
+                retry_after_secs: headers
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(parse_retry_after),
+            },
+            other => Self::HttpStatus(other),
+        }
+    }
+
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::Unauthorized => Some(401),
+            Self::Forbidden => Some(403),
+            Self::NotFound => Some(404),
+            Self::Gone => Some(410),
+            Self::RateLimited { .. } => Some(429),
+            Self::HttpStatus(code) => Some(*code),
+            Self::Request(error) => error.status().map(|status| status.as_u16()),
+            Self::BudgetClosed | Self::BudgetExceeded => None,
+        }
+    }
+}
+
+pub fn parse_retry_after(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(secs);
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let remaining = at.timestamp() - chrono::Utc::now().timestamp();
+    Some(remaining.max(0) as u64)
+}
+
+#[derive(Debug, Clone)]
+pub struct ByteBudget {
+    semaphore: Arc<Semaphore>,
+    capacity_kb: u32,
+}
+
+impl ByteBudget {
+    pub fn new(max_bytes: u64) -> Self {
+        let capacity_kb = (max_bytes / 1024).clamp(1, u64::from(u32::MAX)) as u32;
+        Self {
+            semaphore: Arc::new(Semaphore::new(capacity_kb as usize)),
+            capacity_kb,
+        }
+    }
+
+    pub async fn acquire(&self, bytes: u64) -> Result<OwnedSemaphorePermit, FetchError> {
+        let requested_kb = bytes.div_ceil(1024).clamp(1, u64::from(self.capacity_kb)) as u32;
+        self.semaphore
+            .clone()
+            .acquire_many_owned(requested_kb)
+            .await
+            .map_err(|_| FetchError::BudgetClosed)
+    }
+
+    pub fn try_grow(
+        &self,
+        permit: &mut OwnedSemaphorePermit,
+        bytes: u64,
+    ) -> Result<(), FetchError> {
+        let extra_kb = bytes.div_ceil(1024).clamp(1, u64::from(self.capacity_kb)) as u32;
+        let extra = self
+            .semaphore
+            .clone()
+            .try_acquire_many_owned(extra_kb)
+            .map_err(|_| FetchError::BudgetExceeded)?;
+        permit.merge(extra);
+        Ok(())
+    }
+}
+
+pub fn feed_client_builder(timeout_secs: u64) -> reqwest::ClientBuilder {
+    reqwest::Client::builder().timeout(Duration::from_secs(timeout_secs))
+}
+
+pub fn feed_request(
     client: &reqwest::Client,
     url: &str,
     etag: Option<&str>,
     last_modified: Option<&str>,
-) -> Result<FetchStatus, FetchError> {
+) -> reqwest::RequestBuilder {
     let mut request = client.get(url);
     if let Some(value) = etag {
         request = request.header(IF_NONE_MATCH, value);
@@ -36,14 +142,25 @@ pub async fn fetch_feed(
     if let Some(value) = last_modified {
         request = request.header(IF_MODIFIED_SINCE, value);
     }
+    request
+}
 
-    let response = request.send().await?;
+pub async fn fetch_feed(
+    client: &reqwest::Client,
+    url: &str,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+    budget: Option<&ByteBudget>,
+) -> Result<FetchStatus, FetchError> {
+    let mut response = feed_request(client, url, etag, last_modified)
+        .send()
+        .await?;
     let status = response.status();
     if status.as_u16() == 304 {
         return Ok(FetchStatus::NotModified);
     }
     if !status.is_success() {
-        return Err(FetchError::HttpStatus(status.as_u16()));
+        return Err(FetchError::from_status(status.as_u16(), response.headers()));
     }
 
     let etag = response
@@ -61,13 +178,36 @@ pub async fn fetch_feed(
         .get(reqwest::header::CONTENT_TYPE)
         .and_then(|value| value.to_str().ok())
         .map(ToString::to_string);
-    let body = response.bytes().await?.to_vec();
+    let mut budget_permit = match budget {
+        Some(budget) => Some(
+            budget
+                .acquire(
+                    response
+                        .content_length()
+                        .unwrap_or(UNKNOWN_BODY_ESTIMATE_BYTES),
+                )
+                .await?,
+        ),
+        None => None,
+    };
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if let (Some(budget), Some(permit)) = (budget, budget_permit.as_mut()) {
+            let needed = (body.len() + chunk.len()) as u64;
+            let reserved = permit.num_permits() as u64 * 1024;
+            if needed > reserved {
+                budget.try_grow(permit, (needed - reserved).max(UNKNOWN_BODY_ESTIMATE_BYTES))?;
+            }
+        }
+        body.extend_from_slice(&chunk);
+    }
 
     Ok(FetchStatus::Updated(FetchedFeed {
         body,
         content_type,
         etag,
         last_modified,
+        budget_permit,
     }))
 }
 
@@ -77,10 +217,11 @@ pub async fn fetch_feed_with_retry(
     etag: Option<&str>,
     last_modified: Option<&str>,
     max_retries: usize,
+    budget: Option<&ByteBudget>,
 ) -> Result<FetchStatus, FetchError> {
     let mut attempt = 0_usize;
     loop {
-        match fetch_feed(client, url, etag, last_modified).await {
+        match fetch_feed(client, url, etag, last_modified, budget).await {
             Ok(result) => return Ok(result),
             Err(err) => {
                 let should_retry = matches!(err, FetchError::Request(_))
@@ -170,6 +311,7 @@ mod tests {
         };
         let app = Router::new()
             .route("/feed.xml", get(feed_handler))
+            .route("/huge.xml", get(|| async { "x".repeat(3 * 1024 * 1024) }))
             .with_state(state);
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
             .await
@@ -186,7 +328,8 @@ mod tests {
         let (url, server_task) = spawn_test_server().await;
         let client = reqwest::Client::new();
 
-        let first = fetch_feed_with_retry(&client, &url, None, None, 2)
+        let budget = ByteBudget::new(8 * 1024 * 1024);
+        let first = fetch_feed_with_retry(&client, &url, None, None, 2, Some(&budget))
             .await
             .expect("first fetch should succeed with retry");
         let updated = match first {
@@ -194,6 +337,7 @@ mod tests {
             FetchStatus::NotModified => panic!("first fetch should be updated"),
         };
         assert!(updated.body.starts_with(b"<?xml"));
+        assert!(updated.budget_permit.is_some());
         assert_eq!(updated.content_type.as_deref(), Some("application/rss+xml"));
         assert_eq!(updated.etag.as_deref(), Some("\"rssr-feed-v1\""));
 
@@ -203,6 +347,7 @@ mod tests {
             updated.etag.as_deref(),
             updated.last_modified.as_deref(),
             0,
+            None,
         )
         .await
         .expect("second fetch should succeed");
@@ -210,4 +355,63 @@ mod tests {
 
         server_task.abort();
     }
+
+    #[tokio::test]
+    async fn byte_budget_blocks_until_memory_is_released() {
+        let budget = ByteBudget::new(2 * 1024 * 1024);
+        let first = budget
+            .acquire(1536 * 1024)
+            .await
+            .expect("first reservation fits");
+        let blocked =
+            tokio::time::timeout(Duration::from_millis(50), budget.acquire(1024 * 1024)).await;
+        assert!(blocked.is_err());
+
+        drop(first);
+        let oversized =
+            tokio::time::timeout(Duration::from_millis(50), budget.acquire(64 * 1024 * 1024)).await;
+        assert!(matches!(oversized, Ok(Ok(_))));
+        drop(oversized);
+
+        let (url, server_task) = spawn_test_server().await;
+        let huge = fetch_feed(
+            &reqwest::Client::new(),
+            &url.replace("feed.xml", "huge.xml"),
+            None,
+            None,
+            Some(&budget),
+        )
+        .await;
+        assert!(matches!(huge, Err(FetchError::BudgetExceeded)));
+        assert!(budget.acquire(2 * 1024 * 1024).await.is_ok());
+        server_task.abort();
+    }
+
+    #[test]
+    fn classifies_error_statuses_with_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "120".parse().expect("header must parse"));
+
+        assert!(matches!(
+            FetchError::from_status(429, &headers),
+            FetchError::RateLimited {
+                retry_after_secs: Some(120)
+            }
+        ));
+        assert!(matches!(
+            FetchError::from_status(401, &HeaderMap::new()),
+            FetchError::Unauthorized
+        ));
+        assert!(matches!(
+            FetchError::from_status(410, &HeaderMap::new()),
+            FetchError::Gone
+        ));
+        assert!(matches!(
+            FetchError::from_status(418, &HeaderMap::new()),
+            FetchError::HttpStatus(418)
+        ));
+        assert_eq!(FetchError::NotFound.status_code(), Some(404));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(0));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
 }
@@ -1,6 +1,12 @@
+pub mod backfill;
 pub mod fetcher;
+pub mod paging;
 pub mod parser;
+pub mod probe;
+pub mod sanitize;
+pub mod streaming;
 pub mod types;
+pub mod writer;
 
 #[derive(Debug, Clone, Default)]
 pub struct FeedService;
@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use super::fetcher::{fetch_feed, FetchStatus};
+use super::parser::parse_feed_with_content_type;
+use super::types::{ParsedEntry, ParsedFeed};
+
+pub fn following_page_url(feed: &ParsedFeed, page_url: &str) -> Option<String> {
+    let href = feed
+        .prev_archive_url
+        .as_deref()
+        .or(feed.next_url.as_deref())?;
+    let base = url::Url::parse(page_url).ok()?;
+    base.join(href.trim()).ok().map(String::from)
+}
+
+pub async fn fetch_following_pages(
+    client: &reqwest::Client,
+    feed_url: &str,
+    first: &ParsedFeed,
+    known: &HashSet<String>,
+    max_pages: usize,
+) -> Vec<ParsedEntry> {
+    let mut collected = Vec::new();
+    if !has_unknown_entries(&first.entries, known) {
+        return collected;
+    }
+    let mut visited = HashSet::from([feed_url.to_string()]);
+    let mut next = following_page_url(first, feed_url);
+    let mut pages = 1;
+    while let Some(page_url) = next.take() {
+        if pages >= max_pages || !visited.insert(page_url.clone()) {
+            break;
+        }
+        let Ok(FetchStatus::Updated(payload)) =
+            fetch_feed(client, &page_url, None, None, None).await
+        else {
+            break;
+        };
+        let Ok(page) = parse_feed_with_content_type(&payload.body, payload.content_type.as_deref())
+        else {
+            break;
+        };
+        pages += 1;
+        if !has_unknown_entries(&page.entries, known) {
+            break;
+        }
+        next = following_page_url(&page, &page_url);
+        collected.extend(page.entries);
+    }
+    collected
+}
+
+fn has_unknown_entries(entries: &[ParsedEntry], known: &HashSet<String>) -> bool {
+    entries.iter().any(|entry| !known.contains(&entry.link))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::feed::parser::parse_feed_bytes;
+    use axum::extract::Path;
+    use axum::routing::get;
+    use axum::Router;
+
+    fn archive_page(page: usize) -> String {
+        let prev = if page > 1 {
+            format!(r#"<link rel="prev-archive" href="/archive/{}"/>"#, page - 1)
+        } else {
+            String::new()
+        };
+        format!(
+            r#"<?xml version="1.0"?><feed xmlns="http://www.w3.org/2005/Atom"><title>Blog</title><id>urn:blog</id><updated>2026-01-01T00:00:00Z</updated>{prev}<entry><id>urn:{page}</id><title>Post {page}</title><link href="https://blog.example.com/{page}"/><updated>2026-01-0{page}T00:00:00Z</updated></entry></feed>"#
+        )
+    }
+
+    #[tokio::test]
+    async fn walks_prev_archive_links_until_known_or_limit() {
+        let app = Router::new().route(
+            "/archive/{page}",
+            get(|Path(page): Path<usize>| async move { archive_page(page) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let base = format!("http://{}", listener.local_addr().expect("local addr"));
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        let client = reqwest::Client::new();
+        let feed_url = format!("{base}/archive/5");
+        let first = parse_feed_bytes(archive_page(5).as_bytes()).expect("first page parses");
+        assert_eq!(
+            following_page_url(&first, &feed_url),
+            Some(format!("{base}/archive/4"))
+        );
+
+        let all = fetch_following_pages(&client, &feed_url, &first, &HashSet::new(), 10).await;
+        assert_eq!(all.len(), 4);
+
+        let limited = fetch_following_pages(&client, &feed_url, &first, &HashSet::new(), 3).await;
+        assert_eq!(limited.len(), 2);
+
+        let known = HashSet::from(["https://blog.example.com/3".to_string()]);
+        let until_known = fetch_following_pages(&client, &feed_url, &first, &known, 10).await;
+        assert_eq!(until_known.len(), 1);
+
+        server.abort();
+    }
+}
@@ -1,7 +1,86 @@
+use std::collections::{HashMap, HashSet};
+
 use feed_rs::model::Entry;
-use serde::Deserialize;
+use quick_xml::events::Event;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::sanitize::repair_xml;
+use super::streaming::{
+    parse_xml_feed_streaming, MAX_ENTRY_FIELD_BYTES, STREAMING_THRESHOLD_BYTES,
+};
+use super::types::{FeedFormat, ParsedEntry, ParsedFeed, UNKNOWN_ENTRY_ID};
 
-use super::types::{FeedFormat, ParsedEntry, ParsedFeed};
+const PARKED_DOMAIN_MARKERS: [&str; 7] = [
+    "this domain is for sale",
+    "this domain may be for sale",
+    "buy this domain",
+    "domain is parked",
+    "parked free",
+    "sedoparking",
+    "parkingcrew",
+];
+const TRACKING_PARAMS: [&str; 6] = ["fbclid", "gclid", "mc_cid", "mc_eid", "igshid", "ref_src"];
+const ALWAYS_STRIPPED_TAGS: [&str; 2] = ["script", "style"];
+const BLOCKED_URL_SCHEMES: [&str; 2] = ["javascript", "vbscript"];
+const DEFAULT_ALLOWED_TAGS: [&str; 51] = [
+    "a",
+    "abbr",
+    "audio",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "picture",
+    "pre",
+    "q",
+    "s",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+const DEFAULT_ALLOWED_ATTRIBUTES: [&str; 16] = [
+    "alt", "cite", "colspan", "controls", "datetime", "dir", "height", "href", "lang", "poster",
+    "rowspan", "src", "srcset", "title", "type", "width",
+];
+const DEFAULT_URL_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
 
 #[derive(Debug, thiserror::Error)]
 pub enum FeedParseError {
@@ -11,6 +90,94 @@ pub enum FeedParseError {
     Xml(#[from] feed_rs::parser::ParseFeedError),
     #[error("json feed parse error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("response is an HTML page, not a feed (content-type {})", .0.as_deref().unwrap_or("unknown"))]
+    HtmlPage(Option<String>),
+    #[error("content-type {declared} does not match the {detected} payload: {reason}")]
+    FormatMismatch {
+        declared: String,
+        detected: &'static str,
+        reason: Box<FeedParseError>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SanitizerAllowlist {
+    pub tags: Vec<String>,
+    pub attributes: Vec<String>,
+    pub url_schemes: Vec<String>,
+}
+
+impl Default for SanitizerAllowlist {
+    fn default() -> Self {
+        let owned = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        Self {
+            tags: owned(&DEFAULT_ALLOWED_TAGS),
+            attributes: owned(&DEFAULT_ALLOWED_ATTRIBUTES),
+            url_schemes: owned(&DEFAULT_URL_SCHEMES),
+        }
+    }
+}
+
+impl SanitizerAllowlist {
+    pub fn sanitizer(&self) -> ammonia::Builder<'_> {
+        let tags = self
+            .tags
+            .iter()
+            .map(String::as_str)
+            .filter(|tag| !ALWAYS_STRIPPED_TAGS.contains(tag))
+            .collect::<HashSet<_>>();
+        let attributes = self
+            .attributes
+            .iter()
+            .map(String::as_str)
+            .filter(|attribute| *attribute != "rel")
+            .collect::<HashSet<_>>();
+        let url_schemes = self
+            .url_schemes
+            .iter()
+            .map(String::as_str)
+            .filter(|scheme| !BLOCKED_URL_SCHEMES.contains(scheme))
+            .collect::<HashSet<_>>();
+        let mut builder = ammonia::Builder::empty();
+        builder
+            .tags(tags)
+            .clean_content_tags(ALWAYS_STRIPPED_TAGS.into_iter().collect())
+            .tag_attributes(HashMap::new())
+            .generic_attributes(attributes)
+            .url_schemes(url_schemes)
+            .link_rel(Some("noopener noreferrer"));
+        builder
+    }
+}
+
+pub fn sanitize_entry(entry: &ParsedEntry, sanitizer: &ammonia::Builder<'_>) -> ParsedEntry {
+    let clean = |html: &Option<String>| {
+        html.as_deref()
+            .map(|html| sanitizer.clean(html).to_string())
+    };
+    ParsedEntry {
+        summary: clean(&entry.summary),
+        content: clean(&entry.content),
+        ..entry.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PayloadKind {
+    Json,
+    Xml,
+    Html,
+}
+
+impl PayloadKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Xml => "xml",
+            Self::Html => "html",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -18,6 +185,7 @@ struct JsonFeed {
     title: Option<String>,
     home_page_url: Option<String>,
     feed_url: Option<String>,
+    next_url: Option<String>,
     #[serde(default)]
     items: Vec<JsonFeedItem>,
 }
@@ -34,22 +202,128 @@ struct JsonFeedItem {
 }
 
 pub fn parse_feed_bytes(raw: &[u8]) -> Result<ParsedFeed, FeedParseError> {
+    parse_feed_with_content_type(raw, None)
+}
+
+pub fn parse_feed_with_content_type(
+    raw: &[u8],
+    content_type: Option<&str>,
+) -> Result<ParsedFeed, FeedParseError> {
     let trimmed = trim_leading_ascii_whitespace(raw);
     if trimmed.is_empty() {
         return Err(FeedParseError::EmptyPayload);
     }
-    if trimmed[0] == b'{' {
-        return parse_json_feed(trimmed);
+    let declared = content_type.and_then(declared_kind);
+    let detected = detected_kind(trimmed);
+    let result = match detected.or(declared).unwrap_or(PayloadKind::Xml) {
+        PayloadKind::Json => parse_json_feed(trimmed),
+        PayloadKind::Xml => parse_xml_feed(trimmed),
+        PayloadKind::Html => {
+            return Err(FeedParseError::HtmlPage(content_type.map(str::to_string)))
+        }
+    };
+    match (result, declared, detected) {
+        (Err(reason), Some(declared_kind), Some(detected_kind))
+            if declared_kind != detected_kind =>
+        {
+            Err(FeedParseError::FormatMismatch {
+                declared: content_type.unwrap_or_default().to_string(),
+                detected: detected_kind.label(),
+                reason: Box::new(reason),
+            })
+        }
+        (result, _, _) => result,
     }
-    parse_xml_feed(trimmed)
+}
+
+pub fn detect_payload_format(raw: &[u8], content_type: Option<&str>) -> &'static str {
+    let trimmed = trim_leading_ascii_whitespace(raw);
+    if trimmed.is_empty() {
+        return "empty";
+    }
+    detected_kind(trimmed)
+        .or_else(|| content_type.and_then(declared_kind))
+        .map_or("unknown", PayloadKind::label)
+}
+
+fn detected_kind(trimmed: &[u8]) -> Option<PayloadKind> {
+    match trimmed[0] {
+        b'{' | b'[' => Some(PayloadKind::Json),
+        b'<' if looks_like_html(trimmed) => Some(PayloadKind::Html),
+        b'<' => Some(PayloadKind::Xml),
+        _ => None,
+    }
+}
+
+fn declared_kind(content_type: &str) -> Option<PayloadKind> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match essence.as_str() {
+        "text/html" | "application/xhtml+xml" => Some(PayloadKind::Html),
+        "application/json" | "text/json" => Some(PayloadKind::Json),
+        "application/xml" | "text/xml" => Some(PayloadKind::Xml),
+        _ if essence.ends_with("+json") => Some(PayloadKind::Json),
+        _ if essence.ends_with("+xml") => Some(PayloadKind::Xml),
+        _ => None,
+    }
+}
+
+fn looks_like_html(raw: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&raw[..raw.len().min(512)]).to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+pub fn looks_like_parked_domain(raw: &[u8]) -> bool {
+    let head = &raw[..raw.len().min(64 * 1024)];
+    let text = String::from_utf8_lossy(head).to_lowercase();
+    if !text.contains("<html") {
+        return false;
+    }
+    PARKED_DOMAIN_MARKERS
+        .iter()
+        .any(|marker| text.contains(marker))
+}
+
+pub fn canonicalize_link(link: &str) -> String {
+    let trimmed = link.trim();
+    let Ok(mut url) = url::Url::parse(trimmed) else {
+        return trimmed.to_string();
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return trimmed.to_string();
+    }
+    url.set_fragment(None);
+    let pairs = url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    let kept = pairs
+        .iter()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .collect::<Vec<_>>();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else if kept.len() < pairs.len() {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    url.to_string()
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
 }
 
 pub fn build_dedup_key(feed_url: &str, entry: &ParsedEntry) -> String {
-    if !entry.id.trim().is_empty() {
-        return format!("{feed_url}::id::{}", entry.id.trim());
+    if let Some(guid) = entry.guid() {
+        return format!("{feed_url}::id::{guid}");
     }
     if !entry.link.trim().is_empty() {
-        return format!("{feed_url}::link::{}", entry.link.trim());
+        return format!("{feed_url}::link::{}", canonicalize_link(&entry.link));
     }
     format!(
         "{feed_url}::fallback::{}::{}",
@@ -58,7 +332,83 @@ pub fn build_dedup_key(feed_url: &str, entry: &ParsedEntry) -> String {
     )
 }
 
+pub fn build_entry_fingerprint(entry: &ParsedEntry) -> String {
+    let mut hasher = Sha256::new();
+    for field in [
+        Some(entry.id.as_str()),
+        Some(entry.link.as_str()),
+        Some(entry.title.as_str()),
+        entry.summary.as_deref(),
+        entry.content.as_deref(),
+        entry.published_at.as_deref(),
+    ] {
+        hasher.update(field.unwrap_or_default().as_bytes());
+        hasher.update([0_u8]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
 fn parse_xml_feed(raw: &[u8]) -> Result<ParsedFeed, FeedParseError> {
+    let mut feed = match parse_well_formed_xml_feed(raw) {
+        Ok(feed) => feed,
+        Err(error) => match repair_xml(raw) {
+            Some(repaired) => parse_well_formed_xml_feed(&repaired).map_err(|_| error)?,
+            None => return Err(error),
+        },
+    };
+    (feed.ttl_minutes, feed.skip_hours) = schedule_hints(raw);
+    Ok(feed)
+}
+
+fn schedule_hints(raw: &[u8]) -> (Option<u32>, Vec<u32>) {
+    let mentions = |needle: &[u8]| raw.windows(needle.len()).any(|window| window == needle);
+    let mut ttl_minutes = None;
+    let mut skip_hours = Vec::new();
+    if !mentions(b"<ttl") && !mentions(b"<skipHours") {
+        return (ttl_minutes, skip_hours);
+    }
+    let mut reader = quick_xml::Reader::from_reader(raw);
+    reader.config_mut().trim_text(true);
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => {
+                path.push(element.local_name().as_ref().to_ascii_lowercase());
+            }
+            Ok(Event::End(_)) => {
+                path.pop();
+            }
+            Ok(Event::Text(text)) => {
+                let Ok(value) = text.unescape() else {
+                    continue;
+                };
+                match path.as_slice() {
+                    [.., parent, name] if parent == b"channel" && name == b"ttl" => {
+                        ttl_minutes = value.trim().parse().ok();
+                    }
+                    [.., parent, name] if parent == b"skiphours" && name == b"hour" => {
+                        if let Ok(hour) = value.trim().parse::<u32>() {
+                            skip_hours.push(hour % 24);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    skip_hours.sort_unstable();
+    skip_hours.dedup();
+    (ttl_minutes, skip_hours)
+}
+
+fn parse_well_formed_xml_feed(raw: &[u8]) -> Result<ParsedFeed, FeedParseError> {
+    if raw.len() >= STREAMING_THRESHOLD_BYTES {
+        if let Ok(feed) = parse_xml_feed_streaming(raw, MAX_ENTRY_FIELD_BYTES) {
+            return Ok(feed);
+        }
+    }
     let feed = feed_rs::parser::parse(raw)?;
     let title = feed
         .title
@@ -66,6 +416,14 @@ fn parse_xml_feed(raw: &[u8]) -> Result<ParsedFeed, FeedParseError> {
         .map(|text| text.content.clone())
         .unwrap_or_else(|| "Untitled Feed".to_string());
     let home_page_url = feed.links.first().map(|link| link.href.clone());
+    let link_with_rel = |rel: &str| {
+        feed.links
+            .iter()
+            .find(|link| link.rel.as_deref() == Some(rel))
+            .map(|link| link.href.clone())
+    };
+    let next_url = link_with_rel("next");
+    let prev_archive_url = link_with_rel("prev-archive");
     let entries = feed.entries.iter().map(entry_from_xml).collect();
 
     Ok(ParsedFeed {
@@ -73,6 +431,10 @@ fn parse_xml_feed(raw: &[u8]) -> Result<ParsedFeed, FeedParseError> {
         title,
         home_page_url,
         feed_url: None,
+        next_url,
+        prev_archive_url,
+        ttl_minutes: None,
+        skip_hours: Vec::new(),
         entries,
     })
 }
@@ -87,7 +449,7 @@ fn parse_json_feed(raw: &[u8]) -> Result<ParsedFeed, FeedParseError> {
             id: item
                 .id
                 .or_else(|| item.url.clone())
-                .unwrap_or_else(|| "unknown".to_string()),
+                .unwrap_or_else(|| UNKNOWN_ENTRY_ID.to_string()),
             title: item.title.unwrap_or_else(|| "Untitled Entry".to_string()),
             link: item.url.unwrap_or_default(),
             summary: item.summary,
@@ -101,6 +463,10 @@ fn parse_json_feed(raw: &[u8]) -> Result<ParsedFeed, FeedParseError> {
         title,
         home_page_url: feed.home_page_url,
         feed_url: feed.feed_url,
+        next_url: feed.next_url,
+        prev_archive_url: None,
+        ttl_minutes: None,
+        skip_hours: Vec::new(),
         entries,
     })
 }
@@ -111,7 +477,7 @@ fn entry_from_xml(entry: &Entry) -> ParsedEntry {
             .links
             .first()
             .map(|link| link.href.clone())
-            .unwrap_or_else(|| "unknown".to_string())
+            .unwrap_or_else(|| UNKNOWN_ENTRY_ID.to_string())
     } else {
         entry.id.clone()
     };
@@ -146,6 +512,7 @@ fn entry_from_xml(entry: &Entry) -> ParsedEntry {
 }
 
 fn trim_leading_ascii_whitespace(raw: &[u8]) -> &[u8] {
+    let raw = raw.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(raw);
     let mut index = 0;
     while index < raw.len() && raw[index].is_ascii_whitespace() {
         index += 1;
@@ -157,6 +524,18 @@ fn trim_leading_ascii_whitespace(raw: &[u8]) -> &[u8] {
 mod tests {
     use super::*;
 
+    #[test]
+    fn detects_parked_domain_pages() {
+        let parked = b"<!doctype html><html><head><title>example.com</title></head><body><h1>This domain is for sale!</h1></body></html>";
+        let rss = include_bytes!("../../../../fixtures/import-samples/sample.rss.xml");
+
+        assert!(looks_like_parked_domain(parked));
+        assert!(!looks_like_parked_domain(rss));
+        assert!(!looks_like_parked_domain(
+            b"<html><body>Welcome</body></html>"
+        ));
+    }
+
     #[test]
     fn parses_xml_fixture_feed() {
         let xml = include_bytes!("../../../../fixtures/import-samples/sample.rss.xml");
@@ -167,6 +546,22 @@ mod tests {
         assert_eq!(parsed.entries.len(), 2);
     }
 
+    #[test]
+    fn reads_rss_ttl_and_skip_hours() {
+        let xml = br#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+  <title>Hints</title>
+  <ttl>90</ttl>
+  <skipHours><hour>3</hour><hour>24</hour><hour>1</hour><hour>3</hour></skipHours>
+  <item><title>One</title><link>https://example.com/1</link><ttl>5</ttl></item>
+</channel></rss>"#;
+        let parsed = parse_feed_bytes(xml).expect("xml must parse");
+
+        assert_eq!(parsed.ttl_minutes, Some(90));
+        assert_eq!(parsed.skip_hours, vec![0, 1, 3]);
+        assert_eq!(parsed.entries.len(), 1);
+    }
+
     #[test]
     fn parses_json_feed() {
         let json = include_bytes!("../../../../fixtures/import-samples/sample.jsonfeed.json");
@@ -177,6 +572,76 @@ mod tests {
         assert_eq!(parsed.entries[0].title, "First entry");
     }
 
+    #[test]
+    fn content_type_dispatch_handles_bom_and_html_masqueraders() {
+        let json = include_bytes!("../../../../fixtures/import-samples/sample.jsonfeed.json");
+        let with_bom = [b"\xEF\xBB\xBF".as_slice(), json.as_slice()].concat();
+        let parsed =
+            parse_feed_with_content_type(&with_bom, Some("application/feed+json; charset=utf-8"))
+                .expect("json feed with bom must parse");
+        assert_eq!(parsed.format, FeedFormat::JsonFeed);
+
+        let html = b"<!DOCTYPE html><html><body>Sign in</body></html>";
+        assert!(matches!(
+            parse_feed_with_content_type(html, Some("text/html")),
+            Err(FeedParseError::HtmlPage(Some(kind))) if kind == "text/html"
+        ));
+
+        let error = parse_feed_with_content_type(b"<rss><channel>", Some("application/json"))
+            .expect_err("truncated xml must fail");
+        assert!(error
+            .to_string()
+            .starts_with("content-type application/json does not match the xml payload"));
+    }
+
+    #[test]
+    fn tolerates_garbage_nuls_and_bare_ampersands() {
+        let raw = b"\xEF\xBB\xBF<br /><b>Notice</b>: deprecated\n<?xml version=\"1.0\"?>\0<rss version=\"2.0\"><channel><title>Tom & Jerry&nbsp;Blog</title><item><title>Q&A</title><link>https://example.com/qa?a=1&b=2</link></item></channel></rss>";
+        let feed = parse_feed_bytes(raw).expect("malformed feed should be repaired");
+
+        assert_eq!(feed.title, "Tom & Jerry\u{a0}Blog");
+        assert_eq!(feed.entries.len(), 1);
+        assert_eq!(feed.entries[0].title, "Q&A");
+        assert_eq!(feed.entries[0].link, "https://example.com/qa?a=1&b=2");
+    }
+
+    #[test]
+    fn large_xml_feed_uses_streaming_path() {
+        let item = format!(
+            "<item><title>Large</title><link>https://example.com/large</link><description>{}</description></item>",
+            "y".repeat(MAX_ENTRY_FIELD_BYTES * 2)
+        );
+        let xml = format!(
+            "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel><title>Large Feed</title>{}</channel></rss>",
+            item.repeat(STREAMING_THRESHOLD_BYTES / item.len() + 1)
+        );
+        let parsed = parse_feed_bytes(xml.as_bytes()).expect("large feed must parse");
+
+        assert_eq!(parsed.title, "Large Feed");
+        assert!(parsed.entries.len() > 1);
+        assert!(parsed
+            .entries
+            .iter()
+            .all(|entry| entry.summary.as_deref().map(str::len) == Some(MAX_ENTRY_FIELD_BYTES)));
+    }
+
+    #[test]
+    fn entry_fingerprint_changes_with_content() {
+        let mut entry = ParsedEntry {
+            id: "entry-1".to_string(),
+            title: "Title".to_string(),
+            link: "https://example.com/entry".to_string(),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+        let before = build_entry_fingerprint(&entry);
+        assert_eq!(before, build_entry_fingerprint(&entry));
+
+        entry.summary = Some("edited".to_string());
+        assert_ne!(before, build_entry_fingerprint(&entry));
+    }
+
     #[test]
     fn dedup_key_prefers_entry_id() {
         let entry = ParsedEntry {
@@ -189,5 +654,72 @@ mod tests {
         };
         let key = build_dedup_key("https://example.com/feed.xml", &entry);
         assert_eq!(key, "https://example.com/feed.xml::id::entry-1");
+
+        let url_guid = ParsedEntry {
+            id: " https://example.com/?p=42&utm_source=rss#v2 ".to_string(),
+            ..entry
+        };
+        assert_eq!(
+            build_dedup_key("https://example.com/feed.xml", &url_guid),
+            "https://example.com/feed.xml::id::https://example.com/?p=42&utm_source=rss#v2"
+        );
+    }
+
+    #[test]
+    fn sanitizer_strips_scripts_frames_and_tracking_params() {
+        let allowlist = SanitizerAllowlist::default();
+        let sanitizer = allowlist.sanitizer();
+        let entry = ParsedEntry {
+            id: "https://example.com/post".to_string(),
+            title: "Post".to_string(),
+            link: "https://example.com/post".to_string(),
+            summary: Some("Tom & Jerry<script>alert(1)</script>".to_string()),
+            content: Some(
+                "<p onclick=\"steal()\">Hi <a href=\"https://example.com/a?utm_source=feed&id=2#intro\">there</a></p><iframe src=\"https://ads.example.net\"></iframe><a href=\"javascript:alert(1)\">x</a>".to_string(),
+            ),
+            published_at: None,
+        };
+
+        let sanitized = sanitize_entry(&entry, &sanitizer);
+        assert_eq!(sanitized.summary.as_deref(), Some("Tom &amp; Jerry"));
+        assert_eq!(
+            sanitized.content.as_deref(),
+            Some("<p>Hi <a href=\"https://example.com/a?utm_source=feed&amp;id=2#intro\" rel=\"noopener noreferrer\">there</a></p><a rel=\"noopener noreferrer\">x</a>")
+        );
+
+        let custom = SanitizerAllowlist {
+            tags: vec!["p".to_string(), "script".to_string()],
+            attributes: Vec::new(),
+            url_schemes: vec!["javascript".to_string()],
+        };
+        assert_eq!(
+            custom
+                .sanitizer()
+                .clean("<p><b>bold</b></p><script>x()</script>")
+                .to_string(),
+            "<p>bold</p>"
+        );
+    }
+
+    #[test]
+    fn canonical_links_drop_tracking_params_and_fragments() {
+        assert_eq!(
+            canonicalize_link(
+                " HTTPS://Example.COM:443/post?id=7&utm_source=rss&UTM_Medium=feed&fbclid=abc#comments "
+            ),
+            "https://example.com/post?id=7"
+        );
+        assert_eq!(
+            canonicalize_link("https://example.com/post?utm_campaign=weekly"),
+            "https://example.com/post"
+        );
+        assert_eq!(
+            canonicalize_link("https://example.com/search?q=a+b&page=2"),
+            "https://example.com/search?q=a+b&page=2"
+        );
+        assert_eq!(
+            canonicalize_link("tag:example.com,2026:1"),
+            "tag:example.com,2026:1"
+        );
     }
 }
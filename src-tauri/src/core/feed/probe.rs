@@ -0,0 +1,271 @@
+use std::time::Instant;
+
+use reqwest::header::{HeaderMap, CONTENT_TYPE, LOCATION};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use super::fetcher::feed_request;
+use super::parser::{detect_payload_format, parse_feed_with_content_type};
+use crate::core::redact::{is_secret_key, redact_secrets, REDACTED};
+use crate::core::subscription::discovery::{extract_html_links, AlternateLink};
+
+pub const MAX_PROBE_REDIRECTS: usize = 10;
+const MAX_PROBE_ENTRIES: usize = 5;
+const BODY_PREVIEW_CHARS: usize = 600;
+const SENSITIVE_HEADERS: [&str; 3] = ["set-cookie", "cookie", "www-authenticate"];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProbeError {
+    #[error("invalid feed url: {0}")]
+    InvalidUrl(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProbeHop {
+    pub url: String,
+    pub status: u16,
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProbeHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProbeEntry {
+    pub title: String,
+    pub link: String,
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProbeReport {
+    pub requested_url: String,
+    pub final_url: Option<String>,
+    pub redirects: Vec<ProbeHop>,
+    pub status: Option<u16>,
+    pub headers: Vec<ProbeHeader>,
+    pub content_type: Option<String>,
+    pub detected_format: Option<String>,
+    pub body_bytes: usize,
+    pub elapsed_ms: u64,
+    pub parsed: bool,
+    pub feed_title: Option<String>,
+    pub entry_count: usize,
+    pub entries: Vec<ProbeEntry>,
+    pub alternate_links: Vec<AlternateLink>,
+    pub body_preview: Option<String>,
+    pub error: Option<String>,
+}
+
+pub async fn probe_feed(client: &reqwest::Client, url: &str) -> Result<ProbeReport, ProbeError> {
+    let mut current =
+        Url::parse(url.trim()).map_err(|_| ProbeError::InvalidUrl(url.to_string()))?;
+    let started = Instant::now();
+    let mut report = ProbeReport {
+        requested_url: redact_secrets(current.as_str()),
+        ..ProbeReport::default()
+    };
+
+    let response = loop {
+        let response = match feed_request(client, current.as_str(), None, None)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                report.error = Some(redact_secrets(&error.to_string()));
+                report.elapsed_ms = started.elapsed().as_millis() as u64;
+                return Ok(report);
+            }
+        };
+        let status = response.status();
+        let location = status
+            .is_redirection()
+            .then(|| response.headers().get(LOCATION))
+            .flatten()
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| current.join(value).ok());
+        report.redirects.push(ProbeHop {
+            url: redact_secrets(current.as_str()),
+            status: status.as_u16(),
+            location: location.as_ref().map(|next| redact_secrets(next.as_str())),
+        });
+        match location {
+            Some(next) if report.redirects.len() <= MAX_PROBE_REDIRECTS => current = next,
+            Some(_) => {
+                report.error = Some(format!("more than {MAX_PROBE_REDIRECTS} redirects"));
+                break response;
+            }
+            None => break response,
+        }
+    };
+
+    report.final_url = Some(redact_secrets(current.as_str()));
+    report.status = Some(response.status().as_u16());
+    report.headers = describe_headers(response.headers());
+    report.content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+    let success = response.status().is_success();
+    let body = match response.bytes().await {
+        Ok(body) => body,
+        Err(error) => {
+            report.error = Some(redact_secrets(&error.to_string()));
+            report.elapsed_ms = started.elapsed().as_millis() as u64;
+            return Ok(report);
+        }
+    };
+    report.elapsed_ms = started.elapsed().as_millis() as u64;
+    describe_body(&mut report, &body, &current);
+    if !success && report.error.is_none() {
+        report.error = Some(format!(
+            "unexpected status code: {}",
+            report.status.unwrap_or_default()
+        ));
+    }
+    Ok(report)
+}
+
+fn describe_headers(headers: &HeaderMap) -> Vec<ProbeHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str().to_string();
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) || is_secret_key(&name) {
+                REDACTED.to_string()
+            } else {
+                redact_secrets(&String::from_utf8_lossy(value.as_bytes()))
+            };
+            ProbeHeader { name, value }
+        })
+        .collect()
+}
+
+fn describe_body(report: &mut ProbeReport, body: &[u8], base: &Url) {
+    report.body_bytes = body.len();
+    report.detected_format =
+        Some(detect_payload_format(body, report.content_type.as_deref()).to_string());
+    match parse_feed_with_content_type(body, report.content_type.as_deref()) {
+        Ok(feed) => {
+            report.parsed = true;
+            report.feed_title = Some(feed.title).filter(|title| !title.trim().is_empty());
+            report.entry_count = feed.entries.len();
+            report.entries = feed
+                .entries
+                .into_iter()
+                .take(MAX_PROBE_ENTRIES)
+                .map(|entry| ProbeEntry {
+                    title: entry.title,
+                    link: entry.link,
+                    published_at: entry.published_at,
+                })
+                .collect();
+        }
+        Err(error) => {
+            let text = String::from_utf8_lossy(body);
+            if report.detected_format.as_deref() == Some("html") {
+                report.alternate_links = extract_html_links(&text, base).feeds;
+            }
+            report.body_preview = Some(redact_secrets(
+                &text.chars().take(BODY_PREVIEW_CHARS).collect::<String>(),
+            ));
+            report.error = Some(format!("parse failed: {error}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::feed::fetcher::feed_client_builder;
+    use axum::http::header;
+    use axum::response::{IntoResponse, Redirect};
+    use axum::routing::get;
+    use axum::Router;
+
+    const RSS: &str = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Probe Posts</title><item><title>One</title><link>https://blog.example.com/1</link></item><item><title>Two</title><link>https://blog.example.com/2</link></item></channel></rss>"#;
+    const HOME: &str = r#"<!doctype html><html><head><link rel="alternate" type="application/rss+xml" href="/feed.xml"></head><body>Home</body></html>"#;
+
+    async fn serve() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let base = format!("http://{}", listener.local_addr().expect("local addr"));
+        let app = Router::new()
+            .route("/old", get(|| async { Redirect::permanent("/feed.xml") }))
+            .route(
+                "/feed.xml",
+                get(|| async {
+                    (
+                        [
+                            (header::CONTENT_TYPE, "application/rss+xml"),
+                            (header::SET_COOKIE, "session=abc"),
+                        ],
+                        RSS,
+                    )
+                        .into_response()
+                }),
+            )
+            .route("/", get(|| async { axum::response::Html(HOME) }));
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        base
+    }
+
+    fn client() -> reqwest::Client {
+        feed_client_builder(10)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("client should build")
+    }
+
+    #[tokio::test]
+    async fn follows_redirects_and_reports_parsed_entries() {
+        let base = serve().await;
+        let report = probe_feed(&client(), &format!("{base}/old"))
+            .await
+            .expect("probe should run");
+
+        assert_eq!(report.redirects.len(), 2);
+        assert_eq!(report.redirects[0].status, 308);
+        assert_eq!(
+            report.redirects[0].location.as_deref(),
+            Some(format!("{base}/feed.xml").as_str())
+        );
+        assert_eq!(report.final_url, Some(format!("{base}/feed.xml")));
+        assert_eq!(report.detected_format.as_deref(), Some("xml"));
+        assert!(report.parsed);
+        assert_eq!(report.feed_title.as_deref(), Some("Probe Posts"));
+        assert_eq!(report.entry_count, 2);
+        assert_eq!(report.entries[0].title, "One");
+        assert!(report
+            .headers
+            .iter()
+            .any(|header| header.name == "set-cookie" && header.value == REDACTED));
+        assert!(report.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn html_pages_report_parse_error_and_alternates() {
+        let base = serve().await;
+        let report = probe_feed(&client(), &format!("{base}/"))
+            .await
+            .expect("probe should run");
+
+        assert!(!report.parsed);
+        assert_eq!(report.detected_format.as_deref(), Some("html"));
+        assert_eq!(report.alternate_links.len(), 1);
+        assert_eq!(report.alternate_links[0].url, format!("{base}/feed.xml"));
+        assert!(report.body_preview.is_some());
+        assert!(report
+            .error
+            .as_deref()
+            .is_some_and(|error| error.starts_with("parse failed")));
+    }
+}
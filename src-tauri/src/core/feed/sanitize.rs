@@ -0,0 +1,142 @@
+const XML_DECLARATION: &[u8] = b"<?xml";
+const MAX_LEADING_GARBAGE_BYTES: usize = 4096;
+const MAX_ENTITY_NAME_BYTES: usize = 32;
+const XML_ENTITIES: [&str; 5] = ["amp", "lt", "gt", "quot", "apos"];
+const HTML_ENTITIES: [(&str, u32); 14] = [
+    ("nbsp", 160),
+    ("copy", 169),
+    ("reg", 174),
+    ("laquo", 171),
+    ("raquo", 187),
+    ("ndash", 8211),
+    ("mdash", 8212),
+    ("lsquo", 8216),
+    ("rsquo", 8217),
+    ("ldquo", 8220),
+    ("rdquo", 8221),
+    ("bull", 8226),
+    ("hellip", 8230),
+    ("trade", 8482),
+];
+const VERBATIM_SECTIONS: [(&[u8], &[u8]); 2] = [(b"<![CDATA[", b"]]>"), (b"<!--", b"-->")];
+
+pub fn repair_xml(raw: &[u8]) -> Option<Vec<u8>> {
+    let raw = raw.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(raw);
+    let start = document_start(raw);
+    let mut output = Vec::with_capacity(raw.len() + 64);
+    let mut index = start;
+    while index < raw.len() {
+        if let Some(end) = verbatim_section_end(raw, index) {
+            output.extend_from_slice(&raw[index..end]);
+            index = end;
+            continue;
+        }
+        match raw[index] {
+            b'&' => index = repair_entity(raw, index, &mut output),
+            byte if is_forbidden_control(byte) => index += 1,
+            byte => {
+                output.push(byte);
+                index += 1;
+            }
+        }
+    }
+    (output.as_slice() != raw).then_some(output)
+}
+
+fn document_start(raw: &[u8]) -> usize {
+    let head = &raw[..raw.len().min(MAX_LEADING_GARBAGE_BYTES)];
+    if let Some(position) = head
+        .windows(XML_DECLARATION.len())
+        .position(|window| window == XML_DECLARATION)
+    {
+        return position;
+    }
+    head.iter().position(|byte| *byte == b'<').unwrap_or(0)
+}
+
+fn verbatim_section_end(raw: &[u8], index: usize) -> Option<usize> {
+    let (open, close) = VERBATIM_SECTIONS
+        .iter()
+        .find(|(open, _)| raw[index..].starts_with(open))?;
+    let body = index + open.len();
+    let end = raw[body..]
+        .windows(close.len())
+        .position(|window| window == *close)
+        .map_or(raw.len(), |offset| body + offset + close.len());
+    Some(end)
+}
+
+fn is_forbidden_control(byte: u8) -> bool {
+    byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r')
+}
+
+fn repair_entity(raw: &[u8], index: usize, output: &mut Vec<u8>) -> usize {
+    let rest = &raw[index + 1..];
+    let name_len = rest
+        .iter()
+        .take(MAX_ENTITY_NAME_BYTES)
+        .position(|byte| *byte == b';')
+        .filter(|len| *len > 0);
+    let Some(name_len) = name_len else {
+        output.extend_from_slice(b"&amp;");
+        return index + 1;
+    };
+    let name = String::from_utf8_lossy(&rest[..name_len]);
+    let end = index + name_len + 2;
+    if XML_ENTITIES.contains(&name.as_ref()) || is_char_reference(&name) {
+        output.extend_from_slice(&raw[index..end]);
+        return end;
+    }
+    match HTML_ENTITIES
+        .iter()
+        .find(|(entity, _)| *entity == name.as_ref())
+    {
+        Some((_, code)) => {
+            output.extend_from_slice(format!("&#{code};").as_bytes());
+            end
+        }
+        None => {
+            output.extend_from_slice(b"&amp;");
+            index + 1
+        }
+    }
+}
+
+fn is_char_reference(name: &str) -> bool {
+    match name.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => {
+            hex.len() > 1 && hex[1..].chars().all(|ch| ch.is_ascii_hexdigit())
+        }
+        Some(decimal) => !decimal.is_empty() && decimal.chars().all(|ch| ch.is_ascii_digit()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_leading_garbage_and_control_bytes() {
+        let raw = b"\xEF\xBB\xBFWarning: headers sent\n<?xml version=\"1.0\"?><rss>\0<title>A\x0Bb</title></rss>";
+        let repaired = repair_xml(raw).expect("payload should be repaired");
+
+        assert_eq!(
+            String::from_utf8(repaired).expect("utf8"),
+            "<?xml version=\"1.0\"?><rss><title>Ab</title></rss>"
+        );
+    }
+
+    #[test]
+    fn escapes_bare_ampersands_but_keeps_valid_entities_and_cdata() {
+        let raw = b"<rss><title>Tom & Jerry &mdash; &amp; &#38; &#x26; &bogus;</title><description><![CDATA[a & b]]></description></rss>";
+        let repaired =
+            String::from_utf8(repair_xml(raw).expect("payload should be repaired")).expect("utf8");
+
+        assert_eq!(
+            repaired,
+            "<rss><title>Tom &amp; Jerry &#8212; &amp; &#38; &#x26; &amp;bogus;</title><description><![CDATA[a & b]]></description></rss>"
+        );
+        assert!(repair_xml(b"<rss><title>fine &amp; dandy</title></rss>").is_none());
+    }
+}
@@ -0,0 +1,391 @@
+use chrono::{DateTime, Utc};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::types::{FeedFormat, ParsedEntry, ParsedFeed, UNKNOWN_ENTRY_ID};
+
+pub const STREAMING_THRESHOLD_BYTES: usize = 2 * 1024 * 1024;
+pub const MAX_ENTRY_FIELD_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StreamParseError {
+    #[error("xml stream error: {0}")]
+    Xml(#[from] quick_xml::Error),
+    #[error("unsupported root element: {0}")]
+    UnsupportedRoot(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RootKind {
+    Rss,
+    Atom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Title,
+    Link,
+    Summary,
+    Content,
+    Published,
+    Updated,
+}
+
+#[derive(Debug, Default)]
+struct EntryBuilder {
+    id: Option<String>,
+    title: Option<String>,
+    link: Option<String>,
+    summary: Option<String>,
+    content: Option<String>,
+    published: Option<String>,
+    updated: Option<String>,
+}
+
+impl EntryBuilder {
+    fn set(&mut self, field: Field, value: String) {
+        let slot = match field {
+            Field::Id => &mut self.id,
+            Field::Title => &mut self.title,
+            Field::Link => &mut self.link,
+            Field::Summary => &mut self.summary,
+            Field::Content => &mut self.content,
+            Field::Published => &mut self.published,
+            Field::Updated => &mut self.updated,
+        };
+        if slot.is_none() && !value.trim().is_empty() {
+            *slot = Some(value.trim().to_string());
+        }
+    }
+
+    fn build(self) -> ParsedEntry {
+        let link = self.link.unwrap_or_default();
+        let id = self
+            .id
+            .or_else(|| Some(link.clone()).filter(|value| !value.is_empty()))
+            .unwrap_or_else(|| UNKNOWN_ENTRY_ID.to_string());
+        ParsedEntry {
+            id,
+            title: self.title.unwrap_or_else(|| "Untitled Entry".to_string()),
+            link,
+            summary: self.summary,
+            content: self.content,
+            published_at: self
+                .published
+                .or(self.updated)
+                .and_then(|value| normalize_timestamp(&value)),
+        }
+    }
+}
+
+struct Capture {
+    field: Field,
+    depth: usize,
+    in_entry: bool,
+    buffer: String,
+}
+
+pub fn parse_xml_feed_streaming(
+    raw: &[u8],
+    max_field_bytes: usize,
+) -> Result<ParsedFeed, StreamParseError> {
+    let mut reader = Reader::from_reader(raw);
+    reader.config_mut().trim_text(true);
+
+    let mut root: Option<RootKind> = None;
+    let mut stack: Vec<String> = Vec::new();
+    let mut feed = EntryBuilder::default();
+    let mut current: Option<EntryBuilder> = None;
+    let mut capture: Option<Capture> = None;
+    let mut entries = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let name = element_name(&element);
+                if let Some(active) = capture.as_mut() {
+                    push_capped(
+                        &mut active.buffer,
+                        &format!("<{}>", String::from_utf8_lossy(&element)),
+                        max_field_bytes,
+                    );
+                    stack.push(name);
+                    continue;
+                }
+                let kind = match root {
+                    Some(kind) => kind,
+                    None => {
+                        let kind = root_kind(&name)?;
+                        root = Some(kind);
+                        kind
+                    }
+                };
+                let parent = stack.last().cloned().unwrap_or_default();
+                let in_entry = current.is_some();
+                stack.push(name.clone());
+
+                if is_entry_element(kind, &name) {
+                    current = Some(EntryBuilder::default());
+                    continue;
+                }
+                if kind == RootKind::Atom && name == "link" {
+                    apply_atom_link(&element, in_entry, &mut current, &mut feed);
+                    continue;
+                }
+                if let Some(field) = field_for(kind, &parent, &name) {
+                    capture = Some(Capture {
+                        field,
+                        depth: stack.len(),
+                        in_entry,
+                        buffer: String::new(),
+                    });
+                }
+            }
+            Event::Empty(element) => {
+                if let Some(active) = capture.as_mut() {
+                    push_capped(
+                        &mut active.buffer,
+                        &format!("<{}/>", String::from_utf8_lossy(&element)),
+                        max_field_bytes,
+                    );
+                    continue;
+                }
+                if root == Some(RootKind::Atom) && element_name(&element) == "link" {
+                    let in_entry = current.is_some();
+                    apply_atom_link(&element, in_entry, &mut current, &mut feed);
+                }
+            }
+            Event::Text(text) => {
+                if let Some(active) = capture.as_mut() {
+                    let value = match text.unescape() {
+                        Ok(value) => value.into_owned(),
+                        Err(_) => String::from_utf8_lossy(&text).into_owned(),
+                    };
+                    push_capped(&mut active.buffer, &value, max_field_bytes);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(active) = capture.as_mut() {
+                    push_capped(
+                        &mut active.buffer,
+                        &String::from_utf8_lossy(&data),
+                        max_field_bytes,
+                    );
+                }
+            }
+            Event::End(_) => {
+                let depth = stack.len();
+                let name = stack.pop().unwrap_or_default();
+                if let Some(active) = capture.take() {
+                    if active.depth != depth {
+                        let mut active = active;
+                        push_capped(&mut active.buffer, &format!("</{name}>"), max_field_bytes);
+                        capture = Some(active);
+                        continue;
+                    }
+                    let target = if active.in_entry {
+                        current.as_mut()
+                    } else {
+                        Some(&mut feed)
+                    };
+                    if let Some(target) = target {
+                        target.set(active.field, active.buffer);
+                    }
+                    continue;
+                }
+                if root.is_some_and(|kind| is_entry_element(kind, &name)) {
+                    if let Some(entry) = current.take() {
+                        entries.push(entry.build());
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if root.is_none() {
+        return Err(StreamParseError::UnsupportedRoot(String::new()));
+    }
+    Ok(ParsedFeed {
+        format: FeedFormat::XmlFeed,
+        title: feed.title.unwrap_or_else(|| "Untitled Feed".to_string()),
+        home_page_url: feed.link,
+        feed_url: None,
+        next_url: None,
+        prev_archive_url: None,
+        ttl_minutes: None,
+        skip_hours: Vec::new(),
+        entries,
+    })
+}
+
+fn element_name(element: &BytesStart<'_>) -> String {
+    String::from_utf8_lossy(element.name().as_ref()).to_lowercase()
+}
+
+fn root_kind(name: &str) -> Result<RootKind, StreamParseError> {
+    match name {
+        "rss" | "rdf:rdf" => Ok(RootKind::Rss),
+        "feed" => Ok(RootKind::Atom),
+        other => Err(StreamParseError::UnsupportedRoot(other.to_string())),
+    }
+}
+
+fn is_entry_element(kind: RootKind, name: &str) -> bool {
+    match kind {
+        RootKind::Rss => name == "item",
+        RootKind::Atom => name == "entry",
+    }
+}
+
+fn field_for(kind: RootKind, parent: &str, name: &str) -> Option<Field> {
+    match (kind, parent) {
+        (RootKind::Rss, "channel") => match name {
+            "title" => Some(Field::Title),
+            "link" => Some(Field::Link),
+            _ => None,
+        },
+        (RootKind::Rss, "item") => match name {
+            "guid" => Some(Field::Id),
+            "title" => Some(Field::Title),
+            "link" => Some(Field::Link),
+            "description" => Some(Field::Summary),
+            "content:encoded" => Some(Field::Content),
+            "pubdate" => Some(Field::Published),
+            "dc:date" => Some(Field::Updated),
+            _ => None,
+        },
+        (RootKind::Atom, "feed") => match name {
+            "title" => Some(Field::Title),
+            _ => None,
+        },
+        (RootKind::Atom, "entry") => match name {
+            "id" => Some(Field::Id),
+            "title" => Some(Field::Title),
+            "summary" => Some(Field::Summary),
+            "content" => Some(Field::Content),
+            "published" => Some(Field::Published),
+            "updated" => Some(Field::Updated),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn apply_atom_link(
+    element: &BytesStart<'_>,
+    in_entry: bool,
+    current: &mut Option<EntryBuilder>,
+    feed: &mut EntryBuilder,
+) {
+    let attribute = |key: &str| {
+        element
+            .try_get_attribute(key)
+            .ok()
+            .flatten()
+            .and_then(|value| value.unescape_value().ok().map(|value| value.into_owned()))
+    };
+    let is_alternate = attribute("rel").is_none_or(|rel| rel == "alternate");
+    let Some(href) = attribute("href").filter(|_| is_alternate) else {
+        return;
+    };
+    let target = if in_entry {
+        current.as_mut()
+    } else {
+        Some(feed)
+    };
+    if let Some(target) = target {
+        target.set(Field::Link, href);
+    }
+}
+
+fn push_capped(buffer: &mut String, value: &str, max_bytes: usize) {
+    let remaining = max_bytes.saturating_sub(buffer.len());
+    if remaining == 0 {
+        return;
+    }
+    if value.len() <= remaining {
+        buffer.push_str(value);
+        return;
+    }
+    let mut end = remaining;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    buffer.push_str(&value[..end]);
+}
+
+fn normalize_timestamp(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_rfc2822(value))
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc).to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::feed::parser::parse_feed_bytes;
+
+    #[test]
+    fn streaming_matches_dom_parser_for_rss_fixture() {
+        let xml = include_bytes!("../../../../fixtures/import-samples/sample.rss.xml");
+        let streamed = parse_xml_feed_streaming(xml, MAX_ENTRY_FIELD_BYTES)
+            .expect("streaming parse should succeed");
+        let dom = parse_feed_bytes(xml).expect("dom parse should succeed");
+
+        assert_eq!(streamed.title, dom.title);
+        assert_eq!(streamed.entries.len(), dom.entries.len());
+        for (left, right) in streamed.entries.iter().zip(dom.entries.iter()) {
+            assert_eq!(left.id, right.id);
+            assert_eq!(left.title, right.title);
+            assert_eq!(left.link, right.link);
+            assert_eq!(left.published_at, right.published_at);
+        }
+    }
+
+    #[test]
+    fn streaming_parses_atom_and_caps_large_fields() {
+        let huge = "x".repeat(4096);
+        let atom = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Atom Stream</title>
+              <link rel="self" href="https://example.com/atom.xml"/>
+              <link href="https://example.com/"/>
+              <entry>
+                <id>tag:example.com,2026:1</id>
+                <title>Big &amp; bold</title>
+                <link rel="alternate" href="https://example.com/posts/1"/>
+                <updated>2026-02-24T10:00:00Z</updated>
+                <content type="html"><![CDATA[<p>{huge}</p>]]></content>
+              </entry>
+            </feed>"#
+        );
+        let parsed =
+            parse_xml_feed_streaming(atom.as_bytes(), 1024).expect("atom stream should parse");
+
+        assert_eq!(parsed.title, "Atom Stream");
+        assert_eq!(
+            parsed.home_page_url.as_deref(),
+            Some("https://example.com/")
+        );
+        assert_eq!(parsed.entries.len(), 1);
+        let entry = &parsed.entries[0];
+        assert_eq!(entry.title, "Big & bold");
+        assert_eq!(entry.link, "https://example.com/posts/1");
+        assert_eq!(
+            entry.published_at.as_deref(),
+            Some("2026-02-24T10:00:00+00:00")
+        );
+        assert_eq!(entry.content.as_deref().map(str::len), Some(1024));
+    }
+
+    #[test]
+    fn streaming_rejects_unknown_root() {
+        let result = parse_xml_feed_streaming(b"<opml><body/></opml>", MAX_ENTRY_FIELD_BYTES);
+        assert!(matches!(result, Err(StreamParseError::UnsupportedRoot(_))));
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+pub const UNKNOWN_ENTRY_ID: &str = "unknown";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum FeedFormat {
     XmlFeed,
@@ -16,11 +18,24 @@ pub struct ParsedEntry {
     pub published_at: Option<String>,
 }
 
+impl ParsedEntry {
+    pub fn guid(&self) -> Option<&str> {
+        let id = self.id.trim();
+        (!id.is_empty() && id != UNKNOWN_ENTRY_ID).then_some(id)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ParsedFeed {
     pub format: FeedFormat,
     pub title: String,
     pub home_page_url: Option<String>,
     pub feed_url: Option<String>,
+    pub next_url: Option<String>,
+    pub prev_archive_url: Option<String>,
+    #[serde(default)]
+    pub ttl_minutes: Option<u32>,
+    #[serde(default)]
+    pub skip_hours: Vec<u32>,
     pub entries: Vec<ParsedEntry>,
 }
@@ -0,0 +1,130 @@
+use chrono::DateTime;
+use quick_xml::escape::escape;
+
+use crate::core::storage::models::EntryRecord;
+
+#[derive(Debug, Clone, Default)]
+pub struct FeedChannel {
+    pub title: String,
+    pub link: Option<String>,
+    pub description: String,
+}
+
+pub fn build_rss_feed(channel: &FeedChannel, entries: &[EntryRecord]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str("<rss version=\"2.0\">\n  <channel>\n");
+    output.push_str(&format!("    <title>{}</title>\n", escape(&channel.title)));
+    if let Some(link) = channel.link.as_deref() {
+        output.push_str(&format!("    <link>{}</link>\n", escape(link)));
+    }
+    output.push_str(&format!(
+        "    <description>{}</description>\n",
+        escape(&channel.description)
+    ));
+    if let Some(updated) = entries
+        .iter()
+        .filter_map(|entry| entry.published_at.as_deref().and_then(rfc2822))
+        .next()
+    {
+        output.push_str(&format!("    <lastBuildDate>{updated}</lastBuildDate>\n"));
+    }
+    for entry in entries {
+        output.push_str(&rss_item(entry));
+    }
+    output.push_str("  </channel>\n</rss>\n");
+    output
+}
+
+fn rss_item(entry: &EntryRecord) -> String {
+    let guid = entry
+        .guid
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or(&entry.link);
+    let mut item = String::from("    <item>\n");
+    item.push_str(&format!("      <title>{}</title>\n", escape(&entry.title)));
+    item.push_str(&format!("      <link>{}</link>\n", escape(&entry.link)));
+    item.push_str(&format!(
+        "      <guid isPermaLink=\"{}\">{}</guid>\n",
+        guid == entry.link,
+        escape(guid)
+    ));
+    item.push_str(&format!(
+        "      <source>{}</source>\n",
+        escape(&entry.source_title)
+    ));
+    if let Some(published) = entry.published_at.as_deref().and_then(rfc2822) {
+        item.push_str(&format!("      <pubDate>{published}</pubDate>\n"));
+    }
+    if let Some(summary) = entry
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        item.push_str(&format!(
+            "      <description>{}</description>\n",
+            escape(summary)
+        ));
+    }
+    item.push_str("    </item>\n");
+    item
+}
+
+fn rfc2822(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_rfc2822(value))
+        .ok()
+        .map(|timestamp| timestamp.to_rfc2822())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::feed::parser::parse_feed_bytes;
+
+    fn starred(id: i64, title: &str, summary: Option<&str>) -> EntryRecord {
+        EntryRecord {
+            id,
+            source_id: 1,
+            source_title: "Example Blog".to_string(),
+            guid: None,
+            link: format!("https://example.com/posts/{id}"),
+            title: title.to_string(),
+            translated_title: None,
+            summary: summary.map(str::to_string),
+            content: None,
+            published_at: Some("2026-03-01T08:30:00+00:00".to_string()),
+            is_read: 1,
+            is_starred: 1,
+            created_at: "2026-03-01T08:30:00+00:00".to_string(),
+            updated_at: None,
+            is_updated: false,
+        }
+    }
+
+    #[test]
+    fn builds_parseable_rss_with_summaries() {
+        let channel = FeedChannel {
+            title: "My <linkblog>".to_string(),
+            link: Some("https://me.example.com/".to_string()),
+            description: "Starred items".to_string(),
+        };
+        let entries = vec![
+            starred(1, "Rust & friends", Some("<p>Great read</p>")),
+            starred(2, "No summary", None),
+        ];
+        let xml = build_rss_feed(&channel, &entries);
+        let parsed = parse_feed_bytes(xml.as_bytes()).expect("generated feed should parse");
+
+        assert_eq!(parsed.title, "My <linkblog>");
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.entries[0].title, "Rust & friends");
+        assert_eq!(parsed.entries[0].link, "https://example.com/posts/1");
+        assert_eq!(
+            parsed.entries[0].summary.as_deref(),
+            Some("<p>Great read</p>")
+        );
+        assert!(xml.contains("<pubDate>Sun, 1 Mar 2026 08:30:00 +0000</pubDate>"));
+    }
+}
@@ -1,21 +1,105 @@
 use std::collections::{HashMap, HashSet};
 
+use quick_xml::escape::escape;
+use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
 
+pub const RSSR_OPML_NAMESPACE: &str = "https://github.com/oaeen/rssr/opml";
+pub const MAX_HTML_IMPORT_LINKS: usize = 300;
+const SHARED_FEED_HOSTS: [&str; 9] = [
+    "feeds.feedburner.com",
+    "feedproxy.google.com",
+    "medium.com",
+    "substack.com",
+    "github.com",
+    "youtube.com",
+    "reddit.com",
+    "news.google.com",
+    "rsshub.app",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ImportSource {
     pub title: String,
     pub feed_url: String,
     pub site_url: Option<String>,
     pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rssr: Option<RssrSourceAttributes>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RssrSourceAttributes {
+    pub is_active: Option<bool>,
+    pub color: Option<String>,
+    pub custom_icon: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ImportPreview {
     pub new_sources: Vec<ImportSource>,
     pub duplicate_sources: Vec<ImportSource>,
+    pub possible_duplicates: Vec<ImportSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CategorySuggestion {
+    pub feed_url: String,
+    pub category: String,
+    pub reason: String,
 }
 
+const CATEGORY_KEYWORDS: [(&str, &[&str]); 8] = [
+    (
+        "Tech",
+        &[
+            "tech",
+            "dev",
+            "developer",
+            "code",
+            "coding",
+            "programming",
+            "software",
+            "engineering",
+            "rust",
+            "python",
+            "javascript",
+            "golang",
+            "linux",
+            "opensource",
+        ],
+    ),
+    (
+        "AI",
+        &["ai", "ml", "llm", "gpt", "neural", "machinelearning"],
+    ),
+    (
+        "Security",
+        &["security", "infosec", "privacy", "exploit", "malware"],
+    ),
+    ("Design", &["design", "ux", "ui", "typography", "figma"]),
+    (
+        "Science",
+        &[
+            "science",
+            "research",
+            "physics",
+            "biology",
+            "chemistry",
+            "space",
+            "astronomy",
+        ],
+    ),
+    (
+        "Business",
+        &[
+            "business", "finance", "startup", "startups", "economy", "market", "markets",
+        ],
+    ),
+    ("News", &["news", "daily", "headlines", "times", "journal"]),
+    ("Games", &["game", "games", "gaming"]),
+];
+
 #[derive(Debug, thiserror::Error)]
 pub enum ImportError {
     #[error("invalid OPML content: {0}")]
@@ -37,15 +121,47 @@ impl ImporterService {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum JsonImportDocument {
+    List(Vec<JsonImportItem>),
+    Export {
+        #[serde(default, alias = "subscriptions", alias = "items")]
+        feeds: Vec<JsonImportItem>,
+        #[serde(default)]
+        folders: Vec<JsonImportItem>,
+    },
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
 enum JsonImportItem {
     Url(String),
+    Folder {
+        #[serde(alias = "title")]
+        name: String,
+        #[serde(alias = "children", alias = "subscriptions")]
+        feeds: Vec<JsonImportItem>,
+    },
     Object {
+        #[serde(alias = "feedUrl", alias = "feedURL", alias = "xmlUrl", alias = "url")]
         feed_url: String,
+        #[serde(default, alias = "name")]
         title: Option<String>,
+        #[serde(default, rename = "editedName")]
+        edited_name: Option<String>,
+        #[serde(
+            default,
+            alias = "siteUrl",
+            alias = "homePageURL",
+            alias = "home_page_url",
+            alias = "htmlUrl"
+        )]
         site_url: Option<String>,
+        #[serde(default, alias = "folder", alias = "tag")]
         category: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
     },
 }
 
@@ -79,6 +195,7 @@ pub fn parse_opml(opml_content: &str) -> Result<Vec<ImportSource>, ImportError>
             feed_url: feed_url.to_string(),
             site_url: node.attribute("htmlUrl").map(ToString::to_string),
             category,
+            rssr: read_rssr_attributes(node),
         };
         results.push(source);
     }
@@ -86,6 +203,55 @@ pub fn parse_opml(opml_content: &str) -> Result<Vec<ImportSource>, ImportError>
     Ok(results)
 }
 
+pub fn export_opml(title: &str, sources: &[ImportSource]) -> String {
+    let mut groups = Vec::<(Option<&str>, Vec<&ImportSource>)>::new();
+    for source in sources {
+        let category = source
+            .category
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty());
+        match groups.iter_mut().find(|(name, _)| *name == category) {
+            Some((_, items)) => items.push(source),
+            None => groups.push((category, vec![source])),
+        }
+    }
+    let include_rssr = sources.iter().any(|source| source.rssr.is_some());
+
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    if include_rssr {
+        output.push_str(&format!(
+            "<opml version=\"2.0\" xmlns:rssr=\"{RSSR_OPML_NAMESPACE}\">\n"
+        ));
+    } else {
+        output.push_str("<opml version=\"2.0\">\n");
+    }
+    output.push_str(&format!(
+        "  <head>\n    <title>{}</title>\n  </head>\n  <body>\n",
+        escape(title)
+    ));
+    for (category, items) in groups {
+        let indent = match category {
+            Some(name) => {
+                let name = escape(name);
+                output.push_str(&format!("    <outline text=\"{name}\" title=\"{name}\">\n"));
+                "      "
+            }
+            None => "    ",
+        };
+        for source in items {
+            output.push_str(indent);
+            output.push_str(&opml_outline(source));
+            output.push('\n');
+        }
+        if category.is_some() {
+            output.push_str("    </outline>\n");
+        }
+    }
+    output.push_str("  </body>\n</opml>\n");
+    output
+}
+
 pub fn parse_url_list(input: &str) -> Vec<ImportSource> {
     input
         .lines()
@@ -97,49 +263,96 @@ pub fn parse_url_list(input: &str) -> Vec<ImportSource> {
             feed_url: line.to_string(),
             site_url: None,
             category: None,
+            rssr: None,
+        })
+        .collect()
+}
+
+pub fn extract_html_links(html: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a[href]").expect("selector must parse");
+    let mut seen = HashSet::new();
+    document
+        .select(&selector)
+        .filter_map(|anchor| anchor.value().attr("href"))
+        .filter_map(|href| url::Url::parse(href.trim()).ok())
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .map(|mut url| {
+            url.set_fragment(None);
+            url.to_string()
         })
+        .filter(|url| seen.insert(normalize_url(url)))
+        .take(MAX_HTML_IMPORT_LINKS)
         .collect()
 }
 
 pub fn parse_json_sources(input: &str) -> Result<Vec<ImportSource>, ImportError> {
-    let items: Vec<JsonImportItem> = serde_json::from_str(input)?;
+    let items = match serde_json::from_str(input)? {
+        JsonImportDocument::List(items) => items,
+        JsonImportDocument::Export { feeds, folders } => {
+            folders.into_iter().chain(feeds).collect::<Vec<_>>()
+        }
+    };
     let mut sources = Vec::with_capacity(items.len());
-
     for item in items {
-        match item {
-            JsonImportItem::Url(feed_url) => {
-                sources.push(ImportSource {
-                    title: feed_url.clone(),
-                    feed_url,
-                    site_url: None,
-                    category: None,
-                });
+        collect_json_item(item, None, &mut sources);
+    }
+    Ok(sources)
+}
+
+fn collect_json_item(item: JsonImportItem, folder: Option<&str>, sources: &mut Vec<ImportSource>) {
+    match item {
+        JsonImportItem::Url(feed_url) => {
+            sources.push(ImportSource {
+                title: feed_url.clone(),
+                feed_url,
+                site_url: None,
+                category: folder.map(str::to_string),
+                rssr: None,
+            });
+        }
+        JsonImportItem::Folder { name, feeds } => {
+            let name = name.trim();
+            let folder = if name.is_empty() { folder } else { Some(name) };
+            for item in feeds {
+                collect_json_item(item, folder, sources);
             }
-            JsonImportItem::Object {
+        }
+        JsonImportItem::Object {
+            feed_url,
+            title,
+            edited_name,
+            site_url,
+            category,
+            tags,
+        } => {
+            let category = category
+                .or_else(|| tags.into_iter().next())
+                .map(|category| category.trim().to_string())
+                .filter(|category| !category.is_empty())
+                .or_else(|| folder.map(str::to_string));
+            sources.push(ImportSource {
+                title: edited_name
+                    .or(title)
+                    .filter(|title| !title.trim().is_empty())
+                    .unwrap_or_else(|| feed_url.clone()),
                 feed_url,
-                title,
                 site_url,
                 category,
-            } => {
-                sources.push(ImportSource {
-                    title: title.unwrap_or_else(|| feed_url.clone()),
-                    feed_url,
-                    site_url,
-                    category,
-                });
-            }
+                rssr: None,
+            });
         }
     }
-
-    Ok(sources)
 }
 
 pub fn build_import_preview(
     candidates: Vec<ImportSource>,
     existing_feed_urls: &HashSet<String>,
+    existing_site_hosts: &HashSet<String>,
 ) -> ImportPreview {
     let mut seen = HashMap::<String, ImportSource>::new();
     let mut duplicate_sources = Vec::new();
+    let mut possible_duplicates = Vec::new();
     let mut new_sources = Vec::new();
 
     for source in candidates {
@@ -159,19 +372,226 @@ pub fn build_import_preview(
             continue;
         }
 
+        if source_site_host(&source).is_some_and(|host| existing_site_hosts.contains(&host)) {
+            possible_duplicates.push(source);
+            continue;
+        }
+
         new_sources.push(source);
     }
 
     ImportPreview {
         new_sources,
         duplicate_sources,
+        possible_duplicates,
     }
 }
 
+pub fn apply_category_mapping(
+    candidates: Vec<ImportSource>,
+    mapping: Option<&HashMap<String, String>>,
+) -> Vec<ImportSource> {
+    let Some(mapping) = mapping.filter(|mapping| !mapping.is_empty()) else {
+        return candidates;
+    };
+    let mapping = mapping
+        .iter()
+        .map(|(from, to)| (from.trim().to_lowercase(), to.trim().to_string()))
+        .collect::<HashMap<_, _>>();
+    candidates
+        .into_iter()
+        .map(|mut source| {
+            let key = source
+                .category
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase();
+            if let Some(target) = mapping.get(&key) {
+                source.category = Some(target.clone()).filter(|value| !value.is_empty());
+            }
+            source
+        })
+        .collect()
+}
+
+pub fn select_candidates(
+    candidates: Vec<ImportSource>,
+    selected_feed_urls: Option<&[String]>,
+    excluded_feed_urls: Option<&[String]>,
+) -> Vec<ImportSource> {
+    let normalize_all = |urls: &[String]| {
+        urls.iter()
+            .map(|url| normalize_url(url))
+            .collect::<HashSet<_>>()
+    };
+    let selected = selected_feed_urls.map(normalize_all);
+    let excluded = excluded_feed_urls.map(normalize_all).unwrap_or_default();
+    candidates
+        .into_iter()
+        .filter(|source| {
+            let normalized = normalize_url(&source.feed_url);
+            selected
+                .as_ref()
+                .is_none_or(|selected| selected.contains(&normalized))
+                && !excluded.contains(&normalized)
+        })
+        .collect()
+}
+
+pub fn source_site_host(source: &ImportSource) -> Option<String> {
+    let host = source
+        .site_url
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .and_then(url_host)
+        .or_else(|| url_host(&source.feed_url))?;
+    let shared = SHARED_FEED_HOSTS
+        .iter()
+        .any(|shared| host == *shared || host.ends_with(&format!(".{shared}")));
+    (!shared).then_some(host)
+}
+
+pub fn suggest_categories(
+    sources: &[ImportSource],
+    existing: &[ImportSource],
+) -> Vec<CategorySuggestion> {
+    let mut host_categories = HashMap::<String, String>::new();
+    let mut known_categories = HashMap::<String, String>::new();
+    for source in existing {
+        let Some(category) = source
+            .category
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        else {
+            continue;
+        };
+        known_categories
+            .entry(category.to_lowercase())
+            .or_insert_with(|| category.to_string());
+        for url in [Some(&source.feed_url), source.site_url.as_ref()]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(host) = url_host(url) {
+                host_categories
+                    .entry(host)
+                    .or_insert_with(|| category.to_string());
+            }
+        }
+    }
+
+    sources
+        .iter()
+        .filter(|source| {
+            source
+                .category
+                .as_deref()
+                .is_none_or(|category| category.trim().is_empty())
+        })
+        .filter_map(|source| {
+            let similar = [Some(&source.feed_url), source.site_url.as_ref()]
+                .into_iter()
+                .flatten()
+                .filter_map(|url| url_host(url))
+                .find_map(|host| host_categories.get(&host).cloned());
+            if let Some(category) = similar {
+                return Some(CategorySuggestion {
+                    feed_url: source.feed_url.clone(),
+                    category,
+                    reason: "similar_source".to_string(),
+                });
+            }
+            keyword_category(source).map(|category| CategorySuggestion {
+                feed_url: source.feed_url.clone(),
+                category: known_categories
+                    .get(&category.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| category.to_string()),
+                reason: "keyword".to_string(),
+            })
+        })
+        .collect()
+}
+
 pub fn normalize_url(url: &str) -> String {
     url.trim().trim_end_matches('/').to_lowercase()
 }
 
+fn url_host(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    Some(host.trim_start_matches("www.").to_string())
+}
+
+fn keyword_category(source: &ImportSource) -> Option<&'static str> {
+    let haystack = [
+        Some(source.title.as_str()),
+        Some(source.feed_url.as_str()),
+        source.site_url.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .to_lowercase();
+    let tokens = haystack
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect::<HashSet<_>>();
+
+    CATEGORY_KEYWORDS
+        .iter()
+        .map(|(category, keywords)| {
+            let hits = keywords
+                .iter()
+                .filter(|keyword| tokens.contains(**keyword))
+                .count();
+            (*category, hits)
+        })
+        .filter(|(_, hits)| *hits > 0)
+        .max_by_key(|(_, hits)| *hits)
+        .map(|(category, _)| category)
+}
+
+fn opml_outline(source: &ImportSource) -> String {
+    let title = escape(&source.title);
+    let mut outline = format!(
+        "<outline type=\"rss\" text=\"{title}\" title=\"{title}\" xmlUrl=\"{}\"",
+        escape(&source.feed_url)
+    );
+    if let Some(site_url) = source.site_url.as_deref() {
+        outline.push_str(&format!(" htmlUrl=\"{}\"", escape(site_url)));
+    }
+    if let Some(rssr) = source.rssr.as_ref() {
+        if let Some(is_active) = rssr.is_active {
+            outline.push_str(&format!(" rssr:active=\"{is_active}\""));
+        }
+        if let Some(color) = rssr.color.as_deref() {
+            outline.push_str(&format!(" rssr:color=\"{}\"", escape(color)));
+        }
+        if let Some(custom_icon) = rssr.custom_icon.as_deref() {
+            outline.push_str(&format!(" rssr:customIcon=\"{}\"", escape(custom_icon)));
+        }
+    }
+    outline.push_str("/>");
+    outline
+}
+
+fn read_rssr_attributes(node: roxmltree::Node<'_, '_>) -> Option<RssrSourceAttributes> {
+    let attribute = |name: &str| {
+        node.attribute((RSSR_OPML_NAMESPACE, name))
+            .map(ToString::to_string)
+    };
+    let attributes = RssrSourceAttributes {
+        is_active: attribute("active").and_then(|value| value.parse().ok()),
+        color: attribute("color"),
+        custom_icon: attribute("customIcon"),
+    };
+    (attributes != RssrSourceAttributes::default()).then_some(attributes)
+}
+
 fn infer_opml_category(node: roxmltree::Node<'_, '_>) -> Option<String> {
     for ancestor in node.ancestors() {
         if !ancestor.has_tag_name("outline") {
@@ -220,6 +640,37 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn exported_opml_round_trips_categories_and_rssr_attributes() {
+        let sources = vec![
+            ImportSource {
+                title: "Tom & Jerry's <Blog>".to_string(),
+                feed_url: "https://example.com/feed.xml?a=1&b=2".to_string(),
+                site_url: Some("https://example.com/".to_string()),
+                category: Some("Friends".to_string()),
+                rssr: Some(RssrSourceAttributes {
+                    is_active: Some(false),
+                    color: Some("#ff6600".to_string()),
+                    custom_icon: Some("\u{1f980}".to_string()),
+                }),
+            },
+            ImportSource {
+                title: "Loose".to_string(),
+                feed_url: "https://loose.example.com/rss".to_string(),
+                site_url: None,
+                category: None,
+                rssr: None,
+            },
+        ];
+
+        let opml = export_opml("rssr export", &sources);
+        let parsed = parse_opml(&opml).expect("exported opml should parse");
+
+        assert!(opml.contains(RSSR_OPML_NAMESPACE));
+        assert_eq!(parsed, sources);
+        assert!(!export_opml("plain", &sources[1..]).contains("xmlns:rssr"));
+    }
+
     #[test]
     fn parses_url_list() {
         let input = r#"
@@ -233,6 +684,27 @@ mod tests {
         assert_eq!(items[0].feed_url, "https://example.com/feed.xml");
     }
 
+    #[test]
+    fn extracts_unique_absolute_links_from_pasted_html() {
+        let html = r##"
+            <h2>Blogs</h2>
+            <ul>
+              <li><a href="https://blog.example.com/">Example Blog</a></li>
+              <li><a href="https://blog.example.com#about">Same blog</a></li>
+              <li><a href="/relative/page">Relative</a></li>
+              <li><a href="mailto:me@example.com">Mail</a></li>
+              <li><a href=" http://other.example.org/posts ">Other</a></li>
+            </ul>
+        "##;
+        assert_eq!(
+            extract_html_links(html),
+            vec![
+                "https://blog.example.com/".to_string(),
+                "http://other.example.org/posts".to_string()
+            ]
+        );
+    }
+
     #[test]
     fn parses_json_sources_from_string_and_object() {
         let json = r#"
@@ -253,6 +725,62 @@ mod tests {
         assert_eq!(items[1].title, "Blog");
     }
 
+    #[test]
+    fn parses_feedbin_and_netnewswire_style_exports() {
+        let feedbin = r#"
+            [
+              {
+                "id": 525,
+                "created_at": "2013-03-12T11:30:25.209432Z",
+                "feed_id": 47,
+                "title": "Daring Fireball",
+                "feed_url": "http://daringfireball.net/index.xml",
+                "site_url": "http://daringfireball.net/",
+                "tags": ["Apple", "Blogs"]
+              }
+            ]
+        "#;
+        let items = parse_json_sources(feedbin).expect("feedbin json should parse");
+        assert_eq!(items[0].title, "Daring Fireball");
+        assert_eq!(
+            items[0].site_url.as_deref(),
+            Some("http://daringfireball.net/")
+        );
+        assert_eq!(items[0].category.as_deref(), Some("Apple"));
+
+        let netnewswire = r#"
+            {
+              "folders": [
+                {
+                  "name": "Tech",
+                  "feeds": [
+                    {
+                      "feedURL": "https://inessential.com/feed.json",
+                      "name": "inessential",
+                      "editedName": "Brent",
+                      "homePageURL": "https://inessential.com/"
+                    },
+                    { "name": "Nested", "children": ["https://nested.example.com/rss"] }
+                  ]
+                }
+              ],
+              "feeds": [{ "xmlUrl": "https://loose.example.com/atom.xml", "folder": "Misc" }]
+            }
+        "#;
+        let items = parse_json_sources(netnewswire).expect("netnewswire json should parse");
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].title, "Brent");
+        assert_eq!(items[0].feed_url, "https://inessential.com/feed.json");
+        assert_eq!(
+            items[0].site_url.as_deref(),
+            Some("https://inessential.com/")
+        );
+        assert_eq!(items[0].category.as_deref(), Some("Tech"));
+        assert_eq!(items[1].category.as_deref(), Some("Nested"));
+        assert_eq!(items[2].title, "https://loose.example.com/atom.xml");
+        assert_eq!(items[2].category.as_deref(), Some("Misc"));
+    }
+
     #[test]
     fn preview_marks_existing_and_duplicate_sources() {
         let candidates = vec![
@@ -261,25 +789,165 @@ mod tests {
                 feed_url: "https://example.com/feed.xml".to_string(),
                 site_url: None,
                 category: None,
+                rssr: None,
             },
             ImportSource {
                 title: "A duplicate".to_string(),
                 feed_url: "https://example.com/feed.xml".to_string(),
                 site_url: None,
                 category: None,
+                rssr: None,
             },
             ImportSource {
                 title: "B".to_string(),
                 feed_url: "https://another.com/feed.xml".to_string(),
                 site_url: None,
                 category: None,
+                rssr: None,
             },
         ];
         let existing = HashSet::from([normalize_url("https://another.com/feed.xml")]);
-        let preview = build_import_preview(candidates, &existing);
+        let preview = build_import_preview(candidates, &existing, &HashSet::new());
 
         assert_eq!(preview.new_sources.len(), 1);
         assert_eq!(preview.new_sources[0].title, "A");
         assert_eq!(preview.duplicate_sources.len(), 3);
     }
+
+    #[test]
+    fn maps_categories_case_insensitively() {
+        let source = |feed_url: &str, category: Option<&str>| ImportSource {
+            title: feed_url.to_string(),
+            feed_url: feed_url.to_string(),
+            site_url: None,
+            category: category.map(ToString::to_string),
+            rssr: None,
+        };
+        let candidates = vec![
+            source("https://a.example.com/feed", Some("Programming")),
+            source("https://b.example.com/feed", Some("junk")),
+            source("https://c.example.com/feed", None),
+            source("https://d.example.com/feed", Some("Design")),
+        ];
+        let mapping = HashMap::from([
+            (" programming ".to_string(), "Tech".to_string()),
+            ("Junk".to_string(), String::new()),
+            (String::new(), "Inbox".to_string()),
+        ]);
+
+        let mapped = apply_category_mapping(candidates, Some(&mapping));
+        let categories = mapped
+            .iter()
+            .map(|source| source.category.as_deref())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            categories,
+            vec![Some("Tech"), None, Some("Inbox"), Some("Design")]
+        );
+    }
+
+    #[test]
+    fn selects_candidates_by_inclusion_and_exclusion() {
+        let candidates = parse_url_list(
+            "https://a.example.com/feed\nhttps://b.example.com/feed\nhttps://c.example.com/feed",
+        );
+        let selected = vec![
+            "https://A.example.com/feed/".to_string(),
+            "https://b.example.com/feed".to_string(),
+        ];
+        let excluded = vec!["https://b.example.com/feed".to_string()];
+
+        let picked = select_candidates(candidates.clone(), Some(&selected), Some(&excluded));
+        let all_but_excluded = select_candidates(candidates, None, Some(&excluded));
+
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].feed_url, "https://a.example.com/feed");
+        assert_eq!(all_but_excluded.len(), 2);
+    }
+
+    #[test]
+    fn preview_flags_same_site_with_different_feed_url() {
+        let source = |title: &str, feed_url: &str, site_url: Option<&str>| ImportSource {
+            title: title.to_string(),
+            feed_url: feed_url.to_string(),
+            site_url: site_url.map(ToString::to_string),
+            category: None,
+            rssr: None,
+        };
+        let existing = [
+            source("Native", "https://blog.example.com/feed.xml", None),
+            source(
+                "Burned",
+                "https://feeds.feedburner.com/other",
+                Some("https://other.example.org/"),
+            ),
+        ];
+        let existing_hosts = existing
+            .iter()
+            .filter_map(source_site_host)
+            .collect::<HashSet<_>>();
+        let candidates = vec![
+            source(
+                "Via Feedburner",
+                "https://feeds.feedburner.com/example-blog",
+                Some("https://www.blog.example.com/"),
+            ),
+            source(
+                "Unrelated Feedburner",
+                "https://feeds.feedburner.com/unrelated",
+                None,
+            ),
+            source("Fresh", "https://fresh.example.net/rss", None),
+        ];
+
+        let preview = build_import_preview(candidates, &HashSet::new(), &existing_hosts);
+
+        assert_eq!(existing_hosts.len(), 2);
+        assert_eq!(preview.possible_duplicates.len(), 1);
+        assert_eq!(preview.possible_duplicates[0].title, "Via Feedburner");
+        assert_eq!(preview.new_sources.len(), 2);
+    }
+
+    #[test]
+    fn suggests_categories_from_similar_sources_and_keywords() {
+        let source = |title: &str, feed_url: &str, category: Option<&str>| ImportSource {
+            title: title.to_string(),
+            feed_url: feed_url.to_string(),
+            site_url: None,
+            category: category.map(ToString::to_string),
+            rssr: None,
+        };
+        let existing = vec![
+            source(
+                "Example Main",
+                "https://www.example.com/feed.xml",
+                Some("Friends"),
+            ),
+            source("Tech Weekly", "https://weekly.dev/rss", Some("tech")),
+        ];
+        let candidates = vec![
+            source("Example Notes", "https://example.com/notes/atom.xml", None),
+            source(
+                "Rust Programming Blog",
+                "https://blog.rust.example.org/rss",
+                None,
+            ),
+            source(
+                "Already Sorted",
+                "https://sorted.example.net/rss",
+                Some("Misc"),
+            ),
+            source("Plain", "https://plain.example.net/feed", None),
+        ];
+
+        let suggestions = suggest_categories(&candidates, &existing);
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].category, "Friends");
+        assert_eq!(suggestions[0].reason, "similar_source");
+        assert_eq!(suggestions[1].feed_url, "https://blog.rust.example.org/rss");
+        assert_eq!(suggestions[1].category, "tech");
+        assert_eq!(suggestions[1].reason, "keyword");
+    }
 }
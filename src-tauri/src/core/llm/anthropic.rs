@@ -0,0 +1,124 @@
+use serde::Deserialize;
+
+use super::{GenerationParams, LlmError, SseDecoder};
+
+pub const DEFAULT_ANTHROPIC_MESSAGES_PATH: &str = "messages";
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+const DEFAULT_MAX_TOKENS: u32 = 1024;
+const MAX_TEMPERATURE: f64 = 1.0;
+
+#[derive(Debug, Clone, Deserialize)]
+struct MessagesResponse {
+    #[serde(default)]
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    delta: Option<StreamDelta>,
+    #[serde(default)]
+    error: Option<StreamError>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct StreamError {
+    #[serde(default)]
+    message: String,
+}
+
+pub fn messages_request_body(
+    model: &str,
+    params: &GenerationParams,
+    system_prompt: &str,
+    user_prompt: &str,
+    stream: bool,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": model,
+        "max_tokens": params.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+        "temperature": params.temperature.min(MAX_TEMPERATURE),
+        "system": system_prompt,
+        "messages": [
+            { "role": "user", "content": user_prompt }
+        ]
+    });
+    if let Some(top_p) = params.top_p {
+        body["top_p"] = top_p.into();
+    }
+    if stream {
+        body["stream"] = true.into();
+    }
+    body
+}
+
+pub fn parse_response(body: &str) -> Result<String, LlmError> {
+    let parsed: MessagesResponse =
+        serde_json::from_str(body).map_err(|error| LlmError::InvalidResponse(error.to_string()))?;
+    let content = parsed
+        .content
+        .into_iter()
+        .filter(|block| block.kind == "text")
+        .filter_map(|block| block.text)
+        .collect::<String>();
+    let content = content.trim().to_string();
+    if content.is_empty() {
+        return Err(LlmError::InvalidResponse(
+            "missing text content block".to_string(),
+        ));
+    }
+    Ok(content)
+}
+
+pub async fn read_stream<F>(
+    mut response: reqwest::Response,
+    on_delta: &mut F,
+) -> Result<String, LlmError>
+where
+    F: FnMut(&str),
+{
+    let mut decoder = SseDecoder::default();
+    let mut content = String::new();
+    'read: while let Some(chunk) = response.chunk().await? {
+        for data in decoder.push(&chunk) {
+            let event: StreamEvent = serde_json::from_str(&data)
+                .map_err(|error| LlmError::InvalidResponse(error.to_string()))?;
+            match event.kind.as_str() {
+                "content_block_delta" => {
+                    if let Some(delta) = event
+                        .delta
+                        .and_then(|delta| delta.text)
+                        .filter(|delta| !delta.is_empty())
+                    {
+                        on_delta(&delta);
+                        content.push_str(&delta);
+                    }
+                }
+                "message_stop" => break 'read,
+                "error" => {
+                    return Err(LlmError::InvalidResponse(
+                        event.error.map(|error| error.message).unwrap_or(data),
+                    ))
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(content)
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::storage::models::LlmCacheRecord;
+
+pub const LLM_CACHE_EXPORT_VERSION: u32 = 2;
+pub const LLM_CACHE_KEY_VERSION: i64 = 2;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LlmCacheExportError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialize error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unsupported llm cache export version {0}")]
+    UnsupportedVersion(u32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmCacheExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub records: Vec<LlmCacheRecord>,
+}
+
+pub fn llm_cache_key(task_type: &str, input: &str) -> String {
+    hash_parts(&[task_type, input])
+}
+
+pub fn legacy_llm_cache_key(task_type: &str, model: &str, input: &str) -> String {
+    hash_parts(&[task_type, model, input])
+}
+
+fn hash_parts(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for (index, part) in parts.iter().enumerate() {
+        if index > 0 {
+            hasher.update(b"::");
+        }
+        hasher.update(part.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+pub fn write_llm_cache_export(
+    path: &Path,
+    records: Vec<LlmCacheRecord>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<usize, LlmCacheExportError> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    let count = records.len();
+    let export = LlmCacheExport {
+        version: LLM_CACHE_EXPORT_VERSION,
+        exported_at: now.to_rfc3339(),
+        records,
+    };
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer(file, &export)?;
+    Ok(count)
+}
+
+pub fn read_llm_cache_export(path: &Path) -> Result<LlmCacheExport, LlmCacheExportError> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let export: LlmCacheExport = serde_json::from_reader(file)?;
+    if export.version > LLM_CACHE_EXPORT_VERSION {
+        return Err(LlmCacheExportError::UnsupportedVersion(export.version));
+    }
+    Ok(export)
+}
+
+pub fn remap_models(
+    records: Vec<LlmCacheRecord>,
+    model_map: &BTreeMap<String, String>,
+) -> Vec<LlmCacheRecord> {
+    let mapping = model_map
+        .iter()
+        .map(|(from, to)| (from.trim(), to.trim()))
+        .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+        .collect::<Vec<_>>();
+    records
+        .into_iter()
+        .map(|mut record| {
+            if record.key_version != LLM_CACHE_KEY_VERSION {
+                return record;
+            }
+            let target = mapping
+                .iter()
+                .find(|(from, _)| *from == record.model)
+                .or_else(|| mapping.iter().find(|(from, _)| *from == "*"));
+            if let Some((_, to)) = target {
+                record.model = to.to_string();
+            }
+            record
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(model: &str, hash: &str) -> LlmCacheRecord {
+        LlmCacheRecord {
+            task_type: "summary".to_string(),
+            model: model.to_string(),
+            input_hash: hash.to_string(),
+            output_text: format!("{model} {hash}"),
+            created_at: "2026-01-01 00:00:00".to_string(),
+            key_version: LLM_CACHE_KEY_VERSION,
+        }
+    }
+
+    #[test]
+    fn cache_key_ignores_the_model() {
+        assert_eq!(
+            llm_cache_key("summary", "hello"),
+            llm_cache_key("summary", "hello")
+        );
+        assert_ne!(
+            llm_cache_key("summary", "hello"),
+            llm_cache_key("title_translate_zh", "hello")
+        );
+        assert_ne!(
+            llm_cache_key("summary", "hello"),
+            legacy_llm_cache_key("summary", "deepseek-chat", "hello")
+        );
+    }
+
+    #[test]
+    fn remaps_exact_models_before_the_wildcard() {
+        let model_map = BTreeMap::from([
+            ("gpt-4o".to_string(), "gpt-4.1".to_string()),
+            ("*".to_string(), "deepseek-chat".to_string()),
+            ("ignored".to_string(), " ".to_string()),
+        ]);
+
+        let legacy = LlmCacheRecord {
+            key_version: 1,
+            ..record("gpt-4o", "d")
+        };
+
+        let remapped = remap_models(
+            vec![
+                record("gpt-4o", "a"),
+                record("qwen", "b"),
+                record("ignored", "c"),
+                legacy,
+            ],
+            &model_map,
+        );
+        assert_eq!(
+            remapped
+                .iter()
+                .map(|record| record.model.as_str())
+                .collect::<Vec<_>>(),
+            vec!["gpt-4.1", "deepseek-chat", "deepseek-chat", "gpt-4o"]
+        );
+        assert_eq!(remapped[0].output_text, "gpt-4o a");
+    }
+
+    #[test]
+    fn export_roundtrips_and_rejects_newer_versions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("nested").join("llm-cache.json");
+
+        let written = write_llm_cache_export(
+            &path,
+            vec![record("gpt-4o", "a"), record("gpt-4o", "b")],
+            chrono::Utc::now(),
+        )
+        .expect("write export");
+        assert_eq!(written, 2);
+        let export = read_llm_cache_export(&path).expect("read export");
+        assert_eq!(export.version, LLM_CACHE_EXPORT_VERSION);
+        assert_eq!(export.records[1].input_hash, "b");
+
+        std::fs::write(&path, r#"{"version":99,"exported_at":"","records":[]}"#).expect("write");
+        assert!(matches!(
+            read_llm_cache_export(&path),
+            Err(LlmCacheExportError::UnsupportedVersion(99))
+        ));
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    #[serde(default)]
+    pub id: i64,
+    pub term: String,
+    pub translation: String,
+}
+
+impl GlossaryTerm {
+    pub fn normalized(self) -> Self {
+        Self {
+            id: self.id,
+            term: self.term.trim().to_string(),
+            translation: self.translation.trim().to_string(),
+        }
+    }
+}
+
+pub fn matching_terms<'a>(glossary: &'a [GlossaryTerm], text: &str) -> Vec<&'a GlossaryTerm> {
+    let haystack = text.to_ascii_lowercase();
+    let mut matches = glossary
+        .iter()
+        .filter(|entry| !entry.term.is_empty())
+        .filter(|entry| contains_term(&haystack, &entry.term.to_ascii_lowercase()))
+        .collect::<Vec<_>>();
+    matches.sort_by(|left, right| {
+        right
+            .term
+            .len()
+            .cmp(&left.term.len())
+            .then_with(|| left.term.cmp(&right.term))
+    });
+    matches
+}
+
+pub fn glossary_prompt(terms: &[&GlossaryTerm]) -> String {
+    if terms.is_empty() {
+        return String::new();
+    }
+    let lines = terms
+        .iter()
+        .map(|entry| format!("- {} => {}", entry.term, entry.translation))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("Always translate these terms exactly as listed:\n{lines}")
+}
+
+pub fn apply_glossary(output: &str, terms: &[&GlossaryTerm]) -> String {
+    let haystack = output.to_ascii_lowercase();
+    let needles = terms
+        .iter()
+        .map(|entry| {
+            (
+                entry.term.to_ascii_lowercase(),
+                entry.translation.as_str(),
+                entry.translation.to_ascii_lowercase(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut result = String::with_capacity(output.len());
+    let mut position = 0;
+    while position < output.len() {
+        let replacement = needles
+            .iter()
+            .find(|(needle, _, _)| term_at(&haystack, needle, position));
+        match (replacement, output[position..].chars().next()) {
+            (Some((_, _, translated)), _)
+                if haystack[position..].starts_with(translated.as_str()) =>
+            {
+                result.push_str(&output[position..position + translated.len()]);
+                position += translated.len();
+            }
+            (Some((needle, translation, _)), _) => {
+                result.push_str(translation);
+                position += needle.len();
+            }
+            (None, Some(ch)) => {
+                result.push(ch);
+                position += ch.len_utf8();
+            }
+            (None, None) => break,
+        }
+    }
+    result
+}
+
+fn contains_term(haystack: &str, needle: &str) -> bool {
+    haystack
+        .match_indices(needle)
+        .any(|(found, _)| term_at(haystack, needle, found))
+}
+
+fn term_at(haystack: &str, needle: &str, at: usize) -> bool {
+    if !haystack[at..].starts_with(needle) {
+        return false;
+    }
+    let bounded = |edge: Option<char>, neighbour: Option<char>| {
+        !edge.is_some_and(|ch| ch.is_ascii_alphanumeric())
+            || !neighbour.is_some_and(|ch| ch.is_ascii_alphanumeric())
+    };
+    bounded(needle.chars().next(), haystack[..at].chars().next_back())
+        && bounded(
+            needle.chars().next_back(),
+            haystack[at + needle.len()..].chars().next(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(term: &str, translation: &str) -> GlossaryTerm {
+        GlossaryTerm {
+            id: 0,
+            term: term.to_string(),
+            translation: translation.to_string(),
+        }
+    }
+
+    #[test]
+    fn only_terms_present_on_word_boundaries_reach_the_prompt() {
+        let glossary = vec![
+            term("Go", "Go 语言"),
+            term("borrow checker", "借用检查器"),
+            term("Tokio", "Tokio"),
+        ];
+
+        let matched = matching_terms(&glossary, "Google explains the Borrow Checker in Tokio");
+        assert_eq!(
+            matched
+                .iter()
+                .map(|entry| entry.term.as_str())
+                .collect::<Vec<_>>(),
+            vec!["borrow checker", "Tokio"]
+        );
+        assert_eq!(
+            glossary_prompt(&matched),
+            "Always translate these terms exactly as listed:\n- borrow checker => 借用检查器\n- Tokio => Tokio"
+        );
+        assert_eq!(glossary_prompt(&[]), "");
+    }
+
+    #[test]
+    fn post_processing_replaces_untranslated_terms() {
+        let glossary = vec![term("Rust Foundation", "Rust 基金会"), term("Rust", "Rust")];
+        let matched = matching_terms(&glossary, "Rust Foundation backs Rust");
+
+        assert_eq!(
+            apply_glossary("rust foundation 支持 Rust 与 Rustacean", &matched),
+            "Rust 基金会 支持 Rust 与 Rustacean"
+        );
+
+        let runtime = vec![term("Tokio", "Tokio 运行时")];
+        let matched = matching_terms(&runtime, "An introduction to Tokio");
+        assert_eq!(
+            apply_glossary("Tokio 运行时简介：tokio 入门", &matched),
+            "Tokio 运行时简介：Tokio 运行时 入门"
+        );
+    }
+}
@@ -1,20 +1,157 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::core::redact::redact_secrets;
+
+pub mod anthropic;
+pub mod cache;
+pub mod glossary;
+pub mod ollama;
+
+pub const DEFAULT_CHAT_PATH: &str = "chat/completions";
+pub const DEFAULT_MAX_INPUT_LINES: usize = 1200;
+pub const DEFAULT_MAX_INPUT_CHARS: usize = 12000;
+pub const DEFAULT_TEXT_WRAP_WIDTH: usize = 120;
+pub const DEFAULT_TEMPERATURE: f64 = 0.2;
+const MIN_INPUT_CHARS: usize = 200;
+const TEXT_WRAP_WIDTH_RANGE: std::ops::RangeInclusive<usize> = 20..=1000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmProvider {
+    #[default]
+    OpenaiCompatible,
+    Ollama,
+    Anthropic,
+}
+
+impl LlmProvider {
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ollama" => Self::Ollama,
+            "anthropic" => Self::Anthropic,
+            _ => Self::OpenaiCompatible,
+        }
+    }
+
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, Self::Ollama)
+    }
+
+    fn default_chat_path(self) -> &'static str {
+        match self {
+            Self::OpenaiCompatible => DEFAULT_CHAT_PATH,
+            Self::Ollama => ollama::DEFAULT_OLLAMA_CHAT_PATH,
+            Self::Anthropic => anthropic::DEFAULT_ANTHROPIC_MESSAGES_PATH,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LlmConfig {
+    #[serde(default)]
+    pub provider: LlmProvider,
     pub base_url: String,
     pub api_key: String,
     pub model: String,
     pub timeout_secs: u64,
+    #[serde(default = "default_max_input_lines")]
+    pub max_input_lines: usize,
+    #[serde(default = "default_max_input_chars")]
+    pub max_input_chars: usize,
+    #[serde(default = "default_text_wrap_width")]
+    pub text_wrap_width: usize,
+    #[serde(default)]
+    pub generation: TaskGenerationParams,
+    #[serde(default)]
+    pub endpoint_path: Option<String>,
+    #[serde(default)]
+    pub extra_headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub query_params: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerationParams {
+    pub temperature: f64,
+    pub max_tokens: Option<u32>,
+    pub top_p: Option<f64>,
+}
+
+impl Default for GenerationParams {
+    fn default() -> Self {
+        Self {
+            temperature: DEFAULT_TEMPERATURE,
+            max_tokens: None,
+            top_p: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskGenerationParams {
+    pub summary: GenerationParams,
+    pub translation: GenerationParams,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmTask {
+    Summary,
+    Translation,
+}
+
+impl LlmTask {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::Translation => "translation",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "summary" => Some(Self::Summary),
+            "translation" => Some(Self::Translation),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmProfile {
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+    pub config: LlmConfig,
+    #[serde(default)]
+    pub tasks: Vec<LlmTask>,
+    #[serde(default)]
+    pub active: bool,
+}
+
+fn default_max_input_lines() -> usize {
+    DEFAULT_MAX_INPUT_LINES
+}
+
+fn default_max_input_chars() -> usize {
+    DEFAULT_MAX_INPUT_CHARS
+}
+
+fn default_text_wrap_width() -> usize {
+    DEFAULT_TEXT_WRAP_WIDTH
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum LlmError {
     #[error("invalid llm config: {0}")]
     InvalidConfig(String),
-    #[error("request failed: {0}")]
+    #[error("request failed: {}", redact_secrets(&.0.to_string()))]
     Request(#[from] reqwest::Error),
-    #[error("server returned status {status}: {body}")]
+    #[error("server returned status {status}: {}", redact_secrets(body))]
     HttpStatus { status: u16, body: String },
     #[error("invalid llm response: {0}")]
     InvalidResponse(String),
@@ -48,8 +185,26 @@ struct ChatMessage {
     content: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct ChatStreamChunk {
+    #[serde(default)]
+    choices: Vec<ChatStreamChoice>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ChatStreamChoice {
+    #[serde(default)]
+    delta: ChatStreamDelta,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ChatStreamDelta {
+    content: Option<String>,
+}
+
 pub async fn call_chat_completion(
     config: &LlmConfig,
+    params: &GenerationParams,
     system_prompt: &str,
     user_prompt: &str,
 ) -> Result<String, LlmError> {
@@ -57,19 +212,7 @@ pub async fn call_chat_completion(
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(config.timeout_secs.max(5)))
         .build()?;
-    let endpoint = format!("{}/chat/completions", config.base_url.trim_end_matches('/'));
-
-    let response = client
-        .post(endpoint)
-        .bearer_auth(config.api_key.trim())
-        .json(&serde_json::json!({
-            "model": config.model.trim(),
-            "temperature": 0.2,
-            "messages": [
-                { "role": "system", "content": system_prompt },
-                { "role": "user", "content": user_prompt }
-            ]
-        }))
+    let response = chat_request(&client, config, params, system_prompt, user_prompt, false)?
         .send()
         .await?;
     let status = response.status().as_u16();
@@ -78,20 +221,236 @@ pub async fn call_chat_completion(
         return Err(LlmError::HttpStatus { status, body });
     }
 
-    let parsed: ChatCompletionResponse = serde_json::from_str(&body)
-        .map_err(|error| LlmError::InvalidResponse(error.to_string()))?;
-    let content = parsed
+    match config.provider {
+        LlmProvider::OpenaiCompatible => parse_chat_completion(&body),
+        LlmProvider::Ollama => ollama::parse_response(&body),
+        LlmProvider::Anthropic => anthropic::parse_response(&body),
+    }
+}
+
+fn parse_chat_completion(body: &str) -> Result<String, LlmError> {
+    let parsed: ChatCompletionResponse =
+        serde_json::from_str(body).map_err(|error| LlmError::InvalidResponse(error.to_string()))?;
+    parsed
         .choices
         .first()
         .and_then(|choice| choice.message.content.clone())
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty())
-        .ok_or_else(|| {
-            LlmError::InvalidResponse("missing choices[0].message.content".to_string())
-        })?;
+        .ok_or_else(|| LlmError::InvalidResponse("missing choices[0].message.content".to_string()))
+}
+
+pub async fn stream_chat_completion<F>(
+    config: &LlmConfig,
+    params: &GenerationParams,
+    system_prompt: &str,
+    user_prompt: &str,
+    mut on_delta: F,
+) -> Result<String, LlmError>
+where
+    F: FnMut(&str),
+{
+    validate_config(config)?;
+    let idle = std::time::Duration::from_secs(config.timeout_secs.max(5));
+    let client = reqwest::Client::builder()
+        .connect_timeout(idle)
+        .read_timeout(idle)
+        .build()?;
+    let response = chat_request(&client, config, params, system_prompt, user_prompt, true)?
+        .send()
+        .await?;
+    let status = response.status().as_u16();
+    if status >= 400 {
+        let body = response.text().await?;
+        return Err(LlmError::HttpStatus { status, body });
+    }
+
+    let content = match config.provider {
+        LlmProvider::OpenaiCompatible => {
+            read_chat_completion_stream(response, &mut on_delta).await?
+        }
+        LlmProvider::Ollama => ollama::read_stream(response, &mut on_delta).await?,
+        LlmProvider::Anthropic => anthropic::read_stream(response, &mut on_delta).await?,
+    };
+    let content = content.trim().to_string();
+    if content.is_empty() {
+        return Err(LlmError::InvalidResponse(
+            "stream ended without content".to_string(),
+        ));
+    }
     Ok(content)
 }
 
+async fn read_chat_completion_stream<F>(
+    mut response: reqwest::Response,
+    on_delta: &mut F,
+) -> Result<String, LlmError>
+where
+    F: FnMut(&str),
+{
+    let mut decoder = SseDecoder::default();
+    let mut content = String::new();
+    'read: while let Some(chunk) = response.chunk().await? {
+        for data in decoder.push(&chunk) {
+            if data == "[DONE]" {
+                break 'read;
+            }
+            let parsed: ChatStreamChunk = serde_json::from_str(&data)
+                .map_err(|error| LlmError::InvalidResponse(error.to_string()))?;
+            let Some(delta) = parsed
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.delta.content)
+                .filter(|delta| !delta.is_empty())
+            else {
+                continue;
+            };
+            on_delta(&delta);
+            content.push_str(&delta);
+        }
+    }
+    Ok(content)
+}
+
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(position) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line = self.buffer.drain(..=position).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(data) = line.trim_end().strip_prefix("data:") {
+                events.push(data.trim_start().to_string());
+            }
+        }
+        events
+    }
+}
+
+fn chat_request(
+    client: &reqwest::Client,
+    config: &LlmConfig,
+    params: &GenerationParams,
+    system_prompt: &str,
+    user_prompt: &str,
+    stream: bool,
+) -> Result<reqwest::RequestBuilder, LlmError> {
+    let mut request = client.post(chat_endpoint(config)?);
+    let custom_auth = config.extra_headers.keys().any(|name| {
+        ["authorization", "api-key", "x-api-key"]
+            .iter()
+            .any(|auth| name.eq_ignore_ascii_case(auth))
+    });
+    let api_key = config.api_key.trim();
+    if !custom_auth && !api_key.is_empty() {
+        request = match config.provider {
+            LlmProvider::Anthropic => request.header("x-api-key", api_key),
+            _ => request.bearer_auth(api_key),
+        };
+    }
+    if config.provider == LlmProvider::Anthropic {
+        request = request.header("anthropic-version", anthropic::ANTHROPIC_VERSION);
+    }
+    for (name, value) in &config.extra_headers {
+        request = request.header(name.trim(), value.trim());
+    }
+    let model = config.model.trim();
+    let body = match config.provider {
+        LlmProvider::OpenaiCompatible => {
+            let mut body = chat_request_body(model, params, system_prompt, user_prompt);
+            if stream {
+                body["stream"] = true.into();
+            }
+            body
+        }
+        LlmProvider::Ollama => {
+            ollama::chat_request_body(model, params, system_prompt, user_prompt, stream)
+        }
+        LlmProvider::Anthropic => {
+            anthropic::messages_request_body(model, params, system_prompt, user_prompt, stream)
+        }
+    };
+    Ok(request.json(&body))
+}
+
+pub fn chat_endpoint(config: &LlmConfig) -> Result<reqwest::Url, LlmError> {
+    let path = config
+        .endpoint_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .unwrap_or(config.provider.default_chat_path());
+    let raw = if path.starts_with("http://") || path.starts_with("https://") {
+        path.to_string()
+    } else {
+        format!(
+            "{}/{}",
+            config.base_url.trim().trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    };
+    let mut endpoint = reqwest::Url::parse(&raw)
+        .map_err(|error| LlmError::InvalidConfig(format!("invalid endpoint {raw}: {error}")))?;
+    if !config.query_params.is_empty() {
+        let mut pairs = endpoint.query_pairs_mut();
+        for (key, value) in &config.query_params {
+            pairs.append_pair(key.trim(), value.trim());
+        }
+    }
+    Ok(endpoint)
+}
+
+fn chat_request_body(
+    model: &str,
+    params: &GenerationParams,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": model,
+        "temperature": params.temperature,
+        "messages": [
+            { "role": "system", "content": system_prompt },
+            { "role": "user", "content": user_prompt }
+        ]
+    });
+    if let Some(max_tokens) = params.max_tokens {
+        body["max_tokens"] = max_tokens.into();
+    }
+    if let Some(top_p) = params.top_p {
+        body["top_p"] = top_p.into();
+    }
+    body
+}
+
+fn validate_generation(task: &str, params: &GenerationParams) -> Result<(), LlmError> {
+    if !(0.0..=2.0).contains(&params.temperature) {
+        return Err(LlmError::InvalidConfig(format!(
+            "{task} temperature must be between 0 and 2"
+        )));
+    }
+    if params.max_tokens == Some(0) {
+        return Err(LlmError::InvalidConfig(format!(
+            "{task} max_tokens must be at least 1"
+        )));
+    }
+    if params
+        .top_p
+        .is_some_and(|top_p| top_p <= 0.0 || top_p > 1.0)
+    {
+        return Err(LlmError::InvalidConfig(format!(
+            "{task} top_p must be greater than 0 and at most 1"
+        )));
+    }
+    Ok(())
+}
+
 pub fn validate_config(config: &LlmConfig) -> Result<(), LlmError> {
     if config.base_url.trim().is_empty() {
         return Err(LlmError::InvalidConfig(
@@ -103,7 +462,7 @@ pub fn validate_config(config: &LlmConfig) -> Result<(), LlmError> {
             "base_url must start with http:// or https://".to_string(),
         ));
     }
-    if config.api_key.trim().is_empty() {
+    if config.provider.requires_api_key() && config.api_key.trim().is_empty() {
         return Err(LlmError::InvalidConfig(
             "api_key cannot be empty".to_string(),
         ));
@@ -111,6 +470,33 @@ pub fn validate_config(config: &LlmConfig) -> Result<(), LlmError> {
     if config.model.trim().is_empty() {
         return Err(LlmError::InvalidConfig("model cannot be empty".to_string()));
     }
+    if config.max_input_lines == 0 {
+        return Err(LlmError::InvalidConfig(
+            "max_input_lines must be at least 1".to_string(),
+        ));
+    }
+    if config.max_input_chars < MIN_INPUT_CHARS {
+        return Err(LlmError::InvalidConfig(format!(
+            "max_input_chars must be at least {MIN_INPUT_CHARS}"
+        )));
+    }
+    if !TEXT_WRAP_WIDTH_RANGE.contains(&config.text_wrap_width) {
+        return Err(LlmError::InvalidConfig(format!(
+            "text_wrap_width must be between {} and {}",
+            TEXT_WRAP_WIDTH_RANGE.start(),
+            TEXT_WRAP_WIDTH_RANGE.end()
+        )));
+    }
+    for (name, value) in &config.extra_headers {
+        let valid = reqwest::header::HeaderName::from_bytes(name.trim().as_bytes()).is_ok()
+            && reqwest::header::HeaderValue::from_str(value.trim()).is_ok();
+        if !valid {
+            return Err(LlmError::InvalidConfig(format!("invalid header {name}")));
+        }
+    }
+    chat_endpoint(config)?;
+    validate_generation("summary", &config.generation.summary)?;
+    validate_generation("translation", &config.generation.translation)?;
     Ok(())
 }
 
@@ -126,15 +512,280 @@ mod tests {
     #[test]
     fn validate_config_rejects_invalid_fields() {
         let config = LlmConfig {
+            provider: LlmProvider::OpenaiCompatible,
             base_url: "localhost".to_string(),
             api_key: "".to_string(),
             model: "".to_string(),
             timeout_secs: 10,
+            max_input_lines: DEFAULT_MAX_INPUT_LINES,
+            max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+            text_wrap_width: DEFAULT_TEXT_WRAP_WIDTH,
+            generation: TaskGenerationParams::default(),
+            endpoint_path: None,
+            extra_headers: BTreeMap::new(),
+            query_params: BTreeMap::new(),
         };
         let result = validate_config(&config);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn input_limits_default_for_saved_configs_and_are_validated() {
+        let mut config = serde_json::from_str::<LlmConfig>(
+            r#"{"base_url":"https://api.example.com/v1","api_key":"k","model":"m","timeout_secs":30}"#,
+        )
+        .expect("legacy config should parse");
+        assert_eq!(config.max_input_lines, DEFAULT_MAX_INPUT_LINES);
+        assert_eq!(config.max_input_chars, DEFAULT_MAX_INPUT_CHARS);
+        assert_eq!(config.text_wrap_width, DEFAULT_TEXT_WRAP_WIDTH);
+        assert!(validate_config(&config).is_ok());
+
+        config.max_input_chars = 10;
+        assert!(validate_config(&config).is_err());
+        config.max_input_chars = 4000;
+        config.text_wrap_width = 5;
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn generation_params_shape_request_body() {
+        let defaults = chat_request_body("m", &GenerationParams::default(), "s", "u");
+        assert_eq!(defaults["temperature"], 0.2);
+        assert!(defaults.get("max_tokens").is_none());
+        assert!(defaults.get("top_p").is_none());
+
+        let tuned = GenerationParams {
+            temperature: 0.9,
+            max_tokens: Some(800),
+            top_p: Some(0.95),
+        };
+        let body = chat_request_body("m", &tuned, "s", "u");
+        assert_eq!(body["temperature"], 0.9);
+        assert_eq!(body["max_tokens"], 800);
+        assert_eq!(body["top_p"], 0.95);
+        assert!(validate_generation("summary", &tuned).is_ok());
+        assert!(validate_generation(
+            "summary",
+            &GenerationParams {
+                top_p: Some(0.0),
+                ..tuned
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn endpoint_supports_path_override_and_query_params() {
+        let mut config = serde_json::from_str::<LlmConfig>(
+            r#"{"base_url":"https://api.example.com/v1/","api_key":"k","model":"m","timeout_secs":30}"#,
+        )
+        .expect("config should parse");
+        assert_eq!(
+            chat_endpoint(&config).expect("endpoint").as_str(),
+            "https://api.example.com/v1/chat/completions"
+        );
+
+        config.base_url = "https://res.openai.azure.com".to_string();
+        config.endpoint_path = Some("/openai/deployments/gpt/chat/completions".to_string());
+        config
+            .query_params
+            .insert("api-version".to_string(), "2024-06-01".to_string());
+        assert_eq!(
+            chat_endpoint(&config).expect("endpoint").as_str(),
+            "https://res.openai.azure.com/openai/deployments/gpt/chat/completions?api-version=2024-06-01"
+        );
+
+        config.endpoint_path = Some("https://proxy.example.com/chat".to_string());
+        assert_eq!(
+            chat_endpoint(&config).expect("endpoint").as_str(),
+            "https://proxy.example.com/chat?api-version=2024-06-01"
+        );
+
+        config
+            .extra_headers
+            .insert("bad header".to_string(), "x".to_string());
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn sse_decoder_reassembles_split_lines() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.push(b"data: {\"a\"").is_empty());
+        assert_eq!(
+            decoder.push(b":1}\r\n\n: keep-alive\ndata: [DONE]\n"),
+            vec!["{\"a\":1}".to_string(), "[DONE]".to_string()]
+        );
+    }
+
+    async fn stream_handler(Json(payload): Json<Value>) -> impl axum::response::IntoResponse {
+        let body = if payload.get("stream") == Some(&Value::Bool(true)) {
+            concat!(
+                "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n\n",
+                "data: [DONE]\n\n"
+            )
+        } else {
+            "data: {}\n\n"
+        };
+        ([("content-type", "text/event-stream")], body)
+    }
+
+    #[tokio::test]
+    async fn stream_chat_completion_emits_deltas() {
+        let app = Router::new().route("/chat/completions", post(stream_handler));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let addr = listener.local_addr().expect("local addr");
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        let config = serde_json::from_str::<LlmConfig>(&format!(
+            r#"{{"base_url":"http://{addr}","api_key":"k","model":"m","timeout_secs":10}}"#
+        ))
+        .expect("config should parse");
+
+        let mut deltas = Vec::new();
+        let content = stream_chat_completion(
+            &config,
+            &GenerationParams::default(),
+            "system",
+            "user",
+            |delta| deltas.push(delta.to_string()),
+        )
+        .await
+        .expect("stream should succeed");
+
+        assert_eq!(deltas, vec!["Hel", "lo"]);
+        assert_eq!(content, "Hello");
+        server.abort();
+    }
+
+    async fn ollama_handler(headers: HeaderMap, Json(payload): Json<Value>) -> String {
+        assert!(headers.get("authorization").is_none());
+        assert_eq!(payload["options"]["num_predict"], 64);
+        if payload["stream"] == Value::Bool(true) {
+            concat!(
+                "{\"message\":{\"role\":\"assistant\",\"content\":\"Bon\"},\"done\":false}\n",
+                "{\"message\":{\"role\":\"assistant\",\"content\":\"jour\"},\"done\":false}\n",
+                "{\"done\":true}\n"
+            )
+            .to_string()
+        } else {
+            r#"{"message":{"role":"assistant","content":" Bonjour "},"done":true}"#.to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn ollama_provider_uses_api_chat_without_api_key() {
+        let app = Router::new().route("/api/chat", post(ollama_handler));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let addr = listener.local_addr().expect("local addr");
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        let config = serde_json::from_str::<LlmConfig>(&format!(
+            r#"{{"provider":"ollama","base_url":"http://{addr}","api_key":"","model":"llama3.2","timeout_secs":10}}"#
+        ))
+        .expect("config should parse");
+        assert!(validate_config(&config).is_ok());
+        let params = GenerationParams {
+            max_tokens: Some(64),
+            ..GenerationParams::default()
+        };
+
+        let reply = call_chat_completion(&config, &params, "system", "user")
+            .await
+            .expect("call should succeed");
+        let mut deltas = Vec::new();
+        let streamed = stream_chat_completion(&config, &params, "system", "user", |delta| {
+            deltas.push(delta.to_string())
+        })
+        .await
+        .expect("stream should succeed");
+
+        assert_eq!(reply, "Bonjour");
+        assert_eq!(deltas, vec!["Bon", "jour"]);
+        assert_eq!(streamed, "Bonjour");
+        server.abort();
+    }
+
+    async fn anthropic_handler(
+        headers: HeaderMap,
+        Json(payload): Json<Value>,
+    ) -> axum::response::Response {
+        use axum::response::IntoResponse;
+
+        assert_eq!(
+            headers
+                .get("x-api-key")
+                .and_then(|value| value.to_str().ok()),
+            Some("sk-ant-test")
+        );
+        assert!(headers.get("authorization").is_none());
+        assert_eq!(
+            headers
+                .get("anthropic-version")
+                .and_then(|value| value.to_str().ok()),
+            Some(anthropic::ANTHROPIC_VERSION)
+        );
+        assert_eq!(payload["system"], "system");
+        assert_eq!(payload["max_tokens"], 1024);
+        if payload["stream"] == Value::Bool(true) {
+            let body = concat!(
+                "event: message_start\ndata: {\"type\":\"message_start\",\"message\":{}}\n\n",
+                "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi \"}}\n\n",
+                "event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"there\"}}\n\n",
+                "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"
+            );
+            ([("content-type", "text/event-stream")], body).into_response()
+        } else {
+            Json(serde_json::json!({
+                "type": "message",
+                "content": [{ "type": "text", "text": "Hi there" }]
+            }))
+            .into_response()
+        }
+    }
+
+    #[tokio::test]
+    async fn anthropic_provider_speaks_messages_api() {
+        let app = Router::new().route("/v1/messages", post(anthropic_handler));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let addr = listener.local_addr().expect("local addr");
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        let config = serde_json::from_str::<LlmConfig>(&format!(
+            r#"{{"provider":"anthropic","base_url":"http://{addr}/v1","api_key":"sk-ant-test","model":"claude-sonnet","timeout_secs":10}}"#
+        ))
+        .expect("config should parse");
+
+        let reply = call_chat_completion(&config, &GenerationParams::default(), "system", "user")
+            .await
+            .expect("call should succeed");
+        let mut deltas = Vec::new();
+        let streamed = stream_chat_completion(
+            &config,
+            &GenerationParams::default(),
+            "system",
+            "user",
+            |delta| deltas.push(delta.to_string()),
+        )
+        .await
+        .expect("stream should succeed");
+
+        assert_eq!(reply, "Hi there");
+        assert_eq!(deltas, vec!["Hi ", "there"]);
+        assert_eq!(streamed, "Hi there");
+        server.abort();
+    }
+
     async fn chat_handler(headers: HeaderMap, Json(payload): Json<Value>) -> Json<Value> {
         let auth = headers
             .get("authorization")
@@ -172,12 +823,20 @@ mod tests {
         });
 
         let config = LlmConfig {
+            provider: LlmProvider::OpenaiCompatible,
             base_url: format!("http://{addr}"),
             api_key: "sk-test-123".to_string(),
             model: "deepseek-chat".to_string(),
             timeout_secs: 10,
+            max_input_lines: DEFAULT_MAX_INPUT_LINES,
+            max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+            text_wrap_width: DEFAULT_TEXT_WRAP_WIDTH,
+            generation: TaskGenerationParams::default(),
+            endpoint_path: None,
+            extra_headers: BTreeMap::new(),
+            query_params: BTreeMap::new(),
         };
-        let result = call_chat_completion(&config, "system", "user")
+        let result = call_chat_completion(&config, &GenerationParams::default(), "system", "user")
             .await
             .expect("call should succeed");
 
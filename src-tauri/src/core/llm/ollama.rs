@@ -0,0 +1,98 @@
+use serde::Deserialize;
+
+use super::{GenerationParams, LlmError};
+
+pub const DEFAULT_OLLAMA_CHAT_PATH: &str = "api/chat";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct OllamaChatResponse {
+    #[serde(default)]
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct OllamaMessage {
+    #[serde(default)]
+    content: String,
+}
+
+pub fn chat_request_body(
+    model: &str,
+    params: &GenerationParams,
+    system_prompt: &str,
+    user_prompt: &str,
+    stream: bool,
+) -> serde_json::Value {
+    let mut options = serde_json::json!({ "temperature": params.temperature });
+    if let Some(max_tokens) = params.max_tokens {
+        options["num_predict"] = max_tokens.into();
+    }
+    if let Some(top_p) = params.top_p {
+        options["top_p"] = top_p.into();
+    }
+    serde_json::json!({
+        "model": model,
+        "stream": stream,
+        "options": options,
+        "messages": [
+            { "role": "system", "content": system_prompt },
+            { "role": "user", "content": user_prompt }
+        ]
+    })
+}
+
+pub fn parse_response(body: &str) -> Result<String, LlmError> {
+    let parsed: OllamaChatResponse =
+        serde_json::from_str(body).map_err(|error| LlmError::InvalidResponse(error.to_string()))?;
+    if let Some(error) = parsed.error {
+        return Err(LlmError::InvalidResponse(error));
+    }
+    parsed
+        .message
+        .map(|message| message.content.trim().to_string())
+        .filter(|content| !content.is_empty())
+        .ok_or_else(|| LlmError::InvalidResponse("missing message.content".to_string()))
+}
+
+pub async fn read_stream<F>(
+    mut response: reqwest::Response,
+    on_delta: &mut F,
+) -> Result<String, LlmError>
+where
+    F: FnMut(&str),
+{
+    let mut buffer = Vec::new();
+    let mut content = String::new();
+    'read: while let Some(chunk) = response.chunk().await? {
+        buffer.extend_from_slice(&chunk);
+        while let Some(position) = buffer.iter().position(|byte| *byte == b'\n') {
+            let line = buffer.drain(..=position).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parsed: OllamaChatResponse = serde_json::from_str(line)
+                .map_err(|error| LlmError::InvalidResponse(error.to_string()))?;
+            if let Some(error) = parsed.error {
+                return Err(LlmError::InvalidResponse(error));
+            }
+            if let Some(delta) = parsed
+                .message
+                .map(|message| message.content)
+                .filter(|delta| !delta.is_empty())
+            {
+                on_delta(&delta);
+                content.push_str(&delta);
+            }
+            if parsed.done {
+                break 'read;
+            }
+        }
+    }
+    Ok(content)
+}
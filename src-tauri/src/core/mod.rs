@@ -1,9 +1,23 @@
+pub mod actions;
+pub mod api;
+pub mod backup;
+pub mod catchup;
+pub mod diagnostics;
 pub mod feed;
+pub mod hooks;
 pub mod importer;
 pub mod llm;
+pub mod notifications;
+pub mod reader;
+pub mod redact;
+pub mod render;
+pub mod search;
+pub mod snapshot;
 pub mod storage;
 pub mod subscription;
 pub mod sync;
+pub mod trending;
+pub mod triage;
 
 use std::collections::BTreeMap;
 
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyMode {
+    #[default]
+    Never,
+    All,
+    Keywords,
+}
+
+impl NotifyMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Never => "never",
+            Self::All => "all",
+            Self::Keywords => "keywords",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "all" => Self::All,
+            "keywords" => Self::Keywords,
+            _ => Self::Never,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPrefs {
+    pub mode: NotifyMode,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub muted: bool,
+}
+
+impl NotificationPrefs {
+    pub fn normalized(self) -> Self {
+        let mut keywords = Vec::<String>::new();
+        for keyword in self.keywords {
+            let keyword = keyword.trim().to_string();
+            if !keyword.is_empty()
+                && !keywords
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(&keyword))
+            {
+                keywords.push(keyword);
+            }
+        }
+        Self {
+            mode: self.mode,
+            keywords,
+            muted: self.mode == NotifyMode::Never,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationRule {
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub source_id: Option<i64>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default = "default_rule_enabled")]
+    pub is_enabled: bool,
+}
+
+fn default_rule_enabled() -> bool {
+    true
+}
+
+impl NotificationRule {
+    pub fn normalized(self) -> Self {
+        let keywords = NotificationPrefs {
+            mode: NotifyMode::Keywords,
+            keywords: self.keywords,
+            muted: false,
+        }
+        .normalized()
+        .keywords;
+        Self {
+            name: self.name.trim().to_string(),
+            keywords,
+            category: self
+                .category
+                .map(|category| category.trim().to_string())
+                .filter(|category| !category.is_empty()),
+            ..self
+        }
+    }
+
+    pub fn has_conditions(&self) -> bool {
+        !self.keywords.is_empty() || self.source_id.is_some() || self.category.is_some()
+    }
+
+    pub fn applies_to(&self, source_id: i64, category: Option<&str>) -> bool {
+        self.is_enabled
+            && self.source_id.is_none_or(|id| id == source_id)
+            && self.category.as_deref().is_none_or(|expected| {
+                category.is_some_and(|actual| actual.trim().eq_ignore_ascii_case(expected))
+            })
+    }
+}
+
+pub fn rules_for_source(
+    prefs: Option<&NotificationPrefs>,
+    rules: &[NotificationRule],
+    source_id: i64,
+    category: Option<&str>,
+) -> Vec<NotificationRule> {
+    if prefs.is_some_and(|prefs| prefs.muted) {
+        return Vec::new();
+    }
+    rules
+        .iter()
+        .filter(|rule| rule.applies_to(source_id, category))
+        .cloned()
+        .collect()
+}
+
+pub fn rule_match(
+    rules: &[NotificationRule],
+    title: &str,
+    summary: Option<&str>,
+) -> Option<Option<String>> {
+    rules.iter().find_map(|rule| {
+        if rule.keywords.is_empty() {
+            return Some(None);
+        }
+        notification_match(
+            &NotificationPrefs {
+                mode: NotifyMode::Keywords,
+                keywords: rule.keywords.clone(),
+                muted: false,
+            },
+            title,
+            summary,
+        )
+    })
+}
+
+pub fn notification_match(
+    prefs: &NotificationPrefs,
+    title: &str,
+    summary: Option<&str>,
+) -> Option<Option<String>> {
+    match prefs.mode {
+        NotifyMode::Never => None,
+        NotifyMode::All => Some(None),
+        NotifyMode::Keywords => {
+            let haystack = format!("{} {}", title, summary.unwrap_or("")).to_lowercase();
+            prefs
+                .keywords
+                .iter()
+                .find(|keyword| haystack.contains(&keyword.to_lowercase()))
+                .map(|keyword| Some(keyword.clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_filter_by_source_category_and_keywords() {
+        let rules = [
+            NotificationRule {
+                name: " Security ".to_string(),
+                keywords: vec!["cve".to_string(), "CVE".to_string()],
+                category: Some(" Security ".to_string()),
+                is_enabled: true,
+                ..NotificationRule::default()
+            }
+            .normalized(),
+            NotificationRule {
+                name: "Everything from 9".to_string(),
+                source_id: Some(9),
+                is_enabled: true,
+                ..NotificationRule::default()
+            },
+            NotificationRule {
+                name: "Disabled".to_string(),
+                is_enabled: false,
+                ..NotificationRule::default()
+            },
+        ];
+        assert_eq!(rules[0].name, "Security");
+        assert_eq!(rules[0].keywords, vec!["cve"]);
+        assert!(!rules[2].has_conditions());
+
+        let applicable = |source_id, category| rules_for_source(None, &rules, source_id, category);
+        let security = applicable(1, Some("security"));
+        assert_eq!(security.len(), 1);
+        assert_eq!(
+            rule_match(&security, "New CVE in curl", None),
+            Some(Some("cve".to_string()))
+        );
+        assert_eq!(rule_match(&security, "Release notes", None), None);
+        assert!(applicable(1, None).is_empty());
+        let muted = NotificationPrefs {
+            mode: NotifyMode::Never,
+            ..NotificationPrefs::default()
+        }
+        .normalized();
+        assert!(rules_for_source(Some(&muted), &rules, 9, Some("security")).is_empty());
+        assert_eq!(
+            rules_for_source(Some(&NotificationPrefs::default()), &rules, 9, None).len(),
+            1
+        );
+        assert_eq!(
+            rule_match(&applicable(9, None), "Anything", None),
+            Some(None)
+        );
+    }
+
+    #[test]
+    fn matches_entries_by_mode_and_keyword() {
+        let keywords = NotificationPrefs {
+            mode: NotifyMode::Keywords,
+            keywords: vec![" CVE ".to_string(), "cve".to_string(), "outage".to_string()],
+            muted: false,
+        }
+        .normalized();
+
+        assert_eq!(keywords.keywords, vec!["CVE", "outage"]);
+        assert_eq!(
+            notification_match(&keywords, "New cve-2026-1234 in openssl", None),
+            Some(Some("CVE".to_string()))
+        );
+        assert_eq!(notification_match(&keywords, "Release notes", None), None);
+        assert_eq!(
+            notification_match(
+                &NotificationPrefs {
+                    mode: NotifyMode::All,
+                    keywords: Vec::new(),
+                    muted: false,
+                },
+                "Anything",
+                None
+            ),
+            Some(None)
+        );
+        assert_eq!(
+            notification_match(&NotificationPrefs::default(), "Anything", None),
+            None
+        );
+    }
+}
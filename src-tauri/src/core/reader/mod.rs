@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::core::redact::redact_secrets;
+
+const MIN_PARAGRAPH_CHARS: usize = 25;
+const MIN_ARTICLE_CHARS: usize = 140;
+const CLASS_WEIGHT: f64 = 25.0;
+const MAX_LINK_DENSITY: f64 = 0.5;
+const POSITIVE_HINTS: [&str; 10] = [
+    "article", "body", "content", "entry", "main", "page", "post", "text", "blog", "story",
+];
+const NEGATIVE_HINTS: [&str; 18] = [
+    "comment",
+    "footer",
+    "header",
+    "nav",
+    "sidebar",
+    "sponsor",
+    "share",
+    "social",
+    "related",
+    "promo",
+    "advert",
+    "widget",
+    "menu",
+    "masthead",
+    "banner",
+    "breadcrumb",
+    "subscribe",
+    "cookie",
+];
+const SKIPPED_TAGS: [&str; 17] = [
+    "script", "style", "noscript", "nav", "aside", "footer", "header", "form", "iframe", "button",
+    "input", "select", "textarea", "svg", "template", "object", "embed",
+];
+const KEPT_TAGS: [&str; 27] = [
+    "p",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+    "pre",
+    "code",
+    "em",
+    "strong",
+    "b",
+    "i",
+    "sup",
+    "sub",
+    "figure",
+    "figcaption",
+    "table",
+    "thead",
+    "tbody",
+    "tr",
+    "th",
+    "td",
+    "hr",
+];
+const BLOCK_TAGS: [&str; 14] = [
+    "p",
+    "div",
+    "section",
+    "article",
+    "ul",
+    "ol",
+    "blockquote",
+    "pre",
+    "table",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReaderError {
+    #[error("invalid article url: {0}")]
+    InvalidUrl(String),
+    #[error("request failed: {}", redact_secrets(&.0.to_string()))]
+    Request(#[from] reqwest::Error),
+    #[error("article request returned status {0}")]
+    Status(u16),
+    #[error("no readable article content found")]
+    NoContent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReaderArticle {
+    pub url: String,
+    pub title: Option<String>,
+    pub content_html: String,
+    pub text_length: usize,
+}
+
+pub async fn fetch_article(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<ReaderArticle, ReaderError> {
+    let target = Url::parse(url.trim()).map_err(|_| ReaderError::InvalidUrl(url.to_string()))?;
+    let response = client.get(target.as_str()).send().await?;
+    if !response.status().is_success() {
+        return Err(ReaderError::Status(response.status().as_u16()));
+    }
+    let base = response.url().clone();
+    let html = response.text().await?;
+    extract_article(&html, &base).ok_or(ReaderError::NoContent)
+}
+
+pub fn extract_article(html: &str, base: &Url) -> Option<ReaderArticle> {
+    let document = Html::parse_document(html);
+    let root = best_candidate(&document)?;
+    let mut content_html = String::new();
+    write_children(root, base, &mut content_html);
+    let text_length = collapse_whitespace(&root.text().collect::<String>())
+        .chars()
+        .count();
+    if text_length < MIN_ARTICLE_CHARS || content_html.trim().is_empty() {
+        return None;
+    }
+    Some(ReaderArticle {
+        url: base.to_string(),
+        title: document_title(&document),
+        content_html,
+        text_length,
+    })
+}
+
+fn best_candidate(document: &Html) -> Option<ElementRef<'_>> {
+    let selector = Selector::parse("p, pre, td").expect("selector must parse");
+    let mut scores = HashMap::new();
+    for paragraph in document.select(&selector) {
+        if paragraph
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(|ancestor| SKIPPED_TAGS.contains(&ancestor.value().name()))
+        {
+            continue;
+        }
+        let text = collapse_whitespace(&paragraph.text().collect::<String>());
+        let length = text.chars().count();
+        if length < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let commas = text.matches([',', '，', '、']).count();
+        let score = 1.0 + commas as f64 + (length / 100).min(3) as f64;
+        let ancestors = paragraph.ancestors().filter_map(ElementRef::wrap);
+        for (share, ancestor) in [1.0, 0.5].into_iter().zip(ancestors) {
+            scores
+                .entry(ancestor.id())
+                .or_insert_with(|| (ancestor, initial_score(ancestor)))
+                .1 += score * share;
+        }
+    }
+    scores
+        .into_values()
+        .map(|(element, score)| (element, score * (1.0 - link_density(element))))
+        .max_by(|left, right| left.1.total_cmp(&right.1))
+        .map(|(element, _)| element)
+}
+
+fn initial_score(element: ElementRef<'_>) -> f64 {
+    let base = match element.value().name() {
+        "article" => 10.0,
+        "div" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "ol" | "ul" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    base + class_weight(element)
+}
+
+fn class_weight(element: ElementRef<'_>) -> f64 {
+    let hints = format!(
+        "{} {}",
+        element.value().attr("class").unwrap_or_default(),
+        element.value().attr("id").unwrap_or_default()
+    )
+    .to_lowercase();
+    let mut weight = 0.0;
+    if NEGATIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight -= CLASS_WEIGHT;
+    }
+    if POSITIVE_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight += CLASS_WEIGHT;
+    }
+    weight
+}
+
+fn link_density(element: ElementRef<'_>) -> f64 {
+    let total = element.text().map(str::len).sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let selector = Selector::parse("a").expect("selector must parse");
+    let linked = element
+        .select(&selector)
+        .flat_map(|link| link.text())
+        .map(str::len)
+        .sum::<usize>();
+    linked as f64 / total as f64
+}
+
+fn write_children(element: ElementRef<'_>, base: &Url, output: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => output.push_str(&escape_html(text)),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    write_element(child, base, output);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn write_element(element: ElementRef<'_>, base: &Url, output: &mut String) {
+    let name = element.value().name();
+    if SKIPPED_TAGS.contains(&name) || element.value().attr("hidden").is_some() {
+        return;
+    }
+    if class_weight(element) < 0.0 && link_density(element) > MAX_LINK_DENSITY {
+        return;
+    }
+    match name {
+        "br" => output.push_str("<br>"),
+        "hr" => output.push_str("<hr>"),
+        "img" => {
+            if let Some(src) = resolve_link(base, element.value().attr("src")) {
+                let alt = element.value().attr("alt").unwrap_or_default();
+                output.push_str(&format!(
+                    r#"<img src="{}" alt="{}">"#,
+                    escape_html(&src),
+                    escape_html(alt)
+                ));
+            }
+        }
+        "a" => match resolve_link(base, element.value().attr("href")) {
+            Some(href) => {
+                output.push_str(&format!(r#"<a href="{}">"#, escape_html(&href)));
+                write_children(element, base, output);
+                output.push_str("</a>");
+            }
+            None => write_children(element, base, output),
+        },
+        "h1" => write_wrapped("h2", element, base, output),
+        "div" | "section" if !has_block_children(element) => {
+            write_wrapped("p", element, base, output)
+        }
+        _ if KEPT_TAGS.contains(&name) => write_wrapped(name, element, base, output),
+        _ => write_children(element, base, output),
+    }
+}
+
+fn write_wrapped(tag: &str, element: ElementRef<'_>, base: &Url, output: &mut String) {
+    let mut inner = String::new();
+    write_children(element, base, &mut inner);
+    if inner.trim().is_empty() && !matches!(tag, "td" | "th") {
+        return;
+    }
+    output.push_str(&format!("<{tag}>{inner}</{tag}>"));
+}
+
+fn has_block_children(element: ElementRef<'_>) -> bool {
+    element
+        .children()
+        .filter_map(ElementRef::wrap)
+        .any(|child| BLOCK_TAGS.contains(&child.value().name()))
+}
+
+fn resolve_link(base: &Url, value: Option<&str>) -> Option<String> {
+    let url = base.join(value?.trim()).ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+fn document_title(document: &Html) -> Option<String> {
+    let og = Selector::parse(r#"meta[property="og:title"]"#).expect("selector must parse");
+    let title = Selector::parse("title").expect("selector must parse");
+    document
+        .select(&og)
+        .find_map(|meta| meta.value().attr("content").map(str::to_string))
+        .or_else(|| {
+            document
+                .select(&title)
+                .next()
+                .map(|element| element.text().collect::<String>())
+        })
+        .map(|value| collapse_whitespace(&value))
+        .filter(|value| !value.is_empty())
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE_PAGE: &str = r#"<html><head>
+        <title>Fallback title</title>
+        <meta property="og:title" content="Writing a Feed Reader">
+        <script>track()</script>
+      </head><body>
+        <header class="masthead"><a href="/">Home</a> <a href="/about">About</a></header>
+        <nav><ul><li><a href="/a">A</a></li><li><a href="/b">B</a></li></ul></nav>
+        <div id="main" class="post-content">
+          <h1>Writing a Feed Reader</h1>
+          <p>Feed readers poll many sources, parse RSS, Atom and JSON Feed, and keep track of what you have read.</p>
+          <p>Good readers respect caching headers, back off on failures, and never mark items read behind your back.</p>
+          <img src="/img/diagram.png" alt="Sync diagram" onerror="alert(1)">
+          <p>See the <a href="notes.html">design notes</a> or <a href="javascript:alert(1)">this</a> for details.</p>
+          <div class="share-widget"><a href="https://social.example/share">Share</a></div>
+        </div>
+        <aside class="sidebar"><p>Subscribe to our newsletter for weekly updates, tips, and plenty of other things.</p></aside>
+        <footer>Copyright</footer>
+      </body></html>"#;
+
+    #[test]
+    fn extracts_main_article_and_drops_chrome() {
+        let base = Url::parse("https://blog.example.com/posts/reader").expect("base url");
+        let article = extract_article(ARTICLE_PAGE, &base).expect("article should extract");
+
+        assert_eq!(article.title.as_deref(), Some("Writing a Feed Reader"));
+        assert!(article
+            .content_html
+            .contains("<h2>Writing a Feed Reader</h2>"));
+        assert!(article.content_html.contains(
+            r#"<img src="https://blog.example.com/img/diagram.png" alt="Sync diagram">"#
+        ));
+        assert!(article
+            .content_html
+            .contains(r#"<a href="https://blog.example.com/posts/notes.html">design notes</a>"#));
+        for unwanted in [
+            "track()",
+            "About",
+            "Share",
+            "newsletter",
+            "Copyright",
+            "javascript:",
+            "onerror",
+        ] {
+            assert!(
+                !article.content_html.contains(unwanted),
+                "{unwanted} should be stripped"
+            );
+        }
+    }
+
+    #[test]
+    fn short_pages_have_no_article() {
+        let base = Url::parse("https://example.com/").expect("base url");
+        assert!(
+            extract_article("<html><body><p>Just a short note.</p></body></html>", &base).is_none()
+        );
+    }
+}
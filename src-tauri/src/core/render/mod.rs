@@ -0,0 +1,230 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::storage::models::EntryRecord;
+
+const BROWSER_ENV_VAR: &str = "RSSR_BROWSER";
+const RENDER_TIMEOUT_SECS: u64 = 60;
+const PAGE_WIDTH_PX: usize = 900;
+const MIN_PAGE_HEIGHT_PX: usize = 600;
+const MAX_PAGE_HEIGHT_PX: usize = 16000;
+const BROWSER_NAMES: [&str; 6] = [
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "microsoft-edge",
+    "msedge",
+];
+const BROWSER_PATHS: [&str; 5] = [
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+    "/Applications/Chromium.app/Contents/MacOS/Chromium",
+    "/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+    r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+    r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum RenderError {
+    #[error("no Chromium-based browser found; set {BROWSER_ENV_VAR} to its executable")]
+    BrowserNotFound,
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("browser render timed out")]
+    Timeout,
+    #[error("browser render failed: {0}")]
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderFormat {
+    Pdf,
+    Png,
+}
+
+pub fn build_entry_document(entry: &EntryRecord, summary: Option<&str>) -> String {
+    let body = entry
+        .content
+        .as_deref()
+        .or(entry.summary.as_deref())
+        .map(|html| html2text::from_read(html.as_bytes(), 100))
+        .unwrap_or_default();
+    let paragraphs = text_paragraphs(&body);
+    let summary = summary
+        .map(|summary| {
+            format!(
+                r#"<section class="summary">{}</section>"#,
+                text_paragraphs(summary)
+            )
+        })
+        .unwrap_or_default();
+    let meta = [
+        Some(entry.source_title.as_str()),
+        entry.published_at.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(escape_html)
+    .collect::<Vec<_>>()
+    .join(" · ");
+    format!(
+        r#"<!doctype html>
+<html><head><meta charset="utf-8">
+<meta http-equiv="Content-Security-Policy" content="default-src 'none'; style-src 'unsafe-inline'">
+<title>{title}</title>
+<style>
+body {{ margin: 0; padding: 48px 56px; width: {width}px; box-sizing: border-box; font-family: -apple-system, "PingFang SC", "Microsoft YaHei", "Noto Sans CJK SC", sans-serif; color: #1f2328; background: #fff; line-height: 1.7; }}
+h1 {{ font-size: 28px; line-height: 1.3; margin: 0 0 8px; }}
+.meta {{ color: #656d76; font-size: 14px; margin-bottom: 24px; }}
+.summary {{ background: #f6f8fa; border-left: 4px solid #0969da; padding: 12px 16px; margin-bottom: 24px; }}
+p {{ margin: 0 0 14px; font-size: 16px; }}
+.link {{ color: #656d76; font-size: 13px; word-break: break-all; margin-top: 32px; }}
+</style></head>
+<body><h1>{title}</h1><div class="meta">{meta}</div>{summary}<article>{paragraphs}</article><div class="link">{link}</div></body></html>"#,
+        title = escape_html(&entry.title),
+        width = PAGE_WIDTH_PX,
+        link = escape_html(&entry.link),
+    )
+}
+
+fn estimate_page_height(document: &str) -> usize {
+    let text_len = document.chars().count();
+    (text_len / 3).clamp(MIN_PAGE_HEIGHT_PX, MAX_PAGE_HEIGHT_PX)
+}
+
+pub fn find_browser() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(BROWSER_ENV_VAR).map(PathBuf::from) {
+        return path.is_file().then_some(path);
+    }
+    let path_dirs = std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default();
+    BROWSER_NAMES
+        .iter()
+        .flat_map(|name| {
+            path_dirs
+                .iter()
+                .flat_map(move |dir| [dir.join(name), dir.join(format!("{name}.exe"))])
+        })
+        .chain(BROWSER_PATHS.iter().map(PathBuf::from))
+        .find(|candidate| candidate.is_file())
+}
+
+pub async fn render_document(
+    browser: &Path,
+    document: &str,
+    format: RenderFormat,
+    output: &Path,
+) -> Result<(), RenderError> {
+    let stamp = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let work_dir = std::env::temp_dir().join(format!("rssr-render-{}-{stamp}", std::process::id()));
+    std::fs::create_dir_all(&work_dir)?;
+    let input = work_dir.join("entry.html");
+    std::fs::write(&input, document)?;
+    let input_url = url::Url::from_file_path(&input)
+        .map_err(|_| RenderError::Failed("invalid temporary path".to_string()))?;
+    let mut command = tokio::process::Command::new(browser);
+    command
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--hide-scrollbars")
+        .arg(format!(
+            "--user-data-dir={}",
+            work_dir.join("profile").display()
+        ));
+    match format {
+        RenderFormat::Pdf => {
+            command
+                .arg("--no-pdf-header-footer")
+                .arg(format!("--print-to-pdf={}", output.display()));
+        }
+        RenderFormat::Png => {
+            command
+                .arg(format!(
+                    "--window-size={PAGE_WIDTH_PX},{}",
+                    estimate_page_height(document)
+                ))
+                .arg(format!("--screenshot={}", output.display()));
+        }
+    }
+    command.arg(input_url.as_str()).kill_on_drop(true);
+    let result =
+        tokio::time::timeout(Duration::from_secs(RENDER_TIMEOUT_SECS), command.output()).await;
+    let _ = std::fs::remove_dir_all(&work_dir);
+    let finished = result.map_err(|_| RenderError::Timeout)??;
+    if !finished.status.success() || !output.is_file() {
+        let stderr = String::from_utf8_lossy(&finished.stderr);
+        return Err(RenderError::Failed(
+            stderr
+                .lines()
+                .last()
+                .unwrap_or("no output written")
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn text_paragraphs(text: &str) -> String {
+    text.split("\n\n")
+        .map(|block| {
+            block
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|block| !block.is_empty())
+        .map(|block| format!("<p>{}</p>", escape_html(&block)))
+        .collect()
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_document_is_escaped_and_script_free() {
+        let entry = EntryRecord {
+            id: 1,
+            source_id: 1,
+            source_title: "Example & Co".to_string(),
+            guid: None,
+            link: "https://example.com/post?a=1&b=2".to_string(),
+            title: "<Title>".to_string(),
+            translated_title: None,
+            summary: None,
+            content: Some(
+                "<p>First paragraph</p><script>alert(1)</script><p>Second <b>bold</b></p>"
+                    .to_string(),
+            ),
+            published_at: Some("2026-02-24".to_string()),
+            is_read: 0,
+            is_starred: 0,
+            created_at: "2026-02-24T00:00:00Z".to_string(),
+            updated_at: None,
+            is_updated: false,
+        };
+
+        let document = build_entry_document(&entry, Some("Key point"));
+
+        assert!(document.contains("<h1>&lt;Title&gt;</h1>"));
+        assert!(document.contains("Example &amp; Co · 2026-02-24"));
+        assert!(document.contains(r#"<section class="summary"><p>Key point</p></section>"#));
+        assert!(document.contains("First paragraph"));
+        assert!(!document.contains("<script>"));
+        assert!(document.contains("https://example.com/post?a=1&amp;b=2"));
+    }
+}
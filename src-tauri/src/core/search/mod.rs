@@ -0,0 +1,143 @@
+use chrono::NaiveDate;
+
+pub mod tokenize;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub terms: Vec<String>,
+    pub sources: Vec<String>,
+    pub tags: Vec<String>,
+    pub is_read: Option<bool>,
+    pub is_starred: Option<bool>,
+    pub before: Option<NaiveDate>,
+    pub after: Option<NaiveDate>,
+}
+
+impl SearchQuery {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+pub fn parse_search_query(input: &str) -> SearchQuery {
+    let mut query = SearchQuery::default();
+    for token in split_tokens(input) {
+        let Some((key, value)) = token
+            .text
+            .split_once(':')
+            .filter(|(key, value)| !token.quoted && !key.is_empty() && !value.is_empty())
+        else {
+            query.terms.push(token.text);
+            continue;
+        };
+        let value = value.trim_matches('"').to_string();
+        let applied = match key.to_lowercase().as_str() {
+            "source" => {
+                query.sources.push(value);
+                true
+            }
+            "tag" => {
+                query.tags.push(value);
+                true
+            }
+            "is" => apply_state(&mut query, &value.to_lowercase()),
+            "before" => parse_date(&value)
+                .map(|date| query.before = Some(date))
+                .is_some(),
+            "after" => parse_date(&value)
+                .map(|date| query.after = Some(date))
+                .is_some(),
+            _ => false,
+        };
+        if !applied {
+            query.terms.push(token.text);
+        }
+    }
+    query
+}
+
+struct Token {
+    text: String,
+    quoted: bool,
+}
+
+fn split_tokens(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                if current.is_empty() {
+                    quoted = true;
+                } else {
+                    current.push(ch);
+                }
+            }
+            ch if ch.is_whitespace() && !in_quotes => {
+                push_token(&mut tokens, &mut current, quoted);
+                quoted = false;
+            }
+            ch => current.push(ch),
+        }
+    }
+    push_token(&mut tokens, &mut current, quoted);
+    tokens
+}
+
+fn push_token(tokens: &mut Vec<Token>, current: &mut String, quoted: bool) {
+    let text = current.trim().trim_end_matches('"').to_string();
+    if !text.is_empty() {
+        tokens.push(Token { text, quoted });
+    }
+    current.clear();
+}
+
+fn apply_state(query: &mut SearchQuery, value: &str) -> bool {
+    match value {
+        "unread" => query.is_read = Some(false),
+        "read" => query.is_read = Some(true),
+        "starred" => query.is_starred = Some(true),
+        "unstarred" => query.is_starred = Some(false),
+        _ => return false,
+    }
+    true
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_operators_and_quoted_phrases() {
+        let query = parse_search_query(
+            r#"rust "async closures" source:"This Week" tag:lang is:unread is:starred after:2026-01-01 before:2026-02-01"#,
+        );
+
+        assert_eq!(query.terms, vec!["rust", "async closures"]);
+        assert_eq!(query.sources, vec!["This Week"]);
+        assert_eq!(query.tags, vec!["lang"]);
+        assert_eq!(query.is_read, Some(false));
+        assert_eq!(query.is_starred, Some(true));
+        assert_eq!(query.after, NaiveDate::from_ymd_opt(2026, 1, 1));
+        assert_eq!(query.before, NaiveDate::from_ymd_opt(2026, 2, 1));
+    }
+
+    #[test]
+    fn keeps_unknown_operators_and_bad_dates_as_terms() {
+        let query = parse_search_query("https://example.com before:soon \"is:read\"");
+
+        assert_eq!(
+            query.terms,
+            vec!["https://example.com", "before:soon", "is:read"]
+        );
+        assert!(query.before.is_none());
+        assert!(query.is_read.is_none());
+    }
+}
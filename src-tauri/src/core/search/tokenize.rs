@@ -0,0 +1,174 @@
+const MAX_PINYIN_SYLLABLES: usize = 8;
+const MIN_PINYIN_QUERY_CHARS: usize = 2;
+
+enum Segment {
+    Word(String),
+    Cjk(Vec<char>),
+}
+
+pub fn is_cjk(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+    )
+}
+
+pub fn plain_text(html: &str) -> String {
+    scraper::Html::parse_fragment(html)
+        .root_element()
+        .text()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn index_tokens(text: &str) -> String {
+    let mut tokens = Vec::new();
+    for segment in segments(text) {
+        match segment {
+            Segment::Word(word) => tokens.push(word),
+            Segment::Cjk(run) => {
+                tokens.extend(run.windows(2).map(|pair| pair.iter().collect()));
+                tokens.extend(run.last().map(char::to_string));
+            }
+        }
+    }
+    tokens.join(" ")
+}
+
+pub fn pinyin_tokens(text: &str) -> String {
+    let mut tokens = Vec::new();
+    for segment in segments(text) {
+        let Segment::Cjk(run) = segment else {
+            continue;
+        };
+        let syllables = run
+            .iter()
+            .filter_map(|ch| deunicode::deunicode_char(*ch))
+            .map(|syllable| {
+                syllable
+                    .chars()
+                    .filter(char::is_ascii_alphabetic)
+                    .collect::<String>()
+                    .to_ascii_lowercase()
+            })
+            .filter(|syllable| !syllable.is_empty())
+            .collect::<Vec<_>>();
+        for start in 0..syllables.len() {
+            let window = &syllables[start..syllables.len().min(start + MAX_PINYIN_SYLLABLES)];
+            tokens.push(window.concat());
+            tokens.push(
+                window
+                    .iter()
+                    .filter_map(|syllable| syllable.get(..1))
+                    .collect(),
+            );
+        }
+    }
+    tokens.join(" ")
+}
+
+pub fn fts_match_expression(terms: &[String], pinyin: bool) -> Option<String> {
+    let clauses = terms
+        .iter()
+        .filter_map(|term| {
+            let tokens = query_tokens(term);
+            if tokens.is_empty() {
+                return None;
+            }
+            let text = format!("{{title body}} : \"{}\" *", tokens.join(" "));
+            let romanized = term.to_ascii_lowercase();
+            if pinyin
+                && romanized.len() >= MIN_PINYIN_QUERY_CHARS
+                && romanized.chars().all(|ch| ch.is_ascii_lowercase())
+            {
+                return Some(format!("({text} OR pinyin : \"{romanized}\" *)"));
+            }
+            Some(text)
+        })
+        .collect::<Vec<_>>();
+    (!clauses.is_empty()).then(|| clauses.join(" AND "))
+}
+
+fn query_tokens(term: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for segment in segments(term) {
+        match segment {
+            Segment::Word(word) => tokens.push(word),
+            Segment::Cjk(run) if run.len() == 1 => tokens.push(run[0].to_string()),
+            Segment::Cjk(run) => tokens.extend(run.windows(2).map(|pair| pair.iter().collect())),
+        }
+    }
+    tokens
+}
+
+fn segments(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut word = String::new();
+    let mut run = Vec::new();
+    for ch in text.chars().map(fold_width) {
+        if is_cjk(ch) {
+            if !word.is_empty() {
+                segments.push(Segment::Word(std::mem::take(&mut word)));
+            }
+            run.push(ch);
+        } else {
+            if !run.is_empty() {
+                segments.push(Segment::Cjk(std::mem::take(&mut run)));
+            }
+            if ch.is_alphanumeric() {
+                word.extend(ch.to_lowercase());
+            } else if !word.is_empty() {
+                segments.push(Segment::Word(std::mem::take(&mut word)));
+            }
+        }
+    }
+    if !word.is_empty() {
+        segments.push(Segment::Word(word));
+    }
+    if !run.is_empty() {
+        segments.push(Segment::Cjk(run));
+    }
+    segments
+}
+
+fn fold_width(ch: char) -> char {
+    match ch {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(ch as u32 - 0xFEE0).unwrap_or(ch),
+        '\u{3000}' => ' ',
+        _ => ch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_cjk_runs_as_bigrams_and_folds_latin_text() {
+        assert_eq!(
+            index_tokens("Rust异步运行时，ＲＥＬＥＡＳＥ Über"),
+            "rust 异步 步运 运行 行时 时 release über"
+        );
+        assert_eq!(index_tokens("锈"), "锈");
+        assert_eq!(pinyin_tokens("Rust 北京"), "beijing bj jing j");
+    }
+
+    #[test]
+    fn builds_phrase_queries_with_optional_pinyin() {
+        let terms = vec!["运行时".to_string(), "Rust".to_string(), "锈".to_string()];
+
+        assert_eq!(
+            fts_match_expression(&terms, false).as_deref(),
+            Some("{title body} : \"运行 行时\" * AND {title body} : \"rust\" * AND {title body} : \"锈\" *")
+        );
+        assert_eq!(
+            fts_match_expression(&["bj".to_string()], true).as_deref(),
+            Some("({title body} : \"bj\" * OR pinyin : \"bj\" *)")
+        );
+        assert_eq!(fts_match_expression(&["——".to_string()], true), None);
+    }
+}
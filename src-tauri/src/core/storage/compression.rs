@@ -0,0 +1,41 @@
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const ZSTD_LEVEL: i32 = 3;
+
+pub const COMPRESSION_THRESHOLD_BYTES: usize = 512;
+
+pub fn compress_text(value: &str) -> Option<Vec<u8>> {
+    if value.len() < COMPRESSION_THRESHOLD_BYTES {
+        return None;
+    }
+    let compressed = zstd::bulk::compress(value.as_bytes(), ZSTD_LEVEL).ok()?;
+    (compressed.len() < value.len()).then_some(compressed)
+}
+
+pub fn decompress_text(raw: &[u8]) -> String {
+    if raw.starts_with(&ZSTD_MAGIC) {
+        if let Ok(decoded) = zstd::stream::decode_all(raw) {
+            return String::from_utf8_lossy(&decoded).into_owned();
+        }
+    }
+    String::from_utf8_lossy(raw).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_left_uncompressed() {
+        assert!(compress_text("<p>short</p>").is_none());
+        assert_eq!(decompress_text(b"<p>short</p>"), "<p>short</p>");
+    }
+
+    #[test]
+    fn large_html_roundtrips_and_shrinks() {
+        let html = "<p>Lorem ipsum dolor sit amet, consectetur adipiscing elit.</p>\n".repeat(200);
+        let compressed = compress_text(&html).expect("large html should compress");
+
+        assert!(compressed.len() * 2 < html.len());
+        assert_eq!(decompress_text(&compressed), html);
+    }
+}
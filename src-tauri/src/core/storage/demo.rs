@@ -0,0 +1,122 @@
+use serde::Serialize;
+
+use super::models::NewSource;
+use super::repository::{SourceRepository, StorageError};
+use crate::core::feed::types::ParsedEntry;
+
+const DEMO_HOST: &str = "https://demo.rssr.invalid";
+const DEMO_ENTRIES_PER_SOURCE: usize = 6;
+const DEMO_SOURCES: [(&str, &str, &str); 3] = [
+    ("rust", "Rust Weekly Demo", "Rust"),
+    ("security", "Security Notes Demo", "Security"),
+    ("design", "Design Journal Demo", "Design"),
+];
+const DEMO_TOPICS: [(&str, &str); DEMO_ENTRIES_PER_SOURCE] = [
+    ("Release notes", "发布说明"),
+    ("Deep dive into async runtimes", "深入异步运行时"),
+    ("Community roundup", "社区周报"),
+    ("Interview with a maintainer", "维护者访谈"),
+    ("Benchmarks revisited", "重新审视基准测试"),
+    ("What we learned this year", "今年的收获"),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DemoSeedReport {
+    pub sources: usize,
+    pub entries: usize,
+    pub translated: usize,
+}
+
+pub async fn seed_demo_data(repository: &SourceRepository) -> Result<DemoSeedReport, StorageError> {
+    let mut report = DemoSeedReport::default();
+    for (source_index, (slug, title, category)) in DEMO_SOURCES.iter().enumerate() {
+        let source = repository
+            .upsert_source(&NewSource {
+                title: title.to_string(),
+                site_url: Some(format!("{DEMO_HOST}/{slug}")),
+                feed_url: format!("{DEMO_HOST}/{slug}/feed.xml"),
+                category: Some(category.to_string()),
+                is_active: false,
+            })
+            .await?;
+        report.sources += 1;
+
+        let entries = DEMO_TOPICS
+            .iter()
+            .enumerate()
+            .map(|(index, (topic, _))| demo_entry(slug, source_index, index, topic))
+            .collect::<Vec<_>>();
+        repository.upsert_entries(source.id, &entries).await?;
+        report.entries += entries.len();
+
+        for row in repository.list_entries_after(source.id, 0).await? {
+            let Some(index) = DEMO_TOPICS
+                .iter()
+                .position(|(topic, _)| row.title.ends_with(topic))
+            else {
+                continue;
+            };
+            if index % 2 == 0 {
+                repository
+                    .set_entry_translated_title(row.id, DEMO_TOPICS[index].1)
+                    .await?;
+                report.translated += 1;
+            }
+            repository.mark_entry_read(row.id, index >= 3).await?;
+            repository.set_entry_starred(row.id, index == 1).await?;
+        }
+    }
+    Ok(report)
+}
+
+fn demo_entry(slug: &str, source_index: usize, index: usize, topic: &str) -> ParsedEntry {
+    let day = 1 + source_index * DEMO_ENTRIES_PER_SOURCE + index;
+    ParsedEntry {
+        id: format!("{DEMO_HOST}/{slug}/{index}"),
+        title: format!("[{slug}] {topic}"),
+        link: format!("{DEMO_HOST}/{slug}/posts/{index}"),
+        summary: Some(format!("Demo summary for {topic} from the {slug} feed.")),
+        content: Some(format!(
+            "<p>Demo article {index} for {slug}.</p><p>{topic} — deterministic fixture content.</p>"
+        )),
+        published_at: Some(format!("2026-01-{day:02}T08:00:00Z")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn seeding_is_deterministic_and_idempotent() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+
+        let first = seed_demo_data(&repository).await.expect("first seed");
+        let second = seed_demo_data(&repository).await.expect("second seed");
+        let sources = repository.list_sources().await.expect("list sources");
+        let entries = repository
+            .list_entries(None, None, false, 100)
+            .await
+            .expect("list entries");
+
+        assert_eq!(first, second);
+        assert_eq!(
+            first,
+            DemoSeedReport {
+                sources: 3,
+                entries: 18,
+                translated: 9,
+            }
+        );
+        assert_eq!(sources.len(), 3);
+        assert_eq!(entries.len(), 18);
+        assert_eq!(entries[0].title, "[design] What we learned this year");
+        assert_eq!(
+            entries.iter().filter(|entry| entry.is_starred == 1).count(),
+            3
+        );
+        assert_eq!(entries.iter().filter(|entry| entry.is_read == 1).count(), 9);
+    }
+}
@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use sqlx::SqliteConnection;
+
+use super::repository::StorageError;
+
+const PROGRESS_HANDLER_OPS: i32 = 1000;
+
+#[derive(Debug, Clone)]
+pub struct QueryControl {
+    cancelled: Arc<AtomicBool>,
+    timeout: Duration,
+}
+
+impl QueryControl {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            timeout,
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn same_as(&self, other: &QueryControl) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+
+    pub(crate) async fn arm(
+        &self,
+        connection: &mut SqliteConnection,
+    ) -> Result<ArmedQuery, StorageError> {
+        let deadline = Instant::now() + self.timeout;
+        let active = Arc::new(AtomicBool::new(true));
+        let handler_active = active.clone();
+        let cancelled = self.cancelled.clone();
+        let mut handle = connection.lock_handle().await?;
+        handle.set_progress_handler(PROGRESS_HANDLER_OPS, move || {
+            !handler_active.load(Ordering::SeqCst)
+                || (!cancelled.load(Ordering::SeqCst) && Instant::now() < deadline)
+        });
+        Ok(ArmedQuery {
+            active,
+            deadline,
+            control: self.clone(),
+        })
+    }
+}
+
+pub(crate) struct ArmedQuery {
+    active: Arc<AtomicBool>,
+    deadline: Instant,
+    control: QueryControl,
+}
+
+impl ArmedQuery {
+    pub(crate) fn finish<T>(self, result: Result<T, sqlx::Error>) -> Result<T, StorageError> {
+        match result {
+            Ok(value) => Ok(value),
+            Err(_) if self.control.is_cancelled() => Err(StorageError::QueryCancelled),
+            Err(_) if Instant::now() >= self.deadline => Err(StorageError::QueryTimedOut(
+                self.control.timeout.as_millis() as u64,
+            )),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+impl Drop for ArmedQuery {
+    fn drop(&mut self) {
+        self.active.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Connection;
+
+    const SLOW_QUERY: &str = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 50000000) SELECT COUNT(*) FROM c";
+
+    #[tokio::test]
+    async fn interrupts_cancelled_and_timed_out_queries() {
+        let mut connection = SqliteConnection::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+
+        let cancelled = QueryControl::new(Duration::from_secs(30));
+        cancelled.cancel();
+        let armed = cancelled.arm(&mut connection).await.expect("arm");
+        let result = sqlx::query_scalar::<_, i64>(SLOW_QUERY)
+            .fetch_one(&mut connection)
+            .await;
+        assert!(matches!(
+            armed.finish(result),
+            Err(StorageError::QueryCancelled)
+        ));
+
+        let timed = QueryControl::new(Duration::from_millis(20));
+        let armed = timed.arm(&mut connection).await.expect("arm");
+        let result = sqlx::query_scalar::<_, i64>(SLOW_QUERY)
+            .fetch_one(&mut connection)
+            .await;
+        assert!(matches!(
+            armed.finish(result),
+            Err(StorageError::QueryTimedOut(20))
+        ));
+
+        let value = sqlx::query_scalar::<_, i64>(SLOW_QUERY.replace("50000000", "10").as_str())
+            .fetch_one(&mut connection)
+            .await
+            .expect("disarmed handler must not interrupt");
+        assert_eq!(value, 10);
+    }
+}
@@ -1,3 +1,7 @@
+pub mod compression;
+#[cfg(any(test, feature = "demo-data"))]
+pub mod demo;
+pub mod interrupt;
 pub mod models;
 pub mod repository;
 
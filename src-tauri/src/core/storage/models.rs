@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row};
+
+use super::compression::decompress_text;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewSource {
@@ -22,11 +25,13 @@ pub struct SourceRecord {
     pub etag: Option<String>,
     pub last_modified: Option<String>,
     pub last_synced_at: Option<String>,
+    pub color: Option<String>,
+    pub custom_icon: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntryRecord {
     pub id: i64,
     pub source_id: i64,
@@ -41,6 +46,170 @@ pub struct EntryRecord {
     pub is_read: i64,
     pub is_starred: i64,
     pub created_at: String,
+    pub updated_at: Option<String>,
+    pub is_updated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryCursor {
+    pub before_published_at: String,
+    pub before_id: i64,
+}
+
+impl EntryCursor {
+    pub fn after(entry: &EntryRecord) -> Self {
+        Self {
+            before_published_at: entry
+                .published_at
+                .clone()
+                .unwrap_or_else(|| entry.created_at.clone()),
+            before_id: entry.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.before_id, self.before_published_at)
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        let (id, published_at) = token.split_once(':')?;
+        Some(Self {
+            before_published_at: published_at.to_string(),
+            before_id: id.parse().ok()?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, SqliteRow> for EntryRecord {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        let content = row
+            .try_get::<Option<Vec<u8>>, _>("content")?
+            .map(|raw| decompress_text(&raw));
+        Ok(Self {
+            id: row.try_get("id")?,
+            source_id: row.try_get("source_id")?,
+            source_title: row.try_get("source_title")?,
+            guid: row.try_get("guid")?,
+            link: row.try_get("link")?,
+            title: row.try_get("title")?,
+            translated_title: row.try_get("translated_title")?,
+            summary: row.try_get("summary")?,
+            content,
+            published_at: row.try_get("published_at")?,
+            is_read: row.try_get("is_read")?,
+            is_starred: row.try_get("is_starred")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            is_updated: row.try_get("is_updated")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CatchupCandidate {
+    pub id: i64,
+    pub source_id: i64,
+    pub source_title: String,
+    pub title: String,
+    pub link: String,
+    pub published_at: Option<String>,
+    pub text: String,
+    pub is_starred: bool,
+    pub source_read_ratio: f64,
+    pub source_opens: i64,
+    pub source_notifies: bool,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for CatchupCandidate {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        let text = match row.try_get::<Option<Vec<u8>>, _>("content")? {
+            Some(raw) => decompress_text(&raw),
+            None => row
+                .try_get::<Option<String>, _>("summary")?
+                .unwrap_or_default(),
+        };
+        Ok(Self {
+            id: row.try_get("id")?,
+            source_id: row.try_get("source_id")?,
+            source_title: row.try_get("source_title")?,
+            title: row.try_get("title")?,
+            link: row.try_get("link")?,
+            published_at: row.try_get("published_at")?,
+            text,
+            is_starred: row.try_get("is_starred")?,
+            source_read_ratio: row.try_get("source_read_ratio")?,
+            source_opens: row.try_get("source_opens")?,
+            source_notifies: row.try_get("source_notifies")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryFulltext {
+    pub entry_id: i64,
+    pub url: String,
+    pub title: Option<String>,
+    pub content_html: String,
+    pub text_length: i64,
+    pub fetched_at: String,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for EntryFulltext {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            entry_id: row.try_get("entry_id")?,
+            url: row.try_get("url")?,
+            title: row.try_get("title")?,
+            content_html: decompress_text(&row.try_get::<Vec<u8>, _>("content_html")?),
+            text_length: row.try_get("text_length")?,
+            fetched_at: row.try_get("fetched_at")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadHistoryEntry {
+    #[serde(flatten)]
+    pub entry: EntryRecord,
+    pub read_at: Option<String>,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for ReadHistoryEntry {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            entry: EntryRecord::from_row(row)?,
+            read_at: row.try_get("read_at")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReadOperation {
+    pub id: i64,
+    pub kind: String,
+    pub entry_count: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PendingNotification {
+    pub id: i64,
+    pub source_id: i64,
+    pub source_title: String,
+    pub entry_id: i64,
+    pub title: String,
+    pub link: String,
+    pub matched_keyword: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReadStateRecord {
+    pub feed_url: String,
+    pub link: String,
+    pub is_read: bool,
+    pub is_starred: bool,
+    pub read_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -48,3 +217,298 @@ pub struct EntryTitleRecord {
     pub id: i64,
     pub title: String,
 }
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpsertCounts {
+    pub inserted: usize,
+    pub updated: usize,
+    #[serde(default)]
+    pub conflicted: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceDeletion {
+    pub sources: u64,
+    pub entries: u64,
+    pub preserved_starred: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrphanedEntry {
+    pub id: i64,
+    pub title: String,
+    pub link: String,
+    pub published_at: Option<String>,
+    pub orphaned_from_feed_url: String,
+    pub resubscribed_source_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct LlmCacheRecord {
+    pub task_type: String,
+    pub model: String,
+    pub input_hash: String,
+    pub output_text: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub key_version: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LlmCacheImport {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+impl UpsertCounts {
+    pub fn total(&self) -> usize {
+        self.inserted + self.updated
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DatabaseStats {
+    pub source_count: i64,
+    pub active_source_count: i64,
+    pub failing_source_count: i64,
+    pub entry_count: i64,
+    pub unread_entry_count: i64,
+    pub archived_entry_count: i64,
+    pub llm_cache_count: i64,
+    pub schema_version: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub description: String,
+    pub installed_on: String,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMigration {
+    pub version: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub schema_version: Option<i64>,
+    pub latest_known_version: i64,
+    pub schema_is_newer: bool,
+    pub read_only: bool,
+    pub applied: Vec<AppliedMigration>,
+    pub pending: Vec<PendingMigration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SourceEntryStats {
+    pub entry_count: i64,
+    pub unread_count: i64,
+    pub starred_count: i64,
+    pub archived_count: i64,
+    pub latest_published_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SourceSyncResult {
+    pub last_sync_status: Option<String>,
+    pub last_sync_upserted: i64,
+    pub last_error: Option<String>,
+    pub last_synced_at: Option<String>,
+    pub replacement_feed_url: Option<String>,
+    pub retry_after: Option<String>,
+    pub poll_interval_secs: Option<i64>,
+    pub next_poll_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourcePollSchedule {
+    pub poll_interval_secs: i64,
+    pub next_poll_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncAttempt {
+    pub source_id: i64,
+    pub started_at: String,
+    pub status: String,
+    pub http_status: Option<u16>,
+    pub entries_upserted: i64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SyncLogEntry {
+    pub id: i64,
+    pub source_id: i64,
+    pub started_at: String,
+    pub finished_at: String,
+    pub status: String,
+    pub http_status: Option<i64>,
+    pub entries_upserted: i64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FailingSource {
+    pub id: i64,
+    pub title: String,
+    pub feed_url: String,
+    pub is_active: bool,
+    pub failure_count: i64,
+    pub last_http_status: Option<i64>,
+    pub last_error: Option<String>,
+    pub last_failed_at: Option<String>,
+    pub auto_disabled_at: Option<String>,
+    pub auto_disabled_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProblemSource {
+    pub id: i64,
+    pub title: String,
+    pub feed_url: String,
+    pub site_url: Option<String>,
+    pub is_active: bool,
+    pub failure_count: i64,
+    pub last_http_status: Option<i64>,
+    pub last_sync_status: Option<String>,
+    pub last_error: Option<String>,
+    pub gone_count: i64,
+    pub parked_domain: bool,
+    pub replacement_feed_url: Option<String>,
+    pub auto_disabled_reason: Option<String>,
+    pub recent_timeouts: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceDetails {
+    pub source: SourceRecord,
+    pub stats: SourceEntryStats,
+    pub last_sync: SourceSyncResult,
+    pub recent_entries: Vec<EntryRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct StaleSource {
+    pub id: i64,
+    pub title: String,
+    pub feed_url: String,
+    pub site_url: Option<String>,
+    pub category: Option<String>,
+    pub is_active: bool,
+    pub failure_count: i64,
+    pub last_http_status: Option<i64>,
+    pub gone_count: i64,
+    pub replacement_feed_url: Option<String>,
+    pub last_entry_at: Option<String>,
+    pub no_recent_entries: bool,
+    pub repeatedly_gone: bool,
+    pub parked_domain: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EntryDateBucket {
+    pub day: String,
+    pub total_count: i64,
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TitleSample {
+    pub title: String,
+    pub is_recent: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct SourceSettings {
+    pub source_id: i64,
+    pub sync_interval_secs: Option<i64>,
+    pub timeout_secs: Option<i64>,
+    pub retry_count: Option<i64>,
+    pub translate_titles: Option<bool>,
+    pub open_externally: Option<bool>,
+    pub first_sync_max_age_days: Option<i64>,
+}
+
+impl SourceSettings {
+    pub fn is_empty(&self) -> bool {
+        self.sync_interval_secs.is_none()
+            && self.timeout_secs.is_none()
+            && self.retry_count.is_none()
+            && self.translate_titles.is_none()
+            && self.open_externally.is_none()
+            && self.first_sync_max_age_days.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SourceStorageUsage {
+    pub source_id: i64,
+    pub title: String,
+    pub entry_count: i64,
+    pub archived_count: i64,
+    pub content_bytes: i64,
+    pub archived_content_bytes: i64,
+    pub retention_days: Option<i64>,
+    pub retention_max_entries: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SourceUnreadCount {
+    pub source_id: i64,
+    pub title: String,
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq, Eq)]
+pub struct SourceCounts {
+    pub source_id: i64,
+    pub title: String,
+    pub category: Option<String>,
+    pub unread_count: i64,
+    pub starred_count: i64,
+    pub total_count: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CategoryCounts {
+    pub category: Option<String>,
+    pub unread_count: i64,
+    pub starred_count: i64,
+    pub total_count: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EntryCounts {
+    pub sources: Vec<SourceCounts>,
+    pub categories: Vec<CategoryCounts>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryView {
+    All,
+    Today,
+    Unread,
+    Starred,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SourceSortField {
+    #[default]
+    Newest,
+    Title,
+    LastSyncedAt,
+    FailureCount,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SourceSearchFilter {
+    pub query: Option<String>,
+    pub is_active: Option<bool>,
+    pub failing_only: bool,
+    pub sort_by: SourceSortField,
+    pub limit: i64,
+}
@@ -1,7 +1,46 @@
-use sqlx::{sqlite::SqlitePoolOptions, QueryBuilder, Sqlite, SqlitePool};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use std::time::Duration;
 
-use super::models::{EntryRecord, EntryTitleRecord, NewSource, SourceRecord};
-use crate::core::feed::types::ParsedEntry;
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+
+use super::compression::{compress_text, decompress_text, COMPRESSION_THRESHOLD_BYTES};
+use super::interrupt::QueryControl;
+use super::models::{
+    AppliedMigration, CatchupCandidate, CategoryCounts, DatabaseStats, EntryCounts, EntryCursor,
+    EntryDateBucket, EntryFulltext, EntryRecord, EntryTitleRecord, EntryView, FailingSource,
+    LlmCacheImport, LlmCacheRecord, MigrationStatus, NewSource, OrphanedEntry, PendingMigration,
+    PendingNotification, ProblemSource, ReadHistoryEntry, ReadOperation, ReadStateRecord,
+    SourceCounts, SourceDeletion, SourceDetails, SourceEntryStats, SourcePollSchedule,
+    SourceRecord, SourceSearchFilter, SourceSettings, SourceSortField, SourceStorageUsage,
+    SourceSyncResult, SourceUnreadCount, StaleSource, SyncAttempt, SyncLogEntry, TitleSample,
+    UpsertCounts,
+};
+use crate::core::feed::parser::{
+    build_dedup_key, build_entry_fingerprint, canonicalize_link, sanitize_entry, SanitizerAllowlist,
+};
+use crate::core::feed::types::{ParsedEntry, UNKNOWN_ENTRY_ID};
+use crate::core::llm::cache::LLM_CACHE_KEY_VERSION;
+use crate::core::llm::glossary::GlossaryTerm;
+use crate::core::llm::{LlmConfig, LlmProfile, LlmTask};
+use crate::core::notifications::{NotificationPrefs, NotificationRule, NotifyMode};
+use crate::core::reader::ReaderArticle;
+use crate::core::search::tokenize::{
+    fts_match_expression, index_tokens, pinyin_tokens, plain_text,
+};
+use crate::core::search::{parse_search_query, SearchQuery};
+use crate::core::sync::cadence::{
+    next_poll_at, observed_interval_secs, parse_published_at, poll_interval_secs,
+    CADENCE_SAMPLE_SIZE,
+};
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+const UPSERT_CHUNK_ROWS: usize = 100;
+const MAX_SYNC_LOG_PER_SOURCE: i64 = 200;
+const SYNC_BACKOFF_BASE_MINUTES: i64 = 5;
+const SYNC_BACKOFF_MAX_MINUTES: i64 = 24 * 60;
 
 #[derive(Debug, thiserror::Error)]
 pub enum StorageError {
@@ -9,21 +48,185 @@ pub enum StorageError {
     Database(#[from] sqlx::Error),
     #[error("migration error: {0}")]
     Migration(#[from] sqlx::migrate::MigrateError),
+    #[error(
+        "database schema version {database_version} is newer than supported version {supported_version}; please upgrade the app"
+    )]
+    SchemaTooNew {
+        database_version: i64,
+        supported_version: i64,
+    },
+    #[error("query cancelled")]
+    QueryCancelled,
+    #[error("query timed out after {0} ms")]
+    QueryTimedOut(u64),
+    #[error("database integrity check failed: {0}")]
+    Corrupt(String),
+    #[error("llm profile {name} has an invalid config: {source}")]
+    InvalidLlmProfile {
+        name: String,
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseFault {
+    Corrupt,
+    Locked,
+}
+
+impl StorageError {
+    pub fn fault(&self) -> Option<DatabaseFault> {
+        match self {
+            Self::Corrupt(_) => Some(DatabaseFault::Corrupt),
+            Self::Database(error) | Self::Migration(MigrateError::Execute(error)) => {
+                sqlite_fault(error)
+            }
+            _ => None,
+        }
+    }
+}
+
+const PROTECT_FLAGGED_ENTRIES_KEY: &str = "protect_flagged_entries";
+const FIRST_SYNC_MAX_AGE_DAYS_KEY: &str = "first_sync_max_age_days";
+const ACTIVE_LLM_PROFILE_KEY: &str = "active_llm_profile_id";
+const SANITIZER_ALLOWLIST_KEY: &str = "sanitizer_allowlist";
+const SANITIZED_ALLOWLIST_KEY: &str = "sanitized_content_allowlist";
+const SEARCH_PINYIN_KEY: &str = "search_pinyin";
+pub const ORPHANED_SOURCE_FEED_URL: &str = "rssr://orphaned";
+const ORPHANED_SOURCE_TITLE: &str = "Orphaned starred entries";
+const READ_OPERATION_HISTORY_LIMIT: i64 = 20;
+const CATCHUP_ENGAGEMENT_DAYS: i64 = 30;
+const POOL_MAX_CONNECTIONS: u32 = 8;
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+const CANONICAL_LINK_BACKFILL_BATCH: i64 = 500;
+const SANITIZE_BACKFILL_BATCH: i64 = 200;
+const SEARCH_INDEX_BATCH: i64 = 500;
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+const SQLITE_CORRUPT: i32 = 11;
+const SQLITE_NOTADB: i32 = 26;
+
+#[derive(Debug, Clone, Copy)]
+struct EntryListing<'a> {
+    cursor: Option<&'a EntryCursor>,
+    limit: i64,
+    include_content: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct SourceRepository {
     pool: SqlitePool,
+    read_only: bool,
 }
 
 impl SourceRepository {
     pub async fn connect(database_url: &str) -> Result<Self, StorageError> {
+        let in_memory = is_in_memory(database_url);
+        let mut options = SqliteConnectOptions::from_str(database_url)?.busy_timeout(BUSY_TIMEOUT);
+        if !in_memory {
+            options = options
+                .journal_mode(SqliteJournalMode::Wal)
+                .synchronous(SqliteSynchronous::Normal);
+        }
+        let pool = SqlitePoolOptions::new()
+            .max_connections(if in_memory { 1 } else { POOL_MAX_CONNECTIONS })
+            .connect_with(options)
+            .await?;
+        if let Err(error) = prepare_pool(&pool).await {
+            let error = match error.fault() {
+                None if !in_memory => integrity_error(&pool).await.unwrap_or(error),
+                _ => error,
+            };
+            pool.close().await;
+            return Err(error);
+        }
+        Ok(Self {
+            pool,
+            read_only: false,
+        })
+    }
+
+    pub async fn connect_degraded() -> Result<Self, StorageError> {
         let pool = SqlitePoolOptions::new()
             .max_connections(1)
-            .connect(database_url)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(SqliteConnectOptions::from_str("sqlite::memory:")?)
+            .await?;
+        MIGRATOR.run(&pool).await?;
+        sqlx::query("PRAGMA query_only = ON").execute(&pool).await?;
+        Ok(Self {
+            pool,
+            read_only: true,
+        })
+    }
+
+    pub async fn connect_read_only(database_url: &str) -> Result<Self, StorageError> {
+        let options = SqliteConnectOptions::from_str(database_url)?
+            .read_only(true)
+            .create_if_missing(false)
+            .busy_timeout(BUSY_TIMEOUT);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(if is_in_memory(database_url) {
+                1
+            } else {
+                POOL_MAX_CONNECTIONS
+            })
+            .connect_with(options)
             .await?;
-        sqlx::migrate!("./migrations").run(&pool).await?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            read_only: true,
+        })
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    async fn begin_write(&self) -> Result<sqlx::Transaction<'static, Sqlite>, sqlx::Error> {
+        self.pool.begin_with("BEGIN IMMEDIATE").await
+    }
+
+    pub async fn migration_status(&self) -> Result<MigrationStatus, StorageError> {
+        let applied = if migrations_table_exists(&self.pool).await? {
+            sqlx::query_as::<_, AppliedMigration>(
+                r#"
+                SELECT version, description, CAST(installed_on AS TEXT) AS installed_on, success
+                FROM _sqlx_migrations
+                ORDER BY version
+                "#,
+            )
+            .fetch_all(&self.pool)
+            .await?
+        } else {
+            Vec::new()
+        };
+        let applied_versions: HashSet<i64> = applied
+            .iter()
+            .filter(|migration| migration.success)
+            .map(|migration| migration.version)
+            .collect();
+        let pending = MIGRATOR
+            .iter()
+            .filter(|migration| !applied_versions.contains(&migration.version))
+            .map(|migration| PendingMigration {
+                version: migration.version,
+                description: migration.description.to_string(),
+            })
+            .collect();
+        let schema_version = applied_versions.iter().copied().max();
+        let latest_known_version = latest_known_version();
+
+        Ok(MigrationStatus {
+            schema_version,
+            latest_known_version,
+            schema_is_newer: schema_version.is_some_and(|version| version > latest_known_version),
+            read_only: self.read_only,
+            applied,
+            pending,
+        })
     }
 
     pub async fn upsert_source(&self, source: &NewSource) -> Result<SourceRecord, StorageError> {
@@ -49,7 +252,7 @@ impl SourceRepository {
 
         let record = sqlx::query_as::<_, SourceRecord>(
             r#"
-            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, created_at, updated_at
+            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, color, custom_icon, created_at, updated_at
             FROM sources
             WHERE feed_url = ?1
             "#,
@@ -64,32 +267,199 @@ impl SourceRepository {
     pub async fn list_sources(&self) -> Result<Vec<SourceRecord>, StorageError> {
         let rows = sqlx::query_as::<_, SourceRecord>(
             r#"
-            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, created_at, updated_at
+            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, color, custom_icon, created_at, updated_at
             FROM sources
+            WHERE feed_url != ?1
             ORDER BY id DESC
             "#,
         )
+        .bind(ORPHANED_SOURCE_FEED_URL)
         .fetch_all(&self.pool)
         .await?;
         Ok(rows)
     }
 
-    pub async fn delete_source(&self, id: i64) -> Result<u64, StorageError> {
-        let affected = sqlx::query("DELETE FROM sources WHERE id = ?1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?
-            .rows_affected();
-        Ok(affected)
+    pub async fn get_entry_counts(&self) -> Result<EntryCounts, StorageError> {
+        let sources = sqlx::query_as::<_, SourceCounts>(
+            r#"
+            SELECT
+              s.id AS source_id,
+              s.title,
+              s.category,
+              COALESCE(SUM(e.is_read = 0), 0) AS unread_count,
+              COALESCE(SUM(e.is_starred = 1), 0) AS starred_count,
+              COUNT(e.id) AS total_count
+            FROM sources s
+            LEFT JOIN entries e ON e.source_id = s.id
+            GROUP BY s.id
+            ORDER BY s.id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let mut categories = BTreeMap::<Option<String>, CategoryCounts>::new();
+        for source in &sources {
+            let category = source
+                .category
+                .as_deref()
+                .map(str::trim)
+                .filter(|category| !category.is_empty())
+                .map(str::to_string);
+            let counts = categories
+                .entry(category.clone())
+                .or_insert_with(|| CategoryCounts {
+                    category,
+                    ..CategoryCounts::default()
+                });
+            counts.unread_count += source.unread_count;
+            counts.starred_count += source.starred_count;
+            counts.total_count += source.total_count;
+        }
+        Ok(EntryCounts {
+            sources,
+            categories: categories.into_values().collect(),
+        })
+    }
+
+    pub async fn list_unread_counts(&self) -> Result<Vec<SourceUnreadCount>, StorageError> {
+        let rows = sqlx::query_as::<_, SourceUnreadCount>(
+            r#"
+            SELECT s.id AS source_id, s.title, COUNT(e.id) AS unread_count
+            FROM sources s
+            LEFT JOIN entries e ON e.source_id = s.id AND e.is_read = 0
+            GROUP BY s.id, s.title
+            ORDER BY s.id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn search_sources(
+        &self,
+        filter: &SourceSearchFilter,
+    ) -> Result<Vec<SourceRecord>, StorageError> {
+        let keyword = filter.query.as_deref().unwrap_or("").trim().to_string();
+        let order_by = match filter.sort_by {
+            SourceSortField::Newest => "id DESC",
+            SourceSortField::Title => "title COLLATE NOCASE ASC, id DESC",
+            SourceSortField::LastSyncedAt => "last_synced_at IS NULL, last_synced_at DESC, id DESC",
+            SourceSortField::FailureCount => "failure_count DESC, id DESC",
+        };
+        let rows = sqlx::query_as::<_, SourceRecord>(&format!(
+            r#"
+            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, color, custom_icon, created_at, updated_at
+            FROM sources
+            WHERE (
+                ?1 = ''
+                OR title LIKE '%' || ?1 || '%'
+                OR feed_url LIKE '%' || ?1 || '%'
+                OR IFNULL(site_url, '') LIKE '%' || ?1 || '%'
+                OR IFNULL(category, '') LIKE '%' || ?1 || '%'
+              )
+              AND (?2 IS NULL OR is_active = ?2)
+              AND (?3 = 0 OR failure_count > 0)
+            ORDER BY {order_by}
+            LIMIT ?4
+            "#
+        ))
+        .bind(keyword)
+        .bind(filter.is_active.map(i64::from))
+        .bind(i64::from(filter.failing_only))
+        .bind(filter.limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn delete_source(&self, id: i64) -> Result<SourceDeletion, StorageError> {
+        self.delete_sources(&[id]).await
+    }
+
+    pub async fn delete_sources(&self, source_ids: &[i64]) -> Result<SourceDeletion, StorageError> {
+        let mut deletion = SourceDeletion::default();
+        if source_ids.is_empty() {
+            return Ok(deletion);
+        }
+
+        let mut tx = self.begin_write().await?;
+        deletion.preserved_starred = preserve_starred_entries(&mut tx, source_ids).await?;
+        for table in ["entry_fulltext", "read_operation_entries"] {
+            let mut query = QueryBuilder::<Sqlite>::new(format!(
+                "DELETE FROM {table} WHERE entry_id IN (SELECT id FROM entries WHERE source_id IN "
+            ));
+            push_id_list(&mut query, source_ids);
+            query.push(" UNION ALL SELECT id FROM entries_archive WHERE source_id IN ");
+            push_id_list(&mut query, source_ids);
+            query.push(")");
+            query.build().execute(&mut *tx).await?;
+        }
+        for table in [
+            "pending_notifications",
+            "entry_open_events",
+            "entry_tombstones",
+        ] {
+            delete_by_source(&mut tx, table, "source_id", source_ids).await?;
+        }
+        for table in ["entries_archive", "entries"] {
+            deletion.entries += delete_by_source(&mut tx, table, "source_id", source_ids).await?;
+        }
+        deletion.sources = delete_by_source(&mut tx, "sources", "id", source_ids).await?;
+        tx.commit().await?;
+        Ok(deletion)
     }
 
-    pub async fn upsert_sources_batch(&self, sources: &[NewSource]) -> Result<usize, StorageError> {
-        let mut inserted = 0_usize;
-        for source in sources {
-            self.upsert_source(source).await?;
-            inserted += 1;
+    pub async fn upsert_sources_batch(
+        &self,
+        sources: &[NewSource],
+    ) -> Result<UpsertCounts, StorageError> {
+        let pending = last_by_key(sources, |source| source.feed_url.as_str());
+        let mut counts = UpsertCounts::default();
+        if pending.is_empty() {
+            return Ok(counts);
+        }
+
+        let mut tx = self.begin_write().await?;
+        for chunk in pending.chunks(UPSERT_CHUNK_ROWS) {
+            let mut existing =
+                QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM sources WHERE feed_url IN (");
+            let mut separated = existing.separated(", ");
+            for source in chunk {
+                separated.push_bind(&source.feed_url);
+            }
+            separated.push_unseparated(")");
+            let existing = existing
+                .build_query_scalar::<i64>()
+                .fetch_one(&mut *tx)
+                .await? as usize;
+
+            let mut insert = QueryBuilder::<Sqlite>::new(
+                "INSERT INTO sources (title, site_url, feed_url, category, is_active) ",
+            );
+            insert.push_values(chunk, |mut row, source| {
+                row.push_bind(&source.title)
+                    .push_bind(&source.site_url)
+                    .push_bind(&source.feed_url)
+                    .push_bind(&source.category)
+                    .push_bind(i64::from(source.is_active));
+            });
+            insert.push(
+                r#"
+                ON CONFLICT(feed_url) DO UPDATE SET
+                  title = excluded.title,
+                  site_url = excluded.site_url,
+                  category = excluded.category,
+                  is_active = excluded.is_active,
+                  updated_at = CURRENT_TIMESTAMP
+                "#,
+            );
+            insert.build().execute(&mut *tx).await?;
+            counts.updated += existing;
+            counts.inserted += chunk.len() - existing;
         }
-        Ok(inserted)
+        tx.commit().await?;
+        Ok(counts)
     }
 
     pub async fn set_sources_active(
@@ -103,7 +473,9 @@ impl SourceRepository {
 
         let mut query = QueryBuilder::<Sqlite>::new("UPDATE sources SET is_active = ");
         query.push_bind(i64::from(is_active));
-        query.push(", updated_at = CURRENT_TIMESTAMP WHERE id IN (");
+        query.push(
+            ", auto_disabled_at = NULL, auto_disabled_reason = NULL, updated_at = CURRENT_TIMESTAMP WHERE id IN (",
+        );
         let mut separated = query.separated(", ");
         for source_id in source_ids {
             separated.push_bind(*source_id);
@@ -114,10 +486,123 @@ impl SourceRepository {
         Ok(affected)
     }
 
+    pub async fn mark_backlog_read_on_first_sync(
+        &self,
+        feed_urls: &[String],
+    ) -> Result<u64, StorageError> {
+        if feed_urls.is_empty() {
+            return Ok(0);
+        }
+
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "UPDATE sources SET mark_backlog_read = 1, updated_at = CURRENT_TIMESTAMP WHERE feed_url IN (",
+        );
+        let mut separated = query.separated(", ");
+        for feed_url in feed_urls {
+            separated.push_bind(feed_url);
+        }
+        separated.push_unseparated(")");
+
+        let affected = query.build().execute(&self.pool).await?.rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn list_orphaned_entries(&self) -> Result<Vec<OrphanedEntry>, StorageError> {
+        let rows = sqlx::query_as::<_, OrphanedEntry>(
+            r#"
+            SELECT
+              e.id,
+              e.title,
+              e.link,
+              e.published_at,
+              e.orphaned_from_feed_url,
+              s.id AS resubscribed_source_id
+            FROM entries e
+            LEFT JOIN sources s ON s.feed_url = e.orphaned_from_feed_url
+            WHERE e.orphaned_from_feed_url IS NOT NULL
+            ORDER BY e.id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn rehome_orphaned_entries(&self, source_id: i64) -> Result<u64, StorageError> {
+        let mut tx = self.begin_write().await?;
+        let Some(feed_url) =
+            sqlx::query_scalar::<_, String>("SELECT feed_url FROM sources WHERE id = ?1")
+                .bind(source_id)
+                .fetch_optional(&mut *tx)
+                .await?
+        else {
+            return Ok(0);
+        };
+        sqlx::query(
+            r#"
+            UPDATE entries
+            SET is_starred = 1
+            WHERE source_id = ?1
+              AND EXISTS (
+                SELECT 1 FROM entries orphan
+                WHERE orphan.orphaned_from_feed_url = ?2
+                  AND (
+                    orphan.link = entries.link
+                    OR orphan.guid IN (entries.guid, ?2 || '::' || entries.guid)
+                    OR orphan.dedup_key IN (entries.dedup_key, ?2 || '::' || entries.dedup_key)
+                  )
+              )
+            "#,
+        )
+        .bind(source_id)
+        .bind(&feed_url)
+        .execute(&mut *tx)
+        .await?;
+        let moved = sqlx::query(
+            r#"
+            UPDATE OR IGNORE entries
+            SET
+              source_id = ?1,
+              orphaned_from_feed_url = NULL,
+              guid = CASE
+                WHEN substr(guid, 1, length(?2) + 2) = ?2 || '::' THEN substr(guid, length(?2) + 3)
+                ELSE guid
+              END,
+              dedup_key = CASE
+                WHEN substr(dedup_key, 1, length(?2) + 2) = ?2 || '::' THEN substr(dedup_key, length(?2) + 3)
+                ELSE dedup_key
+              END
+            WHERE orphaned_from_feed_url = ?2
+            "#,
+        )
+        .bind(source_id)
+        .bind(&feed_url)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        let merged = sqlx::query("DELETE FROM entries WHERE orphaned_from_feed_url = ?1")
+            .bind(&feed_url)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        sqlx::query(
+            r#"
+            DELETE FROM sources
+            WHERE feed_url = ?1
+              AND NOT EXISTS (SELECT 1 FROM entries WHERE entries.source_id = sources.id)
+            "#,
+        )
+        .bind(ORPHANED_SOURCE_FEED_URL)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(moved + merged)
+    }
+
     pub async fn get_source_by_id(&self, id: i64) -> Result<Option<SourceRecord>, StorageError> {
         let row = sqlx::query_as::<_, SourceRecord>(
             r#"
-            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, created_at, updated_at
+            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, color, custom_icon, created_at, updated_at
             FROM sources
             WHERE id = ?1
             "#,
@@ -128,6 +613,36 @@ impl SourceRepository {
         Ok(row)
     }
 
+    pub async fn set_source_color(
+        &self,
+        source_id: i64,
+        color: Option<&str>,
+    ) -> Result<u64, StorageError> {
+        let result = sqlx::query(
+            "UPDATE sources SET color = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        )
+        .bind(color)
+        .bind(source_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn set_source_custom_icon(
+        &self,
+        source_id: i64,
+        custom_icon: Option<&str>,
+    ) -> Result<u64, StorageError> {
+        let result = sqlx::query(
+            "UPDATE sources SET custom_icon = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        )
+        .bind(custom_icon)
+        .bind(source_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn update_source_sync_success(
         &self,
         source_id: i64,
@@ -141,6 +656,7 @@ impl SourceRepository {
                 last_modified = ?2,
                 last_synced_at = CURRENT_TIMESTAMP,
                 failure_count = 0,
+                retry_after = NULL,
                 updated_at = CURRENT_TIMESTAMP
             WHERE id = ?3
             "#,
@@ -153,809 +669,7461 @@ impl SourceRepository {
         Ok(())
     }
 
-    pub async fn increment_source_failure(&self, source_id: i64) -> Result<(), StorageError> {
-        sqlx::query(
+    pub async fn increment_source_failure(&self, source_id: i64) -> Result<i64, StorageError> {
+        let failure_count = sqlx::query_scalar::<_, i64>(
             r#"
             UPDATE sources
             SET failure_count = failure_count + 1,
                 last_synced_at = CURRENT_TIMESTAMP,
                 updated_at = CURRENT_TIMESTAMP
             WHERE id = ?1
+            RETURNING failure_count
             "#,
         )
         .bind(source_id)
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
-        Ok(())
+        Ok(failure_count.unwrap_or(0))
     }
 
-    pub async fn list_sync_candidates(
+    pub async fn auto_disable_source(
         &self,
-        limit: i64,
-    ) -> Result<Vec<SourceRecord>, StorageError> {
-        let rows = sqlx::query_as::<_, SourceRecord>(
+        source_id: i64,
+        reason: &str,
+    ) -> Result<u64, StorageError> {
+        let affected = sqlx::query(
             r#"
-            SELECT id, title, site_url, feed_url, category, is_active, failure_count, etag, last_modified, last_synced_at, created_at, updated_at
-            FROM sources
-            WHERE is_active = 1
-              AND (
-                last_synced_at IS NULL
-                OR datetime(
-                  last_synced_at,
-                  '+' || (
-                    CASE
-                      WHEN failure_count <= 1 THEN 1
-                      WHEN failure_count = 2 THEN 5
-                      WHEN failure_count = 3 THEN 15
-                      ELSE 60
-                    END
-                  ) || ' minutes'
-                ) <= datetime('now')
-              )
-            ORDER BY id DESC
-            LIMIT ?1
+            UPDATE sources
+            SET is_active = 0,
+                auto_disabled_at = CURRENT_TIMESTAMP,
+                auto_disabled_reason = ?2,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?1 AND is_active = 1
             "#,
         )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(rows)
+        .bind(source_id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected)
     }
 
-    pub async fn upsert_entries(
-        &self,
-        source_id: i64,
-        entries: &[ParsedEntry],
-    ) -> Result<usize, StorageError> {
-        let mut affected = 0_usize;
-        for entry in entries {
-            sqlx::query(
-                r#"
-                INSERT INTO entries (source_id, guid, link, title, summary, content, published_at)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
-                ON CONFLICT(source_id, link) DO UPDATE SET
-                  guid = excluded.guid,
-                  title = excluded.title,
-                  summary = excluded.summary,
-                  content = excluded.content,
-                  published_at = excluded.published_at
-                "#,
-            )
-            .bind(source_id)
-            .bind(&entry.id)
-            .bind(&entry.link)
-            .bind(&entry.title)
-            .bind(&entry.summary)
-            .bind(&entry.content)
-            .bind(&entry.published_at)
-            .execute(&self.pool)
-            .await?;
-            affected += 1;
-        }
+    pub async fn reactivate_source(&self, source_id: i64) -> Result<u64, StorageError> {
+        let affected = sqlx::query(
+            r#"
+            UPDATE sources
+            SET is_active = 1,
+                failure_count = 0,
+                gone_count = 0,
+                retry_after = NULL,
+                auto_disabled_at = NULL,
+                auto_disabled_reason = NULL,
+                next_poll_at = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?1
+            "#,
+        )
+        .bind(source_id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
         Ok(affected)
     }
 
-    pub async fn list_entries(
+    pub async fn set_source_feed_hints(
         &self,
-        source_id: Option<i64>,
-        search: Option<&str>,
-        unread_only: bool,
-        limit: i64,
-    ) -> Result<Vec<EntryRecord>, StorageError> {
-        let keyword = search.unwrap_or("").trim().to_string();
-        let rows = sqlx::query_as::<_, EntryRecord>(
+        source_id: i64,
+        ttl_minutes: Option<u32>,
+        skip_hours: &[u32],
+    ) -> Result<(), StorageError> {
+        let skip_hours = skip_hours
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        sqlx::query(
             r#"
-            SELECT
-              e.id,
-              e.source_id,
-              s.title AS source_title,
-              e.guid,
-              e.link,
-              e.title,
-              e.translated_title,
-              e.summary,
-              e.content,
-              e.published_at,
-              e.is_read,
-              e.is_starred,
-              e.created_at
-            FROM entries e
-            JOIN sources s ON s.id = e.source_id
-            WHERE (?1 IS NULL OR e.source_id = ?1)
-              AND (?2 = '' OR e.title LIKE '%' || ?2 || '%' OR IFNULL(e.summary, '') LIKE '%' || ?2 || '%')
-              AND (?3 = 0 OR e.is_read = 0)
-            ORDER BY COALESCE(e.published_at, e.created_at) DESC
-            LIMIT ?4
+            UPDATE sources
+            SET feed_ttl_minutes = ?1,
+                feed_skip_hours = NULLIF(?2, '')
+            WHERE id = ?3
             "#,
         )
+        .bind(ttl_minutes.map(i64::from))
+        .bind(skip_hours)
         .bind(source_id)
-        .bind(keyword)
-        .bind(i64::from(unread_only))
-        .bind(limit)
-        .fetch_all(&self.pool)
+        .execute(&self.pool)
         .await?;
-        Ok(rows)
-    }
-
-    pub async fn mark_entry_read(&self, entry_id: i64, is_read: bool) -> Result<u64, StorageError> {
-        let affected = sqlx::query("UPDATE entries SET is_read = ?1 WHERE id = ?2")
-            .bind(i64::from(is_read))
-            .bind(entry_id)
-            .execute(&self.pool)
-            .await?
-            .rows_affected();
-        Ok(affected)
+        Ok(())
     }
 
-    pub async fn list_entries_without_translated_title(
+    pub async fn schedule_source_poll(
         &self,
-        limit: i64,
-    ) -> Result<Vec<EntryTitleRecord>, StorageError> {
-        let rows = sqlx::query_as::<_, EntryTitleRecord>(
+        source_id: i64,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<SourcePollSchedule, StorageError> {
+        let published = sqlx::query_scalar::<_, String>(
             r#"
-            SELECT id, title
+            SELECT published_at
             FROM entries
-            WHERE translated_title IS NULL
-              OR TRIM(translated_title) = ''
-            ORDER BY COALESCE(
-              datetime(published_at),
-              datetime(created_at),
-              published_at,
-              created_at
-            ) DESC, id DESC
-            LIMIT ?1
+            WHERE source_id = ?1
+              AND published_at IS NOT NULL
+            ORDER BY id DESC
+            LIMIT ?2
             "#,
         )
-        .bind(limit)
+        .bind(source_id)
+        .bind(CADENCE_SAMPLE_SIZE)
         .fetch_all(&self.pool)
+        .await?
+        .iter()
+        .filter_map(|value| parse_published_at(value))
+        .collect::<Vec<_>>();
+        let (ttl_minutes, skip_hours) = sqlx::query_as::<_, (Option<i64>, Option<String>)>(
+            "SELECT feed_ttl_minutes, feed_skip_hours FROM sources WHERE id = ?1",
+        )
+        .bind(source_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .unwrap_or_default();
+        let skip_hours = skip_hours
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|hour| hour.trim().parse().ok())
+            .collect::<Vec<u32>>();
+        let interval = poll_interval_secs(
+            observed_interval_secs(published),
+            ttl_minutes.and_then(|minutes| u32::try_from(minutes).ok()),
+        );
+        let schedule = SourcePollSchedule {
+            poll_interval_secs: interval,
+            next_poll_at: next_poll_at(now, interval, &skip_hours)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        };
+        sqlx::query(
+            r#"
+            UPDATE sources
+            SET poll_interval_secs = ?1,
+                next_poll_at = ?2
+            WHERE id = ?3
+            "#,
+        )
+        .bind(schedule.poll_interval_secs)
+        .bind(&schedule.next_poll_at)
+        .bind(source_id)
+        .execute(&self.pool)
         .await?;
-        Ok(rows)
+        Ok(schedule)
     }
 
-    pub async fn set_entry_translated_title(
+    pub async fn reset_source_failures(&self, source_ids: &[i64]) -> Result<u64, StorageError> {
+        if source_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut query = QueryBuilder::<Sqlite>::new(
+            r#"
+            UPDATE sources
+            SET failure_count = 0,
+                gone_count = 0,
+                retry_after = NULL,
+                is_active = CASE WHEN auto_disabled_at IS NULL THEN is_active ELSE 1 END,
+                auto_disabled_at = NULL,
+                auto_disabled_reason = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id IN (
+            "#,
+        );
+        let mut separated = query.separated(", ");
+        for source_id in source_ids {
+            separated.push_bind(*source_id);
+        }
+        separated.push_unseparated(")");
+
+        let affected = query.build().execute(&self.pool).await?.rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn record_source_sync_result(
         &self,
-        entry_id: i64,
-        translated_title: &str,
+        source_id: i64,
+        status: &str,
+        upserted: i64,
+        error: Option<&str>,
     ) -> Result<(), StorageError> {
         sqlx::query(
             r#"
-            UPDATE entries
-            SET translated_title = ?1
-            WHERE id = ?2
+            UPDATE sources
+            SET last_sync_status = ?1,
+                last_sync_upserted = ?2,
+                last_error = ?3
+            WHERE id = ?4
             "#,
         )
-        .bind(translated_title)
-        .bind(entry_id)
+        .bind(status)
+        .bind(upserted)
+        .bind(error)
+        .bind(source_id)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
-    pub async fn get_entry_by_id(
+    pub async fn log_sync_attempt(&self, attempt: &SyncAttempt) -> Result<(), StorageError> {
+        let mut tx = self.begin_write().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO sync_log (source_id, started_at, status, http_status, entries_upserted, error)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )
+        .bind(attempt.source_id)
+        .bind(&attempt.started_at)
+        .bind(&attempt.status)
+        .bind(attempt.http_status.map(i64::from))
+        .bind(attempt.entries_upserted)
+        .bind(&attempt.error)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            DELETE FROM sync_log
+            WHERE source_id = ?1
+              AND id NOT IN (
+                SELECT id FROM sync_log WHERE source_id = ?1 ORDER BY id DESC LIMIT ?2
+              )
+            "#,
+        )
+        .bind(attempt.source_id)
+        .bind(MAX_SYNC_LOG_PER_SOURCE)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn list_sync_log(
         &self,
-        entry_id: i64,
-    ) -> Result<Option<EntryRecord>, StorageError> {
-        let row = sqlx::query_as::<_, EntryRecord>(
+        source_id: i64,
+        limit: i64,
+    ) -> Result<Vec<SyncLogEntry>, StorageError> {
+        let rows = sqlx::query_as::<_, SyncLogEntry>(
             r#"
-            SELECT
-              e.id,
-              e.source_id,
-              s.title AS source_title,
-              e.guid,
-              e.link,
-              e.title,
-              e.translated_title,
-              e.summary,
-              e.content,
-              e.published_at,
-              e.is_read,
-              e.is_starred,
-              e.created_at
-            FROM entries e
-            JOIN sources s ON s.id = e.source_id
-            WHERE e.id = ?1
+            SELECT id, source_id, started_at, finished_at, status, http_status, entries_upserted, error
+            FROM sync_log
+            WHERE source_id = ?1
+            ORDER BY id DESC
+            LIMIT ?2
             "#,
         )
-        .bind(entry_id)
-        .fetch_optional(&self.pool)
+        .bind(source_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
         .await?;
-        Ok(row)
+        Ok(rows)
     }
 
-    pub async fn get_setting(&self, key: &str) -> Result<Option<String>, StorageError> {
-        let value =
-            sqlx::query_scalar::<_, String>("SELECT value FROM app_settings WHERE key = ?1")
-                .bind(key)
-                .fetch_optional(&self.pool)
-                .await?;
-        Ok(value)
+    pub async fn list_failing_sources(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<FailingSource>, StorageError> {
+        let rows = sqlx::query_as::<_, FailingSource>(
+            r#"
+            SELECT
+              s.id,
+              s.title,
+              s.feed_url,
+              s.is_active,
+              s.failure_count,
+              s.last_http_status,
+              s.last_error,
+              (
+                SELECT MAX(l.finished_at) FROM sync_log l
+                WHERE l.source_id = s.id AND l.error IS NOT NULL
+              ) AS last_failed_at,
+              s.auto_disabled_at,
+              s.auto_disabled_reason
+            FROM sources s
+            WHERE s.failure_count > 0 OR s.auto_disabled_at IS NOT NULL
+            ORDER BY s.failure_count DESC, s.id DESC
+            LIMIT ?1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
     }
 
-    pub async fn set_setting(&self, key: &str, value: &str) -> Result<(), StorageError> {
-        sqlx::query(
+    pub async fn list_problem_sources(&self) -> Result<Vec<ProblemSource>, StorageError> {
+        let rows = sqlx::query_as::<_, ProblemSource>(
             r#"
-            INSERT INTO app_settings (key, value)
-            VALUES (?1, ?2)
-            ON CONFLICT(key) DO UPDATE SET
-              value = excluded.value,
-              updated_at = CURRENT_TIMESTAMP
+            SELECT
+              s.id,
+              s.title,
+              s.feed_url,
+              s.site_url,
+              s.is_active,
+              s.failure_count,
+              s.last_http_status,
+              s.last_sync_status,
+              s.last_error,
+              s.gone_count,
+              s.parked_domain,
+              s.replacement_feed_url,
+              s.auto_disabled_reason,
+              (
+                SELECT COUNT(*) FROM sync_log l
+                WHERE l.source_id = s.id
+                  AND l.status = 'timeout'
+                  AND l.id > COALESCE(
+                    (SELECT MAX(ok.id) FROM sync_log ok WHERE ok.source_id = s.id AND ok.error IS NULL),
+                    0
+                  )
+              ) AS recent_timeouts
+            FROM sources s
+            WHERE s.failure_count > 0 OR s.auto_disabled_at IS NOT NULL
+            ORDER BY s.failure_count DESC, s.id DESC
             "#,
         )
-        .bind(key)
-        .bind(value)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn defer_source_sync(
+        &self,
+        source_id: i64,
+        delay_secs: i64,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            "UPDATE sources SET retry_after = datetime('now', '+' || ?1 || ' seconds') WHERE id = ?2",
+        )
+        .bind(delay_secs)
+        .bind(source_id)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
 
-    pub async fn get_llm_cache(
+    pub async fn record_source_health(
         &self,
-        task_type: &str,
-        model: &str,
-        input_hash: &str,
-    ) -> Result<Option<String>, StorageError> {
-        let value = sqlx::query_scalar::<_, String>(
+        source_id: i64,
+        http_status: Option<u16>,
+        parked_domain: bool,
+    ) -> Result<i64, StorageError> {
+        let gone_count = sqlx::query_scalar::<_, i64>(
             r#"
-            SELECT output_text
-            FROM llm_cache
-            WHERE task_type = ?1
-              AND model = ?2
-              AND input_hash = ?3
+            UPDATE sources
+            SET last_http_status = ?1,
+                gone_count = CASE WHEN ?1 IN (404, 410) THEN gone_count + 1 ELSE 0 END,
+                parked_domain = ?2
+            WHERE id = ?3
+            RETURNING gone_count
             "#,
         )
-        .bind(task_type)
-        .bind(model)
-        .bind(input_hash)
+        .bind(http_status.map(i64::from))
+        .bind(i64::from(parked_domain))
+        .bind(source_id)
         .fetch_optional(&self.pool)
         .await?;
-        Ok(value)
+        Ok(gone_count.unwrap_or(0))
     }
 
-    pub async fn set_llm_cache(
+    pub async fn set_source_replacement_feed(
         &self,
-        task_type: &str,
-        model: &str,
-        input_hash: &str,
-        output_text: &str,
-    ) -> Result<(), StorageError> {
-        sqlx::query(
+        source_id: i64,
+        feed_url: Option<&str>,
+    ) -> Result<u64, StorageError> {
+        let result = sqlx::query("UPDATE sources SET replacement_feed_url = ?1 WHERE id = ?2")
+            .bind(feed_url)
+            .bind(source_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn replace_source_feed_url(
+        &self,
+        source_id: i64,
+        feed_url: &str,
+    ) -> Result<u64, StorageError> {
+        let result = sqlx::query(
             r#"
-            INSERT INTO llm_cache (task_type, model, input_hash, output_text)
-            VALUES (?1, ?2, ?3, ?4)
-            ON CONFLICT(task_type, model, input_hash) DO UPDATE SET
-              output_text = excluded.output_text,
-              created_at = CURRENT_TIMESTAMP
+            UPDATE sources
+            SET feed_url = ?1,
+                replacement_feed_url = NULL,
+                etag = NULL,
+                last_modified = NULL,
+                last_synced_at = NULL,
+                failure_count = 0,
+                gone_count = 0,
+                last_http_status = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?2
             "#,
         )
-        .bind(task_type)
-        .bind(model)
-        .bind(input_hash)
-        .bind(output_text)
+        .bind(feed_url)
+        .bind(source_id)
         .execute(&self.pool)
         .await?;
-        Ok(())
+        Ok(result.rows_affected())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::importer::{build_import_preview, parse_opml};
-    use sqlx::Row;
-    use std::collections::HashSet;
+    pub async fn list_stale_sources(
+        &self,
+        threshold_days: i64,
+        min_gone_count: i64,
+    ) -> Result<Vec<StaleSource>, StorageError> {
+        let rows = sqlx::query_as::<_, StaleSource>(
+            r#"
+            WITH latest AS (
+              SELECT source_id, MAX(COALESCE(datetime(published_at), datetime(created_at))) AS last_entry_at
+              FROM (
+                SELECT source_id, published_at, created_at FROM entries
+                UNION ALL
+                SELECT source_id, published_at, created_at FROM entries_archive
+              )
+              GROUP BY source_id
+            ),
+            flagged AS (
+              SELECT
+                s.id,
+                s.title,
+                s.feed_url,
+                s.site_url,
+                s.category,
+                s.is_active,
+                s.failure_count,
+                s.last_http_status,
+                s.gone_count,
+                s.replacement_feed_url,
+                l.last_entry_at,
+                COALESCE(l.last_entry_at, datetime(s.created_at)) <= datetime('now', '-' || ?1 || ' days') AS no_recent_entries,
+                s.gone_count >= ?2 AS repeatedly_gone,
+                s.parked_domain = 1 AS parked_domain
+              FROM sources s
+              LEFT JOIN latest l ON l.source_id = s.id
+            )
+            SELECT *
+            FROM flagged
+            WHERE no_recent_entries OR repeatedly_gone OR parked_domain
+            ORDER BY repeatedly_gone DESC, parked_domain DESC, last_entry_at IS NOT NULL, last_entry_at ASC, id ASC
+            "#,
+        )
+        .bind(threshold_days)
+        .bind(min_gone_count)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
 
-    fn make_source(title: &str, feed_url: &str) -> NewSource {
-        NewSource {
-            title: title.to_string(),
-            site_url: Some("https://example.com".to_string()),
-            feed_url: feed_url.to_string(),
-            category: Some("tech".to_string()),
-            is_active: true,
-        }
+    pub async fn get_source_details(
+        &self,
+        source_id: i64,
+        recent_limit: i64,
+    ) -> Result<Option<SourceDetails>, StorageError> {
+        let Some(source) = self.get_source_by_id(source_id).await? else {
+            return Ok(None);
+        };
+        let stats = sqlx::query_as::<_, SourceEntryStats>(
+            r#"
+            SELECT
+              (SELECT COUNT(*) FROM entries WHERE source_id = ?1) AS entry_count,
+              (SELECT COUNT(*) FROM entries WHERE source_id = ?1 AND is_read = 0) AS unread_count,
+              (SELECT COUNT(*) FROM entries WHERE source_id = ?1 AND is_starred = 1) AS starred_count,
+              (SELECT COUNT(*) FROM entries_archive WHERE source_id = ?1) AS archived_count,
+              (SELECT MAX(published_at) FROM entries WHERE source_id = ?1) AS latest_published_at
+            "#,
+        )
+        .bind(source_id)
+        .fetch_one(&self.pool)
+        .await?;
+        let last_sync = sqlx::query_as::<_, SourceSyncResult>(
+            r#"
+            SELECT last_sync_status, last_sync_upserted, last_error, last_synced_at, replacement_feed_url, retry_after, poll_interval_secs, next_poll_at
+            FROM sources
+            WHERE id = ?1
+            "#,
+        )
+        .bind(source_id)
+        .fetch_one(&self.pool)
+        .await?;
+        let recent_entries = self
+            .list_entries(Some(source_id), None, false, recent_limit)
+            .await?;
+        Ok(Some(SourceDetails {
+            source,
+            stats,
+            last_sync,
+            recent_entries,
+        }))
     }
 
-    #[tokio::test]
-    async fn migration_creates_required_tables() {
-        let repository = SourceRepository::connect("sqlite::memory:")
-            .await
-            .expect("connect must succeed");
-        let rows = sqlx::query(
+    pub async fn list_sync_candidates(
+        &self,
+        limit: i64,
+        respect_schedule: bool,
+    ) -> Result<Vec<SourceRecord>, StorageError> {
+        let rows = sqlx::query_as::<_, SourceRecord>(
             r#"
-            SELECT name
-            FROM sqlite_master
-            WHERE type = 'table'
-              AND name IN ('app_settings', 'sources', 'entries', 'llm_cache')
-            ORDER BY name
+            SELECT s.id, s.title, s.site_url, s.feed_url, s.category, s.is_active, s.failure_count, s.etag, s.last_modified, s.last_synced_at, s.color, s.custom_icon, s.created_at, s.updated_at
+            FROM sources s
+            LEFT JOIN source_settings ss ON ss.source_id = s.id
+            WHERE s.is_active = 1
+              AND (s.retry_after IS NULL OR s.retry_after <= datetime('now'))
+              AND (
+                s.last_synced_at IS NULL
+                OR (
+                  datetime(
+                    s.last_synced_at,
+                    '+' || (
+                      CASE
+                        WHEN s.failure_count <= 1 THEN 1
+                        ELSE MIN(?2 * (1 << MIN(s.failure_count - 2, 16)), ?3)
+                      END
+                    ) || ' minutes'
+                  ) <= datetime('now')
+                  AND (
+                    ?4 = 0
+                    OR ss.sync_interval_secs IS NOT NULL
+                    OR s.next_poll_at IS NULL
+                    OR s.next_poll_at <= datetime('now')
+                  )
+                  AND (
+                    ss.sync_interval_secs IS NULL
+                    OR datetime(s.last_synced_at, '+' || ss.sync_interval_secs || ' seconds') <= datetime('now')
+                  )
+                )
+              )
+            ORDER BY s.last_synced_at IS NOT NULL, datetime(s.last_synced_at) ASC, s.id DESC
+            LIMIT ?1
             "#,
         )
-        .fetch_all(&repository.pool)
-        .await
-        .expect("query must succeed");
+        .bind(limit)
+        .bind(SYNC_BACKOFF_BASE_MINUTES)
+        .bind(SYNC_BACKOFF_MAX_MINUTES)
+        .bind(respect_schedule)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
 
-        let table_names: Vec<String> = rows
-            .into_iter()
-            .map(|row| row.get::<String, _>("name"))
-            .collect();
-        assert_eq!(
-            table_names,
-            vec![
-                "app_settings".to_string(),
-                "entries".to_string(),
-                "llm_cache".to_string(),
-                "sources".to_string()
-            ]
-        );
+    pub async fn upsert_entries(
+        &self,
+        source_id: i64,
+        entries: &[ParsedEntry],
+    ) -> Result<UpsertCounts, StorageError> {
+        self.upsert_source_entries(source_id, entries, true).await
+    }
 
-        let columns = sqlx::query("PRAGMA table_info(sources)")
-            .fetch_all(&repository.pool)
-            .await
-            .expect("pragma should succeed");
-        let has_etag = columns
-            .iter()
-            .any(|row| row.get::<String, _>("name") == "etag");
-        let has_last_modified = columns
-            .iter()
-            .any(|row| row.get::<String, _>("name") == "last_modified");
-        let has_last_synced_at = columns
+    pub async fn upsert_backfill_entries(
+        &self,
+        source_id: i64,
+        entries: &[ParsedEntry],
+    ) -> Result<UpsertCounts, StorageError> {
+        self.upsert_source_entries(source_id, entries, false).await
+    }
+
+    async fn upsert_source_entries(
+        &self,
+        source_id: i64,
+        entries: &[ParsedEntry],
+        apply_first_sync_cutoff: bool,
+    ) -> Result<UpsertCounts, StorageError> {
+        let mut counts = UpsertCounts::default();
+        if entries.is_empty() {
+            return Ok(counts);
+        }
+
+        let global_max_age_days = self.first_sync_max_age_days().await?;
+        let sanitized = {
+            let allowlist = self.sanitizer_allowlist().await?;
+            let sanitizer = allowlist.sanitizer();
+            entries
+                .iter()
+                .map(|entry| sanitize_entry(entry, &sanitizer))
+                .collect::<Vec<_>>()
+        };
+        let mut tx = self.begin_write().await?;
+        let (has_entries, source_max_age_days, mark_backlog_read, stored_cutoff) =
+            sqlx::query_as::<_, (bool, Option<i64>, bool, Option<String>)>(
+                r#"
+            SELECT
+              EXISTS(SELECT 1 FROM entries WHERE source_id = ?1)
+                OR EXISTS(SELECT 1 FROM entries_archive WHERE source_id = ?1),
+              (SELECT first_sync_max_age_days FROM source_settings WHERE source_id = ?1),
+              COALESCE((SELECT mark_backlog_read FROM sources WHERE id = ?1), 0),
+              (SELECT first_sync_cutoff FROM sources WHERE id = ?1)
+            "#,
+            )
+            .bind(source_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let max_age_days = source_max_age_days.unwrap_or(global_max_age_days);
+        if max_age_days <= 0 && stored_cutoff.is_some() {
+            sqlx::query("UPDATE sources SET first_sync_cutoff = NULL WHERE id = ?1")
+                .bind(source_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        let mut cutoff = stored_cutoff
+            .as_deref()
+            .filter(|_| apply_first_sync_cutoff && max_age_days > 0)
+            .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&chrono::Utc));
+        if apply_first_sync_cutoff && cutoff.is_none() && !has_entries && max_age_days > 0 {
+            let first_sync_cutoff = chrono::Utc::now() - chrono::Duration::days(max_age_days);
+            sqlx::query("UPDATE sources SET first_sync_cutoff = ?1 WHERE id = ?2")
+                .bind(first_sync_cutoff.to_rfc3339())
+                .bind(source_id)
+                .execute(&mut *tx)
+                .await?;
+            cutoff = Some(first_sync_cutoff);
+        }
+        let insert_read = !has_entries && mark_backlog_read;
+        if mark_backlog_read {
+            sqlx::query("UPDATE sources SET mark_backlog_read = 0 WHERE id = ?1")
+                .bind(source_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        let seen: HashSet<String> = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT fingerprint FROM entries WHERE source_id = ?1 AND fingerprint IS NOT NULL
+            UNION ALL
+            SELECT fingerprint FROM entries_archive WHERE source_id = ?1 AND fingerprint IS NOT NULL
+            UNION ALL
+            SELECT fingerprint FROM entry_tombstones WHERE source_id = ?1 AND fingerprint IS NOT NULL
+            "#,
+        )
+        .bind(source_id)
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
+        let feed_url =
+            sqlx::query_scalar::<_, String>("SELECT feed_url FROM sources WHERE id = ?1")
+                .bind(source_id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .unwrap_or_default();
+        let pending = entries
             .iter()
-            .any(|row| row.get::<String, _>("name") == "last_synced_at");
-        assert!(has_etag && has_last_modified && has_last_synced_at);
+            .zip(&sanitized)
+            .map(|(raw, entry)| PendingEntry {
+                entry,
+                fingerprint: build_entry_fingerprint(raw),
+                dedup_key: build_dedup_key(&feed_url, raw),
+                canonical_link: canonicalize_link(&raw.link),
+            })
+            .collect::<Vec<_>>();
+        let pending: Vec<&PendingEntry> = last_by_key(&pending, |pending| &pending.dedup_key)
+            .into_iter()
+            .filter(|pending| !seen.contains(&pending.fingerprint))
+            .filter(|pending| {
+                cutoff.is_none_or(|cutoff| published_before(pending.entry, cutoff) != Some(true))
+            })
+            .collect();
+
+        for chunk in pending.chunks(UPSERT_CHUNK_ROWS) {
+            let mut archived = QueryBuilder::<Sqlite>::new(
+                "SELECT dedup_key FROM entries_archive WHERE source_id = ",
+            );
+            archived.push_bind(source_id).push(" AND dedup_key IN (");
+            let mut separated = archived.separated(", ");
+            for pending in chunk {
+                separated.push_bind(&pending.dedup_key);
+            }
+            separated.push_unseparated(
+                ") UNION ALL SELECT dedup_key FROM entry_tombstones WHERE source_id = ",
+            );
+            archived.push_bind(source_id).push(" AND dedup_key IN (");
+            let mut separated = archived.separated(", ");
+            for pending in chunk {
+                separated.push_bind(&pending.dedup_key);
+            }
+            separated.push_unseparated(")");
+            let archived: HashSet<String> = archived
+                .build_query_scalar::<String>()
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .collect();
+            let rows: Vec<&PendingEntry> = chunk
+                .iter()
+                .copied()
+                .filter(|pending| !archived.contains(&pending.dedup_key))
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+
+            let mut lookup = QueryBuilder::<Sqlite>::new(
+                "SELECT id, canonical_link, guid, dedup_key FROM entries WHERE source_id = ",
+            );
+            lookup
+                .push_bind(source_id)
+                .push(" AND (canonical_link IN (");
+            let mut separated = lookup.separated(", ");
+            for pending in &rows {
+                separated.push_bind(&pending.canonical_link);
+            }
+            separated.push_unseparated(") OR dedup_key IN (");
+            let mut separated = lookup.separated(", ");
+            for pending in &rows {
+                separated.push_bind(&pending.dedup_key);
+            }
+            separated.push_unseparated(") OR guid IN (");
+            let mut separated = lookup.separated(", ");
+            for pending in &rows {
+                separated.push_bind(&pending.entry.id);
+            }
+            separated.push_unseparated("))");
+            let mut by_link = HashMap::new();
+            let mut by_guid = HashMap::new();
+            let mut by_key = HashMap::new();
+            for (id, canonical_link, guid, dedup_key) in lookup
+                .build_query_as::<(i64, Option<String>, Option<String>, Option<String>)>()
+                .fetch_all(&mut *tx)
+                .await?
+            {
+                let has_guid = guid
+                    .as_deref()
+                    .is_some_and(|guid| !matches!(guid.trim(), "" | UNKNOWN_ENTRY_ID));
+                if let Some(canonical_link) = canonical_link {
+                    by_link.insert(canonical_link, (id, has_guid));
+                }
+                match dedup_key {
+                    Some(dedup_key) => {
+                        by_key.insert(dedup_key, id);
+                    }
+                    None => {
+                        if let Some(guid) = guid {
+                            by_guid.insert(guid, id);
+                        }
+                    }
+                }
+            }
+
+            let mut inserts = Vec::new();
+            for pending in rows {
+                let existing_id = by_key
+                    .get(&pending.dedup_key)
+                    .or_else(|| {
+                        pending
+                            .entry
+                            .guid()
+                            .and_then(|_| by_guid.get(&pending.entry.id))
+                    })
+                    .or_else(|| {
+                        by_link
+                            .get(&pending.canonical_link)
+                            .filter(|(_, has_guid)| pending.entry.guid().is_none() || !has_guid)
+                            .map(|(id, _)| id)
+                    });
+                let Some(&existing_id) = existing_id else {
+                    inserts.push(pending);
+                    continue;
+                };
+                let entry = pending.entry;
+                let updated = sqlx::query(
+                    r#"
+                    UPDATE OR IGNORE entries
+                    SET guid = ?1, link = ?2, title = ?3, summary = ?4, content = COALESCE(?5, ?6),
+                        published_at = COALESCE(published_at, ?7), fingerprint = ?8, dedup_key = ?9,
+                        canonical_link = ?10, updated_at = CURRENT_TIMESTAMP, is_updated = 1
+                    WHERE id = ?11
+                    "#,
+                )
+                .bind(&entry.id)
+                .bind(&entry.link)
+                .bind(&entry.title)
+                .bind(&entry.summary)
+                .bind(entry.content.as_deref().and_then(compress_text))
+                .bind(&entry.content)
+                .bind(&entry.published_at)
+                .bind(&pending.fingerprint)
+                .bind(&pending.dedup_key)
+                .bind(&pending.canonical_link)
+                .bind(existing_id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected() as usize;
+                counts.updated += updated;
+                counts.conflicted += 1 - updated;
+            }
+            if inserts.is_empty() {
+                continue;
+            }
+
+            let mut insert = QueryBuilder::<Sqlite>::new(
+                "INSERT OR IGNORE INTO entries (source_id, guid, link, title, summary, content, published_at, fingerprint, dedup_key, canonical_link, is_read, read_at) ",
+            );
+            insert.push_values(&inserts, |mut row, pending| {
+                let entry = pending.entry;
+                row.push_bind(source_id)
+                    .push_bind(&entry.id)
+                    .push_bind(&entry.link)
+                    .push_bind(&entry.title)
+                    .push_bind(&entry.summary)
+                    .push("COALESCE(")
+                    .push_bind_unseparated(entry.content.as_deref().and_then(compress_text))
+                    .push_unseparated(", ")
+                    .push_bind_unseparated(&entry.content)
+                    .push_unseparated(")")
+                    .push_bind(&entry.published_at)
+                    .push_bind(&pending.fingerprint)
+                    .push_bind(&pending.dedup_key)
+                    .push_bind(&pending.canonical_link)
+                    .push_bind(i64::from(insert_read))
+                    .push(if insert_read {
+                        "CURRENT_TIMESTAMP"
+                    } else {
+                        "NULL"
+                    });
+            });
+            let inserted = insert.build().execute(&mut *tx).await?.rows_affected() as usize;
+            counts.inserted += inserted;
+            counts.conflicted += inserts.len() - inserted;
+        }
+        tx.commit().await?;
+        Ok(counts)
+    }
+
+    pub async fn list_entries(
+        &self,
+        source_id: Option<i64>,
+        search: Option<&str>,
+        unread_only: bool,
+        limit: i64,
+    ) -> Result<Vec<EntryRecord>, StorageError> {
+        let listing = EntryListing {
+            cursor: None,
+            limit,
+            include_content: true,
+        };
+        self.fetch_entries(source_id, search, unread_only, listing, None)
+            .await
+    }
+
+    pub async fn search_entries(
+        &self,
+        source_id: Option<i64>,
+        search: Option<&str>,
+        unread_only: bool,
+        cursor: Option<&EntryCursor>,
+        limit: i64,
+        control: &QueryControl,
+    ) -> Result<Vec<EntryRecord>, StorageError> {
+        let listing = EntryListing {
+            cursor,
+            limit,
+            include_content: false,
+        };
+        self.fetch_entries(source_id, search, unread_only, listing, Some(control))
+            .await
+    }
+
+    async fn fetch_entries(
+        &self,
+        source_id: Option<i64>,
+        search: Option<&str>,
+        unread_only: bool,
+        listing: EntryListing<'_>,
+        control: Option<&QueryControl>,
+    ) -> Result<Vec<EntryRecord>, StorageError> {
+        let query = parse_search_query(search.unwrap_or(""));
+        let search_match = self.entry_search_match(&query).await?;
+        let content = if listing.include_content {
+            "content"
+        } else {
+            "NULL AS content"
+        };
+        let mut builder = QueryBuilder::<Sqlite>::new(format!(
+            r#"
+            WITH all_entries AS (
+              SELECT id, source_id, guid, link, title, translated_title, summary, {content}, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries
+            "#
+        ));
+        if !query.is_empty() {
+            builder.push(format!(
+                r#"
+              UNION ALL
+              SELECT id, source_id, guid, link, title, translated_title, summary, {content}, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries_archive
+                "#
+            ));
+        }
+        builder.push(
+            r#"
+            )
+            SELECT
+              e.id,
+              e.source_id,
+              s.title AS source_title,
+              e.guid,
+              e.link,
+              e.title,
+              e.translated_title,
+              e.summary,
+              e.content,
+              e.published_at,
+              e.is_read,
+              e.is_starred,
+              e.created_at,
+              e.updated_at,
+              e.is_updated
+            FROM all_entries e
+            JOIN sources s ON s.id = e.source_id
+            WHERE 1 = 1
+            "#,
+        );
+        if unread_only {
+            builder.push(" AND e.is_read = 0");
+        }
+        push_entry_filters(&mut builder, source_id, &query, search_match.as_deref());
+        push_entry_page(&mut builder, listing.cursor, listing.limit);
+        let Some(control) = control else {
+            let rows = builder
+                .build_query_as::<EntryRecord>()
+                .fetch_all(&self.pool)
+                .await?;
+            return Ok(rows);
+        };
+        if control.is_cancelled() {
+            return Err(StorageError::QueryCancelled);
+        }
+        let mut connection = self.pool.acquire().await?;
+        let armed = control.arm(&mut connection).await?;
+        let result = builder
+            .build_query_as::<EntryRecord>()
+            .fetch_all(&mut *connection)
+            .await;
+        armed.finish(result)
+    }
+
+    pub async fn list_view_entries(
+        &self,
+        view: EntryView,
+        cursor: Option<&EntryCursor>,
+        limit: i64,
+    ) -> Result<Vec<EntryRecord>, StorageError> {
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+            WITH all_entries AS (
+              SELECT id, source_id, guid, link, title, translated_title, summary, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries
+            "#,
+        );
+        if view == EntryView::Starred {
+            builder.push(
+                r#"
+              UNION ALL
+              SELECT id, source_id, guid, link, title, translated_title, summary, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries_archive
+              WHERE is_starred = 1 AND id NOT IN (SELECT id FROM entries)
+                "#,
+            );
+        }
+        builder.push(
+            r#"
+            )
+            SELECT
+              e.id,
+              e.source_id,
+              s.title AS source_title,
+              e.guid,
+              e.link,
+              e.title,
+              e.translated_title,
+              e.summary,
+              NULL AS content,
+              e.published_at,
+              e.is_read,
+              e.is_starred,
+              e.created_at,
+              e.updated_at,
+              e.is_updated
+            FROM all_entries e
+            JOIN sources s ON s.id = e.source_id
+            WHERE 1 = 1
+            "#,
+        );
+        match view {
+            EntryView::All => {}
+            EntryView::Unread => {
+                builder.push(" AND e.is_read = 0");
+            }
+            EntryView::Starred => {
+                builder.push(" AND e.is_starred = 1");
+            }
+            EntryView::Today => {
+                let since = local_day_start();
+                builder.push(" AND COALESCE(e.published_at, e.created_at) >= ");
+                builder.push_bind(since.format("%Y-%m-%d").to_string());
+                builder.push(" AND datetime(COALESCE(e.published_at, e.created_at)) >= datetime(");
+                builder.push_bind(since.format("%Y-%m-%d %H:%M:%S").to_string());
+                builder.push(")");
+            }
+        }
+        push_entry_page(&mut builder, cursor, limit);
+        let rows = builder
+            .build_query_as::<EntryRecord>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows)
+    }
+
+    pub async fn get_source_notification_prefs(
+        &self,
+        source_id: i64,
+    ) -> Result<Option<NotificationPrefs>, StorageError> {
+        let row = sqlx::query_as::<_, (String, Option<String>, bool)>(
+            "SELECT notify_mode, notify_keywords, notify_muted FROM sources WHERE id = ?1",
+        )
+        .bind(source_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|(mode, keywords, muted)| NotificationPrefs {
+            mode: NotifyMode::parse(&mode),
+            keywords: keywords
+                .and_then(|raw| serde_json::from_str(&raw).ok())
+                .unwrap_or_default(),
+            muted,
+        }))
+    }
+
+    pub async fn set_source_notification_prefs(
+        &self,
+        source_id: i64,
+        prefs: &NotificationPrefs,
+    ) -> Result<u64, StorageError> {
+        let keywords = serde_json::to_string(&prefs.keywords).unwrap_or_else(|_| "[]".to_string());
+        let affected = sqlx::query(
+            r#"
+            UPDATE sources
+            SET notify_mode = ?1, notify_keywords = ?2, notify_muted = ?3, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?4
+            "#,
+        )
+        .bind(prefs.mode.as_str())
+        .bind(keywords)
+        .bind(prefs.muted)
+        .bind(source_id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn list_notification_rules(&self) -> Result<Vec<NotificationRule>, StorageError> {
+        let rows = sqlx::query_as::<_, (i64, String, String, Option<i64>, Option<String>, i64)>(
+            r#"
+            SELECT id, name, keywords, source_id, category, is_enabled
+            FROM notification_rules
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(id, name, keywords, source_id, category, is_enabled)| NotificationRule {
+                    id,
+                    name,
+                    keywords: serde_json::from_str(&keywords).unwrap_or_default(),
+                    source_id,
+                    category,
+                    is_enabled: is_enabled == 1,
+                },
+            )
+            .collect())
+    }
+
+    pub async fn create_notification_rule(
+        &self,
+        rule: &NotificationRule,
+    ) -> Result<i64, StorageError> {
+        let keywords = serde_json::to_string(&rule.keywords).unwrap_or_else(|_| "[]".to_string());
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO notification_rules (name, keywords, source_id, category, is_enabled)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            RETURNING id
+            "#,
+        )
+        .bind(&rule.name)
+        .bind(keywords)
+        .bind(rule.source_id)
+        .bind(&rule.category)
+        .bind(i64::from(rule.is_enabled))
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(id)
+    }
+
+    pub async fn update_notification_rule(
+        &self,
+        rule: &NotificationRule,
+    ) -> Result<u64, StorageError> {
+        let keywords = serde_json::to_string(&rule.keywords).unwrap_or_else(|_| "[]".to_string());
+        let affected = sqlx::query(
+            r#"
+            UPDATE notification_rules
+            SET name = ?1, keywords = ?2, source_id = ?3, category = ?4, is_enabled = ?5,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?6
+            "#,
+        )
+        .bind(&rule.name)
+        .bind(keywords)
+        .bind(rule.source_id)
+        .bind(&rule.category)
+        .bind(i64::from(rule.is_enabled))
+        .bind(rule.id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn delete_notification_rule(&self, rule_id: i64) -> Result<u64, StorageError> {
+        let affected = sqlx::query("DELETE FROM notification_rules WHERE id = ?1")
+            .bind(rule_id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn list_glossary_terms(&self) -> Result<Vec<GlossaryTerm>, StorageError> {
+        let rows = sqlx::query_as::<_, (i64, String, String)>(
+            "SELECT id, term, translation FROM translation_glossary ORDER BY term COLLATE NOCASE ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, term, translation)| GlossaryTerm {
+                id,
+                term,
+                translation,
+            })
+            .collect())
+    }
+
+    pub async fn save_glossary_term(&self, term: &GlossaryTerm) -> Result<i64, StorageError> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO translation_glossary (term, translation)
+            VALUES (?1, ?2)
+            ON CONFLICT(term) DO UPDATE SET
+              term = excluded.term,
+              translation = excluded.translation,
+              updated_at = CURRENT_TIMESTAMP
+            RETURNING id
+            "#,
+        )
+        .bind(&term.term)
+        .bind(&term.translation)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(id)
+    }
+
+    pub async fn delete_glossary_term(&self, term_id: i64) -> Result<u64, StorageError> {
+        let affected = sqlx::query("DELETE FROM translation_glossary WHERE id = ?1")
+            .bind(term_id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn list_llm_profiles(&self) -> Result<Vec<LlmProfile>, StorageError> {
+        let rows = sqlx::query_as::<_, (i64, String, String, bool)>(
+            r#"
+            SELECT id, name, config, id IS (SELECT CAST(value AS INTEGER) FROM app_settings WHERE key = ?1)
+            FROM llm_profiles
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(ACTIVE_LLM_PROFILE_KEY)
+        .fetch_all(&self.pool)
+        .await?;
+        let mappings = sqlx::query_as::<_, (String, i64)>(
+            "SELECT task, profile_id FROM llm_task_profiles ORDER BY task ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter()
+            .map(|(id, name, config, active)| {
+                let config = parse_llm_profile_config(&name, &config)?;
+                let tasks = mappings
+                    .iter()
+                    .filter(|(_, profile_id)| *profile_id == id)
+                    .filter_map(|(task, _)| LlmTask::parse(task))
+                    .collect();
+                Ok(LlmProfile {
+                    id,
+                    name,
+                    config,
+                    tasks,
+                    active,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn resolve_llm_profile(
+        &self,
+        task: Option<LlmTask>,
+    ) -> Result<Option<LlmProfile>, StorageError> {
+        let row = sqlx::query_as::<_, (i64, String, String, bool)>(
+            r#"
+            SELECT
+              p.id,
+              p.name,
+              p.config,
+              p.id IS (SELECT CAST(value AS INTEGER) FROM app_settings WHERE key = ?2) AS active
+            FROM llm_profiles p
+            LEFT JOIN llm_task_profiles t ON t.profile_id = p.id AND t.task = ?1
+            ORDER BY t.task IS NULL, active DESC, p.id ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(task.map(LlmTask::as_str))
+        .bind(ACTIVE_LLM_PROFILE_KEY)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.map(|(id, name, config, active)| {
+            Ok(LlmProfile {
+                config: parse_llm_profile_config(&name, &config)?,
+                id,
+                name,
+                tasks: task.into_iter().collect(),
+                active,
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn activate_llm_profile(&self, profile_id: i64) -> Result<bool, StorageError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM llm_profiles WHERE id = ?1)",
+        )
+        .bind(profile_id)
+        .fetch_one(&self.pool)
+        .await?;
+        if exists {
+            self.set_setting(ACTIVE_LLM_PROFILE_KEY, &profile_id.to_string())
+                .await?;
+        }
+        Ok(exists)
+    }
+
+    pub async fn create_llm_profile(&self, profile: &LlmProfile) -> Result<i64, StorageError> {
+        let config = serde_json::to_string(&profile.config).unwrap_or_else(|_| "{}".to_string());
+        let id = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO llm_profiles (name, config) VALUES (?1, ?2) RETURNING id",
+        )
+        .bind(&profile.name)
+        .bind(config)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(id)
+    }
+
+    pub async fn update_llm_profile(&self, profile: &LlmProfile) -> Result<u64, StorageError> {
+        let config = serde_json::to_string(&profile.config).unwrap_or_else(|_| "{}".to_string());
+        let affected = sqlx::query(
+            r#"
+            UPDATE llm_profiles
+            SET name = ?1, config = ?2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?3
+            "#,
+        )
+        .bind(&profile.name)
+        .bind(config)
+        .bind(profile.id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn delete_llm_profile(&self, profile_id: i64) -> Result<u64, StorageError> {
+        let affected = sqlx::query("DELETE FROM llm_profiles WHERE id = ?1")
+            .bind(profile_id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn set_task_llm_profile(
+        &self,
+        task: LlmTask,
+        profile_id: Option<i64>,
+    ) -> Result<(), StorageError> {
+        match profile_id {
+            Some(profile_id) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO llm_task_profiles (task, profile_id)
+                    VALUES (?1, ?2)
+                    ON CONFLICT(task) DO UPDATE SET
+                      profile_id = excluded.profile_id,
+                      updated_at = CURRENT_TIMESTAMP
+                    "#,
+                )
+                .bind(task.as_str())
+                .bind(profile_id)
+                .execute(&self.pool)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM llm_task_profiles WHERE task = ?1")
+                    .bind(task.as_str())
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn max_entry_id(&self, source_id: i64) -> Result<i64, StorageError> {
+        let value = sqlx::query_scalar::<_, i64>(
+            "SELECT COALESCE(MAX(id), 0) FROM entries WHERE source_id = ?1",
+        )
+        .bind(source_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(value)
+    }
+
+    pub async fn list_entries_after(
+        &self,
+        source_id: i64,
+        after_id: i64,
+    ) -> Result<Vec<EntryRecord>, StorageError> {
+        let rows = sqlx::query_as::<_, EntryRecord>(
+            r#"
+            SELECT
+              e.id,
+              e.source_id,
+              s.title AS source_title,
+              e.guid,
+              e.link,
+              e.title,
+              e.translated_title,
+              e.summary,
+              e.content,
+              e.published_at,
+              e.is_read,
+              e.is_starred,
+              e.created_at,
+              e.updated_at,
+              e.is_updated
+            FROM entries e
+            JOIN sources s ON s.id = e.source_id
+            WHERE e.source_id = ?1
+              AND e.id > ?2
+            ORDER BY e.id ASC
+            "#,
+        )
+        .bind(source_id)
+        .bind(after_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn list_entry_links(&self, source_id: i64) -> Result<Vec<String>, StorageError> {
+        let rows = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT link FROM entries WHERE source_id = ?1
+            UNION
+            SELECT link FROM entries_archive WHERE source_id = ?1
+            "#,
+        )
+        .bind(source_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn list_read_state(&self) -> Result<Vec<ReadStateRecord>, StorageError> {
+        let rows = sqlx::query_as::<_, ReadStateRecord>(
+            r#"
+            SELECT s.feed_url, e.link, e.is_read = 1 AS is_read, e.is_starred = 1 AS is_starred, e.read_at
+            FROM entries e
+            JOIN sources s ON s.id = e.source_id
+            WHERE (e.is_read = 1 OR e.is_starred = 1)
+              AND e.link != ''
+            ORDER BY e.id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn apply_read_state(&self, records: &[ReadStateRecord]) -> Result<u64, StorageError> {
+        let mut tx = self.begin_write().await?;
+        let mut updated = 0_u64;
+        for record in records {
+            updated += sqlx::query(
+                r#"
+                UPDATE entries
+                SET is_read = MAX(is_read, ?1),
+                    is_starred = MAX(is_starred, ?2),
+                    read_at = CASE WHEN ?1 = 1 THEN COALESCE(read_at, ?3) ELSE read_at END
+                WHERE link = ?4
+                  AND source_id IN (SELECT id FROM sources WHERE feed_url = ?5)
+                  AND (is_read < ?1 OR is_starred < ?2)
+                "#,
+            )
+            .bind(i64::from(record.is_read))
+            .bind(i64::from(record.is_starred))
+            .bind(record.read_at.as_deref())
+            .bind(&record.link)
+            .bind(&record.feed_url)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    pub async fn queue_notification(
+        &self,
+        source_id: i64,
+        entry_id: i64,
+        matched_keyword: Option<&str>,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO pending_notifications (source_id, entry_id, matched_keyword)
+            VALUES (?1, ?2, ?3)
+            "#,
+        )
+        .bind(source_id)
+        .bind(entry_id)
+        .bind(matched_keyword)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn take_pending_notifications(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<PendingNotification>, StorageError> {
+        let mut tx = self.begin_write().await?;
+        let rows = sqlx::query_as::<_, PendingNotification>(
+            r#"
+            SELECT n.id, n.source_id, s.title AS source_title, n.entry_id, e.title, e.link, n.matched_keyword, n.created_at
+            FROM pending_notifications n
+            JOIN entries e ON e.id = n.entry_id
+            JOIN sources s ON s.id = n.source_id
+            WHERE n.delivered_at IS NULL
+            ORDER BY n.id ASC
+            LIMIT ?1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+        if !rows.is_empty() {
+            let mut builder = QueryBuilder::<Sqlite>::new(
+                "UPDATE pending_notifications SET delivered_at = CURRENT_TIMESTAMP WHERE id IN (",
+            );
+            let mut separated = builder.separated(", ");
+            for row in &rows {
+                separated.push_bind(row.id);
+            }
+            separated.push_unseparated(")");
+            builder.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(rows)
+    }
+
+    pub async fn mark_entry_read(&self, entry_id: i64, is_read: bool) -> Result<u64, StorageError> {
+        let mut affected = 0_u64;
+        for table in ["entries", "entries_archive"] {
+            affected += sqlx::query(&format!(
+                r#"
+                UPDATE {table}
+                SET is_read = ?1,
+                    read_at = CASE WHEN ?1 = 1 THEN CURRENT_TIMESTAMP ELSE NULL END
+                WHERE id = ?2
+                "#
+            ))
+            .bind(i64::from(is_read))
+            .bind(entry_id)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        }
+        Ok(affected)
+    }
+
+    pub async fn record_entry_open(
+        &self,
+        entry_id: i64,
+        external: bool,
+        mark_read: bool,
+    ) -> Result<u64, StorageError> {
+        let mut tx = self.begin_write().await?;
+        let recorded = sqlx::query(
+            r#"
+            INSERT INTO entry_open_events (entry_id, source_id, external)
+            SELECT id, source_id, ?2 FROM entries WHERE id = ?1
+            UNION ALL
+            SELECT id, source_id, ?2 FROM entries_archive WHERE id = ?1
+            LIMIT 1
+            "#,
+        )
+        .bind(entry_id)
+        .bind(i64::from(external))
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        for table in ["entries", "entries_archive"]
+            .into_iter()
+            .filter(|_| mark_read)
+        {
+            sqlx::query(&format!(
+                r#"
+                UPDATE {table}
+                SET is_read = 1,
+                    read_at = CASE WHEN is_read = 1 THEN read_at ELSE CURRENT_TIMESTAMP END
+                WHERE id = ?1
+                "#
+            ))
+            .bind(entry_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(recorded)
+    }
+
+    pub async fn count_entry_opens(&self, entry_id: i64) -> Result<i64, StorageError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM entry_open_events WHERE entry_id = ?1",
+        )
+        .bind(entry_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    pub async fn get_entry_fulltext(
+        &self,
+        entry_id: i64,
+    ) -> Result<Option<EntryFulltext>, StorageError> {
+        let row = sqlx::query_as::<_, EntryFulltext>(
+            r#"
+            SELECT entry_id, url, title, content_html, text_length, fetched_at
+            FROM entry_fulltext
+            WHERE entry_id = ?1
+            "#,
+        )
+        .bind(entry_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    pub async fn set_entry_fulltext(
+        &self,
+        entry_id: i64,
+        article: &ReaderArticle,
+    ) -> Result<(), StorageError> {
+        let packed = compress_text(&article.content_html)
+            .unwrap_or_else(|| article.content_html.as_bytes().to_vec());
+        sqlx::query(
+            r#"
+            INSERT INTO entry_fulltext (entry_id, url, title, content_html, text_length)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(entry_id) DO UPDATE SET
+              url = excluded.url,
+              title = excluded.title,
+              content_html = excluded.content_html,
+              text_length = excluded.text_length,
+              fetched_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(entry_id)
+        .bind(&article.url)
+        .bind(&article.title)
+        .bind(packed)
+        .bind(article.text_length as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_catchup_candidates(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<CatchupCandidate>, StorageError> {
+        let rows = sqlx::query_as::<_, CatchupCandidate>(
+            r#"
+            WITH engagement AS (
+              SELECT source_id, AVG(is_read) AS read_ratio
+              FROM entries
+              WHERE created_at >= datetime('now', '-' || ?2 || ' days')
+              GROUP BY source_id
+            ),
+            opens AS (
+              SELECT source_id, COUNT(*) AS open_count
+              FROM entry_open_events
+              WHERE opened_at >= datetime('now', '-' || ?2 || ' days')
+              GROUP BY source_id
+            )
+            SELECT
+              e.id,
+              e.source_id,
+              s.title AS source_title,
+              e.title,
+              e.link,
+              e.published_at,
+              e.summary,
+              e.content,
+              e.is_starred,
+              CAST(COALESCE(engagement.read_ratio, 0) AS REAL) AS source_read_ratio,
+              COALESCE(opens.open_count, 0) AS source_opens,
+              s.notify_mode != 'never' AS source_notifies
+            FROM entries e
+            JOIN sources s ON s.id = e.source_id
+            LEFT JOIN engagement ON engagement.source_id = e.source_id
+            LEFT JOIN opens ON opens.source_id = e.source_id
+            WHERE e.is_read = 0 AND e.skipped_at IS NULL
+            ORDER BY COALESCE(e.published_at, e.created_at) DESC, e.id DESC
+            LIMIT ?1
+            "#,
+        )
+        .bind(limit)
+        .bind(CATCHUP_ENGAGEMENT_DAYS)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn mark_entries_skipped(&self, entry_ids: &[i64]) -> Result<u64, StorageError> {
+        if entry_ids.is_empty() {
+            return Ok(0);
+        }
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "UPDATE entries SET skipped_at = CURRENT_TIMESTAMP WHERE is_read = 0 AND id IN ",
+        );
+        push_id_list(&mut query, entry_ids);
+        Ok(query.build().execute(&self.pool).await?.rows_affected())
+    }
+
+    pub async fn clear_skipped_entries(&self) -> Result<u64, StorageError> {
+        Ok(
+            sqlx::query("UPDATE entries SET skipped_at = NULL WHERE skipped_at IS NOT NULL")
+                .execute(&self.pool)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    pub async fn list_recently_read(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<ReadHistoryEntry>, StorageError> {
+        let rows = sqlx::query_as::<_, ReadHistoryEntry>(
+            r#"
+            WITH all_entries AS (
+              SELECT id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, updated_at, is_updated, read_at
+              FROM entries
+              WHERE read_at IS NOT NULL
+              UNION ALL
+              SELECT id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, updated_at, is_updated, read_at
+              FROM entries_archive
+              WHERE read_at IS NOT NULL
+            )
+            SELECT
+              e.id,
+              e.source_id,
+              s.title AS source_title,
+              e.guid,
+              e.link,
+              e.title,
+              e.translated_title,
+              e.summary,
+              e.content,
+              e.published_at,
+              e.is_read,
+              e.is_starred,
+              e.created_at,
+              e.updated_at,
+              e.is_updated,
+              e.read_at
+            FROM all_entries e
+            JOIN sources s ON s.id = e.source_id
+            ORDER BY e.read_at DESC, e.id DESC
+            LIMIT ?1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn entry_date_histogram(
+        &self,
+        source_id: Option<i64>,
+        search: Option<&str>,
+        unread_only: bool,
+        days: i64,
+        utc_offset_minutes: i64,
+    ) -> Result<Vec<EntryDateBucket>, StorageError> {
+        let query = parse_search_query(search.unwrap_or(""));
+        let search_match = self.entry_search_match(&query).await?;
+        let offset = format!("{utc_offset_minutes:+} minutes");
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+            WITH all_entries AS (
+              SELECT id, source_id, title, summary, published_at, created_at, is_read, is_starred FROM entries
+              UNION ALL
+              SELECT id, source_id, title, summary, published_at, created_at, is_read, is_starred FROM entries_archive
+            ),
+            dated AS (
+              SELECT date(datetime(COALESCE(e.published_at, e.created_at), "#,
+        );
+        builder.push_bind(offset.clone());
+        builder.push(
+            r#")) AS day, e.is_read
+              FROM all_entries e
+              JOIN sources s ON s.id = e.source_id
+              WHERE 1 = 1
+            "#,
+        );
+        if unread_only {
+            builder.push(" AND e.is_read = 0");
+        }
+        push_entry_filters(&mut builder, source_id, &query, search_match.as_deref());
+        builder.push(
+            r#"
+            )
+            SELECT day, COUNT(*) AS total_count, SUM(CASE WHEN is_read = 0 THEN 1 ELSE 0 END) AS unread_count
+            FROM dated
+            WHERE day IS NOT NULL
+              AND day >= date('now', "#,
+        );
+        builder.push_bind(offset);
+        builder.push(", ");
+        builder.push_bind(format!("-{} days", days.max(1) - 1));
+        builder.push(
+            r#")
+            GROUP BY day
+            ORDER BY day DESC
+            "#,
+        );
+        let rows = builder
+            .build_query_as::<EntryDateBucket>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows)
+    }
+
+    pub async fn list_title_samples(
+        &self,
+        recent_days: i64,
+        baseline_days: i64,
+    ) -> Result<Vec<TitleSample>, StorageError> {
+        let rows = sqlx::query_as::<_, TitleSample>(
+            r#"
+            WITH all_entries AS (
+              SELECT title, COALESCE(published_at, created_at) AS stamp FROM entries
+              UNION ALL
+              SELECT title, COALESCE(published_at, created_at) AS stamp FROM entries_archive
+            )
+            SELECT title, datetime(stamp) >= datetime('now', '-' || ?1 || ' days') AS is_recent
+            FROM all_entries
+            WHERE datetime(stamp) >= datetime('now', '-' || (?1 + ?2) || ' days')
+            "#,
+        )
+        .bind(recent_days.max(1))
+        .bind(baseline_days.max(0))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn mark_entries_read_up_to(
+        &self,
+        source_id: Option<i64>,
+        search: Option<&str>,
+        last_seen_entry_id: i64,
+    ) -> Result<u64, StorageError> {
+        let query = parse_search_query(search.unwrap_or(""));
+        let search_match = self.entry_search_match(&query).await?;
+        let mut tables = vec!["entries"];
+        if !query.is_empty() {
+            tables.push("entries_archive");
+        }
+        let anchor = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT COALESCE(published_at, created_at) FROM entries WHERE id = ?1
+            UNION ALL
+            SELECT COALESCE(published_at, created_at) FROM entries_archive WHERE id = ?1
+            LIMIT 1
+            "#,
+        )
+        .bind(last_seen_entry_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(anchor) = anchor else {
+            return Ok(0);
+        };
+
+        let protected = self.protected_entries_clause().await?;
+        let mut tx = self.begin_write().await?;
+        let operation_id = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO read_operations (kind) VALUES ('mark_read_up_to') RETURNING id",
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let mut affected = 0_u64;
+        for table in tables {
+            let scope = ReadUpToScope {
+                table,
+                protected,
+                anchor: &anchor,
+                last_seen_entry_id,
+                source_id,
+                query: &query,
+                search_match: search_match.as_deref(),
+            };
+            let mut journal = QueryBuilder::<Sqlite>::new(
+                "INSERT OR IGNORE INTO read_operation_entries (operation_id, entry_id, previous_is_read, previous_read_at) SELECT ",
+            );
+            journal.push_bind(operation_id);
+            journal.push(format!(", id, is_read, read_at FROM {table}"));
+            scope.push(&mut journal);
+            journal.build().execute(&mut *tx).await?;
+
+            let mut update = QueryBuilder::<Sqlite>::new(format!(
+                "UPDATE {table} SET is_read = 1, read_at = CURRENT_TIMESTAMP"
+            ));
+            scope.push(&mut update);
+            affected += update.build().execute(&mut *tx).await?.rows_affected();
+        }
+        finish_read_operation(&mut tx, operation_id, affected).await?;
+        tx.commit().await?;
+        Ok(affected)
+    }
+
+    pub async fn mark_scope_read(&self, scope: ReadScope<'_>) -> Result<u64, StorageError> {
+        let protected = self.protected_entries_clause().await?;
+        let mut tx = self.begin_write().await?;
+        let operation_id = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO read_operations (kind) VALUES (?1) RETURNING id",
+        )
+        .bind(scope.kind())
+        .fetch_one(&mut *tx)
+        .await?;
+        let mut affected = 0_u64;
+        for table in ["entries", "entries_archive"] {
+            let mut journal = QueryBuilder::<Sqlite>::new(
+                "INSERT OR IGNORE INTO read_operation_entries (operation_id, entry_id, previous_is_read, previous_read_at) SELECT ",
+            );
+            journal.push_bind(operation_id);
+            journal.push(format!(", id, is_read, read_at FROM {table}"));
+            scope.push(&mut journal, protected);
+            journal.build().execute(&mut *tx).await?;
+
+            let mut update = QueryBuilder::<Sqlite>::new(format!(
+                "UPDATE {table} SET is_read = 1, read_at = CURRENT_TIMESTAMP"
+            ));
+            scope.push(&mut update, protected);
+            affected += update.build().execute(&mut *tx).await?.rows_affected();
+        }
+        finish_read_operation(&mut tx, operation_id, affected).await?;
+        tx.commit().await?;
+        Ok(affected)
+    }
+
+    pub async fn sweep_read_older_than(&self, days: i64) -> Result<u64, StorageError> {
+        let protected = self.protected_entries_clause().await?;
+        let mut tx = self.begin_write().await?;
+        let mut affected = 0_u64;
+        for table in ["entries", "entries_archive"] {
+            let mut update = QueryBuilder::<Sqlite>::new(format!(
+                "UPDATE {table} SET is_read = 1, read_at = CURRENT_TIMESTAMP"
+            ));
+            ReadScope::OlderThanDays(days).push(&mut update, protected);
+            affected += update.build().execute(&mut *tx).await?.rows_affected();
+        }
+        tx.commit().await?;
+        Ok(affected)
+    }
+
+    pub async fn undo_last_read_operation(&self) -> Result<Option<ReadOperation>, StorageError> {
+        let mut tx = self.begin_write().await?;
+        let Some(operation) = sqlx::query_as::<_, ReadOperation>(
+            "SELECT id, kind, entry_count, created_at FROM read_operations ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+        for table in ["entries", "entries_archive"] {
+            sqlx::query(&format!(
+                r#"
+                UPDATE {table}
+                SET is_read = j.previous_is_read,
+                    read_at = j.previous_read_at
+                FROM read_operation_entries j
+                WHERE j.operation_id = ?1
+                  AND j.entry_id = {table}.id
+                "#
+            ))
+            .bind(operation.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("DELETE FROM read_operation_entries WHERE operation_id = ?1")
+            .bind(operation.id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM read_operations WHERE id = ?1")
+            .bind(operation.id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(Some(operation))
+    }
+
+    pub async fn set_entry_starred(
+        &self,
+        entry_id: i64,
+        is_starred: bool,
+    ) -> Result<u64, StorageError> {
+        let mut affected = 0_u64;
+        for table in ["entries", "entries_archive"] {
+            affected += sqlx::query(&format!("UPDATE {table} SET is_starred = ?1 WHERE id = ?2"))
+                .bind(i64::from(is_starred))
+                .bind(entry_id)
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+        }
+        Ok(affected)
+    }
+
+    pub async fn list_starred_entries(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<EntryRecord>, StorageError> {
+        let rows = sqlx::query_as::<_, EntryRecord>(
+            r#"
+            WITH all_entries AS (
+              SELECT id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries
+              WHERE is_starred = 1
+              UNION ALL
+              SELECT id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries_archive
+              WHERE is_starred = 1
+            )
+            SELECT
+              e.id,
+              e.source_id,
+              s.title AS source_title,
+              e.guid,
+              e.link,
+              e.title,
+              e.translated_title,
+              e.summary,
+              e.content,
+              e.published_at,
+              e.is_read,
+              e.is_starred,
+              e.created_at,
+              e.updated_at,
+              e.is_updated
+            FROM all_entries e
+            JOIN sources s ON s.id = e.source_id
+            ORDER BY COALESCE(e.published_at, e.created_at) DESC, e.id DESC
+            LIMIT ?1 OFFSET ?2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset.max(0))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn archive_entries_older_than(&self, days: i64) -> Result<u64, StorageError> {
+        let cutoff = format!("-{} days", days.max(1));
+        let protected = self.protected_entries_clause().await?;
+        let mut tx = self.begin_write().await?;
+        sqlx::query(&format!(
+            r#"
+            INSERT OR REPLACE INTO entries_archive (
+              id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, fingerprint, read_at, dedup_key, canonical_link, updated_at, is_updated
+            )
+            SELECT id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, fingerprint, read_at, dedup_key, canonical_link, updated_at, is_updated
+            FROM entries
+            WHERE is_read = 1
+              {protected}
+              AND datetime(COALESCE(published_at, created_at)) < datetime('now', ?1)
+            "#
+        ))
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await?;
+        let archived = sqlx::query(&format!(
+            r#"
+            DELETE FROM entries
+            WHERE is_read = 1
+              {protected}
+              AND datetime(COALESCE(published_at, created_at)) < datetime('now', ?1)
+            "#
+        ))
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        tx.commit().await?;
+        Ok(archived)
+    }
+
+    pub async fn storage_breakdown(&self) -> Result<Vec<SourceStorageUsage>, StorageError> {
+        let rows = sqlx::query_as::<_, SourceStorageUsage>(
+            r#"
+            WITH live AS (
+              SELECT
+                source_id,
+                COUNT(*) AS entry_count,
+                SUM(COALESCE(LENGTH(content), 0) + COALESCE(LENGTH(summary), 0) + LENGTH(title) + LENGTH(link)) AS content_bytes
+              FROM entries
+              GROUP BY source_id
+            ),
+            archived AS (
+              SELECT
+                source_id,
+                COUNT(*) AS archived_count,
+                SUM(COALESCE(LENGTH(content), 0) + COALESCE(LENGTH(summary), 0) + LENGTH(title) + LENGTH(link)) AS archived_content_bytes
+              FROM entries_archive
+              GROUP BY source_id
+            )
+            SELECT
+              s.id AS source_id,
+              s.title,
+              COALESCE(live.entry_count, 0) AS entry_count,
+              COALESCE(archived.archived_count, 0) AS archived_count,
+              COALESCE(live.content_bytes, 0) AS content_bytes,
+              COALESCE(archived.archived_content_bytes, 0) AS archived_content_bytes,
+              s.retention_days,
+              s.retention_max_entries
+            FROM sources s
+            LEFT JOIN live ON live.source_id = s.id
+            LEFT JOIN archived ON archived.source_id = s.id
+            ORDER BY COALESCE(live.content_bytes, 0) + COALESCE(archived.archived_content_bytes, 0) DESC, s.id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn get_source_settings(
+        &self,
+        source_id: i64,
+    ) -> Result<SourceSettings, StorageError> {
+        let row = sqlx::query_as::<_, SourceSettings>(
+            r#"
+            SELECT source_id, sync_interval_secs, timeout_secs, retry_count, translate_titles, open_externally, first_sync_max_age_days
+            FROM source_settings
+            WHERE source_id = ?1
+            "#,
+        )
+        .bind(source_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.unwrap_or(SourceSettings {
+            source_id,
+            ..SourceSettings::default()
+        }))
+    }
+
+    pub async fn set_source_settings(&self, settings: &SourceSettings) -> Result<(), StorageError> {
+        if settings.is_empty() {
+            sqlx::query("DELETE FROM source_settings WHERE source_id = ?1")
+                .bind(settings.source_id)
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO source_settings (source_id, sync_interval_secs, timeout_secs, retry_count, translate_titles, open_externally, first_sync_max_age_days)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(source_id) DO UPDATE SET
+              sync_interval_secs = excluded.sync_interval_secs,
+              timeout_secs = excluded.timeout_secs,
+              retry_count = excluded.retry_count,
+              translate_titles = excluded.translate_titles,
+              open_externally = excluded.open_externally,
+              first_sync_max_age_days = excluded.first_sync_max_age_days,
+              updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(settings.source_id)
+        .bind(settings.sync_interval_secs)
+        .bind(settings.timeout_secs)
+        .bind(settings.retry_count)
+        .bind(settings.translate_titles)
+        .bind(settings.open_externally)
+        .bind(settings.first_sync_max_age_days)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_source_settings(&self) -> Result<Vec<SourceSettings>, StorageError> {
+        let rows = sqlx::query_as::<_, SourceSettings>(
+            r#"
+            SELECT source_id, sync_interval_secs, timeout_secs, retry_count, translate_titles, open_externally, first_sync_max_age_days
+            FROM source_settings
+            ORDER BY source_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn set_source_retention(
+        &self,
+        source_id: i64,
+        retention_days: Option<i64>,
+        retention_max_entries: Option<i64>,
+    ) -> Result<u64, StorageError> {
+        let affected = sqlx::query(
+            r#"
+            UPDATE sources
+            SET retention_days = ?1, retention_max_entries = ?2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?3
+            "#,
+        )
+        .bind(retention_days)
+        .bind(retention_max_entries)
+        .bind(source_id)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+        Ok(affected)
+    }
+
+    pub async fn apply_source_retention(&self) -> Result<u64, StorageError> {
+        let protected = self.protected_entries_clause().await?;
+        let archive_condition = format!(
+            r#"
+            is_read = 1
+              {protected}
+              AND (
+                datetime(COALESCE(published_at, created_at)) < datetime(
+                  'now',
+                  '-' || (SELECT retention_days FROM sources s WHERE s.id = entries_archive.source_id) || ' days'
+                )
+                OR source_id IN (SELECT id FROM sources WHERE retention_max_entries IS NOT NULL)
+              )
+            "#
+        );
+        let condition = format!(
+            r#"
+            is_read = 1
+              {protected}
+              AND (
+                datetime(COALESCE(published_at, created_at)) < datetime(
+                  'now',
+                  '-' || (SELECT retention_days FROM sources s WHERE s.id = entries.source_id) || ' days'
+                )
+                OR id IN (
+                  SELECT id
+                  FROM (
+                    SELECT
+                      e.id,
+                      s.retention_max_entries,
+                      ROW_NUMBER() OVER (
+                        PARTITION BY e.source_id
+                        ORDER BY COALESCE(e.published_at, e.created_at) DESC, e.id DESC
+                      ) AS position
+                    FROM entries e
+                    JOIN sources s ON s.id = e.source_id
+                    WHERE s.retention_max_entries IS NOT NULL
+                  )
+                  WHERE position > retention_max_entries
+                )
+              )
+            "#
+        );
+        let mut tx = self.begin_write().await?;
+        let mut pruned = 0;
+        for (table, condition) in [
+            ("entries_archive", &archive_condition),
+            ("entries", &condition),
+        ] {
+            sqlx::query(&format!(
+                r#"
+                INSERT OR IGNORE INTO entry_tombstones (source_id, dedup_key, fingerprint)
+                SELECT source_id, dedup_key, fingerprint
+                FROM {table}
+                WHERE dedup_key IS NOT NULL AND {condition}
+                "#
+            ))
+            .execute(&mut *tx)
+            .await?;
+            for dependent in [
+                "entry_fulltext",
+                "read_operation_entries",
+                "pending_notifications",
+            ] {
+                sqlx::query(&format!(
+                    "DELETE FROM {dependent} WHERE entry_id IN (SELECT id FROM {table} WHERE {condition})"
+                ))
+                .execute(&mut *tx)
+                .await?;
+            }
+            pruned += sqlx::query(&format!("DELETE FROM {table} WHERE {condition}"))
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(pruned)
+    }
+
+    pub async fn list_entries_without_translated_title(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<EntryTitleRecord>, StorageError> {
+        let rows = sqlx::query_as::<_, EntryTitleRecord>(
+            r#"
+            SELECT e.id, e.title
+            FROM entries e
+            LEFT JOIN source_settings ss ON ss.source_id = e.source_id
+            WHERE (e.translated_title IS NULL OR TRIM(e.translated_title) = '')
+              AND COALESCE(ss.translate_titles, 1) = 1
+            ORDER BY COALESCE(
+              datetime(e.published_at),
+              datetime(e.created_at),
+              e.published_at,
+              e.created_at
+            ) DESC, e.id DESC
+            LIMIT ?1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn count_entries_without_translated_title(&self) -> Result<i64, StorageError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM entries e
+            LEFT JOIN source_settings ss ON ss.source_id = e.source_id
+            WHERE (e.translated_title IS NULL OR TRIM(e.translated_title) = '')
+              AND COALESCE(ss.translate_titles, 1) = 1
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    pub async fn list_source_entries_without_translated_title(
+        &self,
+        source_id: i64,
+        before_id: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<EntryTitleRecord>, StorageError> {
+        let rows = sqlx::query_as::<_, EntryTitleRecord>(
+            r#"
+            SELECT id, title FROM (
+              SELECT id, title, translated_title FROM entries WHERE source_id = ?1
+              UNION ALL
+              SELECT id, title, translated_title FROM entries_archive WHERE source_id = ?1
+            )
+            WHERE (translated_title IS NULL OR TRIM(translated_title) = '')
+              AND TRIM(title) <> ''
+              AND (?2 IS NULL OR id < ?2)
+            ORDER BY id DESC
+            LIMIT ?3
+            "#,
+        )
+        .bind(source_id)
+        .bind(before_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn count_source_entries_without_translated_title(
+        &self,
+        source_id: i64,
+    ) -> Result<i64, StorageError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM (
+              SELECT title, translated_title FROM entries WHERE source_id = ?1
+              UNION ALL
+              SELECT title, translated_title FROM entries_archive WHERE source_id = ?1
+            )
+            WHERE (translated_title IS NULL OR TRIM(translated_title) = '')
+              AND TRIM(title) <> ''
+            "#,
+        )
+        .bind(source_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    pub async fn set_entry_translated_title(
+        &self,
+        entry_id: i64,
+        translated_title: &str,
+    ) -> Result<(), StorageError> {
+        for table in ["entries", "entries_archive"] {
+            sqlx::query(&format!(
+                "UPDATE {table} SET translated_title = ?1 WHERE id = ?2"
+            ))
+            .bind(translated_title)
+            .bind(entry_id)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_entry_by_id(
+        &self,
+        entry_id: i64,
+    ) -> Result<Option<EntryRecord>, StorageError> {
+        let row = sqlx::query_as::<_, EntryRecord>(
+            r#"
+            SELECT
+              e.id,
+              e.source_id,
+              s.title AS source_title,
+              e.guid,
+              e.link,
+              e.title,
+              e.translated_title,
+              e.summary,
+              e.content,
+              e.published_at,
+              e.is_read,
+              e.is_starred,
+              e.created_at,
+              e.updated_at,
+              e.is_updated
+            FROM (
+              SELECT id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries
+              WHERE id = ?1
+              UNION ALL
+              SELECT id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred, created_at, updated_at, is_updated
+              FROM entries_archive
+              WHERE id = ?1
+            ) e
+            JOIN sources s ON s.id = e.source_id
+            LIMIT 1
+            "#,
+        )
+        .bind(entry_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    pub async fn get_setting(&self, key: &str) -> Result<Option<String>, StorageError> {
+        read_setting(&self.pool, key).await
+    }
+
+    pub async fn set_setting(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        write_setting(&self.pool, key, value).await
+    }
+
+    pub async fn sanitizer_allowlist(&self) -> Result<SanitizerAllowlist, StorageError> {
+        load_sanitizer_allowlist(&self.pool).await
+    }
+
+    pub async fn set_sanitizer_allowlist(
+        &self,
+        allowlist: &SanitizerAllowlist,
+    ) -> Result<(), StorageError> {
+        let serialized = serde_json::to_string(allowlist).unwrap_or_else(|_| "{}".to_string());
+        self.set_setting(SANITIZER_ALLOWLIST_KEY, &serialized).await
+    }
+
+    pub async fn sanitize_stored_entries(&self) -> Result<usize, StorageError> {
+        let allowlist = self.sanitizer_allowlist().await?;
+        let sanitized = sanitize_stored_content(&self.pool, &allowlist).await?;
+        let serialized = serde_json::to_string(&allowlist).unwrap_or_else(|_| "{}".to_string());
+        self.set_setting(SANITIZED_ALLOWLIST_KEY, &serialized)
+            .await?;
+        Ok(sanitized)
+    }
+
+    pub async fn backfill_sanitized_content(&self) -> Result<usize, StorageError> {
+        let allowlist = self.sanitizer_allowlist().await?;
+        let serialized = serde_json::to_string(&allowlist).unwrap_or_else(|_| "{}".to_string());
+        if self.get_setting(SANITIZED_ALLOWLIST_KEY).await?.as_deref() == Some(serialized.as_str())
+        {
+            return Ok(0);
+        }
+        self.sanitize_stored_entries().await
+    }
+
+    pub async fn search_pinyin(&self) -> Result<bool, StorageError> {
+        Ok(self
+            .get_setting(SEARCH_PINYIN_KEY)
+            .await?
+            .is_some_and(|value| value == "true"))
+    }
+
+    pub async fn set_search_pinyin(&self, enabled: bool) -> Result<(), StorageError> {
+        self.set_setting(SEARCH_PINYIN_KEY, &enabled.to_string())
+            .await
+    }
+
+    async fn entry_search_match(
+        &self,
+        query: &SearchQuery,
+    ) -> Result<Option<String>, StorageError> {
+        if query.terms.is_empty() {
+            return Ok(None);
+        }
+        if !self.read_only {
+            index_pending_entries(&self.pool).await?;
+        }
+        Ok(fts_match_expression(
+            &query.terms,
+            self.search_pinyin().await?,
+        ))
+    }
+
+    pub async fn flagged_entry_protection(&self) -> Result<bool, StorageError> {
+        Ok(self
+            .get_setting(PROTECT_FLAGGED_ENTRIES_KEY)
+            .await?
+            .is_some_and(|value| value == "true"))
+    }
+
+    pub async fn set_flagged_entry_protection(&self, enabled: bool) -> Result<(), StorageError> {
+        self.set_setting(PROTECT_FLAGGED_ENTRIES_KEY, &enabled.to_string())
+            .await
+    }
+
+    pub async fn first_sync_max_age_days(&self) -> Result<i64, StorageError> {
+        Ok(self
+            .get_setting(FIRST_SYNC_MAX_AGE_DAYS_KEY)
+            .await?
+            .and_then(|value| value.parse::<i64>().ok())
+            .unwrap_or(0))
+    }
+
+    pub async fn set_first_sync_max_age_days(&self, days: i64) -> Result<(), StorageError> {
+        self.set_setting(FIRST_SYNC_MAX_AGE_DAYS_KEY, &days.to_string())
+            .await
+    }
+
+    async fn protected_entries_clause(&self) -> Result<&'static str, StorageError> {
+        if self.flagged_entry_protection().await? {
+            return Ok("AND is_starred = 0");
+        }
+        Ok("")
+    }
+
+    pub async fn get_llm_cache(
+        &self,
+        task_type: &str,
+        model: &str,
+        input_hash: &str,
+    ) -> Result<Option<String>, StorageError> {
+        let value = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT output_text
+            FROM llm_cache
+            WHERE task_type = ?1
+              AND model = ?2
+              AND input_hash = ?3
+            "#,
+        )
+        .bind(task_type)
+        .bind(model)
+        .bind(input_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(value)
+    }
+
+    pub async fn set_llm_cache(
+        &self,
+        task_type: &str,
+        model: &str,
+        input_hash: &str,
+        output_text: &str,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            INSERT INTO llm_cache (task_type, model, input_hash, output_text, key_version)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(task_type, model, input_hash) DO UPDATE SET
+              output_text = excluded.output_text,
+              key_version = excluded.key_version,
+              created_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(task_type)
+        .bind(model)
+        .bind(input_hash)
+        .bind(output_text)
+        .bind(LLM_CACHE_KEY_VERSION)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn rekey_legacy_llm_cache(
+        &self,
+        task_type: &str,
+        model: &str,
+        legacy_hash: &str,
+        input_hash: &str,
+    ) -> Result<Option<String>, StorageError> {
+        let value = sqlx::query_scalar::<_, String>(
+            r#"
+            UPDATE OR IGNORE llm_cache
+            SET input_hash = ?4,
+                key_version = ?5
+            WHERE task_type = ?1
+              AND model = ?2
+              AND input_hash = ?3
+              AND key_version <> ?5
+            RETURNING output_text
+            "#,
+        )
+        .bind(task_type)
+        .bind(model)
+        .bind(legacy_hash)
+        .bind(input_hash)
+        .bind(LLM_CACHE_KEY_VERSION)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(value)
+    }
+
+    pub async fn list_llm_cache_records(&self) -> Result<Vec<LlmCacheRecord>, StorageError> {
+        let records = sqlx::query_as::<_, LlmCacheRecord>(
+            r#"
+            SELECT task_type, model, input_hash, output_text, created_at, key_version
+            FROM llm_cache
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(records)
+    }
+
+    pub async fn import_llm_cache_records(
+        &self,
+        records: &[LlmCacheRecord],
+        overwrite: bool,
+    ) -> Result<LlmCacheImport, StorageError> {
+        let conflict = if overwrite {
+            "DO UPDATE SET output_text = excluded.output_text, created_at = excluded.created_at, key_version = excluded.key_version"
+        } else {
+            "DO NOTHING"
+        };
+        let statement = format!(
+            r#"
+            INSERT INTO llm_cache (task_type, model, input_hash, output_text, created_at, key_version)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(task_type, model, input_hash) {conflict}
+            "#
+        );
+        let mut report = LlmCacheImport::default();
+        let mut tx = self.pool.begin().await?;
+        for record in records {
+            if record.task_type.is_empty()
+                || record.model.is_empty()
+                || record.input_hash.is_empty()
+            {
+                report.skipped += 1;
+                continue;
+            }
+            let affected = sqlx::query(&statement)
+                .bind(&record.task_type)
+                .bind(&record.model)
+                .bind(&record.input_hash)
+                .bind(&record.output_text)
+                .bind(&record.created_at)
+                .bind(record.key_version)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            if affected > 0 {
+                report.imported += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+        tx.commit().await?;
+        Ok(report)
+    }
+
+    pub async fn database_stats(&self) -> Result<DatabaseStats, StorageError> {
+        let stats = sqlx::query_as::<_, DatabaseStats>(
+            r#"
+            SELECT
+              (SELECT COUNT(*) FROM sources) AS source_count,
+              (SELECT COUNT(*) FROM sources WHERE is_active = 1) AS active_source_count,
+              (SELECT COUNT(*) FROM sources WHERE failure_count > 0) AS failing_source_count,
+              (SELECT COUNT(*) FROM entries) AS entry_count,
+              (SELECT COUNT(*) FROM entries WHERE is_read = 0) AS unread_entry_count,
+              (SELECT COUNT(*) FROM entries_archive) AS archived_entry_count,
+              (SELECT COUNT(*) FROM llm_cache) AS llm_cache_count,
+              (SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1) AS schema_version
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(stats)
+    }
+
+    pub async fn compress_legacy_content(&self, batch_size: i64) -> Result<usize, StorageError> {
+        let mut compressed = 0_usize;
+        for table in ["entries", "entries_archive"] {
+            let mut last_id = 0_i64;
+            loop {
+                let rows = sqlx::query_as::<_, (i64, String)>(&format!(
+                    r#"
+                    SELECT id, content
+                    FROM {table}
+                    WHERE typeof(content) = 'text'
+                      AND length(CAST(content AS BLOB)) >= ?1
+                      AND id > ?2
+                    ORDER BY id
+                    LIMIT ?3
+                    "#
+                ))
+                .bind(COMPRESSION_THRESHOLD_BYTES as i64)
+                .bind(last_id)
+                .bind(batch_size.max(1))
+                .fetch_all(&self.pool)
+                .await?;
+                let Some((max_id, _)) = rows.last() else {
+                    break;
+                };
+                last_id = *max_id;
+                let mut tx = self.begin_write().await?;
+                for (id, content) in &rows {
+                    let Some(packed) = compress_text(content) else {
+                        continue;
+                    };
+                    sqlx::query(&format!("UPDATE {table} SET content = ?1 WHERE id = ?2"))
+                        .bind(packed)
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+                    compressed += 1;
+                }
+                tx.commit().await?;
+            }
+        }
+        Ok(compressed)
+    }
+
+    pub async fn vacuum(&self) -> Result<(), StorageError> {
+        sqlx::query("VACUUM").execute(&self.pool).await?;
+        Ok(())
+    }
+}
+
+fn is_in_memory(database_url: &str) -> bool {
+    database_url.contains(":memory:") || database_url.contains("mode=memory")
+}
+
+fn latest_known_version() -> i64 {
+    MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or_default()
+}
+
+async fn migrations_table_exists(pool: &SqlitePool) -> Result<bool, StorageError> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(count > 0)
+}
+
+async fn integrity_error(pool: &SqlitePool) -> Option<StorageError> {
+    match sqlx::query_scalar::<_, String>("PRAGMA quick_check(1)")
+        .fetch_one(pool)
+        .await
+    {
+        Ok(result) if result == "ok" => None,
+        Ok(result) => Some(StorageError::Corrupt(result)),
+        Err(error) => sqlite_fault(&error).map(|_| StorageError::Database(error)),
+    }
+}
+
+async fn prepare_pool(pool: &SqlitePool) -> Result<(), StorageError> {
+    let database_version = applied_schema_version(pool).await?;
+    let supported_version = latest_known_version();
+    if let Some(database_version) = database_version.filter(|version| *version > supported_version)
+    {
+        return Err(StorageError::SchemaTooNew {
+            database_version,
+            supported_version,
+        });
+    }
+    MIGRATOR.run(pool).await?;
+    backfill_canonical_links(pool).await?;
+    index_pending_entries(pool).await.map(|_| ())
+}
+
+async fn index_pending_entries(pool: &SqlitePool) -> Result<usize, StorageError> {
+    let mut indexed = 0_usize;
+    loop {
+        let rows = sqlx::query_as::<_, (i64, Option<String>, Option<String>, Option<String>)>(
+            r#"
+            SELECT
+              q.entry_id,
+              COALESCE(e.title, a.title),
+              COALESCE(e.translated_title, a.translated_title),
+              COALESCE(e.summary, a.summary)
+            FROM entry_search_queue q
+            LEFT JOIN entries e ON e.id = q.entry_id
+            LEFT JOIN entries_archive a ON a.id = q.entry_id
+            ORDER BY q.entry_id
+            LIMIT ?1
+            "#,
+        )
+        .bind(SEARCH_INDEX_BATCH)
+        .fetch_all(pool)
+        .await?;
+        if rows.is_empty() {
+            return Ok(indexed);
+        }
+        let mut tx = pool.begin().await?;
+        for (entry_id, title, translated_title, summary) in rows {
+            sqlx::query("DELETE FROM entry_search WHERE rowid = ?1")
+                .bind(entry_id)
+                .execute(&mut *tx)
+                .await?;
+            if let Some(title) = title {
+                let titles = [Some(title), translated_title]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let body = summary.as_deref().map(plain_text).unwrap_or_default();
+                sqlx::query(
+                    "INSERT INTO entry_search (rowid, title, body, pinyin) VALUES (?1, ?2, ?3, ?4)",
+                )
+                .bind(entry_id)
+                .bind(index_tokens(&titles))
+                .bind(index_tokens(&body))
+                .bind(pinyin_tokens(&titles))
+                .execute(&mut *tx)
+                .await?;
+                indexed += 1;
+            }
+            sqlx::query("DELETE FROM entry_search_queue WHERE entry_id = ?1")
+                .bind(entry_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+    }
+}
+
+async fn read_setting(pool: &SqlitePool, key: &str) -> Result<Option<String>, StorageError> {
+    let value = sqlx::query_scalar::<_, String>("SELECT value FROM app_settings WHERE key = ?1")
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+    Ok(value)
+}
+
+async fn write_setting(pool: &SqlitePool, key: &str, value: &str) -> Result<(), StorageError> {
+    sqlx::query(
+        r#"
+        INSERT INTO app_settings (key, value)
+        VALUES (?1, ?2)
+        ON CONFLICT(key) DO UPDATE SET
+          value = excluded.value,
+          updated_at = CURRENT_TIMESTAMP
+        "#,
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn load_sanitizer_allowlist(pool: &SqlitePool) -> Result<SanitizerAllowlist, StorageError> {
+    Ok(read_setting(pool, SANITIZER_ALLOWLIST_KEY)
+        .await?
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default())
+}
+
+async fn sanitize_stored_content(
+    pool: &SqlitePool,
+    allowlist: &SanitizerAllowlist,
+) -> Result<usize, StorageError> {
+    let mut sanitized = 0_usize;
+    for table in ["entries", "entries_archive"] {
+        let mut last_id = 0_i64;
+        loop {
+            let rows = sqlx::query_as::<_, (i64, Option<String>, Option<Vec<u8>>)>(&format!(
+                r#"
+                SELECT id, summary, content
+                FROM {table}
+                WHERE id > ?1 AND (summary IS NOT NULL OR content IS NOT NULL)
+                ORDER BY id
+                LIMIT ?2
+                "#
+            ))
+            .bind(last_id)
+            .bind(SANITIZE_BACKFILL_BATCH)
+            .fetch_all(pool)
+            .await?;
+            let Some((max_id, _, _)) = rows.last() else {
+                break;
+            };
+            last_id = *max_id;
+            let updates = {
+                let sanitizer = allowlist.sanitizer();
+                rows.into_iter()
+                    .filter_map(|(id, summary, content)| {
+                        let entry = ParsedEntry {
+                            id: String::new(),
+                            title: String::new(),
+                            link: String::new(),
+                            summary,
+                            content: content.map(|raw| decompress_text(&raw)),
+                            published_at: None,
+                        };
+                        let clean = sanitize_entry(&entry, &sanitizer);
+                        (clean.summary != entry.summary || clean.content != entry.content)
+                            .then_some((id, clean.summary, clean.content))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            if updates.is_empty() {
+                continue;
+            }
+            let mut tx = pool.begin().await?;
+            for (id, summary, content) in &updates {
+                sqlx::query(&format!(
+                    "UPDATE {table} SET summary = ?1, content = COALESCE(?2, ?3) WHERE id = ?4"
+                ))
+                .bind(summary)
+                .bind(content.as_deref().and_then(compress_text))
+                .bind(content)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            sanitized += updates.len();
+        }
+    }
+    Ok(sanitized)
+}
+
+async fn backfill_canonical_links(pool: &SqlitePool) -> Result<(), StorageError> {
+    for (table, feed_url) in [
+        ("entries", "COALESCE(e.orphaned_from_feed_url, s.feed_url)"),
+        ("entries_archive", "s.feed_url"),
+    ] {
+        loop {
+            let rows = sqlx::query_as::<
+                _,
+                (i64, Option<String>, String, String, Option<String>, String),
+            >(&format!(
+                r#"
+                SELECT e.id, e.guid, e.link, e.title, e.published_at, {feed_url}
+                FROM {table} e
+                JOIN sources s ON s.id = e.source_id
+                WHERE e.canonical_link IS NULL
+                LIMIT ?1
+                "#
+            ))
+            .bind(CANONICAL_LINK_BACKFILL_BATCH)
+            .fetch_all(pool)
+            .await?;
+            if rows.is_empty() {
+                break;
+            }
+            let mut tx = pool.begin().await?;
+            for (id, guid, link, title, published_at, feed_url) in rows {
+                let entry = ParsedEntry {
+                    id: guid.unwrap_or_default(),
+                    title,
+                    link,
+                    summary: None,
+                    content: None,
+                    published_at,
+                };
+                let canonical_link = canonicalize_link(&entry.link);
+                let rekeyed = sqlx::query(&format!(
+                    "UPDATE OR IGNORE {table} SET canonical_link = ?1, dedup_key = ?2 WHERE id = ?3"
+                ))
+                .bind(&canonical_link)
+                .bind(build_dedup_key(&feed_url, &entry))
+                .bind(id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+                if rekeyed == 0 {
+                    sqlx::query(&format!(
+                        "UPDATE {table} SET canonical_link = ?1 WHERE id = ?2"
+                    ))
+                    .bind(&canonical_link)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+            tx.commit().await?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_llm_profile_config(name: &str, config: &str) -> Result<LlmConfig, StorageError> {
+    serde_json::from_str(config).map_err(|source| StorageError::InvalidLlmProfile {
+        name: name.to_string(),
+        source,
+    })
+}
+
+fn sqlite_fault(error: &sqlx::Error) -> Option<DatabaseFault> {
+    let code = error.as_database_error()?.code()?.parse::<i32>().ok()? & 0xff;
+    match code {
+        SQLITE_CORRUPT | SQLITE_NOTADB => Some(DatabaseFault::Corrupt),
+        SQLITE_BUSY | SQLITE_LOCKED => Some(DatabaseFault::Locked),
+        _ => None,
+    }
+}
+
+async fn applied_schema_version(pool: &SqlitePool) -> Result<Option<i64>, StorageError> {
+    if !migrations_table_exists(pool).await? {
+        return Ok(None);
+    }
+    let version = sqlx::query_scalar::<_, Option<i64>>("SELECT MAX(version) FROM _sqlx_migrations")
+        .fetch_one(pool)
+        .await?;
+    Ok(version)
+}
+
+struct ReadUpToScope<'a> {
+    table: &'a str,
+    protected: &'a str,
+    anchor: &'a str,
+    last_seen_entry_id: i64,
+    source_id: Option<i64>,
+    query: &'a SearchQuery,
+    search_match: Option<&'a str>,
+}
+
+impl ReadUpToScope<'_> {
+    fn push(&self, builder: &mut QueryBuilder<'_, Sqlite>) {
+        builder.push(format!(
+            r#"
+            WHERE is_read = 0
+              {protected}
+              AND id IN (
+                SELECT e.id
+                FROM {table} e
+                JOIN sources s ON s.id = e.source_id
+                WHERE (COALESCE(e.published_at, e.created_at) > "#,
+            protected = self.protected,
+            table = self.table,
+        ));
+        builder.push_bind(self.anchor.to_string());
+        builder.push(" OR (COALESCE(e.published_at, e.created_at) = ");
+        builder.push_bind(self.anchor.to_string());
+        builder.push(" AND e.id >= ");
+        builder.push_bind(self.last_seen_entry_id);
+        builder.push("))");
+        push_entry_filters(builder, self.source_id, self.query, self.search_match);
+        builder.push(")");
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ReadScope<'a> {
+    Source(i64),
+    Category(&'a str),
+    OlderThanDays(i64),
+}
+
+impl ReadScope<'_> {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Source(_) => "mark_source_read",
+            Self::Category(_) => "mark_category_read",
+            Self::OlderThanDays(_) => "mark_read_older_than",
+        }
+    }
+
+    fn push(&self, builder: &mut QueryBuilder<'_, Sqlite>, protected: &str) {
+        builder.push(format!(" WHERE is_read = 0 {protected}"));
+        match *self {
+            Self::Source(source_id) => {
+                builder.push(" AND source_id = ");
+                builder.push_bind(source_id);
+            }
+            Self::Category(category) => {
+                builder.push(
+                    " AND source_id IN (SELECT id FROM sources WHERE TRIM(IFNULL(category, '')) = ",
+                );
+                builder.push_bind(category.trim().to_string());
+                builder.push(")");
+            }
+            Self::OlderThanDays(days) => {
+                builder
+                    .push(" AND datetime(COALESCE(published_at, created_at)) < datetime('now', ");
+                builder.push_bind(format!("-{} days", days.max(0)));
+                builder.push(")");
+            }
+        }
+    }
+}
+
+async fn finish_read_operation(
+    tx: &mut sqlx::Transaction<'_, Sqlite>,
+    operation_id: i64,
+    affected: u64,
+) -> Result<(), StorageError> {
+    if affected == 0 {
+        sqlx::query("DELETE FROM read_operations WHERE id = ?1")
+            .bind(operation_id)
+            .execute(&mut **tx)
+            .await?;
+        return Ok(());
+    }
+    sqlx::query("UPDATE read_operations SET entry_count = ?1 WHERE id = ?2")
+        .bind(affected as i64)
+        .bind(operation_id)
+        .execute(&mut **tx)
+        .await?;
+    prune_read_operations(tx).await
+}
+
+async fn prune_read_operations(tx: &mut sqlx::Transaction<'_, Sqlite>) -> Result<(), StorageError> {
+    sqlx::query(
+        r#"
+        DELETE FROM read_operations
+        WHERE id NOT IN (SELECT id FROM read_operations ORDER BY id DESC LIMIT ?1)
+        "#,
+    )
+    .bind(READ_OPERATION_HISTORY_LIMIT)
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query(
+        r#"
+        DELETE FROM read_operation_entries
+        WHERE operation_id NOT IN (SELECT id FROM read_operations)
+        "#,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+fn push_entry_filters(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    source_id: Option<i64>,
+    query: &SearchQuery,
+    search_match: Option<&str>,
+) {
+    if let Some(source_id) = source_id {
+        builder.push(" AND e.source_id = ");
+        builder.push_bind(source_id);
+    }
+    if let Some(search_match) = search_match {
+        builder.push(" AND e.id IN (SELECT rowid FROM entry_search WHERE entry_search MATCH ");
+        builder.push_bind(search_match.to_string());
+        builder.push(")");
+    }
+    for source in &query.sources {
+        let pattern = format!("%{source}%");
+        builder.push(" AND (s.title LIKE ");
+        builder.push_bind(pattern.clone());
+        builder.push(" OR s.feed_url LIKE ");
+        builder.push_bind(pattern);
+        builder.push(")");
+    }
+    for tag in &query.tags {
+        builder.push(" AND IFNULL(s.category, '') = ");
+        builder.push_bind(tag.clone());
+        builder.push(" COLLATE NOCASE");
+    }
+    if let Some(is_read) = query.is_read {
+        builder.push(" AND e.is_read = ");
+        builder.push_bind(i64::from(is_read));
+    }
+    if let Some(is_starred) = query.is_starred {
+        builder.push(" AND e.is_starred = ");
+        builder.push_bind(i64::from(is_starred));
+    }
+    if let Some(before) = query.before {
+        builder.push(" AND datetime(COALESCE(e.published_at, e.created_at)) < datetime(");
+        builder.push_bind(before.to_string());
+        builder.push(")");
+    }
+    if let Some(after) = query.after {
+        builder.push(" AND datetime(COALESCE(e.published_at, e.created_at)) >= datetime(");
+        builder.push_bind(after.to_string());
+        builder.push(")");
+    }
+}
+
+fn published_before(entry: &ParsedEntry, cutoff: chrono::DateTime<chrono::Utc>) -> Option<bool> {
+    let published = entry.published_at.as_deref()?.trim();
+    let published = chrono::DateTime::parse_from_rfc3339(published)
+        .or_else(|_| chrono::DateTime::parse_from_rfc2822(published))
+        .ok()?;
+    Some(published < cutoff)
+}
+
+struct PendingEntry<'a> {
+    entry: &'a ParsedEntry,
+    fingerprint: String,
+    dedup_key: String,
+    canonical_link: String,
+}
+
+fn push_id_list(query: &mut QueryBuilder<'_, Sqlite>, ids: &[i64]) {
+    query.push("(");
+    let mut separated = query.separated(", ");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    separated.push_unseparated(")");
+}
+
+async fn preserve_starred_entries(
+    tx: &mut sqlx::Transaction<'_, Sqlite>,
+    source_ids: &[i64],
+) -> Result<u64, StorageError> {
+    let mut starred = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM (");
+    push_starred_keys(&mut starred, source_ids);
+    starred
+        .push(") WHERE source_id NOT IN (SELECT id FROM sources WHERE feed_url = ")
+        .push_bind(ORPHANED_SOURCE_FEED_URL)
+        .push(")");
+    if starred
+        .build_query_scalar::<i64>()
+        .fetch_one(&mut **tx)
+        .await?
+        == 0
+    {
+        return Ok(0);
+    }
+
+    sqlx::query("INSERT OR IGNORE INTO sources (title, feed_url, is_active) VALUES (?1, ?2, 0)")
+        .bind(ORPHANED_SOURCE_TITLE)
+        .bind(ORPHANED_SOURCE_FEED_URL)
+        .execute(&mut **tx)
+        .await?;
+    let orphanage = sqlx::query_scalar::<_, i64>("SELECT id FROM sources WHERE feed_url = ?1")
+        .bind(ORPHANED_SOURCE_FEED_URL)
+        .fetch_one(&mut **tx)
+        .await?;
+
+    let mut superseded = QueryBuilder::<Sqlite>::new("DELETE FROM entries WHERE source_id = ");
+    superseded
+        .push_bind(orphanage)
+        .push(" AND EXISTS (SELECT 1 FROM (");
+    push_starred_keys(&mut superseded, source_ids);
+    superseded.push(
+        r#") starred
+        JOIN sources s ON s.id = starred.source_id
+        WHERE entries.orphaned_from_feed_url = s.feed_url
+          AND (entries.guid = s.feed_url || '::' || starred.guid
+            OR entries.dedup_key = s.feed_url || '::' || starred.dedup_key))"#,
+    );
+    superseded.build().execute(&mut **tx).await?;
+
+    let mut query = QueryBuilder::<Sqlite>::new("UPDATE entries SET source_id = ");
+    query
+        .push_bind(orphanage)
+        .push(
+            r#",
+            orphaned_from_feed_url = (SELECT feed_url FROM sources WHERE sources.id = entries.source_id),
+            guid = CASE
+              WHEN TRIM(COALESCE(guid, '')) IN ('', 'unknown') THEN guid
+              ELSE (SELECT feed_url FROM sources WHERE sources.id = entries.source_id) || '::' || guid
+            END,
+            dedup_key = (SELECT feed_url FROM sources WHERE sources.id = entries.source_id) || '::' || dedup_key"#,
+        )
+        .push(" WHERE is_starred = 1 AND source_id <> ")
+        .push_bind(orphanage)
+        .push(" AND source_id IN ");
+    push_id_list(&mut query, source_ids);
+    let mut preserved = query.build().execute(&mut **tx).await?.rows_affected();
+
+    let mut archived = QueryBuilder::<Sqlite>::new(
+        r#"
+        INSERT INTO entries (
+          id, source_id, guid, link, title, translated_title, summary, content, published_at, is_read, is_starred,
+          created_at, fingerprint, read_at, dedup_key, canonical_link, updated_at, is_updated, orphaned_from_feed_url
+        )
+        SELECT
+          a.id, "#,
+    );
+    archived
+        .push_bind(orphanage)
+        .push(
+            r#",
+          CASE WHEN TRIM(COALESCE(a.guid, '')) IN ('', 'unknown') THEN a.guid ELSE s.feed_url || '::' || a.guid END,
+          a.link, a.title, a.translated_title, a.summary, a.content, a.published_at, a.is_read, a.is_starred,
+          a.created_at, a.fingerprint, a.read_at, s.feed_url || '::' || a.dedup_key, a.canonical_link, a.updated_at,
+          a.is_updated, s.feed_url
+        FROM entries_archive a
+        JOIN sources s ON s.id = a.source_id
+        WHERE a.is_starred = 1 AND a.source_id <> "#,
+        )
+        .push_bind(orphanage)
+        .push(" AND a.source_id IN ");
+    push_id_list(&mut archived, source_ids);
+    preserved += archived.build().execute(&mut **tx).await?.rows_affected();
+
+    let mut restored = QueryBuilder::<Sqlite>::new(
+        "DELETE FROM entries_archive WHERE is_starred = 1 AND id IN (SELECT id FROM entries) AND source_id IN ",
+    );
+    push_id_list(&mut restored, source_ids);
+    restored.build().execute(&mut **tx).await?;
+    Ok(preserved)
+}
+
+fn push_starred_keys(builder: &mut QueryBuilder<'_, Sqlite>, source_ids: &[i64]) {
+    builder.push(
+        "SELECT source_id, guid, dedup_key FROM entries WHERE is_starred = 1 AND source_id IN ",
+    );
+    push_id_list(builder, source_ids);
+    builder.push(
+        " UNION ALL SELECT source_id, guid, dedup_key FROM entries_archive WHERE is_starred = 1 AND source_id IN ",
+    );
+    push_id_list(builder, source_ids);
+}
+
+async fn delete_by_source(
+    tx: &mut sqlx::Transaction<'_, Sqlite>,
+    table: &str,
+    column: &str,
+    source_ids: &[i64],
+) -> Result<u64, StorageError> {
+    let mut query = QueryBuilder::<Sqlite>::new(format!("DELETE FROM {table} WHERE {column} IN "));
+    push_id_list(&mut query, source_ids);
+    Ok(query.build().execute(&mut **tx).await?.rows_affected())
+}
+
+fn push_entry_page(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    cursor: Option<&EntryCursor>,
+    limit: i64,
+) {
+    if let Some(cursor) = cursor {
+        builder.push(" AND (COALESCE(e.published_at, e.created_at) < ");
+        builder.push_bind(cursor.before_published_at.clone());
+        builder.push(" OR (COALESCE(e.published_at, e.created_at) = ");
+        builder.push_bind(cursor.before_published_at.clone());
+        builder.push(" AND e.id < ");
+        builder.push_bind(cursor.before_id);
+        builder.push("))");
+    }
+    builder.push(" ORDER BY COALESCE(e.published_at, e.created_at) DESC, e.id DESC LIMIT ");
+    builder.push_bind(limit);
+}
+
+fn local_day_start() -> chrono::DateTime<chrono::Utc> {
+    let now = chrono::Local::now();
+    now.date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+        .unwrap_or(now)
+        .with_timezone(&chrono::Utc)
+}
+
+fn last_by_key<'a, T>(items: &'a [T], key: impl Fn(&T) -> &str) -> Vec<&'a T> {
+    let mut positions = HashMap::new();
+    let mut unique: Vec<&'a T> = Vec::with_capacity(items.len());
+    for item in items {
+        match positions.get(key(item)) {
+            Some(&position) => unique[position] = item,
+            None => {
+                positions.insert(key(item).to_string(), unique.len());
+                unique.push(item);
+            }
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::importer::{build_import_preview, parse_opml};
+    use sqlx::Row;
+    use std::collections::HashSet;
+
+    fn make_source(title: &str, feed_url: &str) -> NewSource {
+        NewSource {
+            title: title.to_string(),
+            site_url: Some("https://example.com".to_string()),
+            feed_url: feed_url.to_string(),
+            category: Some("tech".to_string()),
+            is_active: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn migration_creates_required_tables() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let rows = sqlx::query(
+            r#"
+            SELECT name
+            FROM sqlite_master
+            WHERE type = 'table'
+              AND name IN ('app_settings', 'sources', 'entries', 'llm_cache')
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&repository.pool)
+        .await
+        .expect("query must succeed");
+
+        let table_names: Vec<String> = rows
+            .into_iter()
+            .map(|row| row.get::<String, _>("name"))
+            .collect();
+        assert_eq!(
+            table_names,
+            vec![
+                "app_settings".to_string(),
+                "entries".to_string(),
+                "llm_cache".to_string(),
+                "sources".to_string()
+            ]
+        );
+
+        let columns = sqlx::query("PRAGMA table_info(sources)")
+            .fetch_all(&repository.pool)
+            .await
+            .expect("pragma should succeed");
+        let has_etag = columns
+            .iter()
+            .any(|row| row.get::<String, _>("name") == "etag");
+        let has_last_modified = columns
+            .iter()
+            .any(|row| row.get::<String, _>("name") == "last_modified");
+        let has_last_synced_at = columns
+            .iter()
+            .any(|row| row.get::<String, _>("name") == "last_synced_at");
+        assert!(has_etag && has_last_modified && has_last_synced_at);
+    }
+
+    #[tokio::test]
+    async fn migration_creates_query_indexes() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let index_names: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT name
+            FROM sqlite_master
+            WHERE type = 'index'
+              AND name LIKE 'idx_%'
+            "#,
+        )
+        .fetch_all(&repository.pool)
+        .await
+        .expect("query must succeed");
+
+        for expected in [
+            "idx_entries_link",
+            "idx_entries_published_at",
+            "idx_entries_source_read",
+            "idx_sources_active_synced",
+        ] {
+            assert!(index_names.iter().any(|name| name == expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_source_is_idempotent_for_same_feed_url() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let first = repository
+            .upsert_source(&make_source(
+                "Hacker News",
+                "https://news.ycombinator.com/rss",
+            ))
+            .await
+            .expect("first upsert must succeed");
+
+        let second = repository
+            .upsert_source(&make_source(
+                "HN Updated",
+                "https://news.ycombinator.com/rss",
+            ))
+            .await
+            .expect("second upsert must succeed");
+
+        let all = repository.list_sources().await.expect("list must succeed");
+
+        assert_eq!(all.len(), 1);
+        assert_eq!(first.id, second.id);
+        assert_eq!(all[0].title, "HN Updated");
+    }
+
+    #[tokio::test]
+    async fn delete_source_removes_row() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let created = repository
+            .upsert_source(&make_source(
+                "Rust Blog",
+                "https://blog.rust-lang.org/feed.xml",
+            ))
+            .await
+            .expect("create must succeed");
+
+        let affected = repository
+            .delete_source(created.id)
+            .await
+            .expect("delete must succeed");
+        let all = repository.list_sources().await.expect("list must succeed");
+
+        assert_eq!(affected.sources, 1);
+        assert!(all.is_empty());
+    }
+
+    #[tokio::test]
+    async fn delete_source_removes_entries_and_their_cached_state() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let doomed = repository
+            .upsert_source(&make_source(
+                "Doomed",
+                "https://doomed.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let kept = repository
+            .upsert_source(&make_source("Kept", "https://kept.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let entry = |id: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+        repository
+            .upsert_entries(doomed.id, &[entry("a"), entry("b")])
+            .await
+            .expect("sync doomed");
+        repository
+            .upsert_entries(kept.id, &[entry("c")])
+            .await
+            .expect("sync kept");
+        let doomed_entry = repository
+            .list_entries_after(doomed.id, 0)
+            .await
+            .expect("list entries")[0]
+            .id;
+        repository
+            .set_entry_fulltext(
+                doomed_entry,
+                &ReaderArticle {
+                    url: "https://example.com/a".to_string(),
+                    title: None,
+                    content_html: "<p>a</p>".to_string(),
+                    text_length: 1,
+                },
+            )
+            .await
+            .expect("cache fulltext");
+        repository
+            .queue_notification(doomed.id, doomed_entry, None)
+            .await
+            .expect("queue notification");
+
+        let deletion = repository
+            .delete_source(doomed.id)
+            .await
+            .expect("delete source");
+        let remaining = sqlx::query_as::<_, (i64, i64, i64)>(
+            r#"
+            SELECT
+              (SELECT COUNT(*) FROM entries),
+              (SELECT COUNT(*) FROM entry_fulltext),
+              (SELECT COUNT(*) FROM pending_notifications)
+            "#,
+        )
+        .fetch_one(&repository.pool)
+        .await
+        .expect("count leftovers");
+
+        assert_eq!(
+            deletion,
+            SourceDeletion {
+                sources: 1,
+                entries: 2,
+                preserved_starred: 0,
+            }
+        );
+        assert_eq!(remaining, (1, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn starred_entries_survive_source_deletion_and_can_be_rehomed() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let feed = make_source("Starred", "https://starred.example.com/feed.xml");
+        let source = repository
+            .upsert_source(&feed)
+            .await
+            .expect("create source");
+        let entry = |id: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://starred.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+        repository
+            .upsert_entries(source.id, &[entry("keep"), entry("drop"), entry("both")])
+            .await
+            .expect("first sync");
+        for row in repository
+            .list_entries_after(source.id, 0)
+            .await
+            .expect("list entries")
+            .into_iter()
+            .filter(|row| row.guid.as_deref() != Some("drop"))
+        {
+            repository
+                .set_entry_starred(row.id, true)
+                .await
+                .expect("star entry");
+        }
+
+        let deletion = repository
+            .delete_source(source.id)
+            .await
+            .expect("delete source");
+        let orphans = repository
+            .list_orphaned_entries()
+            .await
+            .expect("list orphans");
+        assert_eq!(deletion.entries, 1);
+        assert_eq!(deletion.preserved_starred, 2);
+        assert_eq!(orphans.len(), 2);
+        assert!(orphans
+            .iter()
+            .all(|orphan| orphan.resubscribed_source_id.is_none()));
+
+        let readded = repository
+            .upsert_source(&feed)
+            .await
+            .expect("re-add source");
+        repository
+            .upsert_entries(readded.id, &[entry("both")])
+            .await
+            .expect("resync");
+        let rehomed = repository
+            .rehome_orphaned_entries(readded.id)
+            .await
+            .expect("rehome");
+        let starred = sqlx::query_scalar::<_, String>(
+            "SELECT guid FROM entries WHERE source_id = ?1 AND is_starred = 1 ORDER BY guid",
+        )
+        .bind(readded.id)
+        .fetch_all(&repository.pool)
+        .await
+        .expect("starred entries");
+
+        assert_eq!(rehomed, 2);
+        assert_eq!(starred, vec!["both".to_string(), "keep".to_string()]);
+        assert!(repository
+            .list_orphaned_entries()
+            .await
+            .expect("list orphans")
+            .is_empty());
+        assert!(repository
+            .list_sources()
+            .await
+            .expect("list sources")
+            .iter()
+            .all(|row| row.feed_url != ORPHANED_SOURCE_FEED_URL));
+    }
+
+    #[tokio::test]
+    async fn starred_archive_rows_and_colliding_guids_survive_source_deletion() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let first_feed = make_source("First", "https://first.example.com/feed.xml");
+        let first = repository
+            .upsert_source(&first_feed)
+            .await
+            .expect("create first");
+        let second = repository
+            .upsert_source(&make_source(
+                "Second",
+                "https://second.example.com/feed.xml",
+            ))
+            .await
+            .expect("create second");
+        let entry = |id: &str, host: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://{host}.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+        repository
+            .upsert_entries(
+                first.id,
+                &[entry("shared", "first"), entry("archived", "first")],
+            )
+            .await
+            .expect("first sync");
+        repository
+            .upsert_entries(second.id, &[entry("shared", "second")])
+            .await
+            .expect("second sync");
+        sqlx::query("UPDATE entries SET is_starred = 1")
+            .execute(&repository.pool)
+            .await
+            .expect("star all");
+        sqlx::query(
+            r#"
+            INSERT INTO entries_archive (
+              id, source_id, guid, link, title, is_starred, created_at, dedup_key, canonical_link
+            )
+            SELECT id, source_id, guid, link, title, is_starred, created_at, dedup_key, canonical_link
+            FROM entries WHERE guid = 'archived'
+            "#,
+        )
+        .execute(&repository.pool)
+        .await
+        .expect("archive entry");
+        sqlx::query("DELETE FROM entries WHERE guid = 'archived'")
+            .execute(&repository.pool)
+            .await
+            .expect("remove archived entry");
+
+        let deletion = repository
+            .delete_sources(&[first.id, second.id])
+            .await
+            .expect("delete sources");
+        assert_eq!(deletion.preserved_starred, 3);
+        assert_eq!(
+            repository
+                .list_orphaned_entries()
+                .await
+                .expect("list orphans")
+                .len(),
+            3
+        );
+        assert!(repository
+            .list_sources()
+            .await
+            .expect("list sources")
+            .is_empty());
+
+        let readded = repository
+            .upsert_source(&first_feed)
+            .await
+            .expect("re-add source");
+        assert_eq!(
+            repository
+                .rehome_orphaned_entries(readded.id)
+                .await
+                .expect("rehome"),
+            2
+        );
+        let rehomed = sqlx::query_scalar::<_, String>(
+            "SELECT guid FROM entries WHERE source_id = ?1 AND is_starred = 1 ORDER BY guid",
+        )
+        .bind(readded.id)
+        .fetch_all(&repository.pool)
+        .await
+        .expect("rehomed entries");
+        assert_eq!(rehomed, vec!["archived".to_string(), "shared".to_string()]);
+        let resynced = repository
+            .upsert_entries(readded.id, &[entry("shared", "first")])
+            .await
+            .expect("resync");
+        assert_eq!(resynced.inserted, 0);
+    }
+
+    #[tokio::test]
+    async fn entry_cursor_pages_through_ties_without_gaps() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Paged", "https://paged.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let entries = (0..5)
+            .map(|index| ParsedEntry {
+                id: format!("post-{index}"),
+                title: format!("Post {index}"),
+                link: format!("https://paged.example.com/{index}"),
+                summary: None,
+                content: Some(format!("<p>Body {index}</p>")),
+                published_at: Some(if index < 3 {
+                    "2026-01-02T00:00:00Z".to_string()
+                } else {
+                    format!("2026-01-0{}T00:00:00Z", index + 2)
+                }),
+            })
+            .collect::<Vec<_>>();
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("sync");
+
+        let control = QueryControl::new(Duration::from_secs(5));
+        let mut cursor = None;
+        let mut seen = Vec::new();
+        loop {
+            let page = repository
+                .search_entries(Some(source.id), None, false, cursor.as_ref(), 2, &control)
+                .await
+                .expect("page");
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = EntryCursor::decode(&EntryCursor::after(last).encode());
+            assert!(page.iter().all(|entry| entry.content.is_none()));
+            seen.extend(page.iter().map(|entry| entry.title.clone()));
+        }
+
+        assert_eq!(seen, vec!["Post 4", "Post 3", "Post 2", "Post 1", "Post 0"]);
+        let full = repository
+            .list_entries(Some(source.id), None, false, 1)
+            .await
+            .expect("full listing");
+        assert_eq!(full[0].content.as_deref(), Some("<p>Body 4</p>"));
+    }
+
+    #[tokio::test]
+    async fn system_views_filter_today_unread_and_starred() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Views", "https://views.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let entries = [
+            ("fresh", now.as_str()),
+            ("old-starred", "2020-01-02T00:00:00Z"),
+            ("old", "2020-01-01T00:00:00Z"),
+        ]
+        .iter()
+        .map(|(id, published_at)| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://views.example.com/{id}"),
+            summary: None,
+            content: Some("<p>body</p>".to_string()),
+            published_at: Some(published_at.to_string()),
+        })
+        .collect::<Vec<_>>();
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("sync");
+        let starred = repository
+            .list_entries(Some(source.id), Some("old-starred"), false, 1)
+            .await
+            .expect("find entry")[0]
+            .id;
+        repository
+            .set_entry_starred(starred, true)
+            .await
+            .expect("star");
+        repository
+            .mark_entry_read(starred, true)
+            .await
+            .expect("read");
+        sqlx::query(
+            r#"
+            INSERT INTO entries_archive (id, source_id, guid, link, title, published_at, is_read, is_starred, created_at)
+            VALUES (9000, ?1, 'archived-starred', 'https://views.example.com/archived', 'archived-starred', '2019-01-01T00:00:00Z', 1, 1, '2019-01-01T00:00:00Z')
+            "#,
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("archive starred entry");
+
+        let mut titles = Vec::new();
+        for view in [
+            EntryView::All,
+            EntryView::Today,
+            EntryView::Unread,
+            EntryView::Starred,
+        ] {
+            let rows = repository
+                .list_view_entries(view, None, 10)
+                .await
+                .expect("view");
+            assert!(rows.iter().all(|row| row.content.is_none()));
+            titles.push(rows.into_iter().map(|row| row.title).collect::<Vec<_>>());
+        }
+        let first = repository
+            .list_view_entries(EntryView::All, None, 1)
+            .await
+            .expect("first page");
+        let after_first = repository
+            .list_view_entries(EntryView::All, Some(&EntryCursor::after(&first[0])), 1)
+            .await
+            .expect("second page");
+
+        assert_eq!(
+            titles,
+            vec![
+                vec!["fresh", "old-starred", "old"],
+                vec!["fresh"],
+                vec!["fresh", "old"],
+                vec!["old-starred", "archived-starred"],
+            ]
+        );
+        assert_eq!(after_first.len(), 1);
+        assert_eq!(after_first[0].title, "old-starred");
+    }
+
+    #[tokio::test]
+    async fn entry_counts_group_by_source_and_category() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let first = repository
+            .upsert_source(&make_source("First", "https://first.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let second = repository
+            .upsert_source(&make_source(
+                "Second",
+                "https://second.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let empty = repository
+            .upsert_source(&NewSource {
+                category: None,
+                ..make_source("Empty", "https://empty.example.com/feed.xml")
+            })
+            .await
+            .expect("create source");
+        let entry = |id: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+        repository
+            .upsert_entries(first.id, &[entry("a"), entry("b"), entry("c")])
+            .await
+            .expect("sync first");
+        repository
+            .upsert_entries(second.id, &[entry("d")])
+            .await
+            .expect("sync second");
+        let first_entries = repository
+            .list_entries_after(first.id, 0)
+            .await
+            .expect("list entries");
+        repository
+            .mark_entry_read(first_entries[0].id, true)
+            .await
+            .expect("mark read");
+        repository
+            .set_entry_starred(first_entries[1].id, true)
+            .await
+            .expect("star entry");
+
+        let counts = repository.get_entry_counts().await.expect("counts");
+        let by_source = counts
+            .sources
+            .iter()
+            .map(|row| {
+                (
+                    row.source_id,
+                    row.unread_count,
+                    row.starred_count,
+                    row.total_count,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            by_source,
+            vec![
+                (empty.id, 0, 0, 0),
+                (second.id, 1, 0, 1),
+                (first.id, 2, 1, 3)
+            ]
+        );
+        assert_eq!(
+            counts.categories,
+            vec![
+                CategoryCounts {
+                    category: None,
+                    ..CategoryCounts::default()
+                },
+                CategoryCounts {
+                    category: Some("tech".to_string()),
+                    unread_count: 3,
+                    starred_count: 1,
+                    total_count: 4,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn set_sources_active_updates_batch_rows() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let first = repository
+            .upsert_source(&make_source("A", "https://a.com/feed.xml"))
+            .await
+            .expect("create A");
+        let second = repository
+            .upsert_source(&make_source("B", "https://b.com/feed.xml"))
+            .await
+            .expect("create B");
+
+        let affected = repository
+            .set_sources_active(&[first.id, second.id], false)
+            .await
+            .expect("batch update should succeed");
+        let rows = repository
+            .list_sources()
+            .await
+            .expect("list should succeed");
+
+        assert_eq!(affected, 2);
+        assert!(rows.iter().all(|row| row.is_active == 0));
+    }
+
+    #[tokio::test]
+    async fn e2e_import_then_delete_flow() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let opml = include_str!("../../../../fixtures/import-samples/hackerNewsStars.xml");
+        let parsed_sources = parse_opml(opml).expect("opml parse should succeed");
+        let preview = build_import_preview(parsed_sources, &HashSet::new(), &HashSet::new());
+        let batch: Vec<NewSource> = preview
+            .new_sources
+            .into_iter()
+            .take(5)
+            .map(|source| NewSource {
+                title: source.title,
+                site_url: source.site_url,
+                feed_url: source.feed_url,
+                category: source.category,
+                is_active: true,
+            })
+            .collect();
+
+        repository
+            .upsert_sources_batch(&batch)
+            .await
+            .expect("batch upsert should succeed");
+        let current = repository
+            .list_sources()
+            .await
+            .expect("list should succeed");
+        let deleted = repository
+            .delete_source(current[0].id)
+            .await
+            .expect("delete should succeed");
+        let after_delete = repository
+            .list_sources()
+            .await
+            .expect("list should succeed");
+
+        assert_eq!(current.len(), 5);
+        assert_eq!(deleted.sources, 1);
+        assert_eq!(after_delete.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn batch_source_upsert_reports_inserted_and_updated() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        repository
+            .upsert_source(&make_source("Old", "https://a.example.com/feed.xml"))
+            .await
+            .expect("create source should succeed");
+        let batch = vec![
+            make_source("A", "https://a.example.com/feed.xml"),
+            make_source("B", "https://b.example.com/feed.xml"),
+            make_source("B renamed", "https://b.example.com/feed.xml"),
+        ];
+
+        let counts = repository
+            .upsert_sources_batch(&batch)
+            .await
+            .expect("batch upsert should succeed");
+        let titles: Vec<String> = repository
+            .list_sources()
+            .await
+            .expect("list should succeed")
+            .into_iter()
+            .map(|source| source.title)
+            .collect();
+
+        assert_eq!(
+            counts,
+            UpsertCounts {
+                inserted: 1,
+                updated: 1,
+                conflicted: 0
+            }
+        );
+        assert_eq!(titles, vec!["B renamed".to_string(), "A".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn entries_sharing_a_link_stay_distinct_by_dedup_key() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Shared",
+                "https://shared.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let episodes = |ids: &[&str]| {
+            ids.iter()
+                .map(|id| ParsedEntry {
+                    id: id.to_string(),
+                    title: format!("Title {id}"),
+                    link: "https://shared.example.com/".to_string(),
+                    summary: None,
+                    content: None,
+                    published_at: None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let entries = episodes(&["episode-1", "episode-2"]);
+
+        let first = repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("first sync");
+        let second = repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("second sync");
+        let third = repository
+            .upsert_entries(source.id, &episodes(&["episode-3", "episode-2"]))
+            .await
+            .expect("third sync");
+        let fourth = repository
+            .upsert_entries(source.id, &episodes(&["episode-3", "episode-2"]))
+            .await
+            .expect("fourth sync");
+        let stored = sqlx::query_as::<_, (String, bool)>(
+            "SELECT dedup_key, is_updated FROM entries WHERE source_id = ?1 ORDER BY id",
+        )
+        .bind(source.id)
+        .fetch_all(&repository.pool)
+        .await
+        .expect("dedup keys");
+
+        assert_eq!(first.inserted, 2);
+        assert_eq!(second, UpsertCounts::default());
+        assert_eq!(
+            third,
+            UpsertCounts {
+                inserted: 1,
+                updated: 0,
+                conflicted: 0
+            }
+        );
+        assert_eq!(fourth, UpsertCounts::default());
+        assert_eq!(
+            stored,
+            vec![
+                (
+                    "https://shared.example.com/feed.xml::id::episode-1".to_string(),
+                    false
+                ),
+                (
+                    "https://shared.example.com/feed.xml::id::episode-2".to_string(),
+                    false
+                ),
+                (
+                    "https://shared.example.com/feed.xml::id::episode-3".to_string(),
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn campaign_params_on_links_do_not_create_duplicates() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Campaign",
+                "https://campaign.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let entry = |campaign: &str| ParsedEntry {
+            id: String::new(),
+            title: "Post".to_string(),
+            link: format!(
+                "https://campaign.example.com/post?utm_source=rss&utm_campaign={campaign}"
+            ),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+        repository
+            .upsert_entries(source.id, &[entry("monday")])
+            .await
+            .expect("first sync");
+        repository
+            .upsert_entries(source.id, &[entry("tuesday")])
+            .await
+            .expect("second sync");
+        sqlx::query(
+            "UPDATE entries SET canonical_link = NULL, dedup_key = 'legacy' WHERE source_id = ?1",
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("simulate legacy row");
+        backfill_canonical_links(&repository.pool)
+            .await
+            .expect("backfill");
+
+        let rows = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT link, canonical_link, dedup_key FROM entries WHERE source_id = ?1",
+        )
+        .bind(source.id)
+        .fetch_all(&repository.pool)
+        .await
+        .expect("rows");
+
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].0.contains("utm_campaign=tuesday"));
+        assert_eq!(rows[0].1, "https://campaign.example.com/post");
+        assert_eq!(
+            rows[0].2,
+            "https://campaign.example.com/feed.xml::link::https://campaign.example.com/post"
+        );
+    }
+
+    #[tokio::test]
+    async fn entry_html_is_sanitized_on_ingest_and_backfill() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Unsafe",
+                "https://unsafe.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        repository
+            .upsert_entries(
+                source.id,
+                &[ParsedEntry {
+                    id: "post".to_string(),
+                    title: "Post".to_string(),
+                    link: "https://unsafe.example.com/post".to_string(),
+                    summary: Some("<b>Hi</b><script>track()</script>".to_string()),
+                    content: Some(
+                        "<p>Body</p><iframe src=\"https://ads.example.net\"></iframe>".to_string(),
+                    ),
+                    published_at: None,
+                }],
+            )
+            .await
+            .expect("sync");
+        let stored = || async {
+            sqlx::query_as::<_, (String, String)>(
+                "SELECT summary, content FROM entries WHERE source_id = ?1",
+            )
+            .bind(source.id)
+            .fetch_one(&repository.pool)
+            .await
+            .expect("stored row")
+        };
+        assert_eq!(
+            stored().await,
+            ("<b>Hi</b>".to_string(), "<p>Body</p>".to_string())
+        );
+
+        sqlx::query("UPDATE entries SET content = ?1 WHERE source_id = ?2")
+            .bind("<p onmouseover=\"x()\">Legacy</p><script>x()</script>")
+            .bind(source.id)
+            .execute(&repository.pool)
+            .await
+            .expect("simulate legacy row");
+        repository
+            .set_sanitizer_allowlist(&SanitizerAllowlist {
+                tags: vec!["p".to_string()],
+                ..SanitizerAllowlist::default()
+            })
+            .await
+            .expect("save allowlist");
+        assert_eq!(
+            repository
+                .sanitize_stored_entries()
+                .await
+                .expect("backfill"),
+            1
+        );
+        assert_eq!(
+            stored().await,
+            ("Hi".to_string(), "<p>Legacy</p>".to_string())
+        );
+        assert_eq!(
+            repository
+                .backfill_sanitized_content()
+                .await
+                .expect("rerun"),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn edited_entries_keep_read_state_and_original_publish_time() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Edits", "https://edits.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let entry = |title: &str, published_at: &str| ParsedEntry {
+            id: "post-1".to_string(),
+            title: title.to_string(),
+            link: "https://edits.example.com/1".to_string(),
+            summary: None,
+            content: None,
+            published_at: Some(published_at.to_string()),
+        };
+        repository
+            .upsert_entries(source.id, &[entry("Original", "2026-01-01T00:00:00Z")])
+            .await
+            .expect("initial sync");
+        let id = repository
+            .list_entries(Some(source.id), None, false, 1)
+            .await
+            .expect("list")[0]
+            .id;
+        repository.mark_entry_read(id, true).await.expect("read");
+
+        repository
+            .upsert_entries(source.id, &[entry("Edited", "2026-03-01T00:00:00Z")])
+            .await
+            .expect("edited sync");
+        let stored = repository
+            .get_entry_by_id(id)
+            .await
+            .expect("get entry")
+            .expect("entry exists");
+
+        assert_eq!(stored.title, "Edited");
+        assert_eq!(stored.published_at.as_deref(), Some("2026-01-01T00:00:00Z"));
+        assert_eq!(stored.is_read, 1);
+        assert!(stored.is_updated);
+        assert!(stored.updated_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn guid_reuse_under_another_dedup_key_is_reported_not_merged() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Reuse", "https://reuse.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let entry = |id: &str, title: &str| ParsedEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            link: format!("https://reuse.example.com/{title}"),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+        repository
+            .upsert_entries(source.id, &[entry("42", "original")])
+            .await
+            .expect("first sync");
+        sqlx::query("UPDATE entries SET dedup_key = 'legacy-key' WHERE source_id = ?1")
+            .bind(source.id)
+            .execute(&repository.pool)
+            .await
+            .expect("simulate older dedup key");
+
+        let reused = repository
+            .upsert_entries(
+                source.id,
+                &[entry("42", "different"), entry("43", "unrelated")],
+            )
+            .await
+            .expect("second sync");
+        let titles = sqlx::query_scalar::<_, String>(
+            "SELECT title FROM entries WHERE source_id = ?1 ORDER BY title",
+        )
+        .bind(source.id)
+        .fetch_all(&repository.pool)
+        .await
+        .expect("titles");
+
+        assert_eq!(
+            reused,
+            UpsertCounts {
+                inserted: 1,
+                updated: 0,
+                conflicted: 1
+            }
+        );
+        assert_eq!(titles, vec!["original", "unrelated"]);
+    }
+
+    #[tokio::test]
+    async fn entry_upsert_dedups_on_guid_when_links_change() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Churn", "https://churn.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let entry = |id: &str, link: &str, title: &str| ParsedEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            link: link.to_string(),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+
+        repository
+            .upsert_entries(
+                source.id,
+                &[entry(
+                    "post-1",
+                    "https://churn.example.com/p/1?v=1",
+                    "First",
+                )],
+            )
+            .await
+            .expect("initial upsert");
+        let moved = repository
+            .upsert_entries(
+                source.id,
+                &[entry(
+                    "post-1",
+                    "https://churn.example.com/p/1?v=2",
+                    "First (edited)",
+                )],
+            )
+            .await
+            .expect("moved link upsert");
+        let regenerated = repository
+            .upsert_entries(
+                source.id,
+                &[entry(
+                    "post-1-rev2",
+                    "https://churn.example.com/p/1?v=2",
+                    "First (again)",
+                )],
+            )
+            .await
+            .expect("regenerated guid upsert");
+        let entries = repository
+            .list_entries(Some(source.id), None, false, 10)
+            .await
+            .expect("list entries");
+
+        assert_eq!(
+            moved,
+            UpsertCounts {
+                inserted: 0,
+                updated: 1,
+                conflicted: 0
+            }
+        );
+        assert_eq!(
+            regenerated,
+            UpsertCounts {
+                inserted: 1,
+                updated: 0,
+                conflicted: 0
+            }
+        );
+        assert_eq!(entries.len(), 2);
+        let edited = entries
+            .iter()
+            .find(|entry| entry.guid.as_deref() == Some("post-1"))
+            .expect("original entry");
+        assert_eq!(edited.link, "https://churn.example.com/p/1?v=2");
+        assert_eq!(edited.title, "First (edited)");
+    }
+
+    #[tokio::test]
+    async fn entry_upsert_and_read_filter_flow() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Reader Source",
+                "https://reader.example.com/feed.xml",
+            ))
+            .await
+            .expect("source create should succeed");
+        let entries = vec![
+            ParsedEntry {
+                id: "entry-1".to_string(),
+                title: "Rust release".to_string(),
+                link: "https://reader.example.com/posts/1".to_string(),
+                summary: Some("Rust update".to_string()),
+                content: Some("content 1".to_string()),
+                published_at: Some("2026-02-24T00:00:00Z".to_string()),
+            },
+            ParsedEntry {
+                id: "entry-2".to_string(),
+                title: "AI news".to_string(),
+                link: "https://reader.example.com/posts/2".to_string(),
+                summary: Some("AI summary".to_string()),
+                content: Some("content 2".to_string()),
+                published_at: Some("2026-02-24T01:00:00Z".to_string()),
+            },
+        ];
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("entry upsert should succeed");
+
+        let all = repository
+            .list_entries(Some(source.id), None, false, 50)
+            .await
+            .expect("list all should succeed");
+        let rust_only = repository
+            .list_entries(Some(source.id), Some("Rust"), false, 50)
+            .await
+            .expect("search should succeed");
+        let marked = repository
+            .mark_entry_read(all[0].id, true)
+            .await
+            .expect("mark read should succeed");
+        let unread = repository
+            .list_entries(Some(source.id), None, true, 50)
+            .await
+            .expect("unread filter should succeed");
+
+        assert_eq!(all.len(), 2);
+        assert_eq!(rust_only.len(), 1);
+        assert_eq!(marked, 1);
+        assert_eq!(unread.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn list_entries_respects_limit_for_large_dataset() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Perf Source",
+                "https://perf.example.com/feed.xml",
+            ))
+            .await
+            .expect("source create should succeed");
+        let entries: Vec<ParsedEntry> = (0..120)
+            .map(|index| ParsedEntry {
+                id: format!("entry-{index}"),
+                title: format!("Entry {index}"),
+                link: format!("https://perf.example.com/posts/{index}"),
+                summary: Some(format!("summary {index}")),
+                content: Some(format!("content {index}")),
+                published_at: Some("2026-02-24T00:00:00Z".to_string()),
+            })
+            .collect();
+
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("entry upsert should succeed");
+        let limited = repository
+            .list_entries(Some(source.id), None, false, 50)
+            .await
+            .expect("list should succeed");
+
+        assert_eq!(limited.len(), 50);
+    }
+
+    #[tokio::test]
+    async fn llm_profiles_resolve_by_task_with_first_profile_fallback() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let profile = |name: &str, model: &str| LlmProfile {
+            id: 0,
+            name: name.to_string(),
+            config: serde_json::from_value(serde_json::json!({
+                "base_url": "https://api.example.com/v1",
+                "api_key": "key",
+                "model": model,
+                "timeout_secs": 30,
+            }))
+            .expect("config should deserialize"),
+            tasks: Vec::new(),
+            active: false,
+        };
+        let strong = repository
+            .create_llm_profile(&profile("strong", "big-model"))
+            .await
+            .expect("create strong");
+        let fast = repository
+            .create_llm_profile(&profile("fast", "small-model"))
+            .await
+            .expect("create fast");
+        repository
+            .set_task_llm_profile(LlmTask::Translation, Some(fast))
+            .await
+            .expect("map translation");
+
+        let model_for = |task| {
+            let repository = repository.clone();
+            async move {
+                repository
+                    .resolve_llm_profile(task)
+                    .await
+                    .expect("resolve profile")
+                    .map(|profile| profile.config.model)
+            }
+        };
+        assert_eq!(
+            model_for(Some(LlmTask::Translation)).await.as_deref(),
+            Some("small-model")
+        );
+        assert_eq!(
+            model_for(Some(LlmTask::Summary)).await.as_deref(),
+            Some("big-model")
+        );
+        let profiles = repository.list_llm_profiles().await.expect("list profiles");
+        assert_eq!(profiles[1].tasks, vec![LlmTask::Translation]);
+
+        assert!(repository
+            .activate_llm_profile(fast)
+            .await
+            .expect("activate fast"));
+        assert!(!repository
+            .activate_llm_profile(fast + 100)
+            .await
+            .expect("activate missing"));
+        assert_eq!(model_for(None).await.as_deref(), Some("small-model"));
+        assert_eq!(
+            model_for(Some(LlmTask::Summary)).await.as_deref(),
+            Some("small-model")
+        );
+        let profiles = repository.list_llm_profiles().await.expect("list profiles");
+        assert!(!profiles[0].active && profiles[1].active);
+
+        sqlx::query("UPDATE llm_profiles SET config = '{' WHERE id = ?1")
+            .bind(strong)
+            .execute(&repository.pool)
+            .await
+            .expect("corrupt config");
+        assert!(matches!(
+            repository.list_llm_profiles().await,
+            Err(StorageError::InvalidLlmProfile { name, .. }) if name == "strong"
+        ));
+        repository
+            .update_llm_profile(&LlmProfile {
+                id: strong,
+                ..profile("strong", "big-model")
+            })
+            .await
+            .expect("repair strong");
+
+        repository
+            .delete_llm_profile(fast)
+            .await
+            .expect("delete fast");
+        assert_eq!(
+            model_for(Some(LlmTask::Translation)).await.as_deref(),
+            Some("big-model")
+        );
+        repository
+            .delete_llm_profile(strong)
+            .await
+            .expect("delete strong");
+        assert!(model_for(None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn settings_and_llm_cache_roundtrip() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+
+        repository
+            .set_setting(
+                "llm_config",
+                "{\"base_url\":\"https://api.deepseek.com/v1\"}",
+            )
+            .await
+            .expect("set setting should succeed");
+        let setting = repository
+            .get_setting("llm_config")
+            .await
+            .expect("get setting should succeed")
+            .expect("setting should exist");
+        assert!(setting.contains("deepseek"));
+
+        repository
+            .set_llm_cache("summary", "deepseek-chat", "abc", "cached text")
+            .await
+            .expect("set cache should succeed");
+        let cached = repository
+            .get_llm_cache("summary", "deepseek-chat", "abc")
+            .await
+            .expect("get cache should succeed");
+        assert_eq!(cached.as_deref(), Some("cached text"));
+    }
+
+    #[tokio::test]
+    async fn llm_cache_import_keeps_local_rows_unless_overwriting() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        repository
+            .set_llm_cache("summary", "deepseek-chat", "abc", "local text")
+            .await
+            .expect("set cache should succeed");
+        let record = |hash: &str, output: &str| LlmCacheRecord {
+            task_type: "summary".to_string(),
+            model: "deepseek-chat".to_string(),
+            input_hash: hash.to_string(),
+            output_text: output.to_string(),
+            created_at: "2026-01-01 00:00:00".to_string(),
+            key_version: LLM_CACHE_KEY_VERSION,
+        };
+        let records = vec![record("abc", "imported text"), record("def", "new text")];
+
+        let report = repository
+            .import_llm_cache_records(&records, false)
+            .await
+            .expect("import should succeed");
+        assert_eq!(
+            report,
+            LlmCacheImport {
+                imported: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(
+            repository
+                .get_llm_cache("summary", "deepseek-chat", "abc")
+                .await
+                .expect("get cache should succeed")
+                .as_deref(),
+            Some("local text")
+        );
+
+        repository
+            .import_llm_cache_records(&records, true)
+            .await
+            .expect("overwrite should succeed");
+        let exported = repository
+            .list_llm_cache_records()
+            .await
+            .expect("list should succeed");
+        assert_eq!(exported, records);
+    }
+
+    #[tokio::test]
+    async fn imported_cache_hits_under_remapped_model_and_legacy_rows_rekey() {
+        use crate::core::llm::cache::{legacy_llm_cache_key, llm_cache_key, remap_models};
+
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let key = llm_cache_key("summary", "article body");
+        let records = remap_models(
+            vec![LlmCacheRecord {
+                task_type: "summary".to_string(),
+                model: "gpt-4o".to_string(),
+                input_hash: key.clone(),
+                output_text: "cached summary".to_string(),
+                created_at: "2026-01-01 00:00:00".to_string(),
+                key_version: LLM_CACHE_KEY_VERSION,
+            }],
+            &BTreeMap::from([("gpt-4o".to_string(), "deepseek-chat".to_string())]),
+        );
+        repository
+            .import_llm_cache_records(&records, false)
+            .await
+            .expect("import should succeed");
+        assert_eq!(
+            repository
+                .get_llm_cache("summary", "deepseek-chat", &key)
+                .await
+                .expect("get cache should succeed")
+                .as_deref(),
+            Some("cached summary")
+        );
+
+        let legacy = legacy_llm_cache_key("summary", "qwen", "old body");
+        sqlx::query(
+            "INSERT INTO llm_cache (task_type, model, input_hash, output_text) VALUES ('summary', 'qwen', ?1, 'old summary')",
+        )
+        .bind(&legacy)
+        .execute(&repository.pool)
+        .await
+        .expect("insert legacy row");
+        let rekeyed_key = llm_cache_key("summary", "old body");
+        assert_eq!(
+            repository
+                .rekey_legacy_llm_cache("summary", "qwen", &legacy, &rekeyed_key)
+                .await
+                .expect("rekey should succeed")
+                .as_deref(),
+            Some("old summary")
+        );
+        assert_eq!(
+            repository
+                .get_llm_cache("summary", "qwen", &rekeyed_key)
+                .await
+                .expect("get cache should succeed")
+                .as_deref(),
+            Some("old summary")
+        );
+    }
+
+    #[tokio::test]
+    async fn entry_title_translation_roundtrip() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Translate Source",
+                "https://translate.example.com/feed.xml",
+            ))
+            .await
+            .expect("source create should succeed");
+        let entries = vec![ParsedEntry {
+            id: "entry-1".to_string(),
+            title: "A long English title".to_string(),
+            link: "https://translate.example.com/posts/1".to_string(),
+            summary: None,
+            content: None,
+            published_at: Some("2026-02-24T00:00:00Z".to_string()),
+        }];
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("entry insert should succeed");
+
+        let untranslated = repository
+            .list_entries_without_translated_title(20)
+            .await
+            .expect("list untranslated should succeed");
+        assert_eq!(untranslated.len(), 1);
+
+        repository
+            .set_entry_translated_title(untranslated[0].id, "中文标题")
+            .await
+            .expect("set translated title should succeed");
+        let untranslated_after = repository
+            .list_entries_without_translated_title(20)
+            .await
+            .expect("list untranslated should succeed");
+        assert!(untranslated_after.is_empty());
+    }
+
+    #[tokio::test]
+    async fn untranslated_entries_are_ordered_by_time_descending() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Order Source",
+                "https://order.example.com/feed.xml",
+            ))
+            .await
+            .expect("source create should succeed");
+        let entries = vec![
+            ParsedEntry {
+                id: "entry-new".to_string(),
+                title: "Newer title".to_string(),
+                link: "https://order.example.com/posts/new".to_string(),
+                summary: None,
+                content: None,
+                published_at: Some("2026-02-24T02:00:00Z".to_string()),
+            },
+            ParsedEntry {
+                id: "entry-old".to_string(),
+                title: "Older title".to_string(),
+                link: "https://order.example.com/posts/old".to_string(),
+                summary: None,
+                content: None,
+                published_at: Some("2026-02-24T00:00:00Z".to_string()),
+            },
+        ];
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("entry insert should succeed");
+
+        let untranslated = repository
+            .list_entries_without_translated_title(20)
+            .await
+            .expect("list untranslated should succeed");
+        assert_eq!(untranslated.len(), 2);
+        assert_eq!(untranslated[0].title, "Newer title");
+        assert_eq!(untranslated[1].title, "Older title");
+    }
+
+    #[tokio::test]
+    async fn untranslated_entries_follow_global_time_order() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source_a = repository
+            .upsert_source(&make_source("Source A", "https://a.example.com/feed.xml"))
+            .await
+            .expect("source A create should succeed");
+        let source_b = repository
+            .upsert_source(&make_source("Source B", "https://b.example.com/feed.xml"))
+            .await
+            .expect("source B create should succeed");
+
+        repository
+            .upsert_entries(
+                source_a.id,
+                &[
+                    ParsedEntry {
+                        id: "a-new".to_string(),
+                        title: "A newer".to_string(),
+                        link: "https://a.example.com/new".to_string(),
+                        summary: None,
+                        content: None,
+                        published_at: Some("2026-02-24T10:00:00Z".to_string()),
+                    },
+                    ParsedEntry {
+                        id: "a-old".to_string(),
+                        title: "A older".to_string(),
+                        link: "https://a.example.com/old".to_string(),
+                        summary: None,
+                        content: None,
+                        published_at: Some("2026-02-24T09:00:00Z".to_string()),
+                    },
+                ],
+            )
+            .await
+            .expect("insert A entries should succeed");
+        repository
+            .upsert_entries(
+                source_b.id,
+                &[
+                    ParsedEntry {
+                        id: "b-new".to_string(),
+                        title: "B newer".to_string(),
+                        link: "https://b.example.com/new".to_string(),
+                        summary: None,
+                        content: None,
+                        published_at: Some("2026-02-24T08:00:00Z".to_string()),
+                    },
+                    ParsedEntry {
+                        id: "b-old".to_string(),
+                        title: "B older".to_string(),
+                        link: "https://b.example.com/old".to_string(),
+                        summary: None,
+                        content: None,
+                        published_at: Some("2026-02-24T07:00:00Z".to_string()),
+                    },
+                ],
+            )
+            .await
+            .expect("insert B entries should succeed");
+
+        let untranslated = repository
+            .list_entries_without_translated_title(10)
+            .await
+            .expect("list untranslated should succeed");
+        let titles = untranslated
+            .iter()
+            .map(|row| row.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["A newer", "A older", "B newer", "B older"]);
+    }
+
+    #[tokio::test]
+    async fn sync_candidates_respect_backoff_window() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Backoff Source",
+                "https://backoff.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+
+        sqlx::query(
+            r#"
+            UPDATE sources
+            SET failure_count = 3,
+                last_synced_at = datetime('now'),
+                is_active = 1
+            WHERE id = ?1
+            "#,
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("update should succeed");
+
+        let candidates_now = repository
+            .list_sync_candidates(50, true)
+            .await
+            .expect("list candidates should succeed");
+        assert!(candidates_now.is_empty());
+
+        sqlx::query(
+            r#"
+            UPDATE sources
+            SET last_synced_at = datetime('now', '-20 minutes')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("update should succeed");
+
+        let candidates_later = repository
+            .list_sync_candidates(50, true)
+            .await
+            .expect("list candidates should succeed");
+        assert_eq!(candidates_later.len(), 1);
+
+        sqlx::query(
+            r#"
+            UPDATE sources
+            SET failure_count = 8,
+                last_synced_at = datetime('now', '-2 hours')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("update should succeed");
+        assert!(repository
+            .list_sync_candidates(50, true)
+            .await
+            .expect("list candidates should succeed")
+            .is_empty());
+
+        repository
+            .reactivate_source(source.id)
+            .await
+            .expect("reactivate should succeed");
+        assert_eq!(
+            repository
+                .list_sync_candidates(50, true)
+                .await
+                .expect("list candidates should succeed")
+                .len(),
+            1
+        );
+
+        repository
+            .defer_source_sync(source.id, 3600)
+            .await
+            .expect("defer should succeed");
+        assert!(repository
+            .list_sync_candidates(50, true)
+            .await
+            .expect("list candidates should succeed")
+            .is_empty());
+
+        repository
+            .update_source_sync_success(source.id, None, None)
+            .await
+            .expect("sync success should succeed");
+        sqlx::query(
+            "UPDATE sources SET last_synced_at = datetime('now', '-5 minutes') WHERE id = ?1",
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("update should succeed");
+        assert_eq!(
+            repository
+                .list_sync_candidates(50, true)
+                .await
+                .expect("list candidates should succeed")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn adaptive_poll_schedule_gates_sync_candidates() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Hourly Source",
+                "https://hourly.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        let now = chrono::Utc::now();
+        let entries = (1..=4)
+            .map(|hours_ago| ParsedEntry {
+                id: format!("hourly-{hours_ago}"),
+                title: format!("Hourly {hours_ago}"),
+                link: format!("https://hourly.example.com/{hours_ago}"),
+                summary: None,
+                content: None,
+                published_at: Some((now - chrono::Duration::hours(hours_ago)).to_rfc3339()),
+            })
+            .collect::<Vec<_>>();
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("upsert should succeed");
+        repository
+            .update_source_sync_success(source.id, None, None)
+            .await
+            .expect("sync success should succeed");
+        repository
+            .set_source_feed_hints(source.id, Some(45), &[])
+            .await
+            .expect("hints should save");
+
+        let schedule = repository
+            .schedule_source_poll(source.id, now)
+            .await
+            .expect("schedule should succeed");
+        assert_eq!(schedule.poll_interval_secs, 45 * 60);
+        sqlx::query(
+            "UPDATE sources SET last_synced_at = datetime('now', '-20 minutes') WHERE id = ?1",
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("update should succeed");
+        assert!(repository
+            .list_sync_candidates(50, true)
+            .await
+            .expect("list candidates should succeed")
+            .is_empty());
+        assert_eq!(
+            repository
+                .list_sync_candidates(50, false)
+                .await
+                .expect("manual sync should ignore the schedule")
+                .len(),
+            1
+        );
+
+        repository
+            .set_source_settings(&SourceSettings {
+                source_id: source.id,
+                sync_interval_secs: Some(600),
+                ..SourceSettings::default()
+            })
+            .await
+            .expect("override interval");
+        assert_eq!(
+            repository
+                .list_sync_candidates(50, true)
+                .await
+                .expect("list candidates should succeed")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn first_sync_skips_entries_older_than_window() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let archive = repository
+            .upsert_source(&make_source(
+                "Archive",
+                "https://archive.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let unlimited = repository
+            .upsert_source(&make_source(
+                "Unlimited",
+                "https://all.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        repository
+            .set_first_sync_max_age_days(30)
+            .await
+            .expect("set window");
+        repository
+            .set_source_settings(&SourceSettings {
+                source_id: unlimited.id,
+                first_sync_max_age_days: Some(0),
+                ..SourceSettings::default()
+            })
+            .await
+            .expect("override window");
+        let entry = |id: &str, days_ago: i64| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some(
+                (chrono::Utc::now() - chrono::Duration::days(days_ago)).to_rfc3339(),
+            ),
+        };
+        let feed = [entry("ancient", 3650), entry("fresh", 1)];
+
+        let first = repository
+            .upsert_entries(archive.id, &feed)
+            .await
+            .expect("first sync");
+        let later = repository
+            .upsert_entries(
+                archive.id,
+                &[
+                    entry("ancient", 3650),
+                    entry("late-ancient", 3000),
+                    entry("newer", 0),
+                ],
+            )
+            .await
+            .expect("later sync");
+        let all = repository
+            .upsert_entries(unlimited.id, &feed)
+            .await
+            .expect("unlimited first sync");
+
+        assert_eq!(first.inserted, 1);
+        assert_eq!(later.inserted, 1);
+        assert_eq!(all.inserted, 2);
+        let titles = sqlx::query_scalar::<_, String>(
+            "SELECT title FROM entries WHERE source_id = ?1 ORDER BY title",
+        )
+        .bind(archive.id)
+        .fetch_all(&repository.pool)
+        .await
+        .expect("titles");
+        assert_eq!(titles, vec!["fresh", "newer"]);
+
+        let backfilled = repository
+            .upsert_backfill_entries(archive.id, &[entry("late-ancient", 3000)])
+            .await
+            .expect("backfill");
+        assert_eq!(backfilled.inserted, 1);
+
+        repository
+            .set_first_sync_max_age_days(0)
+            .await
+            .expect("clear window");
+        let reopened = repository
+            .upsert_entries(archive.id, &[entry("ancient", 3650)])
+            .await
+            .expect("sync without window");
+        assert_eq!(reopened.inserted, 1);
+        let stored_cutoff = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT first_sync_cutoff FROM sources WHERE id = ?1",
+        )
+        .bind(archive.id)
+        .fetch_one(&repository.pool)
+        .await
+        .expect("cutoff");
+        assert_eq!(stored_cutoff, None);
+    }
+
+    #[tokio::test]
+    async fn backlog_read_flag_marks_only_the_first_sync_read() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Backlog",
+                "https://backlog.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        repository
+            .mark_backlog_read_on_first_sync(std::slice::from_ref(&source.feed_url))
+            .await
+            .expect("flag source");
+        let entry = |id: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://backlog.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: None,
+        };
+
+        repository
+            .upsert_entries(source.id, &[entry("old-1"), entry("old-2")])
+            .await
+            .expect("first sync");
+        repository
+            .upsert_entries(source.id, &[entry("new")])
+            .await
+            .expect("later sync");
+        let read_states = sqlx::query_as::<_, (String, bool)>(
+            "SELECT guid, is_read FROM entries WHERE source_id = ?1 ORDER BY guid",
+        )
+        .bind(source.id)
+        .fetch_all(&repository.pool)
+        .await
+        .expect("read states");
+
+        assert_eq!(
+            read_states,
+            vec![
+                ("new".to_string(), false),
+                ("old-1".to_string(), true),
+                ("old-2".to_string(), true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn source_settings_override_sync_interval_and_translation() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Monthly Blog",
+                "https://monthly.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        sqlx::query(
+            "UPDATE sources SET last_synced_at = datetime('now', '-2 hours') WHERE id = ?1",
+        )
+        .bind(source.id)
+        .execute(&repository.pool)
+        .await
+        .expect("update should succeed");
+        assert_eq!(
+            repository
+                .list_sync_candidates(50, true)
+                .await
+                .expect("list candidates should succeed")
+                .len(),
+            1
+        );
+
+        let overrides = SourceSettings {
+            source_id: source.id,
+            sync_interval_secs: Some(86_400),
+            translate_titles: Some(false),
+            ..SourceSettings::default()
+        };
+        repository
+            .set_source_settings(&overrides)
+            .await
+            .expect("save settings should succeed");
+        assert_eq!(
+            repository
+                .get_source_settings(source.id)
+                .await
+                .expect("load settings should succeed"),
+            overrides
+        );
+        assert!(repository
+            .list_sync_candidates(50, true)
+            .await
+            .expect("list candidates should succeed")
+            .is_empty());
+
+        repository
+            .upsert_entries(
+                source.id,
+                &[ParsedEntry {
+                    id: "post-1".to_string(),
+                    title: "Hello".to_string(),
+                    link: "https://monthly.example.com/posts/1".to_string(),
+                    summary: None,
+                    content: None,
+                    published_at: None,
+                }],
+            )
+            .await
+            .expect("insert entries should succeed");
+        assert!(repository
+            .list_entries_without_translated_title(10)
+            .await
+            .expect("list untranslated should succeed")
+            .is_empty());
+        assert_eq!(
+            repository
+                .count_entries_without_translated_title()
+                .await
+                .expect("count untranslated should succeed"),
+            0
+        );
+        assert_eq!(
+            repository
+                .count_source_entries_without_translated_title(source.id)
+                .await
+                .expect("count source untranslated should succeed"),
+            1
+        );
+        assert_eq!(
+            repository
+                .list_source_entries_without_translated_title(source.id, None, 10)
+                .await
+                .expect("list source untranslated should succeed")
+                .len(),
+            1
+        );
+
+        repository
+            .set_source_settings(&SourceSettings {
+                source_id: source.id,
+                ..SourceSettings::default()
+            })
+            .await
+            .expect("clear settings should succeed");
+        assert!(repository
+            .list_source_settings()
+            .await
+            .expect("list settings should succeed")
+            .is_empty());
+        assert_eq!(
+            repository
+                .list_entries_without_translated_title(10)
+                .await
+                .expect("list untranslated should succeed")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn source_title_backfill_pages_by_id_and_includes_the_archive() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Titles",
+                "https://titles.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let entries = (1..=3)
+            .map(|index| ParsedEntry {
+                id: format!("post-{index}"),
+                title: format!("Post {index}"),
+                link: format!("https://titles.example.com/{index}"),
+                summary: None,
+                content: None,
+                published_at: None,
+            })
+            .collect::<Vec<_>>();
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("insert entries");
+        sqlx::query(
+            r#"
+            INSERT INTO entries_archive (id, source_id, guid, link, title, created_at)
+            SELECT id, source_id, guid, link, title, created_at
+            FROM entries WHERE guid = 'post-1'
+            "#,
+        )
+        .execute(&repository.pool)
+        .await
+        .expect("archive entry");
+        sqlx::query("DELETE FROM entries WHERE guid = 'post-1'")
+            .execute(&repository.pool)
+            .await
+            .expect("remove archived entry");
+
+        let first = repository
+            .list_source_entries_without_translated_title(source.id, None, 2)
+            .await
+            .expect("list untranslated");
+        let rest = repository
+            .list_source_entries_without_translated_title(source.id, Some(first[1].id), 2)
+            .await
+            .expect("list untranslated");
+        assert_eq!(
+            first
+                .iter()
+                .chain(&rest)
+                .map(|entry| entry.title.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Post 3", "Post 2", "Post 1"]
+        );
+
+        let archived = rest[0].id;
+        repository
+            .set_entry_translated_title(archived, "文章 1")
+            .await
+            .expect("translate archived title");
+        assert_eq!(
+            repository
+                .count_source_entries_without_translated_title(source.id)
+                .await
+                .expect("count untranslated"),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn database_stats_counts_rows_and_schema_version() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Stats Source",
+                "https://stats.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        repository
+            .upsert_entries(
+                source.id,
+                &[ParsedEntry {
+                    id: "stats-1".to_string(),
+                    title: "Stats entry".to_string(),
+                    link: "https://stats.example.com/posts/1".to_string(),
+                    summary: None,
+                    content: None,
+                    published_at: None,
+                }],
+            )
+            .await
+            .expect("entry insert should succeed");
+        repository
+            .increment_source_failure(source.id)
+            .await
+            .expect("failure increment should succeed");
+
+        let stats = repository
+            .database_stats()
+            .await
+            .expect("stats should succeed");
+        assert_eq!(stats.source_count, 1);
+        assert_eq!(stats.active_source_count, 1);
+        assert_eq!(stats.failing_source_count, 1);
+        assert_eq!(stats.entry_count, 1);
+        assert_eq!(stats.unread_entry_count, 1);
+        assert_eq!(stats.archived_entry_count, 0);
+        assert_eq!(stats.llm_cache_count, 0);
+        assert!(stats.schema_version.is_some());
+    }
+
+    #[tokio::test]
+    async fn migration_status_reports_applied_and_pending() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let status = repository
+            .migration_status()
+            .await
+            .expect("status should succeed");
+
+        assert!(status.pending.is_empty());
+        assert!(!status.schema_is_newer);
+        assert!(!status.read_only);
+        assert_eq!(status.applied.len(), MIGRATOR.iter().count());
+        assert_eq!(status.schema_version, Some(status.latest_known_version));
+    }
+
+    #[tokio::test]
+    async fn file_database_uses_wal_and_reads_during_open_write() {
+        let dir = tempfile::tempdir().expect("tempdir should exist");
+        let database_url = format!(
+            "sqlite://{}?mode=rwc",
+            dir.path().join("rssr.db").to_string_lossy()
+        );
+        let repository = SourceRepository::connect(&database_url)
+            .await
+            .expect("connect must succeed");
+        let journal_mode = sqlx::query_scalar::<_, String>("PRAGMA journal_mode")
+            .fetch_one(&repository.pool)
+            .await
+            .expect("journal mode should load");
+        assert_eq!(journal_mode, "wal");
+
+        let mut tx = repository.begin_write().await.expect("begin write");
+        sqlx::query("INSERT INTO sources (title, feed_url) VALUES ('Pending', 'https://pending.example.com/feed')")
+            .execute(&mut *tx)
+            .await
+            .expect("insert inside transaction");
+        let visible = tokio::time::timeout(Duration::from_secs(2), repository.list_sources())
+            .await
+            .expect("reads must not wait for the writer")
+            .expect("list sources");
+        assert!(visible.is_empty());
+        tx.commit().await.expect("commit");
+        assert_eq!(
+            repository.list_sources().await.expect("list sources").len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn newer_schema_is_rejected_and_opens_read_only() {
+        let dir = tempfile::tempdir().expect("tempdir should exist");
+        let database_url = format!(
+            "sqlite://{}?mode=rwc",
+            dir.path().join("rssr.db").to_string_lossy()
+        );
+        let repository = SourceRepository::connect(&database_url)
+            .await
+            .expect("first connect must succeed");
+        sqlx::query(
+            r#"
+            INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+            VALUES (99990101, 'from the future', 1, x'00', 0)
+            "#,
+        )
+        .execute(&repository.pool)
+        .await
+        .expect("future migration row should insert");
+        repository.pool.close().await;
+
+        let error = SourceRepository::connect(&database_url)
+            .await
+            .expect_err("newer schema must be rejected");
+        assert!(matches!(
+            error,
+            StorageError::SchemaTooNew {
+                database_version: 99990101,
+                ..
+            }
+        ));
+
+        let read_only = SourceRepository::connect_read_only(&database_url)
+            .await
+            .expect("read-only connect must succeed");
+        let status = read_only
+            .migration_status()
+            .await
+            .expect("status should succeed");
+        assert!(read_only.is_read_only());
+        assert!(status.schema_is_newer);
+        assert!(read_only.list_sources().await.is_ok());
+        assert!(read_only
+            .upsert_source(&make_source(
+                "Blocked",
+                "https://blocked.example.com/feed.xml"
+            ))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn corrupt_file_is_reported_and_degraded_mode_rejects_writes() {
+        let dir = tempfile::tempdir().expect("tempdir should exist");
+        let database_path = dir.path().join("rssr.db");
+        std::fs::write(&database_path, vec![0x5a_u8; 8192]).expect("garbage file");
+        let database_url = format!("sqlite://{}?mode=rwc", database_path.to_string_lossy());
+
+        let error = SourceRepository::connect(&database_url)
+            .await
+            .expect_err("garbage database must be rejected");
+        assert_eq!(error.fault(), Some(DatabaseFault::Corrupt));
+
+        let degraded = SourceRepository::connect_degraded()
+            .await
+            .expect("degraded connect must succeed");
+        assert!(degraded.is_read_only());
+        assert!(degraded.list_sources().await.expect("list").is_empty());
+        assert!(degraded
+            .upsert_source(&make_source(
+                "Blocked",
+                "https://blocked.example.com/feed.xml"
+            ))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn archived_entries_stay_searchable() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Archive Source",
+                "https://archive.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        repository
+            .upsert_entries(
+                source.id,
+                &[
+                    ParsedEntry {
+                        id: "old".to_string(),
+                        title: "Old research notes".to_string(),
+                        link: "https://archive.example.com/old".to_string(),
+                        summary: None,
+                        content: None,
+                        published_at: Some("2020-01-01T00:00:00Z".to_string()),
+                    },
+                    ParsedEntry {
+                        id: "old-unread".to_string(),
+                        title: "Old unread notes".to_string(),
+                        link: "https://archive.example.com/old-unread".to_string(),
+                        summary: None,
+                        content: None,
+                        published_at: Some("2020-01-02T00:00:00Z".to_string()),
+                    },
+                ],
+            )
+            .await
+            .expect("entry insert should succeed");
+        let all = repository
+            .list_entries(Some(source.id), None, false, 50)
+            .await
+            .expect("list should succeed");
+        let old = all
+            .iter()
+            .find(|entry| entry.title == "Old research notes")
+            .expect("old entry should exist");
+        repository
+            .mark_entry_read(old.id, true)
+            .await
+            .expect("mark read should succeed");
+
+        let archived = repository
+            .archive_entries_older_than(90)
+            .await
+            .expect("archive should succeed");
+        let hot = repository
+            .list_entries(Some(source.id), None, false, 50)
+            .await
+            .expect("list should succeed");
+        let searched = repository
+            .list_entries(Some(source.id), Some("research"), false, 50)
+            .await
+            .expect("search should succeed");
+        let by_id = repository
+            .get_entry_by_id(old.id)
+            .await
+            .expect("get should succeed");
+
+        repository
+            .upsert_entries(
+                source.id,
+                &[ParsedEntry {
+                    id: "old".to_string(),
+                    title: "Old research notes".to_string(),
+                    link: "https://archive.example.com/old".to_string(),
+                    summary: None,
+                    content: None,
+                    published_at: Some("2020-01-01T00:00:00Z".to_string()),
+                }],
+            )
+            .await
+            .expect("re-sync should succeed");
+        let after_resync = repository
+            .list_entries(Some(source.id), None, false, 50)
+            .await
+            .expect("list should succeed");
+
+        assert_eq!(archived, 1);
+        assert_eq!(after_resync.len(), 1);
+        assert_eq!(hot.len(), 1);
+        assert_eq!(hot[0].title, "Old unread notes");
+        assert_eq!(searched.len(), 1);
+        assert_eq!(searched[0].id, old.id);
+        assert!(by_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn large_entry_content_is_compressed_at_rest() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Compressed Source",
+                "https://zstd.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        let html = "<p>Repeated paragraph for compression.</p>\n".repeat(300);
+        repository
+            .upsert_entries(
+                source.id,
+                &[
+                    ParsedEntry {
+                        id: "big".to_string(),
+                        title: "Big".to_string(),
+                        link: "https://zstd.example.com/big".to_string(),
+                        summary: None,
+                        content: Some(html.clone()),
+                        published_at: None,
+                    },
+                    ParsedEntry {
+                        id: "small".to_string(),
+                        title: "Small".to_string(),
+                        link: "https://zstd.example.com/small".to_string(),
+                        summary: None,
+                        content: Some("<p>tiny</p>".to_string()),
+                        published_at: None,
+                    },
+                ],
+            )
+            .await
+            .expect("entry insert should succeed");
+        sqlx::query("UPDATE entries SET content = ?1 WHERE link = ?2")
+            .bind(&html)
+            .bind("https://zstd.example.com/small")
+            .execute(&repository.pool)
+            .await
+            .expect("legacy text row should be written");
+
+        let storage: Vec<(String, String, i64)> = sqlx::query_as(
+            "SELECT title, typeof(content), length(CAST(content AS BLOB)) FROM entries ORDER BY title",
+        )
+        .fetch_all(&repository.pool)
+        .await
+        .expect("storage query should succeed");
+        assert_eq!(storage[0].1, "blob");
+        assert!((storage[0].2 as usize) * 2 < html.len());
+        assert_eq!(storage[1].1, "text");
+
+        let compressed = repository
+            .compress_legacy_content(10)
+            .await
+            .expect("legacy compression should succeed");
+        let entries = repository
+            .list_entries(Some(source.id), None, false, 10)
+            .await
+            .expect("list should succeed");
+
+        assert_eq!(compressed, 1);
+        assert!(entries
+            .iter()
+            .all(|entry| entry.content.as_deref() == Some(html.as_str())));
+    }
+
+    #[tokio::test]
+    async fn unchanged_entries_are_skipped_by_fingerprint() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Seen Source",
+                "https://seen.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        let mut entries: Vec<ParsedEntry> = (0..3)
+            .map(|index| ParsedEntry {
+                id: format!("seen-{index}"),
+                title: format!("Seen {index}"),
+                link: format!("https://seen.example.com/posts/{index}"),
+                summary: None,
+                content: None,
+                published_at: None,
+            })
+            .collect();
+
+        let first = repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("first upsert should succeed");
+        let unchanged = repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("second upsert should succeed");
+        entries[1].title = "Seen 1 (edited)".to_string();
+        let edited = repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("third upsert should succeed");
+        let titles: Vec<String> = repository
+            .list_entries(Some(source.id), None, false, 10)
+            .await
+            .expect("list should succeed")
+            .into_iter()
+            .map(|entry| entry.title)
+            .collect();
+
+        assert_eq!(
+            first,
+            UpsertCounts {
+                inserted: 3,
+                updated: 0,
+                conflicted: 0
+            }
+        );
+        assert_eq!(unchanged.total(), 0);
+        assert_eq!(
+            edited,
+            UpsertCounts {
+                inserted: 0,
+                updated: 1,
+                conflicted: 0
+            }
+        );
+        assert!(titles.contains(&"Seen 1 (edited)".to_string()));
+    }
+
+    #[tokio::test]
+    async fn search_sources_filters_and_sorts() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let rust = repository
+            .upsert_source(&make_source(
+                "Rust Blog",
+                "https://blog.rust-lang.org/feed.xml",
+            ))
+            .await
+            .expect("create rust");
+        let go = repository
+            .upsert_source(&make_source("Go Blog", "https://go.dev/blog/feed.atom"))
+            .await
+            .expect("create go");
+        repository
+            .upsert_source(&NewSource {
+                category: Some("design".to_string()),
+                is_active: false,
+                ..make_source("Paused", "https://paused.example.com/rss")
+            })
+            .await
+            .expect("create paused");
+        for _ in 0..2 {
+            repository
+                .increment_source_failure(go.id)
+                .await
+                .expect("increment go");
+        }
+        repository
+            .increment_source_failure(rust.id)
+            .await
+            .expect("increment rust");
+
+        let by_url = repository
+            .search_sources(&SourceSearchFilter {
+                query: Some("rust-lang".to_string()),
+                limit: 50,
+                ..SourceSearchFilter::default()
+            })
+            .await
+            .expect("search should succeed");
+        let by_category = repository
+            .search_sources(&SourceSearchFilter {
+                query: Some("design".to_string()),
+                limit: 50,
+                ..SourceSearchFilter::default()
+            })
+            .await
+            .expect("search should succeed");
+        let failing = repository
+            .search_sources(&SourceSearchFilter {
+                is_active: Some(true),
+                failing_only: true,
+                sort_by: SourceSortField::FailureCount,
+                limit: 50,
+                ..SourceSearchFilter::default()
+            })
+            .await
+            .expect("search should succeed");
+
+        assert_eq!(by_url.len(), 1);
+        assert_eq!(by_url[0].id, rust.id);
+        assert_eq!(by_category.len(), 1);
+        assert_eq!(by_category[0].title, "Paused");
+        assert_eq!(
+            failing.iter().map(|row| row.id).collect::<Vec<_>>(),
+            vec![go.id, rust.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn sync_log_records_attempts_and_failing_sources_explain_errors() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Logged",
+                "https://logged.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        let attempt = |status: &str, http_status: u16, error: Option<&str>| SyncAttempt {
+            source_id: source.id,
+            started_at: "2026-03-01T00:00:00Z".to_string(),
+            status: status.to_string(),
+            http_status: Some(http_status),
+            entries_upserted: if error.is_some() { 0 } else { 4 },
+            error: error.map(str::to_string),
+        };
+        repository
+            .log_sync_attempt(&attempt("updated", 200, None))
+            .await
+            .expect("log success");
+        repository
+            .log_sync_attempt(&attempt("failed", 503, Some("unexpected status code: 503")))
+            .await
+            .expect("log failure");
+        repository
+            .increment_source_failure(source.id)
+            .await
+            .expect("increment failure");
+        repository
+            .record_source_sync_result(source.id, "failed", 0, Some("unexpected status code: 503"))
+            .await
+            .expect("record result");
+
+        let history = repository
+            .list_sync_log(source.id, 10)
+            .await
+            .expect("list history");
+        let failing = repository
+            .list_failing_sources(10)
+            .await
+            .expect("list failing");
+
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| (entry.status.as_str(), entry.http_status))
+                .collect::<Vec<_>>(),
+            vec![("failed", Some(503)), ("updated", Some(200))]
+        );
+        assert_eq!(history[1].entries_upserted, 4);
+        assert_eq!(failing.len(), 1);
+        assert_eq!(
+            failing[0].last_error.as_deref(),
+            Some("unexpected status code: 503")
+        );
+        assert!(failing[0].last_failed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn problem_sources_count_timeouts_since_last_success() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Slow", "https://slow.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        for (status, error) in [
+            ("timeout", Some("operation timed out")),
+            ("updated", None),
+            ("timeout", Some("operation timed out")),
+            ("timeout", Some("operation timed out")),
+        ] {
+            repository
+                .log_sync_attempt(&SyncAttempt {
+                    source_id: source.id,
+                    started_at: "2026-03-01T00:00:00Z".to_string(),
+                    status: status.to_string(),
+                    http_status: None,
+                    entries_upserted: 0,
+                    error: error.map(str::to_string),
+                })
+                .await
+                .expect("log attempt");
+        }
+        repository
+            .increment_source_failure(source.id)
+            .await
+            .expect("increment failure");
+        repository
+            .record_source_sync_result(source.id, "timeout", 0, Some("operation timed out"))
+            .await
+            .expect("record result");
+
+        let problems = repository
+            .list_problem_sources()
+            .await
+            .expect("list problems");
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].recent_timeouts, 2);
+        assert_eq!(problems[0].last_sync_status.as_deref(), Some("timeout"));
+    }
+
+    #[tokio::test]
+    async fn reset_failures_reactivates_only_auto_disabled_sources() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let flaky = repository
+            .upsert_source(&make_source("Flaky", "https://flaky.example.com/feed.xml"))
+            .await
+            .expect("create source should succeed");
+        let paused = repository
+            .upsert_source(&make_source(
+                "Paused",
+                "https://paused.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source should succeed");
+        for _ in 0..2 {
+            repository
+                .increment_source_failure(flaky.id)
+                .await
+                .expect("increment failure");
+        }
+        assert_eq!(
+            repository
+                .increment_source_failure(paused.id)
+                .await
+                .expect("increment failure"),
+            1
+        );
+        repository
+            .auto_disable_source(flaky.id, "disabled after 2 consecutive failures")
+            .await
+            .expect("auto disable");
+        repository
+            .set_sources_active(&[paused.id], false)
+            .await
+            .expect("pause source");
+
+        let reset = repository
+            .reset_source_failures(&[flaky.id, paused.id])
+            .await
+            .expect("reset failures");
+        let flaky = repository
+            .get_source_by_id(flaky.id)
+            .await
+            .expect("load source")
+            .expect("source exists");
+        let paused = repository
+            .get_source_by_id(paused.id)
+            .await
+            .expect("load source")
+            .expect("source exists");
+
+        assert_eq!(reset, 2);
+        assert_eq!((flaky.is_active, flaky.failure_count), (1, 0));
+        assert_eq!((paused.is_active, paused.failure_count), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn source_details_include_stats_sync_result_and_recent_entries() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Details",
+                "https://details.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let entries = (0..12)
+            .map(|index| ParsedEntry {
+                id: format!("details-{index}"),
+                title: format!("Entry {index}"),
+                link: format!("https://details.example.com/posts/{index}"),
+                summary: None,
+                content: None,
+                published_at: Some(format!("2026-02-{:02}T00:00:00Z", index + 1)),
+            })
+            .collect::<Vec<_>>();
+        repository
+            .upsert_entries(source.id, &entries)
+            .await
+            .expect("upsert entries");
+        repository
+            .increment_source_failure(source.id)
+            .await
+            .expect("increment failure");
+        repository
+            .record_source_sync_result(source.id, "failed", 0, Some("http status 503"))
+            .await
+            .expect("record result");
+
+        let details = repository
+            .get_source_details(source.id, 10)
+            .await
+            .expect("details should load")
+            .expect("source should exist");
+        let missing = repository
+            .get_source_details(source.id + 1, 10)
+            .await
+            .expect("details should load");
+
+        assert_eq!(details.source.failure_count, 1);
+        assert_eq!(details.stats.entry_count, 12);
+        assert_eq!(details.stats.unread_count, 12);
+        assert_eq!(
+            details.stats.latest_published_at.as_deref(),
+            Some("2026-02-12T00:00:00Z")
+        );
+        assert_eq!(
+            details.last_sync.last_sync_status.as_deref(),
+            Some("failed")
+        );
+        assert_eq!(
+            details.last_sync.last_error.as_deref(),
+            Some("http status 503")
+        );
+        assert_eq!(details.recent_entries.len(), 10);
+        assert_eq!(details.recent_entries[0].title, "Entry 11");
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn source_color_and_icon_survive_upsert() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Styled",
+                "https://styled.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        repository
+            .set_source_color(source.id, Some("#ff6600"))
+            .await
+            .expect("set color");
+        repository
+            .set_source_custom_icon(source.id, Some("🦀"))
+            .await
+            .expect("set icon");
+        let updated = repository
+            .upsert_source(&make_source(
+                "Styled v2",
+                "https://styled.example.com/feed.xml",
+            ))
+            .await
+            .expect("re-upsert source");
+
+        assert_eq!(updated.color.as_deref(), Some("#ff6600"));
+        assert_eq!(updated.custom_icon.as_deref(), Some("🦀"));
+
+        repository
+            .set_source_color(source.id, None)
+            .await
+            .expect("clear color");
+        let cleared = repository
+            .get_source_by_id(source.id)
+            .await
+            .expect("load source")
+            .expect("source exists");
+        assert!(cleared.color.is_none());
+    }
+
+    #[tokio::test]
+    async fn stale_sources_flag_quiet_gone_and_parked_feeds() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let fresh = repository
+            .upsert_source(&make_source("Fresh", "https://fresh.example.com/feed.xml"))
+            .await
+            .expect("create fresh");
+        let quiet = repository
+            .upsert_source(&make_source("Quiet", "https://quiet.example.com/feed.xml"))
+            .await
+            .expect("create quiet");
+        let gone = repository
+            .upsert_source(&make_source("Gone", "https://gone.example.com/feed.xml"))
+            .await
+            .expect("create gone");
+        let parked = repository
+            .upsert_source(&make_source(
+                "Parked",
+                "https://parked.example.com/feed.xml",
+            ))
+            .await
+            .expect("create parked");
+        let entry = |id: &str, published_at: String| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some(published_at),
+        };
+        repository
+            .upsert_entries(fresh.id, &[entry("fresh", chrono::Utc::now().to_rfc3339())])
+            .await
+            .expect("fresh entries");
+        repository
+            .upsert_entries(
+                quiet.id,
+                &[entry("quiet", "2024-01-01T00:00:00Z".to_string())],
+            )
+            .await
+            .expect("quiet entries");
+        repository
+            .upsert_entries(gone.id, &[entry("gone", chrono::Utc::now().to_rfc3339())])
+            .await
+            .expect("gone entries");
+        for _ in 0..3 {
+            repository
+                .record_source_health(gone.id, Some(410), false)
+                .await
+                .expect("record gone");
+        }
+        repository
+            .record_source_health(parked.id, Some(200), true)
+            .await
+            .expect("record parked");
+
+        let stale = repository
+            .list_stale_sources(180, 3)
+            .await
+            .expect("stale sources should load");
+
+        let ids = stale.iter().map(|row| row.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![gone.id, parked.id, quiet.id]);
+        assert!(stale[0].repeatedly_gone && !stale[0].no_recent_entries);
+        assert_eq!(stale[0].last_http_status, Some(410));
+        assert!(stale[1].parked_domain);
+        assert!(stale[2].no_recent_entries);
+
+        let deleted = repository
+            .delete_sources(&[quiet.id, parked.id])
+            .await
+            .expect("bulk delete");
+        assert_eq!(deleted.sources, 2);
+    }
+
+    #[tokio::test]
+    async fn replacing_feed_url_resets_sync_state() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Moved", "https://moved.example.com/feed"))
+            .await
+            .expect("create source");
+        let mut gone_count = 0;
+        for _ in 0..2 {
+            gone_count = repository
+                .record_source_health(source.id, Some(404), false)
+                .await
+                .expect("record gone");
+        }
+        repository
+            .set_source_replacement_feed(source.id, Some("https://moved.example.com/rss.xml"))
+            .await
+            .expect("set replacement");
+        repository
+            .replace_source_feed_url(source.id, "https://moved.example.com/rss.xml")
+            .await
+            .expect("replace feed url");
+
+        let details = repository
+            .get_source_details(source.id, 1)
+            .await
+            .expect("details should load")
+            .expect("source exists");
+        assert_eq!(gone_count, 2);
+        assert_eq!(details.source.feed_url, "https://moved.example.com/rss.xml");
+        assert!(details.source.last_synced_at.is_none());
+        assert!(details.last_sync.replacement_feed_url.is_none());
+    }
+
+    #[tokio::test]
+    async fn opening_an_entry_records_the_event_and_optionally_marks_it_read() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Opens", "https://opens.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        repository
+            .upsert_entries(
+                source.id,
+                &[ParsedEntry {
+                    id: "post".to_string(),
+                    title: "Post".to_string(),
+                    link: "https://opens.example.com/post".to_string(),
+                    summary: None,
+                    content: None,
+                    published_at: None,
+                }],
+            )
+            .await
+            .expect("upsert entries");
+        let entry_id = repository
+            .list_entries(Some(source.id), None, false, 10)
+            .await
+            .expect("entries should load")[0]
+            .id;
+
+        assert_eq!(
+            repository
+                .record_entry_open(entry_id, true, false)
+                .await
+                .expect("record open"),
+            1
+        );
+        let entry = repository
+            .get_entry_by_id(entry_id)
+            .await
+            .expect("load entry")
+            .expect("entry exists");
+        assert_eq!(entry.is_read, 0);
+        repository
+            .record_entry_open(entry_id, false, true)
+            .await
+            .expect("record second open");
+        assert_eq!(
+            repository
+                .record_entry_open(entry_id + 100, true, true)
+                .await
+                .expect("record missing entry"),
+            0
+        );
+
+        assert_eq!(
+            repository
+                .count_entry_opens(entry_id)
+                .await
+                .expect("count opens"),
+            2
+        );
+        let entry = repository
+            .get_entry_by_id(entry_id)
+            .await
+            .expect("load entry")
+            .expect("entry exists");
+        assert_eq!(entry.is_read, 1);
+    }
+
+    #[tokio::test]
+    async fn entry_fulltext_is_cached_compressed_and_replaced() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        assert!(repository
+            .get_entry_fulltext(1)
+            .await
+            .expect("lookup should succeed")
+            .is_none());
+
+        let mut article = ReaderArticle {
+            url: "https://example.com/post".to_string(),
+            title: Some("Post".to_string()),
+            content_html: "<p>long paragraph</p>".repeat(100),
+            text_length: 1400,
+        };
+        repository
+            .set_entry_fulltext(1, &article)
+            .await
+            .expect("cache should succeed");
+        let stored_bytes = sqlx::query_scalar::<_, i64>(
+            "SELECT LENGTH(content_html) FROM entry_fulltext WHERE entry_id = 1",
+        )
+        .fetch_one(&repository.pool)
+        .await
+        .expect("length should load");
+        assert!((stored_bytes as usize) < article.content_html.len());
+
+        article.content_html = "<p>updated</p>".to_string();
+        repository
+            .set_entry_fulltext(1, &article)
+            .await
+            .expect("replace should succeed");
+        let cached = repository
+            .get_entry_fulltext(1)
+            .await
+            .expect("lookup should succeed")
+            .expect("fulltext cached");
+        assert_eq!(cached.content_html, "<p>updated</p>");
+        assert_eq!(cached.title.as_deref(), Some("Post"));
+    }
+
+    #[tokio::test]
+    async fn glossary_terms_upsert_case_insensitively() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let term = |term: &str, translation: &str| GlossaryTerm {
+            id: 0,
+            term: term.to_string(),
+            translation: translation.to_string(),
+        };
+        let first = repository
+            .save_glossary_term(&term("borrow checker", "借用检查"))
+            .await
+            .expect("save term");
+        let second = repository
+            .save_glossary_term(&term("Borrow Checker", "借用检查器"))
+            .await
+            .expect("update term");
+        let third = repository
+            .save_glossary_term(&term("Tokio", "Tokio"))
+            .await
+            .expect("save second term");
+
+        assert_eq!(first, second);
+        assert_eq!(
+            repository.list_glossary_terms().await.expect("list terms"),
+            vec![
+                GlossaryTerm {
+                    id: first,
+                    term: "Borrow Checker".to_string(),
+                    translation: "借用检查器".to_string(),
+                },
+                GlossaryTerm {
+                    id: third,
+                    term: "Tokio".to_string(),
+                    translation: "Tokio".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            repository
+                .delete_glossary_term(first)
+                .await
+                .expect("delete"),
+            1
+        );
+        assert_eq!(
+            repository
+                .list_glossary_terms()
+                .await
+                .expect("list terms")
+                .len(),
+            1
+        );
     }
 
     #[tokio::test]
-    async fn upsert_source_is_idempotent_for_same_feed_url() {
+    async fn notification_rules_round_trip_and_cascade_with_source() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-        let first = repository
+        let source = repository
             .upsert_source(&make_source(
-                "Hacker News",
-                "https://news.ycombinator.com/rss",
+                "Alerts",
+                "https://alerts.example.com/feed.xml",
             ))
             .await
-            .expect("first upsert must succeed");
+            .expect("create source");
+        let mut rule = NotificationRule {
+            name: "Outages".to_string(),
+            keywords: vec!["outage".to_string()],
+            source_id: Some(source.id),
+            is_enabled: true,
+            ..NotificationRule::default()
+        };
+        rule.id = repository
+            .create_notification_rule(&rule)
+            .await
+            .expect("create rule");
+        rule.is_enabled = false;
+        assert_eq!(
+            repository
+                .update_notification_rule(&rule)
+                .await
+                .expect("update rule"),
+            1
+        );
+        assert_eq!(
+            repository
+                .list_notification_rules()
+                .await
+                .expect("list rules"),
+            vec![rule.clone()]
+        );
 
-        let second = repository
-            .upsert_source(&make_source(
-                "HN Updated",
-                "https://news.ycombinator.com/rss",
-            ))
+        repository
+            .delete_source(source.id)
             .await
-            .expect("second upsert must succeed");
+            .expect("delete source");
+        assert!(repository
+            .list_notification_rules()
+            .await
+            .expect("list rules")
+            .is_empty());
+        assert_eq!(
+            repository
+                .delete_notification_rule(rule.id)
+                .await
+                .expect("delete rule"),
+            0
+        );
+    }
 
-        let all = repository.list_sources().await.expect("list must succeed");
+    #[tokio::test]
+    async fn starred_entries_are_listed_newest_first() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("Stars", "https://stars.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let entry = |id: &str, published_at: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://stars.example.com/{id}"),
+            summary: Some(format!("summary of {id}")),
+            content: None,
+            published_at: Some(published_at.to_string()),
+        };
+        repository
+            .upsert_entries(
+                source.id,
+                &[
+                    entry("old", "2026-01-01T00:00:00Z"),
+                    entry("new", "2026-02-01T00:00:00Z"),
+                    entry("plain", "2026-03-01T00:00:00Z"),
+                ],
+            )
+            .await
+            .expect("upsert entries");
+        let entries = repository
+            .list_entries(Some(source.id), None, false, 10)
+            .await
+            .expect("entries should load");
+        for entry in entries.iter().filter(|entry| entry.title != "plain") {
+            repository
+                .set_entry_starred(entry.id, true)
+                .await
+                .expect("star entry");
+        }
 
-        assert_eq!(all.len(), 1);
-        assert_eq!(first.id, second.id);
-        assert_eq!(all[0].title, "HN Updated");
+        let starred = repository
+            .list_starred_entries(10, 0)
+            .await
+            .expect("starred entries should load");
+        let titles = starred
+            .iter()
+            .map(|entry| entry.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["new", "old"]);
+        assert_eq!(starred[0].source_title, "Stars");
+
+        let second_page = repository
+            .list_starred_entries(1, 1)
+            .await
+            .expect("starred page should load");
+        assert_eq!(second_page.len(), 1);
+        assert_eq!(second_page[0].title, "old");
     }
 
     #[tokio::test]
-    async fn delete_source_removes_row() {
+    async fn entry_histogram_groups_by_local_day() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-        let created = repository
-            .upsert_source(&make_source(
-                "Rust Blog",
-                "https://blog.rust-lang.org/feed.xml",
-            ))
+        let source = repository
+            .upsert_source(&make_source("Days", "https://days.example.com/feed.xml"))
             .await
-            .expect("create must succeed");
+            .expect("create source");
+        let now = chrono::Utc::now();
+        let entry = |id: &str, published_at: chrono::DateTime<chrono::Utc>| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://days.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some(published_at.to_rfc3339()),
+        };
+        repository
+            .upsert_entries(
+                source.id,
+                &[
+                    entry("today-1", now),
+                    entry("today-2", now),
+                    entry("last-week", now - chrono::Duration::days(6)),
+                    entry("ancient", now - chrono::Duration::days(400)),
+                ],
+            )
+            .await
+            .expect("upsert entries");
 
-        let affected = repository
-            .delete_source(created.id)
+        let buckets = repository
+            .entry_date_histogram(Some(source.id), None, false, 30, 0)
             .await
-            .expect("delete must succeed");
-        let all = repository.list_sources().await.expect("list must succeed");
+            .expect("histogram should load");
 
-        assert_eq!(affected, 1);
-        assert!(all.is_empty());
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].day, now.format("%Y-%m-%d").to_string());
+        assert_eq!(buckets[0].total_count, 2);
+        assert_eq!(buckets[0].unread_count, 2);
+        assert_eq!(buckets[1].total_count, 1);
     }
 
     #[tokio::test]
-    async fn set_sources_active_updates_batch_rows() {
+    async fn title_samples_split_recent_and_baseline_windows() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-        let first = repository
-            .upsert_source(&make_source("A", "https://a.com/feed.xml"))
+        let source = repository
+            .upsert_source(&make_source(
+                "Trends",
+                "https://trends.example.com/feed.xml",
+            ))
             .await
-            .expect("create A");
-        let second = repository
-            .upsert_source(&make_source("B", "https://b.com/feed.xml"))
+            .expect("create source");
+        let now = chrono::Utc::now();
+        let entry = |id: &str, days_ago: i64| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://trends.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some((now - chrono::Duration::days(days_ago)).to_rfc3339()),
+        };
+        repository
+            .upsert_entries(
+                source.id,
+                &[
+                    entry("fresh", 1),
+                    entry("baseline", 10),
+                    entry("expired", 90),
+                ],
+            )
             .await
-            .expect("create B");
+            .expect("upsert entries");
 
-        let affected = repository
-            .set_sources_active(&[first.id, second.id], false)
-            .await
-            .expect("batch update should succeed");
-        let rows = repository
-            .list_sources()
+        let samples = repository
+            .list_title_samples(3, 27)
             .await
-            .expect("list should succeed");
+            .expect("samples should load");
 
-        assert_eq!(affected, 2);
-        assert!(rows.iter().all(|row| row.is_active == 0));
+        assert_eq!(samples.len(), 2);
+        assert!(samples
+            .iter()
+            .any(|sample| sample.title == "fresh" && sample.is_recent));
+        assert!(samples
+            .iter()
+            .any(|sample| sample.title == "baseline" && !sample.is_recent));
     }
 
     #[tokio::test]
-    async fn e2e_import_then_delete_flow() {
+    async fn mark_read_up_to_covers_entries_above_the_anchor() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-        let opml = include_str!("../../../../fixtures/import-samples/hackerNewsStars.xml");
-        let parsed_sources = parse_opml(opml).expect("opml parse should succeed");
-        let preview = build_import_preview(parsed_sources, &HashSet::new());
-        let batch: Vec<NewSource> = preview
-            .new_sources
-            .into_iter()
-            .take(5)
-            .map(|source| NewSource {
-                title: source.title,
-                site_url: source.site_url,
-                feed_url: source.feed_url,
-                category: source.category,
-                is_active: true,
-            })
-            .collect();
-
+        let source = repository
+            .upsert_source(&make_source(
+                "Scroll",
+                "https://scroll.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let entry = |id: &str, published_at: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://scroll.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some(published_at.to_string()),
+        };
         repository
-            .upsert_sources_batch(&batch)
+            .upsert_entries(
+                source.id,
+                &[
+                    entry("newest", "2026-03-03T00:00:00Z"),
+                    entry("middle", "2026-03-02T00:00:00Z"),
+                    entry("oldest", "2026-03-01T00:00:00Z"),
+                ],
+            )
             .await
-            .expect("batch upsert should succeed");
-        let current = repository
-            .list_sources()
+            .expect("upsert entries");
+        let listed = repository
+            .list_entries(Some(source.id), None, false, 10)
             .await
-            .expect("list should succeed");
-        let deleted = repository
-            .delete_source(current[0].id)
+            .expect("entries should load");
+        let middle = listed
+            .iter()
+            .find(|entry| entry.title == "middle")
+            .expect("middle entry exists");
+
+        let marked = repository
+            .mark_entries_read_up_to(Some(source.id), None, middle.id)
             .await
-            .expect("delete should succeed");
-        let after_delete = repository
-            .list_sources()
+            .expect("batch mark should succeed");
+        let unread = repository
+            .list_entries(Some(source.id), None, true, 10)
             .await
-            .expect("list should succeed");
+            .expect("unread entries should load");
 
-        assert_eq!(current.len(), 5);
-        assert_eq!(deleted, 1);
-        assert_eq!(after_delete.len(), 4);
+        assert_eq!(marked, 2);
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].title, "oldest");
     }
 
     #[tokio::test]
-    async fn entry_upsert_and_read_filter_flow() {
+    async fn flagged_entry_protection_skips_starred_entries_in_bulk_operations() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
         let source = repository
-            .upsert_source(&make_source(
-                "Reader Source",
-                "https://reader.example.com/feed.xml",
-            ))
+            .upsert_source(&make_source("Keep", "https://keep.example.com/feed.xml"))
             .await
-            .expect("source create should succeed");
-        let entries = vec![
-            ParsedEntry {
-                id: "entry-1".to_string(),
-                title: "Rust release".to_string(),
-                link: "https://reader.example.com/posts/1".to_string(),
-                summary: Some("Rust update".to_string()),
-                content: Some("content 1".to_string()),
-                published_at: Some("2026-02-24T00:00:00Z".to_string()),
-            },
-            ParsedEntry {
-                id: "entry-2".to_string(),
-                title: "AI news".to_string(),
-                link: "https://reader.example.com/posts/2".to_string(),
-                summary: Some("AI summary".to_string()),
-                content: Some("content 2".to_string()),
-                published_at: Some("2026-02-24T01:00:00Z".to_string()),
-            },
-        ];
+            .expect("create source");
+        let entry = |id: &str, published_at: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://keep.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some(published_at.to_string()),
+        };
         repository
-            .upsert_entries(source.id, &entries)
+            .upsert_entries(
+                source.id,
+                &[
+                    entry("starred", "2020-01-02T00:00:00Z"),
+                    entry("plain", "2020-01-01T00:00:00Z"),
+                ],
+            )
             .await
-            .expect("entry upsert should succeed");
-
-        let all = repository
-            .list_entries(Some(source.id), None, false, 50)
+            .expect("upsert entries");
+        let listed = repository
+            .list_entries(Some(source.id), None, false, 10)
             .await
-            .expect("list all should succeed");
-        let rust_only = repository
-            .list_entries(Some(source.id), Some("Rust"), false, 50)
+            .expect("entries should load");
+        let starred = listed
+            .iter()
+            .find(|entry| entry.title == "starred")
+            .expect("starred entry exists");
+        let plain = listed
+            .iter()
+            .find(|entry| entry.title == "plain")
+            .expect("plain entry exists");
+        repository
+            .set_entry_starred(starred.id, true)
             .await
-            .expect("search should succeed");
+            .expect("star entry");
+        repository
+            .set_flagged_entry_protection(true)
+            .await
+            .expect("enable protection");
+
         let marked = repository
-            .mark_entry_read(all[0].id, true)
+            .mark_entries_read_up_to(Some(source.id), None, plain.id)
             .await
-            .expect("mark read should succeed");
-        let unread = repository
-            .list_entries(Some(source.id), None, true, 50)
+            .expect("batch mark should succeed");
+        repository
+            .mark_entry_read(starred.id, true)
             .await
-            .expect("unread filter should succeed");
+            .expect("explicit mark read");
+        let archived = repository
+            .archive_entries_older_than(30)
+            .await
+            .expect("archive should succeed");
 
-        assert_eq!(all.len(), 2);
-        assert_eq!(rust_only.len(), 1);
         assert_eq!(marked, 1);
-        assert_eq!(unread.len(), 1);
+        assert_eq!(archived, 1);
+        assert_eq!(
+            repository
+                .list_entries(Some(source.id), None, false, 10)
+                .await
+                .expect("entries should load")
+                .len(),
+            1
+        );
     }
 
     #[tokio::test]
-    async fn list_entries_respects_limit_for_large_dataset() {
+    async fn entry_search_applies_query_operators() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-        let source = repository
+        let rust = repository
             .upsert_source(&make_source(
-                "Perf Source",
-                "https://perf.example.com/feed.xml",
+                "Rust Blog",
+                "https://blog.rust-lang.org/feed.xml",
             ))
             .await
-            .expect("source create should succeed");
-        let entries: Vec<ParsedEntry> = (0..120)
-            .map(|index| ParsedEntry {
-                id: format!("entry-{index}"),
-                title: format!("Entry {index}"),
-                link: format!("https://perf.example.com/posts/{index}"),
-                summary: Some(format!("summary {index}")),
-                content: Some(format!("content {index}")),
-                published_at: Some("2026-02-24T00:00:00Z".to_string()),
+            .expect("create rust source");
+        let other = repository
+            .upsert_source(&NewSource {
+                category: Some("news".to_string()),
+                ..make_source("Daily News", "https://news.example.com/feed.xml")
             })
-            .collect();
+            .await
+            .expect("create news source");
+        let entry = |id: &str, title: &str, published_at: &str| ParsedEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            link: format!("https://example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some(published_at.to_string()),
+        };
+        repository
+            .upsert_entries(
+                rust.id,
+                &[
+                    entry("r1", "Announcing async closures", "2026-02-10T00:00:00Z"),
+                    entry("r2", "Async in traits recap", "2026-01-10T00:00:00Z"),
+                ],
+            )
+            .await
+            .expect("rust entries");
+        repository
+            .upsert_entries(
+                other.id,
+                &[entry(
+                    "n1",
+                    "Async closures explained",
+                    "2026-02-11T00:00:00Z",
+                )],
+            )
+            .await
+            .expect("news entries");
+
+        let search = |query: &'static str| {
+            let repository = repository.clone();
+            async move {
+                repository
+                    .list_entries(None, Some(query), false, 10)
+                    .await
+                    .expect("search should succeed")
+                    .into_iter()
+                    .map(|entry| entry.title)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(
+            search(r#""async closures" source:rust"#).await,
+            vec!["Announcing async closures"]
+        );
+        assert_eq!(
+            search("async tag:news").await,
+            vec!["Async closures explained"]
+        );
+        assert_eq!(
+            search("async before:2026-02-01").await,
+            vec!["Async in traits recap"]
+        );
+        assert_eq!(search("is:starred").await, Vec::<String>::new());
+    }
 
+    #[tokio::test]
+    async fn cjk_search_matches_bigrams_folded_text_and_pinyin() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source("中文", "https://cjk.example.com/feed.xml"))
+            .await
+            .expect("create source");
+        let entry = |id: &str, title: &str, summary: &str| ParsedEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            link: format!("https://cjk.example.com/{id}"),
+            summary: Some(summary.to_string()),
+            content: None,
+            published_at: Some("2026-03-01T00:00:00Z".to_string()),
+        };
         repository
-            .upsert_entries(source.id, &entries)
+            .upsert_entries(
+                source.id,
+                &[
+                    entry("async", "异步运行时的未来", "<p>深入理解Tokio调度</p>"),
+                    entry("release", "Rust 发布说明", "<p>新版本</p>"),
+                ],
+            )
             .await
-            .expect("entry upsert should succeed");
-        let limited = repository
-            .list_entries(Some(source.id), None, false, 50)
+            .expect("upsert entries");
+        let titles = |search: &'static str| {
+            let repository = &repository;
+            async move {
+                repository
+                    .list_entries(None, Some(search), false, 10)
+                    .await
+                    .expect("search")
+                    .into_iter()
+                    .map(|entry| entry.title)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(titles("运行时").await, vec!["异步运行时的未来"]);
+        assert_eq!(titles("时 tokio").await, vec!["异步运行时的未来"]);
+        assert_eq!(titles("ＲＵＳＴ 说明").await, vec!["Rust 发布说明"]);
+        assert!(titles("运行说明").await.is_empty());
+        assert!(titles("yibu").await.is_empty());
+
+        repository
+            .set_search_pinyin(true)
             .await
-            .expect("list should succeed");
+            .expect("enable pinyin");
+        assert_eq!(titles("yibu").await, vec!["异步运行时的未来"]);
+        assert_eq!(titles("fbsm").await, vec!["Rust 发布说明"]);
 
-        assert_eq!(limited.len(), 50);
+        sqlx::query("UPDATE entries SET title = '稳定版发布' WHERE guid = 'release'")
+            .execute(&repository.pool)
+            .await
+            .expect("edit title");
+        assert_eq!(titles("稳定版").await, vec!["稳定版发布"]);
+        assert!(titles("说明").await.is_empty());
     }
 
     #[tokio::test]
-    async fn settings_and_llm_cache_roundtrip() {
+    async fn catchup_candidates_exclude_read_and_skipped_entries() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-
+        let source = repository
+            .upsert_source(&make_source(
+                "Catchup",
+                "https://catchup.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let entry = |id: &str, day: u32| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://catchup.example.com/{id}"),
+            summary: Some(format!("<p>{id} summary</p>")),
+            content: None,
+            published_at: Some(format!("2026-03-{day:02}T00:00:00Z")),
+        };
         repository
-            .set_setting(
-                "llm_config",
-                "{\"base_url\":\"https://api.deepseek.com/v1\"}",
+            .upsert_entries(
+                source.id,
+                &[entry("read", 1), entry("skipped", 2), entry("fresh", 3)],
             )
             .await
-            .expect("set setting should succeed");
-        let setting = repository
-            .get_setting("llm_config")
+            .expect("upsert entries");
+        let listed = repository
+            .list_entries(Some(source.id), None, false, 10)
             .await
-            .expect("get setting should succeed")
-            .expect("setting should exist");
-        assert!(setting.contains("deepseek"));
-
+            .expect("entries should load");
+        let id_of = |title: &str| {
+            listed
+                .iter()
+                .find(|entry| entry.title == title)
+                .expect("entry exists")
+                .id
+        };
         repository
-            .set_llm_cache("summary", "deepseek-chat", "abc", "cached text")
+            .mark_entry_read(id_of("read"), true)
             .await
-            .expect("set cache should succeed");
-        let cached = repository
-            .get_llm_cache("summary", "deepseek-chat", "abc")
+            .expect("mark read");
+
+        let candidates = repository
+            .list_catchup_candidates(10)
             .await
-            .expect("get cache should succeed");
-        assert_eq!(cached.as_deref(), Some("cached text"));
+            .expect("candidates");
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|candidate| candidate.title.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fresh", "skipped"]
+        );
+        assert_eq!(candidates[0].text, "<p>fresh summary</p>");
+        assert!((candidates[0].source_read_ratio - 1.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(
+            repository
+                .mark_entries_skipped(&[id_of("skipped"), id_of("read")])
+                .await
+                .expect("skip"),
+            1
+        );
+        let candidates = repository
+            .list_catchup_candidates(10)
+            .await
+            .expect("candidates");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].title, "fresh");
+
+        assert_eq!(
+            repository
+                .clear_skipped_entries()
+                .await
+                .expect("reset skips"),
+            1
+        );
+        assert_eq!(
+            repository
+                .list_catchup_candidates(10)
+                .await
+                .expect("candidates")
+                .len(),
+            2
+        );
     }
 
     #[tokio::test]
-    async fn entry_title_translation_roundtrip() {
+    async fn recently_read_lists_entries_by_read_time() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
         let source = repository
             .upsert_source(&make_source(
-                "Translate Source",
-                "https://translate.example.com/feed.xml",
+                "History",
+                "https://history.example.com/feed.xml",
             ))
             .await
-            .expect("source create should succeed");
-        let entries = vec![ParsedEntry {
-            id: "entry-1".to_string(),
-            title: "A long English title".to_string(),
-            link: "https://translate.example.com/posts/1".to_string(),
+            .expect("create source");
+        let entry = |id: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://history.example.com/{id}"),
             summary: None,
             content: None,
-            published_at: Some("2026-02-24T00:00:00Z".to_string()),
-        }];
+            published_at: Some("2026-03-01T00:00:00Z".to_string()),
+        };
         repository
-            .upsert_entries(source.id, &entries)
+            .upsert_entries(
+                source.id,
+                &[entry("read"), entry("unread"), entry("undone")],
+            )
             .await
-            .expect("entry insert should succeed");
+            .expect("upsert entries");
+        let listed = repository
+            .list_entries(Some(source.id), None, false, 10)
+            .await
+            .expect("entries should load");
+        for entry in &listed {
+            if entry.title != "unread" {
+                repository
+                    .mark_entry_read(entry.id, true)
+                    .await
+                    .expect("mark read");
+            }
+        }
+        let undone = listed
+            .iter()
+            .find(|entry| entry.title == "undone")
+            .expect("undone entry exists");
+        repository
+            .mark_entry_read(undone.id, false)
+            .await
+            .expect("mark unread");
 
-        let untranslated = repository
-            .list_entries_without_translated_title(20)
+        let history = repository
+            .list_recently_read(10)
             .await
-            .expect("list untranslated should succeed");
-        assert_eq!(untranslated.len(), 1);
+            .expect("history should load");
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].entry.title, "read");
+        assert!(history[0].read_at.is_some());
 
+        let unread = listed
+            .iter()
+            .find(|entry| entry.title == "unread")
+            .expect("unread entry exists");
         repository
-            .set_entry_translated_title(untranslated[0].id, "中文标题")
+            .mark_entries_read_up_to(Some(source.id), None, unread.id)
             .await
-            .expect("set translated title should succeed");
-        let untranslated_after = repository
-            .list_entries_without_translated_title(20)
+            .expect("mark read up to");
+        let history = repository
+            .list_recently_read(10)
             .await
-            .expect("list untranslated should succeed");
-        assert!(untranslated_after.is_empty());
+            .expect("history should load");
+        assert!(history
+            .iter()
+            .any(|item| item.entry.title == "unread" && item.read_at.is_some()));
     }
 
     #[tokio::test]
-    async fn untranslated_entries_are_ordered_by_time_descending() {
+    async fn undo_restores_the_last_bulk_mark_read() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
         let source = repository
-            .upsert_source(&make_source(
-                "Order Source",
-                "https://order.example.com/feed.xml",
-            ))
+            .upsert_source(&make_source("Undo", "https://undo.example.com/feed.xml"))
             .await
-            .expect("source create should succeed");
-        let entries = vec![
-            ParsedEntry {
-                id: "entry-new".to_string(),
-                title: "Newer title".to_string(),
-                link: "https://order.example.com/posts/new".to_string(),
-                summary: None,
-                content: None,
-                published_at: Some("2026-02-24T02:00:00Z".to_string()),
-            },
-            ParsedEntry {
-                id: "entry-old".to_string(),
-                title: "Older title".to_string(),
-                link: "https://order.example.com/posts/old".to_string(),
-                summary: None,
-                content: None,
-                published_at: Some("2026-02-24T00:00:00Z".to_string()),
-            },
-        ];
+            .expect("create source");
+        let entry = |id: &str, published_at: &str| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://undo.example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: Some(published_at.to_string()),
+        };
         repository
-            .upsert_entries(source.id, &entries)
+            .upsert_entries(
+                source.id,
+                &[
+                    entry("newer", "2026-03-02T00:00:00Z"),
+                    entry("older", "2026-03-01T00:00:00Z"),
+                    entry("already", "2026-02-01T00:00:00Z"),
+                ],
+            )
             .await
-            .expect("entry insert should succeed");
+            .expect("upsert entries");
+        let listed = repository
+            .list_entries(Some(source.id), None, false, 10)
+            .await
+            .expect("entries should load");
+        let already = listed
+            .iter()
+            .find(|entry| entry.title == "already")
+            .expect("entry exists");
+        repository
+            .mark_entry_read(already.id, true)
+            .await
+            .expect("mark read");
+        let marked = repository
+            .mark_entries_read_up_to(Some(source.id), None, already.id)
+            .await
+            .expect("bulk mark should succeed");
 
-        let untranslated = repository
-            .list_entries_without_translated_title(20)
+        let undone = repository
+            .undo_last_read_operation()
             .await
-            .expect("list untranslated should succeed");
-        assert_eq!(untranslated.len(), 2);
-        assert_eq!(untranslated[0].title, "Newer title");
-        assert_eq!(untranslated[1].title, "Older title");
+            .expect("undo should succeed")
+            .expect("operation should exist");
+        let unread = repository
+            .list_entries(Some(source.id), None, true, 10)
+            .await
+            .expect("unread entries should load");
+
+        assert_eq!(marked, 2);
+        assert_eq!(undone.entry_count, 2);
+        assert_eq!(unread.len(), 2);
+        assert!(repository
+            .undo_last_read_operation()
+            .await
+            .expect("second undo should succeed")
+            .is_none());
     }
 
     #[tokio::test]
-    async fn untranslated_entries_follow_global_time_order() {
+    async fn mark_scope_read_by_age_category_and_source() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-        let source_a = repository
-            .upsert_source(&make_source("Source A", "https://a.example.com/feed.xml"))
+        let tech = repository
+            .upsert_source(&make_source("Tech", "https://tech.example.com/feed.xml"))
             .await
-            .expect("source A create should succeed");
-        let source_b = repository
-            .upsert_source(&make_source("Source B", "https://b.example.com/feed.xml"))
+            .expect("create source");
+        let news = repository
+            .upsert_source(&NewSource {
+                category: Some("news".to_string()),
+                ..make_source("News", "https://news.example.com/feed.xml")
+            })
             .await
-            .expect("source B create should succeed");
+            .expect("create source");
+        let entry = |id: &str, published_at: Option<&str>| ParsedEntry {
+            id: id.to_string(),
+            title: id.to_string(),
+            link: format!("https://example.com/{id}"),
+            summary: None,
+            content: None,
+            published_at: published_at.map(str::to_string),
+        };
+        for source_id in [tech.id, news.id] {
+            repository
+                .upsert_entries(
+                    source_id,
+                    &[
+                        entry("old", Some("2020-01-01T00:00:00Z")),
+                        entry("fresh", None),
+                    ],
+                )
+                .await
+                .expect("upsert entries");
+        }
+        let unread = |source_id| {
+            let repository = repository.clone();
+            async move {
+                repository
+                    .list_entries(Some(source_id), None, true, 10)
+                    .await
+                    .expect("unread entries should load")
+                    .len()
+            }
+        };
+
+        let history = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM read_operations")
+                .fetch_one(&repository.pool)
+                .await
+                .expect("history")
+        };
+        let before = history().await;
+        let aged = repository
+            .sweep_read_older_than(30)
+            .await
+            .expect("age sweep should succeed");
+        assert_eq!(aged, 2);
+        assert_eq!(unread(tech.id).await, 1);
+        assert_eq!(history().await, before);
+
+        let by_category = repository
+            .mark_scope_read(ReadScope::Category(" news "))
+            .await
+            .expect("category mark should succeed");
+        assert_eq!(by_category, 1);
+        assert_eq!(unread(news.id).await, 0);
+        assert_eq!(unread(tech.id).await, 1);
+
+        let by_source = repository
+            .mark_scope_read(ReadScope::Source(tech.id))
+            .await
+            .expect("source mark should succeed");
+        assert_eq!(by_source, 1);
+        assert_eq!(unread(tech.id).await, 0);
+
+        let undone = repository
+            .undo_last_read_operation()
+            .await
+            .expect("undo should succeed")
+            .expect("operation should exist");
+        assert_eq!(undone.kind, "mark_source_read");
+        assert_eq!(unread(tech.id).await, 1);
+    }
 
+    #[tokio::test]
+    async fn notification_prefs_and_queue_round_trip() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        let source = repository
+            .upsert_source(&make_source(
+                "Alerts",
+                "https://alerts.example.com/feed.xml",
+            ))
+            .await
+            .expect("create source");
+        let prefs = NotificationPrefs {
+            mode: NotifyMode::Keywords,
+            keywords: vec!["outage".to_string()],
+            muted: false,
+        };
         repository
-            .upsert_entries(
-                source_a.id,
-                &[
-                    ParsedEntry {
-                        id: "a-new".to_string(),
-                        title: "A newer".to_string(),
-                        link: "https://a.example.com/new".to_string(),
-                        summary: None,
-                        content: None,
-                        published_at: Some("2026-02-24T10:00:00Z".to_string()),
-                    },
-                    ParsedEntry {
-                        id: "a-old".to_string(),
-                        title: "A older".to_string(),
-                        link: "https://a.example.com/old".to_string(),
-                        summary: None,
-                        content: None,
-                        published_at: Some("2026-02-24T09:00:00Z".to_string()),
-                    },
-                ],
-            )
+            .set_source_notification_prefs(source.id, &prefs)
             .await
-            .expect("insert A entries should succeed");
+            .expect("save prefs");
+        let before = repository
+            .max_entry_id(source.id)
+            .await
+            .expect("max id should load");
         repository
             .upsert_entries(
-                source_b.id,
-                &[
-                    ParsedEntry {
-                        id: "b-new".to_string(),
-                        title: "B newer".to_string(),
-                        link: "https://b.example.com/new".to_string(),
-                        summary: None,
-                        content: None,
-                        published_at: Some("2026-02-24T08:00:00Z".to_string()),
-                    },
-                    ParsedEntry {
-                        id: "b-old".to_string(),
-                        title: "B older".to_string(),
-                        link: "https://b.example.com/old".to_string(),
-                        summary: None,
-                        content: None,
-                        published_at: Some("2026-02-24T07:00:00Z".to_string()),
-                    },
-                ],
+                source.id,
+                &[ParsedEntry {
+                    id: "outage".to_string(),
+                    title: "Major outage".to_string(),
+                    link: "https://alerts.example.com/outage".to_string(),
+                    summary: None,
+                    content: None,
+                    published_at: None,
+                }],
             )
             .await
-            .expect("insert B entries should succeed");
+            .expect("upsert entries");
+        let fresh = repository
+            .list_entries_after(source.id, before)
+            .await
+            .expect("new entries should load");
+        repository
+            .queue_notification(source.id, fresh[0].id, Some("outage"))
+            .await
+            .expect("queue notification");
 
-        let untranslated = repository
-            .list_entries_without_translated_title(10)
+        assert_eq!(
+            repository
+                .get_source_notification_prefs(source.id)
+                .await
+                .expect("prefs should load"),
+            Some(prefs)
+        );
+        let pending = repository
+            .take_pending_notifications(10)
             .await
-            .expect("list untranslated should succeed");
-        let titles = untranslated
-            .iter()
-            .map(|row| row.title.as_str())
-            .collect::<Vec<_>>();
-        assert_eq!(titles, vec!["A newer", "A older", "B newer", "B older"]);
+            .expect("pending should load");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].title, "Major outage");
+        assert!(repository
+            .take_pending_notifications(10)
+            .await
+            .expect("pending should load")
+            .is_empty());
     }
 
     #[tokio::test]
-    async fn sync_candidates_respect_backoff_window() {
+    async fn source_retention_trims_only_the_configured_source() {
         let repository = SourceRepository::connect("sqlite::memory:")
             .await
             .expect("connect must succeed");
-        let source = repository
+        let firehose = repository
             .upsert_source(&make_source(
-                "Backoff Source",
-                "https://backoff.example.com/feed.xml",
+                "Firehose",
+                "https://firehose.example.com/feed.xml",
             ))
             .await
-            .expect("create source should succeed");
-
-        sqlx::query(
-            r#"
-            UPDATE sources
-            SET failure_count = 3,
-                last_synced_at = datetime('now'),
-                is_active = 1
-            WHERE id = ?1
-            "#,
-        )
-        .bind(source.id)
-        .execute(&repository.pool)
-        .await
-        .expect("update should succeed");
-
-        let candidates_now = repository
-            .list_sync_candidates(50)
+            .expect("create source");
+        let quiet = repository
+            .upsert_source(&make_source("Quiet", "https://quiet.example.com/feed.xml"))
             .await
-            .expect("list candidates should succeed");
-        assert!(candidates_now.is_empty());
-
-        sqlx::query(
-            r#"
-            UPDATE sources
-            SET last_synced_at = datetime('now', '-20 minutes')
-            WHERE id = ?1
-            "#,
-        )
-        .bind(source.id)
-        .execute(&repository.pool)
-        .await
-        .expect("update should succeed");
+            .expect("create source");
+        for source in [&firehose, &quiet] {
+            let entries = (0..5)
+                .map(|index| ParsedEntry {
+                    id: format!("{}-{index}", source.id),
+                    title: format!("Entry {index}"),
+                    link: format!("https://example.com/{}/{index}", source.id),
+                    summary: Some("x".repeat(100)),
+                    content: None,
+                    published_at: Some(format!("2026-01-0{}T00:00:00Z", index + 1)),
+                })
+                .collect::<Vec<_>>();
+            repository
+                .upsert_entries(source.id, &entries)
+                .await
+                .expect("upsert entries");
+        }
+        sqlx::query("UPDATE entries SET is_read = 1")
+            .execute(&repository.pool)
+            .await
+            .expect("mark read");
+        repository
+            .set_source_retention(firehose.id, None, Some(2))
+            .await
+            .expect("set retention");
 
-        let candidates_later = repository
-            .list_sync_candidates(50)
+        let pruned = repository
+            .apply_source_retention()
             .await
-            .expect("list candidates should succeed");
-        assert_eq!(candidates_later.len(), 1);
+            .expect("retention should apply");
+        let breakdown = repository
+            .storage_breakdown()
+            .await
+            .expect("breakdown should load");
+        let resynced = repository
+            .upsert_entries(
+                firehose.id,
+                &[ParsedEntry {
+                    id: format!("{}-0", firehose.id),
+                    title: "Entry 0".to_string(),
+                    link: format!("https://example.com/{}/0", firehose.id),
+                    summary: Some("x".repeat(100)),
+                    content: None,
+                    published_at: Some("2026-01-01T00:00:00Z".to_string()),
+                }],
+            )
+            .await
+            .expect("resync");
+
+        assert_eq!(pruned, 3);
+        assert_eq!(resynced.inserted, 0);
+        let firehose_usage = breakdown
+            .iter()
+            .find(|usage| usage.source_id == firehose.id)
+            .expect("firehose usage");
+        let quiet_usage = breakdown
+            .iter()
+            .find(|usage| usage.source_id == quiet.id)
+            .expect("quiet usage");
+        assert_eq!(firehose_usage.entry_count, 2);
+        assert_eq!(firehose_usage.archived_count, 0);
+        assert_eq!(firehose_usage.retention_max_entries, Some(2));
+        assert_eq!(quiet_usage.entry_count, 5);
+        assert!(quiet_usage.content_bytes > 500);
     }
 }
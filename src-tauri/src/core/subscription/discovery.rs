@@ -0,0 +1,546 @@
+use std::collections::{BTreeMap, HashSet};
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::core::feed::fetcher::{fetch_feed, FetchStatus};
+use crate::core::feed::parser::{parse_feed_bytes, parse_feed_with_content_type};
+use crate::core::feed::types::FeedFormat;
+use crate::core::importer::{normalize_url, parse_opml, ImportSource};
+
+pub const FEED_LINK_TYPES: [&str; 4] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+    "application/json",
+];
+pub const COMMON_FEED_PATHS: [&str; 8] = [
+    "feed",
+    "feed.xml",
+    "rss",
+    "rss.xml",
+    "atom.xml",
+    "index.xml",
+    "feed.json",
+    "blog/feed",
+];
+
+pub const RECOMMENDATIONS_OPML_PATH: &str = "/.well-known/recommendations.opml";
+const MAX_BLOGROLLS_PER_SITE: usize = 3;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DiscoveryError {
+    #[error("invalid site url: {0}")]
+    InvalidUrl(String),
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AlternateLink {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeedCandidate {
+    pub url: String,
+    pub title: String,
+    pub format: FeedFormat,
+    pub discovered_by: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SiteDiscovery {
+    pub site_url: String,
+    pub feeds: Vec<ImportSource>,
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlLinks {
+    pub feeds: Vec<AlternateLink>,
+    pub blogrolls: Vec<String>,
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiscoverySuggestion {
+    pub kind: String,
+    pub url: String,
+    pub title: String,
+    pub site_url: Option<String>,
+    pub referrers: Vec<String>,
+}
+
+pub async fn discover_site(
+    client: &reqwest::Client,
+    site_url: &str,
+) -> Result<SiteDiscovery, DiscoveryError> {
+    let base = Url::parse(site_url.trim())
+        .map_err(|_| DiscoveryError::InvalidUrl(site_url.to_string()))?;
+    let mut discovery = SiteDiscovery {
+        site_url: base.to_string(),
+        ..SiteDiscovery::default()
+    };
+
+    if let Ok(recommendations) = base.join(RECOMMENDATIONS_OPML_PATH) {
+        if let Some(body) = fetch_text(client, recommendations.as_str()).await? {
+            discovery
+                .feeds
+                .extend(parse_opml(&body).unwrap_or_default());
+        }
+    }
+
+    let Some(html) = fetch_text(client, base.as_str()).await? else {
+        return Ok(discovery);
+    };
+    let links = extract_html_links(&html, &base);
+    for blogroll in links.blogrolls.iter().take(MAX_BLOGROLLS_PER_SITE) {
+        if let Some(body) = fetch_text(client, blogroll).await? {
+            discovery
+                .feeds
+                .extend(parse_opml(&body).unwrap_or_default());
+        }
+    }
+    discovery.profiles = links.profiles;
+    Ok(discovery)
+}
+
+pub async fn discover_feeds(
+    client: &reqwest::Client,
+    site_url: &str,
+) -> Result<Vec<FeedCandidate>, DiscoveryError> {
+    let base = Url::parse(site_url.trim())
+        .map_err(|_| DiscoveryError::InvalidUrl(site_url.to_string()))?;
+    let response = client.get(base.as_str()).send().await?;
+    let base = response.url().clone();
+    let body = if response.status().is_success() {
+        response.bytes().await?.to_vec()
+    } else {
+        Vec::new()
+    };
+
+    if let Ok(feed) = parse_feed_bytes(&body) {
+        return Ok(vec![FeedCandidate {
+            url: base.to_string(),
+            title: feed.title,
+            format: feed.format,
+            discovered_by: "direct".to_string(),
+        }]);
+    }
+
+    let mut candidates = Vec::new();
+    let mut checked = HashSet::new();
+    let links = extract_html_links(&String::from_utf8_lossy(&body), &base).feeds;
+    for link in links {
+        checked.insert(normalize_url(&link.url));
+        if let Some(candidate) = fetch_candidate(client, &link.url, link.title, "link").await {
+            candidates.push(candidate);
+        }
+    }
+    if !candidates.is_empty() {
+        return Ok(candidates);
+    }
+
+    for path in COMMON_FEED_PATHS {
+        let Ok(url) = base.join(path) else {
+            continue;
+        };
+        if !checked.insert(normalize_url(url.as_str())) {
+            continue;
+        }
+        if let Some(candidate) = fetch_candidate(client, url.as_str(), None, "path").await {
+            candidates.push(candidate);
+        }
+    }
+    Ok(candidates)
+}
+
+async fn fetch_candidate(
+    client: &reqwest::Client,
+    url: &str,
+    title: Option<String>,
+    discovered_by: &str,
+) -> Option<FeedCandidate> {
+    let FetchStatus::Updated(fetched) = fetch_feed(client, url, None, None, None).await.ok()?
+    else {
+        return None;
+    };
+    let feed = parse_feed_with_content_type(&fetched.body, fetched.content_type.as_deref()).ok()?;
+    Some(FeedCandidate {
+        url: url.to_string(),
+        title: title
+            .or_else(|| Some(feed.title).filter(|title| !title.trim().is_empty()))
+            .unwrap_or_else(|| url.to_string()),
+        format: feed.format,
+        discovered_by: discovered_by.to_string(),
+    })
+}
+
+pub async fn find_replacement_feed(
+    client: &reqwest::Client,
+    site_url: &str,
+    broken_feed_url: &str,
+) -> Result<Option<String>, DiscoveryError> {
+    let base = Url::parse(site_url.trim())
+        .map_err(|_| DiscoveryError::InvalidUrl(site_url.to_string()))?;
+    let mut candidates = match fetch_text(client, base.as_str()).await? {
+        Some(html) => extract_html_links(&html, &base).feeds,
+        None => Vec::new(),
+    };
+    candidates.extend(
+        COMMON_FEED_PATHS
+            .iter()
+            .filter_map(|path| base.join(path).ok())
+            .map(|url| AlternateLink {
+                url: url.to_string(),
+                title: None,
+            }),
+    );
+
+    let broken = normalize_url(broken_feed_url);
+    let mut checked = HashSet::new();
+    for candidate in candidates {
+        let normalized = normalize_url(&candidate.url);
+        if normalized == broken || !checked.insert(normalized) {
+            continue;
+        }
+        if fetch_candidate(client, &candidate.url, None, "path")
+            .await
+            .is_some()
+        {
+            return Ok(Some(candidate.url));
+        }
+    }
+    Ok(None)
+}
+
+pub fn extract_html_links(html: &str, base: &Url) -> HtmlLinks {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a[rel][href], link[rel][href]").expect("selector must parse");
+    let mut links = HtmlLinks::default();
+
+    for element in document.select(&selector) {
+        let value = element.value();
+        let Some(href) = value
+            .attr("href")
+            .and_then(|href| base.join(href.trim()).ok())
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+        else {
+            continue;
+        };
+        let rels = value
+            .attr("rel")
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+            .map(str::to_ascii_lowercase)
+            .collect::<Vec<_>>();
+        let is_feed_type = value
+            .attr("type")
+            .map(|kind| kind.trim().to_ascii_lowercase())
+            .is_some_and(|kind| FEED_LINK_TYPES.contains(&kind.as_str()));
+        let href = href.to_string();
+        if rels.iter().any(|rel| rel == "alternate") && is_feed_type {
+            if !links.feeds.iter().any(|link| link.url == href) {
+                links.feeds.push(AlternateLink {
+                    url: href,
+                    title: value
+                        .attr("title")
+                        .map(str::trim)
+                        .filter(|title| !title.is_empty())
+                        .map(str::to_string),
+                });
+            }
+            continue;
+        }
+        let target = if rels.iter().any(|rel| rel == "blogroll") {
+            &mut links.blogrolls
+        } else if rels.iter().any(|rel| rel == "me") {
+            &mut links.profiles
+        } else {
+            continue;
+        };
+        if !target.contains(&href) {
+            target.push(href);
+        }
+    }
+
+    links
+}
+
+pub fn aggregate_suggestions(
+    discoveries: &[SiteDiscovery],
+    subscribed_urls: &HashSet<String>,
+) -> Vec<DiscoverySuggestion> {
+    let mut suggestions = BTreeMap::<String, DiscoverySuggestion>::new();
+    let mut add = |key: String, suggestion: DiscoverySuggestion, referrer: &str| {
+        if subscribed_urls.contains(&key) {
+            return;
+        }
+        let entry = suggestions.entry(key).or_insert(suggestion);
+        if !entry.referrers.iter().any(|item| item == referrer) {
+            entry.referrers.push(referrer.to_string());
+        }
+    };
+
+    for discovery in discoveries {
+        for feed in &discovery.feeds {
+            add(
+                normalize_url(&feed.feed_url),
+                DiscoverySuggestion {
+                    kind: "feed".to_string(),
+                    url: feed.feed_url.clone(),
+                    title: feed.title.clone(),
+                    site_url: feed.site_url.clone(),
+                    referrers: Vec::new(),
+                },
+                &discovery.site_url,
+            );
+        }
+        for profile in &discovery.profiles {
+            add(
+                normalize_url(profile),
+                DiscoverySuggestion {
+                    kind: "profile".to_string(),
+                    url: profile.clone(),
+                    title: profile.clone(),
+                    site_url: Some(profile.clone()),
+                    referrers: Vec::new(),
+                },
+                &discovery.site_url,
+            );
+        }
+    }
+
+    let mut suggestions = suggestions.into_values().collect::<Vec<_>>();
+    suggestions.sort_by(|left, right| {
+        right
+            .referrers
+            .len()
+            .cmp(&left.referrers.len())
+            .then_with(|| left.kind.cmp(&right.kind))
+            .then_with(|| left.title.cmp(&right.title))
+    });
+    suggestions
+}
+
+async fn fetch_text(client: &reqwest::Client, url: &str) -> Result<Option<String>, DiscoveryError> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+    Ok(Some(response.text().await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use axum::Router;
+
+    const RSS: &str = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Posts</title><item><title>One</title><link>https://blog.example.com/1</link></item></channel></rss>"#;
+    const ATOM: &str = r#"<?xml version="1.0"?><feed xmlns="http://www.w3.org/2005/Atom"><title>Atom Posts</title><id>urn:blog</id><updated>2026-01-01T00:00:00Z</updated></feed>"#;
+    const LINKED_HOME: &str = r#"<html><head>
+          <link rel="alternate" type="application/rss+xml" title="Main feed" href="/posts.rss">
+          <link rel="alternate" type="application/atom+xml" href="atom-only">
+          <link rel="alternate" type="application/rss+xml" href="/missing.rss">
+          <link rel="stylesheet" href="/style.css">
+        </head><body></body></html>"#;
+
+    const RECOMMENDATIONS: &str = r#"<?xml version="1.0"?>
+        <opml version="2.0"><body>
+          <outline text="Friend" xmlUrl="https://friend.example.com/feed.xml" htmlUrl="https://friend.example.com/"/>
+        </body></opml>"#;
+    const BLOGROLL: &str = r#"<?xml version="1.0"?>
+        <opml version="2.0"><body>
+          <outline text="Neighbour" xmlUrl="https://neighbour.example.com/rss"/>
+        </body></opml>"#;
+    const HOME: &str = r#"<html><head>
+          <link rel="blogroll" type="text/xml" href="/blogroll.opml">
+          <link rel="me" href="https://social.example.com/@owner">
+        </head><body>
+          <a rel="me noopener" href="https://code.example.com/owner">code</a>
+          <a href="https://unrelated.example.com/">plain link</a>
+        </body></html>"#;
+
+    #[tokio::test]
+    async fn discovers_recommendations_blogroll_and_profiles() {
+        let app = Router::new()
+            .route(RECOMMENDATIONS_OPML_PATH, get(|| async { RECOMMENDATIONS }))
+            .route("/blogroll.opml", get(|| async { BLOGROLL }))
+            .route("/", get(|| async { axum::response::Html(HOME) }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let address = listener.local_addr().expect("local addr should exist");
+        let server_task = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+
+        let discovery = discover_site(&reqwest::Client::new(), &format!("http://{address}/"))
+            .await
+            .expect("discovery should succeed");
+        server_task.abort();
+
+        let feeds = discovery
+            .feeds
+            .iter()
+            .map(|feed| feed.feed_url.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            feeds,
+            vec![
+                "https://friend.example.com/feed.xml",
+                "https://neighbour.example.com/rss"
+            ]
+        );
+        assert_eq!(
+            discovery.profiles,
+            vec![
+                "https://social.example.com/@owner".to_string(),
+                "https://code.example.com/owner".to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_replacement_feed_from_alternate_link_or_common_path() {
+        let rss = include_str!("../../../../fixtures/import-samples/sample.rss.xml");
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    axum::response::Html(
+                        r#"<html><head>
+                          <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+                          <link rel="alternate" type="application/rss+xml" href="/moved/feed.xml">
+                        </head></html>"#,
+                    )
+                }),
+            )
+            .route("/moved/feed.xml", get(move || async move { rss }))
+            .route("/other/", get(|| async { "<html></html>" }))
+            .route("/other/rss.xml", get(move || async move { rss }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let address = listener.local_addr().expect("local addr should exist");
+        let server_task = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        let client = reqwest::Client::new();
+
+        let from_link = find_replacement_feed(
+            &client,
+            &format!("http://{address}/"),
+            &format!("http://{address}/feed.xml"),
+        )
+        .await
+        .expect("discovery should succeed");
+        let from_path = find_replacement_feed(
+            &client,
+            &format!("http://{address}/other/"),
+            &format!("http://{address}/other/feed"),
+        )
+        .await
+        .expect("discovery should succeed");
+        server_task.abort();
+
+        assert_eq!(from_link, Some(format!("http://{address}/moved/feed.xml")));
+        assert_eq!(from_path, Some(format!("http://{address}/other/rss.xml")));
+    }
+
+    #[test]
+    fn aggregates_shared_links_and_skips_subscribed() {
+        let feed = |url: &str| ImportSource {
+            title: url.to_string(),
+            feed_url: url.to_string(),
+            site_url: None,
+            category: None,
+            rssr: None,
+        };
+        let discoveries = vec![
+            SiteDiscovery {
+                site_url: "https://a.example.com/".to_string(),
+                feeds: vec![
+                    feed("https://shared.example.com/feed.xml"),
+                    feed("https://known.example.com/rss"),
+                ],
+                profiles: vec![],
+            },
+            SiteDiscovery {
+                site_url: "https://b.example.com/".to_string(),
+                feeds: vec![feed("https://shared.example.com/feed.xml/")],
+                profiles: vec!["https://social.example.com/@b".to_string()],
+            },
+        ];
+        let subscribed = HashSet::from([normalize_url("https://known.example.com/rss")]);
+
+        let suggestions = aggregate_suggestions(&discoveries, &subscribed);
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].url, "https://shared.example.com/feed.xml");
+        assert_eq!(suggestions[0].referrers.len(), 2);
+        assert_eq!(suggestions[1].kind, "profile");
+    }
+
+    #[test]
+    fn extracts_alternate_feed_links_with_titles() {
+        let base = Url::parse("https://blog.example.com/about/").expect("base url");
+        let links = extract_html_links(LINKED_HOME, &base).feeds;
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[0].url, "https://blog.example.com/posts.rss");
+        assert_eq!(links[0].title.as_deref(), Some("Main feed"));
+        assert_eq!(links[1].url, "https://blog.example.com/about/atom-only");
+        assert_eq!(links[1].title, None);
+    }
+
+    #[tokio::test]
+    async fn discovers_linked_feeds_then_common_paths() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("listener should bind");
+        let base = format!("http://{}", listener.local_addr().expect("local addr"));
+        let app = Router::new()
+            .route(
+                "/linked/",
+                get(|| async { axum::response::Html(LINKED_HOME) }),
+            )
+            .route("/posts.rss", get(|| async { RSS }))
+            .route("/linked/atom-only", get(|| async { ATOM }))
+            .route("/plain/", get(|| async { "<html><body>hi</body></html>" }))
+            .route("/plain/atom.xml", get(|| async { ATOM }))
+            .route("/feed.xml", get(|| async { RSS }));
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("server should run");
+        });
+        let client = reqwest::Client::new();
+
+        let linked = discover_feeds(&client, &format!("{base}/linked/"))
+            .await
+            .expect("discovery should succeed");
+        assert_eq!(linked.len(), 2);
+        assert_eq!(linked[0].title, "Main feed");
+        assert_eq!(linked[0].discovered_by, "link");
+        assert_eq!(linked[1].title, "Atom Posts");
+
+        let probed = discover_feeds(&client, &format!("{base}/plain/"))
+            .await
+            .expect("discovery should succeed");
+        assert_eq!(probed.len(), 1);
+        assert_eq!(probed[0].url, format!("{base}/plain/atom.xml"));
+        assert_eq!(probed[0].discovered_by, "path");
+
+        let direct = discover_feeds(&client, &format!("{base}/feed.xml"))
+            .await
+            .expect("discovery should succeed");
+        assert_eq!(direct.len(), 1);
+        assert_eq!(direct[0].discovered_by, "direct");
+        assert_eq!(direct[0].title, "Posts");
+
+        server.abort();
+    }
+}
@@ -1,3 +1,41 @@
+pub mod discovery;
+pub mod naming;
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::importer::{normalize_url, parse_opml, ImportError, ImportSource};
+use crate::core::storage::models::SourceRecord;
+
+const CURATED_FEEDS_JSON: &str = include_str!("curated_feeds.json");
+const STARTER_PACKS: [(&str, &str, &str, &str); 4] = [
+    (
+        "rust",
+        "Rust",
+        "Language news, compiler internals and community writing.",
+        include_str!("starter_packs/rust.opml"),
+    ),
+    (
+        "ml",
+        "Machine Learning",
+        "Research explainers, model releases and applied LLM notes.",
+        include_str!("starter_packs/ml.opml"),
+    ),
+    (
+        "security",
+        "Security",
+        "Breach reporting, vulnerability research and cryptography.",
+        include_str!("starter_packs/security.opml"),
+    ),
+    (
+        "design",
+        "Design",
+        "Web design, CSS craft and usability research.",
+        include_str!("starter_packs/design.opml"),
+    ),
+];
+
 #[derive(Debug, Clone, Default)]
 pub struct SubscriptionService;
 
@@ -10,3 +48,215 @@ impl SubscriptionService {
         "ready"
     }
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubscriptionError {
+    #[error("invalid curated index: {0}")]
+    CuratedIndex(#[from] serde_json::Error),
+    #[error("invalid starter pack: {0}")]
+    StarterPack(#[from] ImportError),
+    #[error("unknown starter pack: {0}")]
+    UnknownStarterPack(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StarterPack {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub source_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CuratedFeed {
+    pub title: String,
+    pub feed_url: String,
+    pub site_url: Option<String>,
+    pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SourceRecommendation {
+    pub title: String,
+    pub feed_url: String,
+    pub site_url: Option<String>,
+    pub category: String,
+    pub score: f64,
+    pub reason: String,
+}
+
+pub fn curated_index() -> Result<Vec<CuratedFeed>, SubscriptionError> {
+    Ok(serde_json::from_str(CURATED_FEEDS_JSON)?)
+}
+
+pub fn list_starter_packs() -> Result<Vec<StarterPack>, SubscriptionError> {
+    STARTER_PACKS
+        .iter()
+        .map(|(id, title, description, opml)| {
+            Ok(StarterPack {
+                id: id.to_string(),
+                title: title.to_string(),
+                description: description.to_string(),
+                source_count: parse_opml(opml)?.len(),
+            })
+        })
+        .collect()
+}
+
+pub fn starter_pack_sources(id: &str) -> Result<Vec<ImportSource>, SubscriptionError> {
+    let (_, _, _, opml) = STARTER_PACKS
+        .iter()
+        .find(|(pack_id, _, _, _)| *pack_id == id)
+        .ok_or_else(|| SubscriptionError::UnknownStarterPack(id.to_string()))?;
+    Ok(parse_opml(opml)?)
+}
+
+pub fn recommend_sources(
+    subscriptions: &[SourceRecord],
+    index: &[CuratedFeed],
+    limit: usize,
+) -> Vec<SourceRecommendation> {
+    let subscribed = subscriptions
+        .iter()
+        .map(|source| normalize_url(&source.feed_url))
+        .collect::<HashSet<_>>();
+    let mut category_weights = HashMap::<String, usize>::new();
+    let mut keywords = HashSet::<String>::new();
+    for source in subscriptions {
+        if let Some(category) = source
+            .category
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            *category_weights.entry(category.to_lowercase()).or_default() += 1;
+            keywords.extend(tokenize(category));
+        }
+        keywords.extend(tokenize(&source.title));
+    }
+
+    let mut recommendations = index
+        .iter()
+        .filter(|feed| !subscribed.contains(&normalize_url(&feed.feed_url)))
+        .filter_map(|feed| {
+            let category_hits = category_weights
+                .get(&feed.category.to_lowercase())
+                .copied()
+                .unwrap_or(0);
+            let matched_tags = feed
+                .tags
+                .iter()
+                .filter(|tag| keywords.contains(&tag.to_lowercase()))
+                .cloned()
+                .collect::<Vec<_>>();
+            if category_hits == 0 && matched_tags.is_empty() {
+                return None;
+            }
+            let score = category_hits as f64 * 2.0 + matched_tags.len() as f64 * 3.0;
+            let reason = if matched_tags.is_empty() {
+                format!("matches category {}", feed.category)
+            } else {
+                format!("matches {}", matched_tags.join(", "))
+            };
+            Some(SourceRecommendation {
+                title: feed.title.clone(),
+                feed_url: feed.feed_url.clone(),
+                site_url: feed.site_url.clone(),
+                category: feed.category.clone(),
+                score,
+                reason,
+            })
+        })
+        .collect::<Vec<_>>();
+    recommendations.sort_by(|left, right| {
+        right
+            .score
+            .total_cmp(&left.score)
+            .then_with(|| left.title.cmp(&right.title))
+    });
+    recommendations.truncate(limit);
+    recommendations
+}
+
+fn tokenize(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|token| token.len() > 1)
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(title: &str, feed_url: &str, category: Option<&str>) -> SourceRecord {
+        SourceRecord {
+            id: 1,
+            title: title.to_string(),
+            site_url: None,
+            feed_url: feed_url.to_string(),
+            category: category.map(ToString::to_string),
+            is_active: 1,
+            failure_count: 0,
+            etag: None,
+            last_modified: None,
+            last_synced_at: None,
+            color: None,
+            custom_icon: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+            updated_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn bundled_index_parses_with_unique_feeds() {
+        let index = curated_index().expect("curated index should parse");
+        let unique = index
+            .iter()
+            .map(|feed| normalize_url(&feed.feed_url))
+            .collect::<HashSet<_>>();
+
+        assert!(index.len() >= 20);
+        assert_eq!(unique.len(), index.len());
+    }
+
+    #[test]
+    fn starter_packs_parse_with_categories() {
+        let packs = list_starter_packs().expect("starter packs should parse");
+        assert_eq!(packs.len(), STARTER_PACKS.len());
+        assert!(packs.iter().all(|pack| pack.source_count > 0));
+
+        let rust = starter_pack_sources("rust").expect("rust pack should load");
+        assert!(rust
+            .iter()
+            .all(|source| source.category.as_deref() == Some("Rust")));
+        assert!(matches!(
+            starter_pack_sources("missing"),
+            Err(SubscriptionError::UnknownStarterPack(_))
+        ));
+    }
+
+    #[test]
+    fn recommends_related_feeds_and_skips_subscribed() {
+        let index = curated_index().expect("curated index should parse");
+        let subscriptions = vec![
+            subscription(
+                "Rust Blog",
+                "https://blog.rust-lang.org/feed.xml/",
+                Some("Tech"),
+            ),
+            subscription("My Rust Notes", "https://notes.example.com/rss", None),
+        ];
+
+        let recommendations = recommend_sources(&subscriptions, &index, 5);
+
+        assert_eq!(recommendations.len(), 5);
+        assert!(recommendations
+            .iter()
+            .all(|item| item.feed_url != "https://blog.rust-lang.org/feed.xml"));
+        assert!(recommendations[0].reason.contains("rust"));
+        assert!(recommend_sources(&[], &index, 5).is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+pub const NAMING_SYSTEM_PROMPT: &str =
+    "You name RSS subscriptions. Reply with a single JSON object and nothing else.";
+const MAX_PROMPT_TITLES: usize = 10;
+const MAX_SUGGESTED_TITLE_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SourceSuggestion {
+    pub title: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawSuggestion {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    category: Option<String>,
+}
+
+pub fn naming_user_prompt(
+    feed_url: &str,
+    feed_title: Option<&str>,
+    entry_titles: &[String],
+    categories: &[String],
+) -> String {
+    let titles = entry_titles
+        .iter()
+        .take(MAX_PROMPT_TITLES)
+        .map(|title| format!("- {title}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let categories = if categories.is_empty() {
+        "(none)".to_string()
+    } else {
+        categories.join(", ")
+    };
+    format!(
+        "Feed URL: {feed_url}\nFeed title: {}\nRecent entry titles:\n{titles}\n\nExisting categories: {categories}\n\nPropose a short, clean display title for this feed (drop taglines, site suffixes and \"RSS\"/\"Feed\" noise) and pick the best matching category from the existing categories, or null if none fits. Answer as {{\"title\": \"...\", \"category\": \"...\"}}.",
+        feed_title.unwrap_or("(none)")
+    )
+}
+
+pub fn parse_naming_response(response: &str, categories: &[String]) -> SourceSuggestion {
+    let json = match (response.find('{'), response.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => return SourceSuggestion::default(),
+    };
+    let raw = serde_json::from_str::<RawSuggestion>(json).unwrap_or_default();
+    let title = raw
+        .title
+        .map(|title| title.trim().trim_matches('"').trim().to_string())
+        .filter(|title| !title.is_empty())
+        .map(|title| title.chars().take(MAX_SUGGESTED_TITLE_CHARS).collect());
+    let category = raw.category.and_then(|category| {
+        let category = category.trim().to_lowercase();
+        categories
+            .iter()
+            .find(|existing| existing.trim().to_lowercase() == category)
+            .cloned()
+    });
+    SourceSuggestion { title, category }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fenced_json_and_keeps_only_existing_categories() {
+        let categories = vec!["Rust".to_string(), "Security".to_string()];
+        let fenced = "```json\n{\"title\": \" This Week in Rust \", \"category\": \"rust\"}\n```";
+
+        assert_eq!(
+            parse_naming_response(fenced, &categories),
+            SourceSuggestion {
+                title: Some("This Week in Rust".to_string()),
+                category: Some("Rust".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_naming_response(
+                "{\"title\": \"Blog\", \"category\": \"Cooking\"}",
+                &categories
+            )
+            .category,
+            None
+        );
+        assert_eq!(
+            parse_naming_response("no idea", &categories),
+            SourceSuggestion::default()
+        );
+    }
+
+    #[test]
+    fn prompt_lists_recent_titles_and_categories() {
+        let titles = (0..15)
+            .map(|index| format!("Post {index}"))
+            .collect::<Vec<_>>();
+        let prompt = naming_user_prompt(
+            "https://example.com/feed.xml",
+            Some("Example Blog – Thoughts"),
+            &titles,
+            &["Tech".to_string()],
+        );
+
+        assert!(prompt.contains("Feed title: Example Blog – Thoughts"));
+        assert!(prompt.contains("- Post 9\n"));
+        assert!(!prompt.contains("Post 10"));
+        assert!(prompt.contains("Existing categories: Tech"));
+    }
+}
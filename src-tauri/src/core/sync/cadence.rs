@@ -0,0 +1,101 @@
+use chrono::{DateTime, Duration, DurationRound, Timelike, Utc};
+
+pub const CADENCE_SAMPLE_SIZE: i64 = 20;
+pub const MIN_POLL_INTERVAL_SECS: i64 = 15 * 60;
+pub const MAX_POLL_INTERVAL_SECS: i64 = 24 * 60 * 60;
+const UNKNOWN_CADENCE_POLL_SECS: i64 = 6 * 60 * 60;
+const MAX_TTL_MINUTES: u32 = 7 * 24 * 60;
+
+pub fn parse_published_at(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_rfc2822(value))
+        .ok()
+        .map(|published| published.with_timezone(&Utc))
+}
+
+pub fn observed_interval_secs(mut published: Vec<DateTime<Utc>>) -> Option<i64> {
+    published.sort();
+    published.dedup();
+    let mut gaps = published
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).num_seconds())
+        .filter(|gap| *gap > 0)
+        .collect::<Vec<_>>();
+    if gaps.is_empty() {
+        return None;
+    }
+    gaps.sort_unstable();
+    Some(gaps[gaps.len() / 2])
+}
+
+pub fn poll_interval_secs(observed_secs: Option<i64>, ttl_minutes: Option<u32>) -> i64 {
+    let cadence = observed_secs
+        .map(|gap| gap / 2)
+        .unwrap_or(UNKNOWN_CADENCE_POLL_SECS)
+        .clamp(MIN_POLL_INTERVAL_SECS, MAX_POLL_INTERVAL_SECS);
+    let ttl_secs = ttl_minutes.map_or(0, |minutes| i64::from(minutes.min(MAX_TTL_MINUTES)) * 60);
+    cadence.max(ttl_secs)
+}
+
+pub fn next_poll_at(now: DateTime<Utc>, interval_secs: i64, skip_hours: &[u32]) -> DateTime<Utc> {
+    let mut next = now + Duration::seconds(interval_secs);
+    if skip_hours.len() >= 24 {
+        return next;
+    }
+    while skip_hours.contains(&next.hour()) {
+        next = next.duration_trunc(Duration::hours(1)).unwrap_or(next) + Duration::hours(1);
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_published_at(value).expect("timestamp should parse")
+    }
+
+    #[test]
+    fn interval_follows_publish_cadence_and_honors_ttl() {
+        let hourly = (0..6)
+            .map(|hour| at(&format!("2026-03-01T{hour:02}:00:00Z")))
+            .collect::<Vec<_>>();
+        assert_eq!(observed_interval_secs(hourly.clone()), Some(3600));
+        assert_eq!(
+            poll_interval_secs(observed_interval_secs(hourly), None),
+            1800
+        );
+
+        let monthly = vec![
+            at("Sun, 01 Feb 2026 08:00:00 GMT"),
+            at("2026-03-01T08:00:00Z"),
+            at("2026-01-01T08:00:00+00:00"),
+        ];
+        assert_eq!(
+            poll_interval_secs(observed_interval_secs(monthly), None),
+            MAX_POLL_INTERVAL_SECS
+        );
+        assert_eq!(poll_interval_secs(None, None), 6 * 60 * 60);
+        assert_eq!(poll_interval_secs(Some(600), Some(60)), 3600);
+        assert_eq!(
+            observed_interval_secs(vec![at("2026-03-01T00:00:00Z")]),
+            None
+        );
+    }
+
+    #[test]
+    fn next_poll_skips_blocked_hours() {
+        let now = at("2026-03-01T21:30:00Z");
+
+        assert_eq!(
+            next_poll_at(now, 1800, &[22, 23, 0]),
+            at("2026-03-02T01:00:00Z")
+        );
+        assert_eq!(next_poll_at(now, 1800, &[]), at("2026-03-01T22:00:00Z"));
+        assert_eq!(
+            next_poll_at(now, 1800, &(0..24).collect::<Vec<_>>()),
+            at("2026-03-01T22:00:00Z")
+        );
+    }
+}
@@ -1,3 +1,7 @@
+pub mod cadence;
+pub mod politeness;
+pub mod problems;
+
 #[derive(Debug, Clone, Default)]
 pub struct SyncService;
 
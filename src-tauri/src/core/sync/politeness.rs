@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::core::feed::fetcher::feed_client_builder;
+
+const SECOND_LEVEL_LABELS: &[&str] = &["ac", "co", "com", "edu", "gov", "net", "or", "org"];
+
+struct HostSlot {
+    permits: Arc<Semaphore>,
+    next_start: tokio::sync::Mutex<Instant>,
+}
+
+pub struct HostPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+pub struct SyncPermit {
+    _host: HostPermit,
+    _global: OwnedSemaphorePermit,
+}
+
+pub struct HostLimiter {
+    per_host: usize,
+    min_delay: Duration,
+    hosts: Mutex<HashMap<String, Arc<HostSlot>>>,
+}
+
+impl HostLimiter {
+    pub fn new(per_host: u32, min_delay: Duration) -> Self {
+        Self {
+            per_host: per_host.max(1) as usize,
+            min_delay,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn acquire(&self, url: &str) -> Result<HostPermit, AcquireError> {
+        let slot = self.slot(host_key(url));
+        let permit = slot.permits.clone().acquire_owned().await?;
+        let mut next_start = slot.next_start.lock().await;
+        tokio::time::sleep_until(*next_start).await;
+        *next_start = Instant::now() + self.min_delay;
+        Ok(HostPermit { _permit: permit })
+    }
+
+    fn slot(&self, key: String) -> Arc<HostSlot> {
+        self.hosts
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .entry(key)
+            .or_insert_with(|| {
+                Arc::new(HostSlot {
+                    permits: Arc::new(Semaphore::new(self.per_host)),
+                    next_start: tokio::sync::Mutex::new(Instant::now()),
+                })
+            })
+            .clone()
+    }
+}
+
+pub struct SyncHttp {
+    per_host: u32,
+    clients: Mutex<HashMap<u64, reqwest::Client>>,
+    hosts: HostLimiter,
+    global: Arc<Semaphore>,
+}
+
+impl SyncHttp {
+    pub fn new(max_concurrency: u32, per_host: u32, min_delay: Duration) -> Self {
+        Self {
+            per_host,
+            clients: Mutex::new(HashMap::new()),
+            hosts: HostLimiter::new(per_host, min_delay),
+            global: Arc::new(Semaphore::new(max_concurrency.max(1) as usize)),
+        }
+    }
+
+    pub async fn acquire(&self, url: &str) -> Result<SyncPermit, AcquireError> {
+        let host = self.hosts.acquire(url).await?;
+        let global = self.global.clone().acquire_owned().await?;
+        Ok(SyncPermit {
+            _host: host,
+            _global: global,
+        })
+    }
+
+    pub fn client(&self, timeout_secs: u64) -> Result<reqwest::Client, reqwest::Error> {
+        let mut clients = self
+            .clients
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if let Some(client) = clients.get(&timeout_secs) {
+            return Ok(client.clone());
+        }
+        let client = feed_client_builder(timeout_secs)
+            .pool_max_idle_per_host(self.per_host.max(1) as usize)
+            .build()?;
+        clients.insert(timeout_secs, client.clone());
+        Ok(client)
+    }
+}
+
+pub fn host_key(url: &str) -> String {
+    let Ok(parsed) = url::Url::parse(url.trim()) else {
+        return url.trim().to_ascii_lowercase();
+    };
+    match parsed.host() {
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            let labels = domain.split('.').collect::<Vec<_>>();
+            let keep = match labels.as_slice() {
+                [.., second, tld]
+                    if labels.len() >= 3
+                        && tld.len() == 2
+                        && SECOND_LEVEL_LABELS.contains(second) =>
+                {
+                    3
+                }
+                _ => 2,
+            };
+            labels[labels.len().saturating_sub(keep)..].join(".")
+        }
+        Some(host) => host.to_string(),
+        None => url.trim().to_ascii_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_subdomains_under_their_registrable_domain() {
+        assert_eq!(host_key("https://alice.substack.com/feed"), "substack.com");
+        assert_eq!(host_key("https://Medium.com/feed/@bob"), "medium.com");
+        assert_eq!(host_key("https://news.bbc.co.uk/rss.xml"), "bbc.co.uk");
+        assert_eq!(host_key("http://127.0.0.1:8080/feed.xml"), "127.0.0.1");
+        assert_eq!(host_key("not a url"), "not a url");
+    }
+
+    #[tokio::test]
+    async fn limits_concurrency_and_spaces_requests_per_host() {
+        let limiter = Arc::new(HostLimiter::new(2, Duration::from_millis(80)));
+        let started = Instant::now();
+
+        let first = limiter
+            .acquire("https://a.substack.com/feed")
+            .await
+            .expect("acquire");
+        let other_host = limiter
+            .acquire("https://example.org/feed")
+            .await
+            .expect("acquire");
+        assert!(started.elapsed() < Duration::from_millis(80));
+        drop(other_host);
+
+        let _second = limiter
+            .acquire("https://b.substack.com/feed")
+            .await
+            .expect("acquire");
+        assert!(started.elapsed() >= Duration::from_millis(80));
+
+        let third = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                let _permit = limiter
+                    .acquire("https://c.substack.com/feed")
+                    .await
+                    .expect("acquire");
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!third.is_finished());
+        drop(first);
+        third.await.expect("join");
+    }
+
+    #[tokio::test]
+    async fn a_busy_host_does_not_hold_global_slots() {
+        let http = Arc::new(SyncHttp::new(2, 1, Duration::ZERO));
+        let busy = http
+            .acquire("https://busy.example.com/a")
+            .await
+            .expect("acquire");
+
+        let queued = tokio::spawn({
+            let http = http.clone();
+            async move {
+                let _permit = http
+                    .acquire("https://busy.example.com/b")
+                    .await
+                    .expect("acquire");
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let other = tokio::time::timeout(
+            Duration::from_millis(200),
+            http.acquire("https://quiet.example.org/feed"),
+        )
+        .await
+        .expect("another host must not wait behind the busy one");
+        assert!(other.is_ok());
+        assert!(!queued.is_finished());
+        drop(busy);
+        queued.await.expect("join");
+    }
+}
@@ -0,0 +1,148 @@
+use serde::Serialize;
+
+use crate::core::storage::models::ProblemSource;
+
+const REPEATED_TIMEOUT_THRESHOLD: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemClass {
+    AuthRequired,
+    Gone,
+    ParseErrors,
+    RepeatedTimeouts,
+    Other,
+}
+
+impl ProblemClass {
+    pub fn remediation(self) -> &'static str {
+        match self {
+            Self::AuthRequired => {
+                "The feed now requires credentials. Check whether the site offers a public or token-based feed URL, or unsubscribe."
+            }
+            Self::Gone => {
+                "The feed no longer exists. Apply a discovered replacement feed, rediscover it from the site, or unsubscribe."
+            }
+            Self::ParseErrors => {
+                "The server answers but the response is not a valid feed. Probe the URL to see what it returns, or rediscover the feed from the site."
+            }
+            Self::RepeatedTimeouts => {
+                "The server keeps timing out. Lower the sync frequency for this source or pause it until the site recovers."
+            }
+            Self::Other => {
+                "Failures look transient. Reset the failure count after checking the last error, or pause the source if it keeps failing."
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProblemGroup {
+    pub class: ProblemClass,
+    pub remediation: String,
+    pub sources: Vec<ProblemSource>,
+}
+
+pub fn classify_problem(source: &ProblemSource) -> ProblemClass {
+    let status = source.last_sync_status.as_deref().unwrap_or_default();
+    if matches!(source.last_http_status, Some(401 | 403)) || status == "auth_required" {
+        return ProblemClass::AuthRequired;
+    }
+    if matches!(source.last_http_status, Some(404 | 410))
+        || status == "not_found"
+        || source.gone_count > 0
+        || source.parked_domain
+    {
+        return ProblemClass::Gone;
+    }
+    if status == "parse_error" {
+        return ProblemClass::ParseErrors;
+    }
+    if source.recent_timeouts >= REPEATED_TIMEOUT_THRESHOLD {
+        return ProblemClass::RepeatedTimeouts;
+    }
+    ProblemClass::Other
+}
+
+pub fn group_problem_sources(sources: Vec<ProblemSource>) -> Vec<ProblemGroup> {
+    let mut groups: Vec<ProblemGroup> = Vec::new();
+    for source in sources {
+        let class = classify_problem(&source);
+        match groups.iter_mut().find(|group| group.class == class) {
+            Some(group) => group.sources.push(source),
+            None => groups.push(ProblemGroup {
+                class,
+                remediation: class.remediation().to_string(),
+                sources: vec![source],
+            }),
+        }
+    }
+    groups.sort_by_key(|group| group.class);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(
+        id: i64,
+        http_status: Option<i64>,
+        status: &str,
+        recent_timeouts: i64,
+    ) -> ProblemSource {
+        ProblemSource {
+            id,
+            title: format!("Source {id}"),
+            feed_url: format!("https://example.com/{id}.xml"),
+            site_url: None,
+            is_active: true,
+            failure_count: 3,
+            last_http_status: http_status,
+            last_sync_status: Some(status.to_string()),
+            last_error: None,
+            gone_count: 0,
+            parked_domain: false,
+            replacement_feed_url: None,
+            auto_disabled_reason: None,
+            recent_timeouts,
+        }
+    }
+
+    #[test]
+    fn groups_sources_by_problem_class_in_priority_order() {
+        let groups = group_problem_sources(vec![
+            problem(1, None, "timeout", 3),
+            problem(2, Some(401), "auth_required", 0),
+            problem(3, Some(200), "parse_error", 0),
+            problem(4, Some(410), "not_found", 0),
+            problem(5, None, "timeout", 1),
+            problem(6, Some(403), "auth_required", 0),
+        ]);
+
+        let summary = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.class,
+                    group
+                        .sources
+                        .iter()
+                        .map(|source| source.id)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (ProblemClass::AuthRequired, vec![2, 6]),
+                (ProblemClass::Gone, vec![4]),
+                (ProblemClass::ParseErrors, vec![3]),
+                (ProblemClass::RepeatedTimeouts, vec![1]),
+                (ProblemClass::Other, vec![5]),
+            ]
+        );
+        assert!(groups[1].remediation.contains("replacement"));
+    }
+}
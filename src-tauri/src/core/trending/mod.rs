@@ -0,0 +1,183 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+const MIN_RECENT_DOCUMENTS: usize = 2;
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "an", "and", "are", "as", "at", "be", "been", "but", "by", "can",
+    "do", "does", "for", "from", "get", "has", "have", "how", "i", "in", "into", "is", "it", "its",
+    "just", "more", "my", "new", "no", "not", "now", "of", "on", "one", "or", "our", "out", "over",
+    "so", "than", "that", "the", "their", "this", "to", "up", "us", "vs", "was", "we", "what",
+    "when", "where", "which", "who", "why", "will", "with", "you", "your",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendingTerm {
+    pub term: String,
+    pub recent_count: usize,
+    pub baseline_count: usize,
+    pub score: f64,
+}
+
+pub fn compute_trending_terms(
+    recent: &[String],
+    baseline: &[String],
+    limit: usize,
+) -> Vec<TrendingTerm> {
+    let recent_counts = document_frequencies(recent);
+    let baseline_counts = document_frequencies(baseline);
+    let total_documents = (recent.len() + baseline.len()) as f64;
+    let recent_documents = recent.len().max(1) as f64;
+
+    let mut terms = recent_counts
+        .into_iter()
+        .filter(|(_, count)| *count >= MIN_RECENT_DOCUMENTS)
+        .map(|(term, recent_count)| {
+            let baseline_count = baseline_counts.get(&term).copied().unwrap_or(0);
+            let document_frequency = (recent_count + baseline_count) as f64;
+            let idf = ((total_documents + 1.0) / (document_frequency + 1.0)).ln() + 1.0;
+            let phrase_boost = if term.contains(' ') { 1.5 } else { 1.0 };
+            TrendingTerm {
+                score: recent_count as f64 / recent_documents * idf * phrase_boost,
+                term,
+                recent_count,
+                baseline_count,
+            }
+        })
+        .collect::<Vec<_>>();
+    terms.sort_by(|left, right| {
+        right
+            .score
+            .total_cmp(&left.score)
+            .then_with(|| left.term.cmp(&right.term))
+    });
+
+    let mut selected: Vec<TrendingTerm> = Vec::new();
+    for term in terms {
+        let covered = selected.iter().any(|chosen| {
+            chosen.term.contains(' ')
+                && chosen.recent_count >= term.recent_count
+                && chosen.term.split(' ').any(|word| word == term.term)
+        });
+        if !covered {
+            selected.push(term);
+        }
+        if selected.len() >= limit {
+            break;
+        }
+    }
+    selected
+}
+
+fn document_frequencies(titles: &[String]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for title in titles {
+        for term in title_terms(title) {
+            *counts.entry(term).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn title_terms(title: &str) -> HashSet<String> {
+    let mut terms = HashSet::new();
+    let mut previous: Option<String> = None;
+    for token in tokenize(title) {
+        if is_cjk_token(&token) {
+            let chars = token.chars().collect::<Vec<_>>();
+            for pair in chars.windows(2) {
+                terms.insert(pair.iter().collect());
+            }
+            previous = None;
+            continue;
+        }
+        let numeric = token.parse::<f64>().is_ok();
+        if (token.len() < 3 && !numeric) || STOPWORDS.contains(&token.as_str()) {
+            previous = None;
+            continue;
+        }
+        if let Some(previous) = previous.as_deref() {
+            terms.insert(format!("{previous} {token}"));
+        }
+        if !numeric {
+            terms.insert(token.clone());
+        }
+        previous = Some(token);
+    }
+    terms
+}
+
+fn tokenize(title: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut current_is_cjk = false;
+    for ch in title.chars() {
+        let cjk = is_cjk(ch);
+        let word = cjk || ch.is_alphanumeric() || matches!(ch, '+' | '#' | '-' | '.');
+        if !word || (!current.is_empty() && cjk != current_is_cjk) {
+            push_token(&mut tokens, &mut current);
+        }
+        if word {
+            current.extend(ch.to_lowercase());
+            current_is_cjk = cjk;
+        }
+    }
+    push_token(&mut tokens, &mut current);
+    tokens
+}
+
+fn push_token(tokens: &mut Vec<String>, current: &mut String) {
+    let token = current.trim_matches(|ch| matches!(ch, '-' | '.'));
+    if !token.is_empty() {
+        tokens.push(token.to_string());
+    }
+    current.clear();
+}
+
+fn is_cjk(ch: char) -> bool {
+    matches!(ch as u32, 0x3040..=0x30ff | 0x3400..=0x4dbf | 0x4e00..=0x9fff | 0xac00..=0xd7af)
+}
+
+fn is_cjk_token(token: &str) -> bool {
+    token.chars().next().is_some_and(is_cjk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn ranks_new_phrases_above_everyday_words() {
+        let recent = titles(&[
+            "Rust 2024 edition released",
+            "What the Rust 2024 edition means for async",
+            "Migrating a crate to the 2024 edition",
+            "Weekly links: databases",
+        ]);
+        let baseline = titles(&[
+            "Weekly links: compilers",
+            "Weekly links: networking",
+            "Weekly links: testing",
+            "Async traits in practice",
+        ]);
+
+        let trending = compute_trending_terms(&recent, &baseline, 5);
+
+        assert_eq!(trending[0].term, "2024 edition");
+        assert_eq!(trending[0].recent_count, 3);
+        assert!(trending.iter().all(|term| term.term != "edition"));
+        assert!(trending.iter().all(|term| term.term != "weekly"));
+    }
+
+    #[test]
+    fn splits_cjk_titles_into_character_bigrams() {
+        let recent = titles(&["大模型推理优化", "大模型的训练成本"]);
+        let trending = compute_trending_terms(&recent, &[], 3);
+
+        assert!(trending.iter().any(|term| term.term == "模型"));
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+pub const MAX_TRIAGE_SESSIONS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageAction {
+    Read,
+    Unread,
+    Star,
+    Unstar,
+    Skip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriageCursor {
+    pub session_id: String,
+    pub position: usize,
+    pub total: usize,
+    pub entry_id: Option<i64>,
+}
+
+#[derive(Debug)]
+struct TriageSession {
+    sequence: u64,
+    entry_ids: Vec<i64>,
+    position: usize,
+}
+
+impl TriageSession {
+    fn cursor(&self, session_id: &str) -> TriageCursor {
+        TriageCursor {
+            session_id: session_id.to_string(),
+            position: self.position,
+            total: self.entry_ids.len(),
+            entry_id: self.entry_ids.get(self.position).copied(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TriageSessions {
+    sessions: Mutex<HashMap<String, TriageSession>>,
+    next_sequence: AtomicU64,
+}
+
+impl TriageSessions {
+    pub fn start(&self, entry_ids: Vec<i64>) -> TriageCursor {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst) + 1;
+        let session_id = format!("triage-{sequence}");
+        let session = TriageSession {
+            sequence,
+            entry_ids,
+            position: 0,
+        };
+        let cursor = session.cursor(&session_id);
+        let mut sessions = self.lock();
+        while sessions.len() >= MAX_TRIAGE_SESSIONS {
+            let Some(oldest) = sessions
+                .iter()
+                .min_by_key(|(_, session)| session.sequence)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            sessions.remove(&oldest);
+        }
+        sessions.insert(session_id, session);
+        cursor
+    }
+
+    pub fn current(&self, session_id: &str) -> Option<TriageCursor> {
+        self.lock()
+            .get(session_id)
+            .map(|session| session.cursor(session_id))
+    }
+
+    pub fn step(&self, session_id: &str, offset: i64) -> Option<TriageCursor> {
+        let mut sessions = self.lock();
+        let session = sessions.get_mut(session_id)?;
+        let total = session.entry_ids.len() as i64;
+        session.position = (session.position as i64 + offset).clamp(0, total) as usize;
+        Some(session.cursor(session_id))
+    }
+
+    pub fn end(&self, session_id: &str) -> bool {
+        self.lock().remove(session_id).is_some()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, TriageSession>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_walks_snapshot_and_clamps_at_the_ends() {
+        let sessions = TriageSessions::default();
+        let started = sessions.start(vec![30, 20, 10]);
+        assert_eq!(started.position, 0);
+        assert_eq!(started.total, 3);
+        assert_eq!(started.entry_id, Some(30));
+
+        let id = started.session_id;
+        assert_eq!(sessions.step(&id, 1).and_then(|c| c.entry_id), Some(20));
+        assert_eq!(sessions.step(&id, -5).and_then(|c| c.entry_id), Some(30));
+        let done = sessions.step(&id, 10).expect("session exists");
+        assert_eq!(done.position, 3);
+        assert_eq!(done.entry_id, None);
+        assert_eq!(sessions.current(&id), Some(done));
+
+        assert!(sessions.end(&id));
+        assert!(sessions.step(&id, 1).is_none());
+    }
+
+    #[test]
+    fn evicts_oldest_session_when_full() {
+        let sessions = TriageSessions::default();
+        let first = sessions.start(vec![1]).session_id;
+        for _ in 0..MAX_TRIAGE_SESSIONS {
+            sessions.start(vec![2]);
+        }
+        assert!(sessions.current(&first).is_none());
+    }
+}
@@ -1,46 +1,260 @@
 mod core;
 
-use core::feed::fetcher::{fetch_feed_with_retry, FetchStatus};
-use core::feed::parser::parse_feed_bytes;
+use core::actions::ActionDescriptor;
+use core::api::{generate_api_token, start_local_api, LocalApiServer, DEFAULT_LOCAL_API_PORT};
+use core::backup::{
+    latest_backup_at, latest_backup_opml, prune_backups, recovery_backup_dirs, remember_backup_dir,
+    write_backup_snapshot,
+};
+use core::catchup::{select_catchup, CatchupSelection, CATCHUP_BATCH_SIZE, MAX_CATCHUP_MINUTES};
+use core::diagnostics::DiagnosticsBundle;
+use core::feed::backfill::{backfill_feed, BackfillLimits, WAYBACK_BASE_URL};
+use core::feed::fetcher::{
+    feed_client_builder, fetch_feed, fetch_feed_with_retry, ByteBudget, FetchError, FetchStatus,
+};
+use core::feed::paging::fetch_following_pages;
+use core::feed::parser::{
+    looks_like_parked_domain, parse_feed_with_content_type, SanitizerAllowlist,
+};
+use core::feed::probe::{probe_feed as probe_feed_url, ProbeReport};
+use core::feed::writer::{build_rss_feed, FeedChannel};
+use core::hooks::{hook_payload, hooks_for, run_hook, AutomationHook, HookAction, HookEvent};
 use core::importer::{
-    build_import_preview, normalize_url, parse_json_sources, parse_opml, parse_url_list,
-    ImportSource,
+    apply_category_mapping, build_import_preview, extract_html_links, normalize_url,
+    parse_json_sources, parse_opml, parse_url_list, select_candidates, source_site_host,
+    suggest_categories, CategorySuggestion, ImportPreview, ImportSource, RssrSourceAttributes,
+};
+use core::llm::cache::{
+    legacy_llm_cache_key, llm_cache_key, read_llm_cache_export, remap_models,
+    write_llm_cache_export,
+};
+use core::llm::glossary::{apply_glossary, glossary_prompt, matching_terms, GlossaryTerm};
+use core::llm::{
+    call_chat_completion, stream_chat_completion, validate_config, GenerationParams, LlmConfig,
+    LlmProfile, LlmProvider, LlmTask, TaskGenerationParams, DEFAULT_MAX_INPUT_CHARS,
+    DEFAULT_MAX_INPUT_LINES, DEFAULT_TEXT_WRAP_WIDTH,
+};
+use core::notifications::{
+    notification_match, rule_match, rules_for_source, NotificationPrefs, NotificationRule,
+    NotifyMode,
+};
+use core::reader::fetch_article;
+use core::redact::redact_secrets;
+use core::render::{
+    build_entry_document, find_browser, render_document, RenderError, RenderFormat,
+};
+use core::snapshot::{
+    download_snapshot, open_snapshot, seal_snapshot, upload_snapshot, ProfileSnapshot, RemoteKind,
+    RemoteTarget, SNAPSHOT_FORMAT_VERSION,
 };
-use core::llm::{call_chat_completion, validate_config, LlmConfig};
-use core::storage::models::{EntryRecord, NewSource, SourceRecord};
-use core::storage::repository::SourceRepository;
+use core::storage::interrupt::QueryControl;
+use core::storage::models::{
+    EntryCounts, EntryCursor, EntryDateBucket, EntryFulltext, EntryRecord, EntryTitleRecord,
+    EntryView, FailingSource, LlmCacheImport, MigrationStatus, NewSource, OrphanedEntry,
+    PendingNotification, ReadOperation, SourceDeletion, SourceEntryStats, SourceRecord,
+    SourceSearchFilter, SourceSettings, SourceSortField, SourceStorageUsage, SourceSyncResult,
+    StaleSource, SyncAttempt, SyncLogEntry,
+};
+use core::storage::repository::{DatabaseFault, ReadScope, SourceRepository, StorageError};
+use core::subscription::discovery::{
+    aggregate_suggestions, discover_feeds as discover_site_feeds, discover_site,
+    find_replacement_feed, DiscoverySuggestion, FeedCandidate,
+};
+use core::subscription::naming::{
+    naming_user_prompt, parse_naming_response, SourceSuggestion, NAMING_SYSTEM_PROMPT,
+};
+use core::subscription::{curated_index, starter_pack_sources, SourceRecommendation, StarterPack};
+use core::sync::politeness::SyncHttp;
+use core::sync::problems::{group_problem_sources, ProblemGroup};
+use core::trending::{compute_trending_terms, TrendingTerm};
+use core::triage::{TriageAction, TriageCursor, TriageSessions};
 use core::AppServices;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
-use std::collections::{BTreeMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
 use tokio::sync::RwLock;
 use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
-const LLM_CONFIG_KEY: &str = "llm_config";
+const DEFAULT_LLM_PROFILE_NAME: &str = "default";
 const SYNC_SETTINGS_KEY: &str = "sync_settings";
+const DISCOVERY_SUGGESTIONS_KEY: &str = "discovery_suggestions";
+const BACKUP_SETTINGS_KEY: &str = "backup_settings";
+const LLM_BUDGET_KEY: &str = "llm_budget";
+const READ_POLICY_KEY: &str = "read_policy";
+const LOCAL_API_SETTINGS_KEY: &str = "local_api_settings";
+const AUTOMATION_HOOKS_KEY: &str = "automation_hooks";
+const ADVANCED_SETTINGS_KEY: &str = "advanced_settings";
+const OFFLINE_MODE_KEY: &str = "offline_mode";
+const ONBOARDING_KEY: &str = "onboarding";
+const MAX_SOURCE_RETENTION_DAYS: i64 = 3650;
+const MAX_SOURCE_SYNC_INTERVAL_SECS: i64 = 30 * 24 * 3600;
+const MAX_FIRST_SYNC_MAX_AGE_DAYS: i64 = 36_500;
+const BACKFILL_MAX_PAGES: usize = 20;
+const BACKFILL_MAX_SNAPSHOTS: usize = 24;
+const UI_QUERY_TIMEOUT_MS: u64 = 5000;
+const DEFAULT_TRIAGE_LIMIT: i64 = 300;
+const MAX_TRIAGE_LIMIT: i64 = 5000;
+const SNAPSHOT_TARGET_KEY: &str = "snapshot_target";
+const SNAPSHOT_TRANSFER_TIMEOUT_SECS: u64 = 120;
+const LAST_SYNC_REPORT_KEY: &str = "last_sync_report";
+const ONBOARDING_SYNC_PROGRESS_EVENT: &str = "onboarding://sync-progress";
+const SUMMARY_CHUNK_EVENT: &str = "summary-chunk";
+const TITLE_BACKFILL_PROGRESS_EVENT: &str = "translation://backfill-progress";
+const SUMMARY_SYSTEM_PROMPT: &str = "You summarize technical articles in concise Chinese.";
 
 const DEFAULT_SYNC_INTERVAL_SECS: u64 = 600;
 const DEFAULT_SYNC_MAX_CONCURRENCY: u32 = 6;
 const DEFAULT_SYNC_BATCH_LIMIT: u32 = 24;
 const DEFAULT_SYNC_TIMEOUT_SECS: u64 = 12;
 const DEFAULT_SYNC_RETRY_COUNT: u32 = 1;
+const DEFAULT_SYNC_MAX_INFLIGHT_MB: u32 = 64;
+const DEFAULT_SYNC_MAX_PAGES: u32 = 5;
+const DEFAULT_SYNC_PER_HOST_CONCURRENCY: u32 = 2;
+const DEFAULT_SYNC_PER_HOST_DELAY_MS: u64 = 500;
+const MAX_SYNC_PER_HOST_DELAY_MS: u64 = 10_000;
+const MAX_SYNC_PAGES_PER_SYNC: u32 = 50;
+const DEFAULT_FAILURE_DISABLE_THRESHOLD: u32 = 10;
+const MAX_FAILURE_DISABLE_THRESHOLD: u32 = 1000;
+const ABSOLUTE_MIN_SYNC_INTERVAL_SECS: u64 = 5;
+const ABSOLUTE_MAX_SYNC_CONCURRENCY: u32 = 64;
+const ABSOLUTE_MAX_SYNC_BATCH_LIMIT: u32 = 2000;
+const ABSOLUTE_MIN_SYNC_TIMEOUT_SECS: u64 = 1;
+const ABSOLUTE_MAX_SYNC_TIMEOUT_SECS: u64 = 600;
+const ABSOLUTE_MAX_SYNC_RETRY_COUNT: u32 = 10;
+const ABSOLUTE_MAX_SYNC_INFLIGHT_MB: u32 = 8192;
 const DEFAULT_TITLE_TRANSLATE_INTERVAL_SECS: u64 = 45;
 const DEFAULT_TITLE_TRANSLATE_BATCH_SIZE: i64 = 300;
 const DEFAULT_TITLE_TRANSLATE_MAX_CONCURRENCY: usize = 4;
+const TITLE_BACKFILL_BATCH_SIZE: i64 = 40;
+const DEFAULT_SUMMARIES_PER_CYCLE: u32 = 10;
+const MAX_TRANSLATIONS_PER_CYCLE: u32 = 2000;
+const MAX_SUMMARIES_PER_CYCLE: u32 = 200;
+const MAX_SUMMARY_ATTEMPTS: u32 = 3;
+const DEFAULT_READ_AFTER_DAYS: u32 = 7;
+const MAX_READ_AFTER_DAYS: u32 = 365;
+const COMPACT_STORAGE_BATCH_SIZE: i64 = 200;
+const SOURCE_DETAILS_RECENT_ENTRIES: i64 = 10;
+const DEFAULT_SYNC_HISTORY_LIMIT: i64 = 50;
+const MAX_SYNC_HISTORY_LIMIT: i64 = 200;
+const DEFAULT_FAILING_SOURCES_LIMIT: i64 = 100;
+const DIAGNOSTICS_SYNC_LOG_LIMIT: i64 = 10;
+const MAX_CUSTOM_ICON_BYTES: usize = 128 * 1024;
+const DEFAULT_RECOMMENDATION_LIMIT: usize = 12;
+const DEFAULT_STALE_THRESHOLD_DAYS: i64 = 180;
+const STALE_GONE_STATUS_THRESHOLD: i64 = 3;
+const RATE_LIMIT_MIN_BACKOFF_SECS: u64 = 15 * 60;
+const RATE_LIMIT_DEFAULT_BACKOFF_SECS: u64 = 6 * 60 * 60;
+const RATE_LIMIT_MAX_BACKOFF_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_BACKUP_INTERVAL_HOURS: u32 = 24;
+const DEFAULT_BACKUP_RETENTION_COUNT: u32 = 7;
+const BACKUP_CHECK_INTERVAL_SECS: u64 = 600;
+const STARRED_FEED_MAX_ENTRIES: i64 = 500;
+const DEFAULT_STARRED_PAGE_SIZE: i64 = 50;
+const MAX_STARRED_PAGE_SIZE: i64 = 500;
+const NOTIFICATION_BATCH_LIMIT: i64 = 50;
+const NOTIFICATIONS_EVENT: &str = "notifications://new-entries";
+const MAX_NATIVE_NOTIFICATIONS: usize = 3;
+const DIGEST_HOOK_MAX_ENTRIES: i64 = 20;
+const DEFAULT_HISTOGRAM_DAYS: i64 = 90;
+const DEFAULT_TRENDING_DAYS: i64 = 3;
+const TRENDING_BASELINE_MULTIPLIER: i64 = 9;
+const DEFAULT_TRENDING_LIMIT: usize = 20;
+const DEFAULT_RECENTLY_READ_LIMIT: i64 = 50;
 
 struct SharedState {
     services: AppServices,
     source_repository: SourceRepository,
+    database: DatabaseStartup,
     sync_runtime: Arc<SyncRuntime>,
+    network: Arc<NetworkRuntime>,
+    local_api: Arc<tokio::sync::Mutex<Option<LocalApiServer>>>,
+    queries: QueryRuntime,
+    triage: TriageSessions,
+    html_imports: HtmlImportCache,
+}
+
+type SiteFeeds = Vec<(String, Vec<FeedCandidate>)>;
+
+#[derive(Default)]
+struct HtmlImportCache {
+    last: std::sync::Mutex<Option<(String, SiteFeeds)>>,
+}
+
+impl HtmlImportCache {
+    fn get(&self, html: &str) -> Option<SiteFeeds> {
+        self.last
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .as_ref()
+            .filter(|(content, _)| content == html)
+            .map(|(_, discovered)| discovered.clone())
+    }
+
+    fn store(&self, html: &str, discovered: SiteFeeds) {
+        *self.last.lock().unwrap_or_else(|error| error.into_inner()) =
+            Some((html.to_string(), discovered));
+    }
+}
+
+#[derive(Default)]
+struct QueryRuntime {
+    active: std::sync::Mutex<HashMap<String, QueryControl>>,
+}
+
+impl QueryRuntime {
+    fn begin(&self, key: &str, timeout: Duration) -> QueryControl {
+        let control = QueryControl::new(timeout);
+        let previous = self
+            .active
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .insert(key.to_string(), control.clone());
+        if let Some(previous) = previous {
+            previous.cancel();
+        }
+        control
+    }
+
+    fn finish(&self, key: &str, control: &QueryControl) {
+        let mut active = self
+            .active
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if active
+            .get(key)
+            .is_some_and(|current| current.same_as(control))
+        {
+            active.remove(key);
+        }
+    }
+
+    fn cancel(&self, key: &str) -> bool {
+        let removed = self
+            .active
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .remove(key);
+        match removed {
+            Some(control) => {
+                control.cancel();
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 struct SyncRuntime {
     running: AtomicBool,
+    paused: AtomicBool,
+    cancel: std::sync::Mutex<CancellationToken>,
     last_report: RwLock<Option<SyncBatchResponse>>,
     last_error: RwLock<Option<String>>,
 }
@@ -49,12 +263,136 @@ impl Default for SyncRuntime {
     fn default() -> Self {
         Self {
             running: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            cancel: std::sync::Mutex::new(CancellationToken::new()),
             last_report: RwLock::new(None),
             last_error: RwLock::new(None),
         }
     }
 }
 
+impl SyncRuntime {
+    fn try_begin(&self) -> Option<CancellationToken> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        let token = CancellationToken::new();
+        *self
+            .cancel
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) = token.clone();
+        Some(token)
+    }
+
+    fn cancel(&self) -> bool {
+        if !self.running.load(Ordering::SeqCst) {
+            return false;
+        }
+        self.cancel
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .cancel();
+        true
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum QueuedRequest {
+    SyncSource { source_id: i64 },
+    SyncActiveSources,
+    SummarizeEntry { entry_id: i64 },
+}
+
+#[derive(Default)]
+struct NetworkRuntime {
+    offline: AtomicBool,
+    queued: std::sync::Mutex<Vec<QueuedRequest>>,
+    summary_attempts: std::sync::Mutex<HashMap<i64, u32>>,
+}
+
+impl NetworkRuntime {
+    fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::SeqCst)
+    }
+
+    fn enqueue(&self, request: QueuedRequest) {
+        let mut queued = self
+            .queued
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if !queued.contains(&request) {
+            queued.push(request);
+        }
+    }
+
+    fn queued(&self) -> Vec<QueuedRequest> {
+        self.queued
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+
+    fn drain(&self, summary_limit: usize) -> Vec<QueuedRequest> {
+        let mut queued = self
+            .queued
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let mut summaries = 0;
+        let (taken, kept) = std::mem::take(&mut *queued)
+            .into_iter()
+            .partition::<Vec<_>, _>(|request| match request {
+                QueuedRequest::SummarizeEntry { .. } if summaries < summary_limit => {
+                    summaries += 1;
+                    true
+                }
+                QueuedRequest::SummarizeEntry { .. } => false,
+                _ => true,
+            });
+        *queued = kept;
+        taken
+    }
+
+    fn settle_summary(&self, entry_id: i64, succeeded: bool) -> bool {
+        let mut attempts = self
+            .summary_attempts
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if succeeded {
+            attempts.remove(&entry_id);
+            return false;
+        }
+        let failures = attempts.entry(entry_id).or_default();
+        *failures += 1;
+        if *failures >= MAX_SUMMARY_ATTEMPTS {
+            attempts.remove(&entry_id);
+            return false;
+        }
+        drop(attempts);
+        self.enqueue(QueuedRequest::SummarizeEntry { entry_id });
+        true
+    }
+
+    fn queued_summaries(&self) -> usize {
+        self.queued
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .iter()
+            .filter(|request| matches!(request, QueuedRequest::SummarizeEntry { .. }))
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct NetworkStatus {
+    offline: bool,
+    queued_requests: Vec<QueuedRequest>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct UpsertSourceRequest {
     title: String,
@@ -62,6 +400,25 @@ struct UpsertSourceRequest {
     feed_url: String,
     category: Option<String>,
     is_active: bool,
+    #[serde(default)]
+    mark_existing_read: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct SourceFiltersRequest {
+    is_active: Option<bool>,
+    failing_only: bool,
+    sort_by: Option<String>,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct OpmlExportRequest {
+    categories: Vec<String>,
+    source_ids: Vec<i64>,
+    include_rssr_attributes: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -70,6 +427,13 @@ struct ImportRequest {
     content: String,
     default_category: Option<String>,
     is_active: Option<bool>,
+    selected_feed_urls: Option<Vec<String>>,
+    excluded_feed_urls: Option<Vec<String>>,
+    category_mapping: Option<HashMap<String, String>>,
+    #[serde(default)]
+    mark_existing_read: bool,
+    #[serde(default)]
+    all_feeds_per_site: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -78,6 +442,34 @@ struct ListEntriesRequest {
     search: Option<String>,
     unread_only: bool,
     limit: Option<i64>,
+    query_key: Option<String>,
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct EntryFilterRequest {
+    source_id: Option<i64>,
+    search: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct TriageFilterRequest {
+    source_id: Option<i64>,
+    search: Option<String>,
+    unread_only: bool,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct EntryHistogramRequest {
+    source_id: Option<i64>,
+    search: Option<String>,
+    unread_only: bool,
+    days: Option<i64>,
+    utc_offset_minutes: i64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -92,10 +484,20 @@ struct SourceDto {
     etag: Option<String>,
     last_modified: Option<String>,
     last_synced_at: Option<String>,
+    color: Option<String>,
+    custom_icon: Option<String>,
     created_at: String,
     updated_at: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct SourceDetailsDto {
+    source: SourceDto,
+    stats: SourceEntryStats,
+    last_sync: SourceSyncResult,
+    recent_entries: Vec<EntryDto>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct EntryDto {
     id: i64,
@@ -111,20 +513,131 @@ struct EntryDto {
     is_read: bool,
     is_starred: bool,
     created_at: String,
+    updated_at: Option<String>,
+    is_updated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct EntriesPage {
+    entries: Vec<EntryDto>,
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StarredEntriesPage {
+    entries: Vec<EntryDto>,
+    next_offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OpenedEntry {
+    entry_id: i64,
+    link: String,
+    opened_externally: bool,
+    open_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SummaryChunk {
+    entry_id: i64,
+    delta: String,
+    done: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TriageStepResponse {
+    session_id: String,
+    position: usize,
+    total: usize,
+    entry: Option<EntryDto>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ReadHistoryDto {
+    #[serde(flatten)]
+    entry: EntryDto,
+    read_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 struct ImportPreviewResponse {
     new_count: usize,
     duplicate_count: usize,
+    possible_duplicate_count: usize,
     new_sources: Vec<ImportSource>,
     duplicate_sources: Vec<ImportSource>,
+    possible_duplicates: Vec<ImportSource>,
+    category_suggestions: Vec<CategorySuggestion>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum OnboardingStep {
+    ImportSources,
+    FirstSync,
+    Completed,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct OnboardingRecord {
+    first_sync_at: Option<String>,
+    completed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OnboardingState {
+    step: OnboardingStep,
+    source_count: usize,
+    first_sync_at: Option<String>,
+    completed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct OnboardingImportRequest {
+    starter_pack_ids: Vec<String>,
+    opml: Option<String>,
+    mark_existing_read: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OnboardingSyncProgress {
+    completed: usize,
+    total: usize,
+    source_id: i64,
+    title: String,
+    status: String,
+    upserted_entries: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
 struct ImportExecuteResponse {
     imported_count: usize,
+    updated_count: usize,
     duplicate_count: usize,
+    possible_duplicate_count: usize,
+    possible_duplicates: Vec<ImportSource>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SnapshotTransferResponse {
+    created_at: String,
+    source_count: usize,
+    read_state_count: usize,
+    bytes: usize,
+    imported_sources: usize,
+    updated_entries: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BackfillResponse {
+    source_id: i64,
+    pages_fetched: usize,
+    snapshots_fetched: usize,
+    upserted_entries: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -132,22 +645,178 @@ struct SyncSourceResponse {
     source_id: i64,
     status: String,
     upserted_entries: usize,
+    inserted_entries: usize,
+    updated_entries: usize,
+    conflicted_entries: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SyncFailureClass {
+    Timeout,
+    Network,
+    ClientError,
+    ServerError,
+    ParseError,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct SyncFailure {
+    class: SyncFailureClass,
+    message: String,
+}
+
+impl SyncFailure {
+    fn new(class: SyncFailureClass, message: impl Into<String>) -> Self {
+        Self {
+            class,
+            message: redact_secrets(&message.into()),
+        }
+    }
+
+    fn from_fetch(error: &FetchError) -> Self {
+        let class = match error {
+            FetchError::Request(error) if error.is_timeout() => SyncFailureClass::Timeout,
+            FetchError::Request(_) => SyncFailureClass::Network,
+            FetchError::Unauthorized
+            | FetchError::Forbidden
+            | FetchError::NotFound
+            | FetchError::Gone
+            | FetchError::RateLimited { .. } => SyncFailureClass::ClientError,
+            FetchError::HttpStatus(code) if (400..500).contains(code) => {
+                SyncFailureClass::ClientError
+            }
+            FetchError::HttpStatus(code) if *code >= 500 => SyncFailureClass::ServerError,
+            _ => SyncFailureClass::Other,
+        };
+        Self::new(class, error.to_string())
+    }
+}
+
+impl From<String> for SyncFailure {
+    fn from(message: String) -> Self {
+        Self::new(SyncFailureClass::Other, message)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncSourceOutcome {
+    source_id: i64,
+    title: String,
+    status: String,
+    error: Option<String>,
+    failure_class: Option<SyncFailureClass>,
+    upserted_entries: usize,
+    #[serde(default)]
+    inserted_entries: usize,
+    #[serde(default)]
+    updated_entries: usize,
+    #[serde(default)]
+    conflicted_entries: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncFailureCounts {
+    timeout: usize,
+    network: usize,
+    client_error: usize,
+    server_error: usize,
+    parse_error: usize,
+    other: usize,
+}
+
+impl SyncFailureCounts {
+    fn record(&mut self, class: SyncFailureClass) {
+        let slot = match class {
+            SyncFailureClass::Timeout => &mut self.timeout,
+            SyncFailureClass::Network => &mut self.network,
+            SyncFailureClass::ClientError => &mut self.client_error,
+            SyncFailureClass::ServerError => &mut self.server_error,
+            SyncFailureClass::ParseError => &mut self.parse_error,
+            SyncFailureClass::Other => &mut self.other,
+        };
+        *slot += 1;
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct SyncBatchResponse {
     synced_sources: usize,
     failed_sources: usize,
     total_upserted_entries: usize,
+    total_inserted_entries: usize,
+    failure_counts: SyncFailureCounts,
+    sources: Vec<SyncSourceOutcome>,
+    cancelled: bool,
+    finished_at: Option<String>,
+}
+
+impl SyncBatchResponse {
+    fn record(
+        &mut self,
+        source_id: i64,
+        title: String,
+        result: Result<SyncSourceResponse, SyncFailure>,
+    ) -> &SyncSourceOutcome {
+        let outcome = match result {
+            Ok(response) => {
+                self.synced_sources += 1;
+                self.total_upserted_entries += response.upserted_entries;
+                self.total_inserted_entries += response.inserted_entries;
+                SyncSourceOutcome {
+                    source_id,
+                    title,
+                    status: response.status,
+                    error: None,
+                    failure_class: None,
+                    upserted_entries: response.upserted_entries,
+                    inserted_entries: response.inserted_entries,
+                    updated_entries: response.updated_entries,
+                    conflicted_entries: response.conflicted_entries,
+                }
+            }
+            Err(failure) => {
+                self.failed_sources += 1;
+                self.failure_counts.record(failure.class);
+                SyncSourceOutcome {
+                    source_id,
+                    title,
+                    status: "failed".to_string(),
+                    error: Some(failure.message),
+                    failure_class: Some(failure.class),
+                    upserted_entries: 0,
+                    inserted_entries: 0,
+                    updated_entries: 0,
+                    conflicted_entries: 0,
+                }
+            }
+        };
+        self.sources.push(outcome);
+        &self.sources[self.sources.len() - 1]
+    }
+
+    fn record_aborted(&mut self) {
+        self.failed_sources += 1;
+        self.failure_counts.record(SyncFailureClass::Other);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct SyncSettings {
     interval_secs: u64,
     max_concurrency: u32,
     batch_limit: u32,
     timeout_secs: u64,
     retry_count: u32,
+    max_inflight_mb: u32,
+    auto_fix_moved_feeds: bool,
+    max_pages_per_sync: u32,
+    failure_disable_threshold: u32,
+    per_host_concurrency: u32,
+    per_host_delay_ms: u64,
 }
 
 impl Default for SyncSettings {
@@ -158,6 +827,136 @@ impl Default for SyncSettings {
             batch_limit: DEFAULT_SYNC_BATCH_LIMIT,
             timeout_secs: DEFAULT_SYNC_TIMEOUT_SECS,
             retry_count: DEFAULT_SYNC_RETRY_COUNT,
+            max_inflight_mb: DEFAULT_SYNC_MAX_INFLIGHT_MB,
+            auto_fix_moved_feeds: false,
+            max_pages_per_sync: DEFAULT_SYNC_MAX_PAGES,
+            failure_disable_threshold: DEFAULT_FAILURE_DISABLE_THRESHOLD,
+            per_host_concurrency: DEFAULT_SYNC_PER_HOST_CONCURRENCY,
+            per_host_delay_ms: DEFAULT_SYNC_PER_HOST_DELAY_MS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct AdvancedSettings {
+    min_sync_interval_secs: u64,
+    max_sync_interval_secs: u64,
+    max_sync_concurrency: u32,
+    max_sync_batch_limit: u32,
+    min_sync_timeout_secs: u64,
+    max_sync_timeout_secs: u64,
+    max_sync_retry_count: u32,
+    max_sync_inflight_mb: u32,
+}
+
+impl Default for AdvancedSettings {
+    fn default() -> Self {
+        Self {
+            min_sync_interval_secs: 60,
+            max_sync_interval_secs: 3600,
+            max_sync_concurrency: 16,
+            max_sync_batch_limit: 200,
+            min_sync_timeout_secs: 5,
+            max_sync_timeout_secs: 60,
+            max_sync_retry_count: 4,
+            max_sync_inflight_mb: 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct BackupSettings {
+    enabled: bool,
+    directory: Option<String>,
+    interval_hours: u32,
+    retention_count: u32,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: None,
+            interval_hours: DEFAULT_BACKUP_INTERVAL_HOURS,
+            retention_count: DEFAULT_BACKUP_RETENTION_COUNT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReadMarkMode {
+    #[default]
+    OnOpen,
+    OnScroll,
+    AfterDays,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct ReadPolicySettings {
+    mode: ReadMarkMode,
+    after_days: u32,
+}
+
+impl Default for ReadPolicySettings {
+    fn default() -> Self {
+        Self {
+            mode: ReadMarkMode::OnOpen,
+            after_days: DEFAULT_READ_AFTER_DAYS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+struct LlmBudgetSettings {
+    translations_per_cycle: u32,
+    summaries_per_cycle: u32,
+}
+
+impl Default for LlmBudgetSettings {
+    fn default() -> Self {
+        Self {
+            translations_per_cycle: DEFAULT_TITLE_TRANSLATE_BATCH_SIZE as u32,
+            summaries_per_cycle: DEFAULT_SUMMARIES_PER_CYCLE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LlmBacklogStatus {
+    untranslated_titles: i64,
+    queued_summaries: usize,
+    budget: LlmBudgetSettings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TitleBackfillProgress {
+    source_id: i64,
+    translated: usize,
+    remaining: i64,
+    total: i64,
+    done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct LocalApiSettings {
+    enabled: bool,
+    port: u16,
+    token: Option<String>,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_LOCAL_API_PORT,
+            token: None,
         }
     }
 }
@@ -165,13 +964,59 @@ impl Default for SyncSettings {
 #[derive(Debug, Clone, Serialize)]
 struct SyncRuntimeStatus {
     running: bool,
+    paused: bool,
     last_report: Option<SyncBatchResponse>,
     last_error: Option<String>,
 }
 
 #[tauri::command]
 fn app_health(state: tauri::State<'_, SharedState>) -> BTreeMap<String, String> {
-    state.services.health_report()
+    let mut report = state.services.health_report();
+    report.insert("database".to_string(), state.database.status.to_string());
+    if let Some(detail) = &state.database.detail {
+        report.insert("database_detail".to_string(), redact_secrets(detail));
+    }
+    let network = if state.network.is_offline() {
+        "offline"
+    } else {
+        "online"
+    };
+    report.insert("network".to_string(), network.to_string());
+    report.insert(
+        "queued_requests".to_string(),
+        state.network.queued().len().to_string(),
+    );
+    report
+}
+
+#[tauri::command]
+fn get_network_status(state: tauri::State<'_, SharedState>) -> NetworkStatus {
+    network_status(&state.network)
+}
+
+#[tauri::command]
+async fn set_offline_mode(
+    offline: bool,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<NetworkStatus, String> {
+    state
+        .source_repository
+        .set_setting(OFFLINE_MODE_KEY, &offline.to_string())
+        .await
+        .map_err(|error| error.to_string())?;
+    state.network.offline.store(offline, Ordering::SeqCst);
+    if !offline {
+        let budget = load_llm_budget(&state.source_repository).await?;
+        let queued = state.network.drain(budget.summaries_per_cycle as usize);
+        let repository = state.source_repository.clone();
+        let runtime = state.sync_runtime.clone();
+        let network = state.network.clone();
+        tauri::async_runtime::spawn(async move {
+            run_queued_requests(app, repository, runtime, network, queued).await;
+        });
+    }
+    Ok(network_status(&state.network))
 }
 
 #[tauri::command]
@@ -185,579 +1030,4371 @@ async fn list_sources(state: tauri::State<'_, SharedState>) -> Result<Vec<Source
 }
 
 #[tauri::command]
-async fn upsert_source(
-    request: UpsertSourceRequest,
+async fn search_sources(
+    query: Option<String>,
+    filters: Option<SourceFiltersRequest>,
     state: tauri::State<'_, SharedState>,
-) -> Result<SourceDto, String> {
-    let source = NewSource {
-        title: request.title,
-        site_url: request.site_url,
-        feed_url: request.feed_url,
-        category: request.category,
-        is_active: request.is_active,
+) -> Result<Vec<SourceDto>, String> {
+    let filters = filters.unwrap_or_default();
+    let filter = SourceSearchFilter {
+        query,
+        is_active: filters.is_active,
+        failing_only: filters.failing_only,
+        sort_by: parse_source_sort_field(filters.sort_by.as_deref())?,
+        limit: filters.limit.unwrap_or(500).clamp(1, 5000),
     };
-    let row = state
+    let rows = state
         .source_repository
-        .upsert_source(&source)
+        .search_sources(&filter)
         .await
         .map_err(|error| error.to_string())?;
-    Ok(source_to_dto(row))
+    Ok(rows.into_iter().map(source_to_dto).collect())
 }
 
 #[tauri::command]
-async fn delete_source(id: i64, state: tauri::State<'_, SharedState>) -> Result<u64, String> {
-    state
+async fn get_source_details(
+    source_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SourceDetailsDto, String> {
+    let details = state
         .source_repository
-        .delete_source(id)
+        .get_source_details(source_id, SOURCE_DETAILS_RECENT_ENTRIES)
         .await
-        .map_err(|error| error.to_string())
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("source {source_id} not found"))?;
+    Ok(SourceDetailsDto {
+        source: source_to_dto(details.source),
+        stats: details.stats,
+        last_sync: details.last_sync,
+        recent_entries: details
+            .recent_entries
+            .into_iter()
+            .map(entry_to_dto)
+            .collect(),
+    })
 }
 
 #[tauri::command]
-async fn set_sources_active(
-    source_ids: Vec<i64>,
-    is_active: bool,
+async fn get_source_sync_history(
+    source_id: i64,
+    limit: Option<i64>,
     state: tauri::State<'_, SharedState>,
-) -> Result<u64, String> {
+) -> Result<Vec<SyncLogEntry>, String> {
     state
         .source_repository
-        .set_sources_active(&source_ids, is_active)
+        .list_sync_log(
+            source_id,
+            limit
+                .unwrap_or(DEFAULT_SYNC_HISTORY_LIMIT)
+                .clamp(1, MAX_SYNC_HISTORY_LIMIT),
+        )
         .await
         .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn preview_import(
-    request: ImportRequest,
+async fn list_problem_sources(
     state: tauri::State<'_, SharedState>,
-) -> Result<ImportPreviewResponse, String> {
-    let candidates = parse_import_sources(&request)?;
-    let existing_rows = state
+) -> Result<Vec<ProblemGroup>, String> {
+    let sources = state
         .source_repository
-        .list_sources()
+        .list_problem_sources()
         .await
         .map_err(|error| error.to_string())?;
-    let existing_feed_urls: HashSet<String> = existing_rows
-        .into_iter()
-        .map(|row| normalize_url(&row.feed_url))
-        .collect();
-    let preview = build_import_preview(candidates, &existing_feed_urls);
-
-    Ok(ImportPreviewResponse {
-        new_count: preview.new_sources.len(),
-        duplicate_count: preview.duplicate_sources.len(),
-        new_sources: preview.new_sources,
-        duplicate_sources: preview.duplicate_sources,
-    })
+    Ok(group_problem_sources(sources))
 }
 
 #[tauri::command]
-async fn import_sources(
-    request: ImportRequest,
+async fn list_failing_sources(
+    limit: Option<i64>,
     state: tauri::State<'_, SharedState>,
-) -> Result<ImportExecuteResponse, String> {
-    let candidates = parse_import_sources(&request)?;
-    let existing_rows = state
+) -> Result<Vec<FailingSource>, String> {
+    state
         .source_repository
-        .list_sources()
+        .list_failing_sources(
+            limit
+                .unwrap_or(DEFAULT_FAILING_SOURCES_LIMIT)
+                .clamp(1, 1000),
+        )
         .await
-        .map_err(|error| error.to_string())?;
-    let existing_feed_urls: HashSet<String> = existing_rows
-        .into_iter()
-        .map(|row| normalize_url(&row.feed_url))
-        .collect();
-    let preview = build_import_preview(candidates, &existing_feed_urls);
-    let is_active = request.is_active.unwrap_or(true);
-    let default_category = request.default_category;
-    let sources_to_import: Vec<NewSource> = preview
-        .new_sources
-        .iter()
-        .map(|source| NewSource {
-            title: source.title.clone(),
-            site_url: source.site_url.clone(),
-            feed_url: source.feed_url.clone(),
-            category: source.category.clone().or_else(|| default_category.clone()),
-            is_active,
-        })
-        .collect();
+        .map_err(|error| error.to_string())
+}
 
-    let imported_count = state
+#[tauri::command]
+async fn upsert_source(
+    request: UpsertSourceRequest,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SourceDto, String> {
+    let source = NewSource {
+        title: request.title,
+        site_url: request.site_url,
+        feed_url: request.feed_url,
+        category: request.category,
+        is_active: request.is_active,
+    };
+    let row = state
         .source_repository
-        .upsert_sources_batch(&sources_to_import)
+        .upsert_source(&source)
         .await
         .map_err(|error| error.to_string())?;
+    if request.mark_existing_read {
+        state
+            .source_repository
+            .mark_backlog_read_on_first_sync(std::slice::from_ref(&row.feed_url))
+            .await
+            .map_err(|error| error.to_string())?;
+    }
+    Ok(source_to_dto(row))
+}
 
-    Ok(ImportExecuteResponse {
-        imported_count,
-        duplicate_count: preview.duplicate_sources.len(),
-    })
+#[tauri::command]
+async fn delete_source(
+    id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SourceDeletion, String> {
+    state
+        .source_repository
+        .delete_source(id)
+        .await
+        .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn list_entries(
-    request: ListEntriesRequest,
+async fn unsubscribe_sources(
+    source_ids: Vec<i64>,
     state: tauri::State<'_, SharedState>,
-) -> Result<Vec<EntryDto>, String> {
-    let rows = state
+) -> Result<SourceDeletion, String> {
+    state
         .source_repository
-        .list_entries(
-            request.source_id,
-            request.search.as_deref(),
-            request.unread_only,
-            request.limit.unwrap_or(300),
-        )
+        .delete_sources(&source_ids)
         .await
-        .map_err(|error| error.to_string())?;
-    Ok(rows.into_iter().map(entry_to_dto).collect())
+        .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn mark_entry_read(
-    entry_id: i64,
-    is_read: bool,
+async fn list_orphaned_entries(
     state: tauri::State<'_, SharedState>,
-) -> Result<u64, String> {
+) -> Result<Vec<OrphanedEntry>, String> {
     state
         .source_repository
-        .mark_entry_read(entry_id, is_read)
+        .list_orphaned_entries()
         .await
         .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn sync_source(
+async fn rehome_orphaned_entries(
     source_id: i64,
     state: tauri::State<'_, SharedState>,
-) -> Result<SyncSourceResponse, String> {
-    let source = state
+) -> Result<u64, String> {
+    state
         .source_repository
-        .get_source_by_id(source_id)
+        .rehome_orphaned_entries(source_id)
         .await
-        .map_err(|error| error.to_string())?
-        .ok_or_else(|| format!("source {source_id} not found"))?;
-    let settings = load_sync_settings(&state.source_repository).await?;
-    sync_single_source(&state.source_repository, source, &settings).await
+        .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn sync_active_sources(
+async fn get_stale_sources(
+    threshold_days: Option<i64>,
     state: tauri::State<'_, SharedState>,
-) -> Result<SyncRuntimeStatus, String> {
-    if state.sync_runtime.running.swap(true, Ordering::SeqCst) {
-        return get_sync_runtime_status(state).await;
-    }
-
-    let repository = state.source_repository.clone();
-    let runtime = state.sync_runtime.clone();
-    tauri::async_runtime::spawn(async move {
-        let result = sync_active_sources_internal(&repository).await;
-        match result {
-            Ok(report) => {
-                {
-                    let mut guard = runtime.last_report.write().await;
-                    *guard = Some(report);
-                }
-                {
-                    let mut guard = runtime.last_error.write().await;
-                    *guard = None;
-                }
-                let title_repository = repository.clone();
-                tauri::async_runtime::spawn(async move {
-                    let _ = translate_titles_background(
-                        &title_repository,
-                        DEFAULT_TITLE_TRANSLATE_BATCH_SIZE,
-                    )
-                    .await;
-                });
-            }
-            Err(error) => {
-                let mut guard = runtime.last_error.write().await;
-                *guard = Some(error);
-            }
-        }
-        runtime.running.store(false, Ordering::SeqCst);
-    });
+) -> Result<Vec<StaleSource>, String> {
+    state
+        .source_repository
+        .list_stale_sources(
+            threshold_days
+                .unwrap_or(DEFAULT_STALE_THRESHOLD_DAYS)
+                .clamp(1, 3650),
+            STALE_GONE_STATUS_THRESHOLD,
+        )
+        .await
+        .map_err(|error| error.to_string())
+}
 
-    get_sync_runtime_status(state).await
+#[tauri::command]
+async fn discover_feeds(
+    url: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<FeedCandidate>, String> {
+    ensure_online(&state.network)?;
+    let url = url.trim();
+    let url = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("https://{url}")
+    };
+    let settings = load_sync_settings(&state.source_repository).await?;
+    let client = sync_http(&settings)
+        .client(settings.timeout_secs)
+        .map_err(|error| error.to_string())?;
+    discover_site_feeds(&client, &url)
+        .await
+        .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn get_sync_runtime_status(
+async fn probe_feed(
+    url: String,
     state: tauri::State<'_, SharedState>,
-) -> Result<SyncRuntimeStatus, String> {
-    let last_report = state.sync_runtime.last_report.read().await.clone();
-    let last_error = state.sync_runtime.last_error.read().await.clone();
-    Ok(SyncRuntimeStatus {
-        running: state.sync_runtime.running.load(Ordering::SeqCst),
-        last_report,
-        last_error,
-    })
+) -> Result<ProbeReport, String> {
+    ensure_online(&state.network)?;
+    let settings = load_sync_settings(&state.source_repository).await?;
+    let client = feed_client_builder(settings.timeout_secs)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|error| error.to_string())?;
+    probe_feed_url(&client, &url)
+        .await
+        .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn get_sync_settings(state: tauri::State<'_, SharedState>) -> Result<SyncSettings, String> {
-    load_sync_settings(&state.source_repository).await
+async fn suggest_source_metadata(
+    url: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SourceSuggestion, String> {
+    ensure_online(&state.network)?;
+    let repository = &state.source_repository;
+    let config = resolve_llm_config(None, None, repository).await?;
+    let settings = load_sync_settings(repository).await?;
+    let client = sync_http(&settings)
+        .client(settings.timeout_secs)
+        .map_err(|error| error.to_string())?;
+    let FetchStatus::Updated(fetched) = fetch_feed(&client, url.trim(), None, None, None)
+        .await
+        .map_err(|error| error.to_string())?
+    else {
+        return Err("feed returned no content".to_string());
+    };
+    let feed = parse_feed_with_content_type(&fetched.body, fetched.content_type.as_deref())
+        .map_err(|error| error.to_string())?;
+    let entry_titles = feed
+        .entries
+        .into_iter()
+        .map(|entry| entry.title)
+        .collect::<Vec<_>>();
+    let mut categories = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .filter_map(|source| source.category)
+        .filter(|category| !category.trim().is_empty())
+        .collect::<Vec<_>>();
+    categories.sort();
+    categories.dedup();
+    let response = call_chat_completion(
+        &config,
+        &GenerationParams::default(),
+        NAMING_SYSTEM_PROMPT,
+        &naming_user_prompt(
+            url.trim(),
+            Some(feed.title.as_str()).filter(|title| !title.trim().is_empty()),
+            &entry_titles,
+            &categories,
+        ),
+    )
+    .await
+    .map_err(|error| error.to_string())?;
+    Ok(parse_naming_response(&response, &categories))
 }
 
 #[tauri::command]
-async fn save_sync_settings(
-    settings: SyncSettings,
+async fn rediscover_source_feed(
+    source_id: i64,
     state: tauri::State<'_, SharedState>,
-) -> Result<SyncSettings, String> {
-    let normalized = normalize_sync_settings(settings);
-    let serialized = serde_json::to_string(&normalized).map_err(|error| error.to_string())?;
-    state
+) -> Result<Option<String>, String> {
+    ensure_online(&state.network)?;
+    let source = state
         .source_repository
-        .set_setting(SYNC_SETTINGS_KEY, &serialized)
+        .get_source_by_id(source_id)
         .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("source {source_id} not found"))?;
+    let settings = load_sync_settings(&state.source_repository).await?;
+    let client = sync_http(&settings)
+        .client(settings.timeout_secs)
         .map_err(|error| error.to_string())?;
-    Ok(normalized)
+    rediscover_moved_feed(&state.source_repository, &client, &source, false).await
 }
 
 #[tauri::command]
-async fn get_llm_config(state: tauri::State<'_, SharedState>) -> Result<Option<LlmConfig>, String> {
-    get_saved_or_env_llm_config(&state.source_repository).await
+async fn accept_feed_replacement(
+    source_id: i64,
+    feed_url: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .replace_source_feed_url(source_id, feed_url.trim())
+        .await
+        .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn save_llm_config(
-    config: LlmConfig,
+async fn dismiss_feed_replacement(
+    source_id: i64,
     state: tauri::State<'_, SharedState>,
-) -> Result<(), String> {
-    validate_config(&config).map_err(|error| error.to_string())?;
-    let serialized = serde_json::to_string(&config).map_err(|error| error.to_string())?;
+) -> Result<u64, String> {
     state
         .source_repository
-        .set_setting(LLM_CONFIG_KEY, &serialized)
+        .set_source_replacement_feed(source_id, None)
         .await
         .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn test_llm_connection(
-    config: Option<LlmConfig>,
+async fn set_sources_active(
+    source_ids: Vec<i64>,
+    is_active: bool,
     state: tauri::State<'_, SharedState>,
-) -> Result<String, String> {
-    let resolved = resolve_llm_config(config, &state.source_repository).await?;
-    let response = call_chat_completion(
-        &resolved,
-        "You are a connectivity checker.",
-        "Reply with exactly: ok",
-    )
-    .await
-    .map_err(|error| error.to_string())?;
-    Ok(response)
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .set_sources_active(&source_ids, is_active)
+        .await
+        .map_err(|error| error.to_string())
 }
 
 #[tauri::command]
-async fn summarize_entry(
-    entry_id: i64,
+async fn reactivate_source(
+    source_id: i64,
     state: tauri::State<'_, SharedState>,
-) -> Result<String, String> {
-    let config = resolve_llm_config(None, &state.source_repository).await?;
-    let entry = state
+) -> Result<u64, String> {
+    state
         .source_repository
-        .get_entry_by_id(entry_id)
-        .await
-        .map_err(|error| error.to_string())?
-        .ok_or_else(|| format!("entry {entry_id} not found"))?;
-    let article_text = fetch_webpage_text_for_summary(&entry.link, config.timeout_secs)
+        .reactivate_source(source_id)
         .await
-        .unwrap_or_else(|_| fallback_entry_text(&entry));
-    let input = build_summary_input(&entry, &article_text);
-    let hash = hash_llm_input("summary", &config.model, &input);
-    if let Some(cached) = state
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn reset_source_failures(
+    source_ids: Vec<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
         .source_repository
-        .get_llm_cache("summary", &config.model, &hash)
+        .reset_source_failures(&source_ids)
         .await
-        .map_err(|error| error.to_string())?
-    {
-        return Ok(cached);
-    }
+        .map_err(|error| error.to_string())
+}
 
-    let output = call_chat_completion(
-        &config,
-        "You summarize technical articles in concise Chinese.",
-        &format!("请总结下面这篇文章，输出 5 条以内要点：\n\n{input}"),
-    )
-    .await
-    .map_err(|error| error.to_string())?;
+#[tauri::command]
+async fn set_source_color(
+    source_id: i64,
+    color: Option<String>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    let color = normalize_source_color(color.as_deref())?;
     state
         .source_repository
-        .set_llm_cache("summary", &config.model, &hash, &output)
+        .set_source_color(source_id, color.as_deref())
         .await
-        .map_err(|error| error.to_string())?;
-    Ok(output)
+        .map_err(|error| error.to_string())
 }
 
-fn parse_import_sources(request: &ImportRequest) -> Result<Vec<ImportSource>, String> {
-    match request.format.to_lowercase().as_str() {
-        "opml" | "xml" => parse_opml(&request.content).map_err(|error| error.to_string()),
-        "url_list" | "urls" | "txt" => Ok(parse_url_list(&request.content)),
+#[tauri::command]
+async fn set_source_custom_icon(
+    source_id: i64,
+    custom_icon: Option<String>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    let custom_icon = custom_icon
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if custom_icon
+        .as_ref()
+        .is_some_and(|value| value.len() > MAX_CUSTOM_ICON_BYTES)
+    {
+        return Err(format!("custom icon exceeds {MAX_CUSTOM_ICON_BYTES} bytes"));
+    }
+    state
+        .source_repository
+        .set_source_custom_icon(source_id, custom_icon.as_deref())
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn recommend_sources(
+    limit: Option<usize>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<SourceRecommendation>, String> {
+    let subscriptions = state
+        .source_repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?;
+    let index = curated_index().map_err(|error| error.to_string())?;
+    Ok(core::subscription::recommend_sources(
+        &subscriptions,
+        &index,
+        limit.unwrap_or(DEFAULT_RECOMMENDATION_LIMIT).clamp(1, 100),
+    ))
+}
+
+#[tauri::command]
+async fn run_source_discovery(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<DiscoverySuggestion>, String> {
+    ensure_online(&state.network)?;
+    run_source_discovery_internal(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn get_discovery_suggestions(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<DiscoverySuggestion>, String> {
+    let Some(raw) = state
+        .source_repository
+        .get_setting(DISCOVERY_SUGGESTIONS_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    else {
+        return Ok(Vec::new());
+    };
+    serde_json::from_str(&raw).map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn preview_import(
+    request: ImportRequest,
+    state: tauri::State<'_, SharedState>,
+) -> Result<ImportPreviewResponse, String> {
+    let candidates = apply_category_mapping(
+        load_import_sources(&state, &request).await?,
+        request.category_mapping.as_ref(),
+    );
+    preview_import_candidates(&state.source_repository, candidates).await
+}
+
+#[tauri::command]
+async fn import_sources(
+    request: ImportRequest,
+    state: tauri::State<'_, SharedState>,
+) -> Result<ImportExecuteResponse, String> {
+    let candidates = select_candidates(
+        apply_category_mapping(
+            load_import_sources(&state, &request).await?,
+            request.category_mapping.as_ref(),
+        ),
+        request.selected_feed_urls.as_deref(),
+        request.excluded_feed_urls.as_deref(),
+    );
+    import_candidates(
+        &state.source_repository,
+        candidates,
+        ImportOptions {
+            default_category: request.default_category,
+            is_active: request.is_active.unwrap_or(true),
+            include_possible_duplicates: request.selected_feed_urls.is_some(),
+            mark_existing_read: request.mark_existing_read,
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+async fn export_opml(
+    path: String,
+    request: Option<OpmlExportRequest>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<usize, String> {
+    let target = PathBuf::from(path.trim());
+    if target.as_os_str().is_empty() {
+        return Err("path cannot be empty".to_string());
+    }
+    let rows = state
+        .source_repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?;
+    let sources = select_export_sources(rows, &request.unwrap_or_default());
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    std::fs::write(
+        &target,
+        core::importer::export_opml("rssr subscriptions", &sources),
+    )
+    .map_err(|error| error.to_string())?;
+    Ok(sources.len())
+}
+
+#[tauri::command]
+async fn get_snapshot_target(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Option<RemoteTarget>, String> {
+    Ok(load_snapshot_target(&state.source_repository)
+        .await?
+        .map(redact_snapshot_target))
+}
+
+#[tauri::command]
+async fn save_snapshot_target(
+    target: RemoteTarget,
+    state: tauri::State<'_, SharedState>,
+) -> Result<RemoteTarget, String> {
+    let stored = load_snapshot_target(&state.source_repository).await?;
+    let target = normalize_snapshot_target(keep_snapshot_password(target, stored.as_ref()))?;
+    let serialized = serde_json::to_string(&target).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(SNAPSHOT_TARGET_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(redact_snapshot_target(target))
+}
+
+#[tauri::command]
+async fn push_snapshot(
+    passphrase: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SnapshotTransferResponse, String> {
+    ensure_online(&state.network)?;
+    let repository = &state.source_repository;
+    let target = load_snapshot_target(repository)
+        .await?
+        .ok_or_else(|| "snapshot target is not configured".to_string())?;
+    let rows = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?;
+    let sources = select_export_sources(
+        rows,
+        &OpmlExportRequest {
+            include_rssr_attributes: true,
+            ..OpmlExportRequest::default()
+        },
+    );
+    let snapshot = ProfileSnapshot {
+        version: SNAPSHOT_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        opml: core::importer::export_opml("rssr snapshot", &sources),
+        read_state: repository
+            .list_read_state()
+            .await
+            .map_err(|error| error.to_string())?,
+    };
+    let sealed = seal_snapshot(&snapshot, &passphrase).map_err(|error| error.to_string())?;
+    let bytes = sealed.len();
+    upload_snapshot(&snapshot_client()?, &target, sealed)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(SnapshotTransferResponse {
+        created_at: snapshot.created_at,
+        source_count: sources.len(),
+        read_state_count: snapshot.read_state.len(),
+        bytes,
+        imported_sources: 0,
+        updated_entries: 0,
+    })
+}
+
+#[tauri::command]
+async fn pull_snapshot(
+    passphrase: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SnapshotTransferResponse, String> {
+    ensure_online(&state.network)?;
+    let repository = &state.source_repository;
+    let target = load_snapshot_target(repository)
+        .await?
+        .ok_or_else(|| "snapshot target is not configured".to_string())?;
+    let sealed = download_snapshot(&snapshot_client()?, &target)
+        .await
+        .map_err(|error| error.to_string())?;
+    let bytes = sealed.len();
+    let snapshot = open_snapshot(&sealed, &passphrase).map_err(|error| error.to_string())?;
+    let candidates = parse_import_sources_blocking(&ImportRequest {
+        format: "opml".to_string(),
+        content: snapshot.opml,
+        default_category: None,
+        is_active: Some(true),
+        selected_feed_urls: None,
+        excluded_feed_urls: None,
+        category_mapping: None,
+        mark_existing_read: false,
+        all_feeds_per_site: false,
+    })
+    .await?;
+    let source_count = candidates.len();
+    let imported = import_candidates(repository, candidates, ImportOptions::default()).await?;
+    let updated_entries = repository
+        .apply_read_state(&snapshot.read_state)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(SnapshotTransferResponse {
+        created_at: snapshot.created_at,
+        source_count,
+        read_state_count: snapshot.read_state.len(),
+        bytes,
+        imported_sources: imported.imported_count,
+        updated_entries,
+    })
+}
+
+#[tauri::command]
+async fn undo_last_read_operation(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Option<ReadOperation>, String> {
+    state
+        .source_repository
+        .undo_last_read_operation()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn list_recently_read(
+    limit: Option<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<ReadHistoryDto>, String> {
+    let rows = state
+        .source_repository
+        .list_recently_read(limit.unwrap_or(DEFAULT_RECENTLY_READ_LIMIT).clamp(1, 500))
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(rows
+        .into_iter()
+        .map(|row| ReadHistoryDto {
+            entry: entry_to_dto(row.entry),
+            read_at: row.read_at,
+        })
+        .collect())
+}
+
+#[tauri::command]
+async fn mark_entries_read_up_to(
+    filter: Option<EntryFilterRequest>,
+    last_seen_entry_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    let filter = filter.unwrap_or_default();
+    state
+        .source_repository
+        .mark_entries_read_up_to(
+            filter.source_id,
+            filter.search.as_deref(),
+            last_seen_entry_id,
+        )
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn report_entries_scrolled_past(
+    filter: Option<EntryFilterRequest>,
+    last_seen_entry_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    let policy = load_read_policy(&state.source_repository).await?;
+    if policy.mode != ReadMarkMode::OnScroll {
+        return Ok(0);
+    }
+    let filter = filter.unwrap_or_default();
+    state
+        .source_repository
+        .mark_entries_read_up_to(
+            filter.source_id,
+            filter.search.as_deref(),
+            last_seen_entry_id,
+        )
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn mark_all_read(
+    source_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .mark_scope_read(ReadScope::Source(source_id))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn mark_category_read(
+    category: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .mark_scope_read(ReadScope::Category(&category))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn mark_read_older_than(
+    days: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    if days < 0 {
+        return Err("days must not be negative".to_string());
+    }
+    state
+        .source_repository
+        .mark_scope_read(ReadScope::OlderThanDays(days))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn get_flagged_entry_protection(
+    state: tauri::State<'_, SharedState>,
+) -> Result<bool, String> {
+    state
+        .source_repository
+        .flagged_entry_protection()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn set_flagged_entry_protection(
+    enabled: bool,
+    state: tauri::State<'_, SharedState>,
+) -> Result<bool, String> {
+    state
+        .source_repository
+        .set_flagged_entry_protection(enabled)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(enabled)
+}
+
+#[tauri::command]
+async fn set_entry_starred(
+    entry_id: i64,
+    is_starred: bool,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    set_entry_starred_with_hooks(
+        &state.source_repository,
+        &state.network,
+        entry_id,
+        is_starred,
+    )
+    .await
+}
+
+#[tauri::command]
+async fn fetch_full_content(
+    entry_id: i64,
+    refresh: Option<bool>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<EntryFulltext, String> {
+    let repository = &state.source_repository;
+    if !refresh.unwrap_or(false) {
+        if let Some(cached) = repository
+            .get_entry_fulltext(entry_id)
+            .await
+            .map_err(|error| error.to_string())?
+        {
+            return Ok(cached);
+        }
+    }
+    ensure_online(&state.network)?;
+    let entry = repository
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("entry {entry_id} not found"))?;
+    let settings = load_sync_settings(repository).await?;
+    let client = sync_http(&settings)
+        .client(settings.timeout_secs)
+        .map_err(|error| error.to_string())?;
+    let article = fetch_article(&client, &entry.link)
+        .await
+        .map_err(|error| error.to_string())?;
+    repository
+        .set_entry_fulltext(entry_id, &article)
+        .await
+        .map_err(|error| error.to_string())?;
+    repository
+        .get_entry_fulltext(entry_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("entry {entry_id} full content was not cached"))
+}
+
+#[tauri::command]
+async fn open_entry_external(
+    entry_id: i64,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<OpenedEntry, String> {
+    let repository = &state.source_repository;
+    let entry = repository
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("entry {entry_id} not found"))?;
+    let link = entry.link.trim().to_string();
+    if link.is_empty() {
+        return Err(format!("entry {entry_id} has no link"));
+    }
+    let open_externally = repository
+        .get_source_settings(entry.source_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .open_externally
+        .unwrap_or(true);
+    if open_externally {
+        app.opener()
+            .open_url(link.clone(), None::<&str>)
+            .map_err(|error| error.to_string())?;
+    }
+    let policy = load_read_policy(repository).await?;
+    repository
+        .record_entry_open(
+            entry_id,
+            open_externally,
+            policy.mode == ReadMarkMode::OnOpen,
+        )
+        .await
+        .map_err(|error| error.to_string())?;
+    let open_count = repository
+        .count_entry_opens(entry_id)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(OpenedEntry {
+        entry_id,
+        link,
+        opened_externally: open_externally,
+        open_count,
+    })
+}
+
+#[tauri::command]
+async fn get_counts(state: tauri::State<'_, SharedState>) -> Result<EntryCounts, String> {
+    state
+        .source_repository
+        .get_entry_counts()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn list_starred_entries(
+    limit: Option<i64>,
+    offset: Option<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<StarredEntriesPage, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_STARRED_PAGE_SIZE)
+        .clamp(1, MAX_STARRED_PAGE_SIZE);
+    let offset = offset.unwrap_or(0).max(0);
+    let mut rows = state
+        .source_repository
+        .list_starred_entries(limit + 1, offset)
+        .await
+        .map_err(|error| error.to_string())?;
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    Ok(StarredEntriesPage {
+        next_offset: has_more.then_some(offset + limit),
+        entries: rows.into_iter().map(entry_to_dto).collect(),
+    })
+}
+
+async fn set_entry_starred_with_hooks(
+    repository: &SourceRepository,
+    network: &NetworkRuntime,
+    entry_id: i64,
+    is_starred: bool,
+) -> Result<u64, String> {
+    let affected = repository
+        .set_entry_starred(entry_id, is_starred)
+        .await
+        .map_err(|error| error.to_string())?;
+    if is_starred && affected > 0 {
+        if let Ok(Some(entry)) = repository.get_entry_by_id(entry_id).await {
+            fire_hooks(
+                repository,
+                network,
+                HookEvent::EntryStarred,
+                entry_hook_data(&entry),
+            )
+            .await;
+        }
+    }
+    Ok(affected)
+}
+
+#[tauri::command]
+async fn get_source_settings(
+    source_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SourceSettings, String> {
+    state
+        .source_repository
+        .get_source_settings(source_id)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn list_source_settings(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<SourceSettings>, String> {
+    state
+        .source_repository
+        .list_source_settings()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn set_source_settings(
+    settings: SourceSettings,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SourceSettings, String> {
+    let repository = &state.source_repository;
+    repository
+        .get_source_by_id(settings.source_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("source {} not found", settings.source_id))?;
+    let limits = load_advanced_settings(repository).await?;
+    let settings = normalize_source_settings(settings, &limits);
+    repository
+        .set_source_settings(&settings)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(settings)
+}
+
+#[tauri::command]
+async fn get_search_pinyin(state: tauri::State<'_, SharedState>) -> Result<bool, String> {
+    state
+        .source_repository
+        .search_pinyin()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn set_search_pinyin(
+    enabled: bool,
+    state: tauri::State<'_, SharedState>,
+) -> Result<bool, String> {
+    state
+        .source_repository
+        .set_search_pinyin(enabled)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(enabled)
+}
+
+#[tauri::command]
+async fn get_first_sync_max_age_days(state: tauri::State<'_, SharedState>) -> Result<i64, String> {
+    state
+        .source_repository
+        .first_sync_max_age_days()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn set_first_sync_max_age_days(
+    days: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<i64, String> {
+    let days = days.clamp(0, MAX_FIRST_SYNC_MAX_AGE_DAYS);
+    state
+        .source_repository
+        .set_first_sync_max_age_days(days)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(days)
+}
+
+#[tauri::command]
+async fn get_source_notifications(
+    source_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<NotificationPrefs, String> {
+    state
+        .source_repository
+        .get_source_notification_prefs(source_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("source {source_id} not found"))
+}
+
+#[tauri::command]
+async fn set_source_notifications(
+    source_id: i64,
+    prefs: NotificationPrefs,
+    state: tauri::State<'_, SharedState>,
+) -> Result<NotificationPrefs, String> {
+    let prefs = prefs.normalized();
+    if prefs.mode == NotifyMode::Keywords && prefs.keywords.is_empty() {
+        return Err("keyword notifications need at least one keyword".to_string());
+    }
+    let affected = state
+        .source_repository
+        .set_source_notification_prefs(source_id, &prefs)
+        .await
+        .map_err(|error| error.to_string())?;
+    if affected == 0 {
+        return Err(format!("source {source_id} not found"));
+    }
+    Ok(prefs)
+}
+
+#[tauri::command]
+async fn list_notification_rules(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<NotificationRule>, String> {
+    state
+        .source_repository
+        .list_notification_rules()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn create_notification_rule(
+    rule: NotificationRule,
+    state: tauri::State<'_, SharedState>,
+) -> Result<NotificationRule, String> {
+    let mut rule = validate_notification_rule(rule)?;
+    rule.id = state
+        .source_repository
+        .create_notification_rule(&rule)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(rule)
+}
+
+#[tauri::command]
+async fn update_notification_rule(
+    rule: NotificationRule,
+    state: tauri::State<'_, SharedState>,
+) -> Result<NotificationRule, String> {
+    let rule = validate_notification_rule(rule)?;
+    let affected = state
+        .source_repository
+        .update_notification_rule(&rule)
+        .await
+        .map_err(|error| error.to_string())?;
+    if affected == 0 {
+        return Err(format!("notification rule {} not found", rule.id));
+    }
+    Ok(rule)
+}
+
+#[tauri::command]
+async fn delete_notification_rule(
+    rule_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .delete_notification_rule(rule_id)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+fn validate_notification_rule(rule: NotificationRule) -> Result<NotificationRule, String> {
+    let rule = rule.normalized();
+    if rule.name.is_empty() {
+        return Err("notification rule name cannot be empty".to_string());
+    }
+    if !rule.has_conditions() {
+        return Err("notification rule needs a keyword, source or category".to_string());
+    }
+    Ok(rule)
+}
+
+#[tauri::command]
+async fn take_pending_notifications(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<PendingNotification>, String> {
+    state
+        .source_repository
+        .take_pending_notifications(NOTIFICATION_BATCH_LIMIT)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn render_entry(
+    entry_id: i64,
+    format: RenderFormat,
+    path: String,
+    include_summary: Option<bool>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<String, String> {
+    let target = PathBuf::from(path.trim());
+    if target.as_os_str().is_empty() {
+        return Err("path cannot be empty".to_string());
+    }
+    let browser = find_browser().ok_or_else(|| RenderError::BrowserNotFound.to_string())?;
+    let repository = &state.source_repository;
+    let entry = repository
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("entry {entry_id} not found"))?;
+    let summary = if include_summary.unwrap_or(false) {
+        ensure_online(&state.network)?;
+        Some(summarize_entry_internal(repository, entry_id).await?)
+    } else {
+        None
+    };
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    let document = build_entry_document(&entry, summary.as_deref());
+    render_document(&browser, &document, format, &target)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn export_starred_as_feed(
+    path: String,
+    title: Option<String>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<usize, String> {
+    let target = PathBuf::from(path.trim());
+    if target.as_os_str().is_empty() {
+        return Err("path cannot be empty".to_string());
+    }
+    let entries = state
+        .source_repository
+        .list_starred_entries(STARRED_FEED_MAX_ENTRIES, 0)
+        .await
+        .map_err(|error| error.to_string())?;
+    let channel = FeedChannel {
+        title: title
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "rssr starred".to_string()),
+        link: None,
+        description: "Starred items from rssr".to_string(),
+    };
+    if let Some(parent) = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    std::fs::write(&target, build_rss_feed(&channel, &entries))
+        .map_err(|error| error.to_string())?;
+    Ok(entries.len())
+}
+
+#[tauri::command]
+fn list_actions() -> Vec<ActionDescriptor> {
+    core::actions::list_actions().to_vec()
+}
+
+#[tauri::command]
+fn list_starter_packs() -> Result<Vec<StarterPack>, String> {
+    core::subscription::list_starter_packs().map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn preview_starter_pack(
+    id: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<ImportPreviewResponse, String> {
+    let candidates = starter_pack_sources(&id).map_err(|error| error.to_string())?;
+    preview_import_candidates(&state.source_repository, candidates).await
+}
+
+#[tauri::command]
+async fn import_starter_pack(
+    id: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<ImportExecuteResponse, String> {
+    let candidates = starter_pack_sources(&id).map_err(|error| error.to_string())?;
+    import_candidates(
+        &state.source_repository,
+        candidates,
+        ImportOptions::default(),
+    )
+    .await
+}
+
+#[tauri::command]
+async fn get_onboarding_state(
+    state: tauri::State<'_, SharedState>,
+) -> Result<OnboardingState, String> {
+    load_onboarding_state(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn onboarding_import(
+    request: OnboardingImportRequest,
+    state: tauri::State<'_, SharedState>,
+) -> Result<ImportExecuteResponse, String> {
+    let mut candidates = Vec::new();
+    for id in &request.starter_pack_ids {
+        candidates.extend(starter_pack_sources(id).map_err(|error| error.to_string())?);
+    }
+    if let Some(content) = request.opml.filter(|value| !value.trim().is_empty()) {
+        let opml_request = ImportRequest {
+            format: "opml".to_string(),
+            content,
+            default_category: None,
+            is_active: Some(true),
+            selected_feed_urls: None,
+            excluded_feed_urls: None,
+            category_mapping: None,
+            mark_existing_read: false,
+            all_feeds_per_site: false,
+        };
+        candidates.extend(parse_import_sources_blocking(&opml_request).await?);
+    }
+    if candidates.is_empty() {
+        return Err("choose a starter pack or provide an OPML file".to_string());
+    }
+    import_candidates(
+        &state.source_repository,
+        candidates,
+        ImportOptions {
+            mark_existing_read: request.mark_existing_read,
+            ..ImportOptions::default()
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+async fn run_onboarding_sync(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SyncBatchResponse, String> {
+    ensure_online(&state.network)?;
+    let Some(cancel) = state.sync_runtime.try_begin() else {
+        return Err("a sync is already running".to_string());
+    };
+    let result = sync_onboarding_sources(
+        &state.source_repository,
+        &state.network,
+        &cancel,
+        |progress| {
+            let _ = app.emit(ONBOARDING_SYNC_PROGRESS_EVENT, progress);
+        },
+    )
+    .await;
+    record_sync_result(&state.sync_runtime, &result).await;
+    state.sync_runtime.running.store(false, Ordering::SeqCst);
+    result
+}
+
+async fn sync_onboarding_sources(
+    repository: &SourceRepository,
+    network: &Arc<NetworkRuntime>,
+    cancel: &CancellationToken,
+    on_progress: impl Fn(OnboardingSyncProgress),
+) -> Result<SyncBatchResponse, String> {
+    let settings = load_sync_settings(repository).await?;
+    let mut sources = prioritize_first_sync(
+        repository
+            .list_sources()
+            .await
+            .map_err(|error| error.to_string())?
+            .into_iter()
+            .filter(|source| source.is_active == 1)
+            .collect(),
+    );
+    sources.truncate(settings.batch_limit as usize);
+    let total = sources.len();
+    let report = run_sync_batch(
+        repository,
+        network,
+        &settings,
+        sources,
+        cancel,
+        |report, outcome| {
+            on_progress(OnboardingSyncProgress {
+                completed: report.synced_sources + report.failed_sources,
+                total,
+                source_id: outcome.source_id,
+                title: outcome.title.clone(),
+                status: match &outcome.error {
+                    Some(error) => format!("failed: {error}"),
+                    None => outcome.status.clone(),
+                },
+                upserted_entries: outcome.upserted_entries,
+            });
+        },
+    )
+    .await?;
+    if report.cancelled {
+        return Ok(report);
+    }
+
+    let mut record = load_onboarding_record(repository).await?;
+    record.first_sync_at = Some(chrono::Utc::now().to_rfc3339());
+    save_onboarding_record(repository, &record).await?;
+    Ok(report)
+}
+
+#[tauri::command]
+async fn complete_onboarding(
+    state: tauri::State<'_, SharedState>,
+) -> Result<OnboardingState, String> {
+    let mut record = load_onboarding_record(&state.source_repository).await?;
+    record.completed_at = Some(chrono::Utc::now().to_rfc3339());
+    save_onboarding_record(&state.source_repository, &record).await?;
+    load_onboarding_state(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn list_entries(
+    request: ListEntriesRequest,
+    state: tauri::State<'_, SharedState>,
+) -> Result<EntriesPage, String> {
+    let cursor = decode_entry_cursor(request.cursor.as_deref())?;
+    let limit = request.limit.unwrap_or(300).max(1);
+    enforce_read_policy(&state.source_repository).await?;
+    let timeout = Duration::from_millis(UI_QUERY_TIMEOUT_MS);
+    let query_key = request
+        .query_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|key| !key.is_empty());
+    let control = match query_key {
+        Some(key) => state.queries.begin(key, timeout),
+        None => QueryControl::new(timeout),
+    };
+    let rows = state
+        .source_repository
+        .search_entries(
+            request.source_id,
+            request.search.as_deref(),
+            request.unread_only,
+            cursor.as_ref(),
+            limit + 1,
+            &control,
+        )
+        .await;
+    if let Some(key) = query_key {
+        state.queries.finish(key, &control);
+    }
+    let rows = rows.map_err(|error| error.to_string())?;
+    Ok(entries_page(rows, limit))
+}
+
+#[tauri::command]
+async fn list_view_entries(
+    view: String,
+    cursor: Option<String>,
+    limit: Option<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<EntriesPage, String> {
+    let view = parse_entry_view(&view)?;
+    let cursor = decode_entry_cursor(cursor.as_deref())?;
+    let limit = limit.unwrap_or(300).max(1);
+    enforce_read_policy(&state.source_repository).await?;
+    let rows = state
+        .source_repository
+        .list_view_entries(view, cursor.as_ref(), limit + 1)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(entries_page(rows, limit))
+}
+
+fn decode_entry_cursor(token: Option<&str>) -> Result<Option<EntryCursor>, String> {
+    match token.filter(|token| !token.is_empty()) {
+        Some(token) => EntryCursor::decode(token)
+            .map(Some)
+            .ok_or_else(|| "invalid entry cursor".to_string()),
+        None => Ok(None),
+    }
+}
+
+fn entries_page(mut rows: Vec<EntryRecord>, limit: i64) -> EntriesPage {
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+    EntriesPage {
+        next_cursor: has_more
+            .then(|| rows.last().map(|row| EntryCursor::after(row).encode()))
+            .flatten(),
+        entries: rows.into_iter().map(entry_to_dto).collect(),
+    }
+}
+
+fn parse_entry_view(view: &str) -> Result<EntryView, String> {
+    match view.trim() {
+        "" | "all" => Ok(EntryView::All),
+        "today" => Ok(EntryView::Today),
+        "unread" => Ok(EntryView::Unread),
+        "starred" => Ok(EntryView::Starred),
+        unsupported => Err(format!("unsupported entry view: {unsupported}")),
+    }
+}
+
+#[tauri::command]
+async fn get_entry(
+    entry_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<EntryDto, String> {
+    state
+        .source_repository
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .map(entry_to_dto)
+        .ok_or_else(|| format!("entry {entry_id} not found"))
+}
+
+#[tauri::command]
+async fn start_triage_session(
+    filter: Option<TriageFilterRequest>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<TriageStepResponse, String> {
+    let filter = filter.unwrap_or_default();
+    let control = QueryControl::new(Duration::from_millis(UI_QUERY_TIMEOUT_MS));
+    let entry_ids = state
+        .source_repository
+        .search_entries(
+            filter.source_id,
+            filter.search.as_deref(),
+            filter.unread_only,
+            None,
+            filter
+                .limit
+                .unwrap_or(DEFAULT_TRIAGE_LIMIT)
+                .clamp(1, MAX_TRIAGE_LIMIT),
+            &control,
+        )
+        .await
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .map(|entry| entry.id)
+        .collect();
+    let cursor = state.triage.start(entry_ids);
+    triage_step_response(&state, cursor, 1).await
+}
+
+#[tauri::command]
+async fn triage_next(
+    session_id: String,
+    offset: Option<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<TriageStepResponse, String> {
+    let offset = offset.unwrap_or(1);
+    let cursor = state
+        .triage
+        .step(&session_id, offset)
+        .ok_or_else(|| format!("triage session {session_id} not found"))?;
+    let direction = if offset < 0 { -1 } else { 1 };
+    triage_step_response(&state, cursor, direction).await
+}
+
+#[tauri::command]
+async fn triage_action(
+    session_id: String,
+    action: TriageAction,
+    state: tauri::State<'_, SharedState>,
+) -> Result<TriageStepResponse, String> {
+    let current = state
+        .triage
+        .current(&session_id)
+        .ok_or_else(|| format!("triage session {session_id} not found"))?;
+    if let Some(entry_id) = current.entry_id {
+        let repository = &state.source_repository;
+        match action {
+            TriageAction::Read | TriageAction::Unread => {
+                repository
+                    .mark_entry_read(entry_id, action == TriageAction::Read)
+                    .await
+                    .map_err(|error| error.to_string())?;
+            }
+            TriageAction::Star | TriageAction::Unstar => {
+                set_entry_starred_with_hooks(
+                    repository,
+                    &state.network,
+                    entry_id,
+                    action == TriageAction::Star,
+                )
+                .await?;
+            }
+            TriageAction::Skip => {}
+        }
+    }
+    let cursor = state
+        .triage
+        .step(&session_id, 1)
+        .ok_or_else(|| format!("triage session {session_id} not found"))?;
+    triage_step_response(&state, cursor, 1).await
+}
+
+#[tauri::command]
+fn end_triage_session(session_id: String, state: tauri::State<'_, SharedState>) -> bool {
+    state.triage.end(&session_id)
+}
+
+async fn triage_step_response(
+    state: &SharedState,
+    mut cursor: TriageCursor,
+    direction: i64,
+) -> Result<TriageStepResponse, String> {
+    let mut entry = None;
+    while let Some(entry_id) = cursor.entry_id {
+        if let Some(record) = state
+            .source_repository
+            .get_entry_by_id(entry_id)
+            .await
+            .map_err(|error| error.to_string())?
+        {
+            entry = Some(entry_to_dto(record));
+            break;
+        }
+        match state.triage.step(&cursor.session_id, direction) {
+            Some(next) if next.position != cursor.position => cursor = next,
+            _ => break,
+        }
+    }
+    Ok(TriageStepResponse {
+        session_id: cursor.session_id,
+        position: cursor.position,
+        total: cursor.total,
+        entry,
+    })
+}
+
+#[tauri::command]
+fn cancel_query(query_key: String, state: tauri::State<'_, SharedState>) -> bool {
+    state.queries.cancel(query_key.trim())
+}
+
+#[tauri::command]
+async fn get_entry_date_histogram(
+    filter: Option<EntryHistogramRequest>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<EntryDateBucket>, String> {
+    let filter = filter.unwrap_or_default();
+    state
+        .source_repository
+        .entry_date_histogram(
+            filter.source_id,
+            filter.search.as_deref(),
+            filter.unread_only,
+            filter.days.unwrap_or(DEFAULT_HISTOGRAM_DAYS).clamp(1, 3660),
+            filter.utc_offset_minutes.clamp(-14 * 60, 14 * 60),
+        )
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn get_trending_terms(
+    days: Option<i64>,
+    limit: Option<usize>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<TrendingTerm>, String> {
+    let days = days.unwrap_or(DEFAULT_TRENDING_DAYS).clamp(1, 90);
+    let samples = state
+        .source_repository
+        .list_title_samples(days, days * TRENDING_BASELINE_MULTIPLIER)
+        .await
+        .map_err(|error| error.to_string())?;
+    let (recent, baseline): (Vec<_>, Vec<_>) =
+        samples.into_iter().partition(|sample| sample.is_recent);
+    let recent = recent
+        .into_iter()
+        .map(|sample| sample.title)
+        .collect::<Vec<_>>();
+    let baseline = baseline
+        .into_iter()
+        .map(|sample| sample.title)
+        .collect::<Vec<_>>();
+    let limit = limit.unwrap_or(DEFAULT_TRENDING_LIMIT).clamp(1, 100);
+    tokio::task::spawn_blocking(move || compute_trending_terms(&recent, &baseline, limit))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn get_catchup_selection(
+    minutes_available: u32,
+    state: tauri::State<'_, SharedState>,
+) -> Result<CatchupSelection, String> {
+    let minutes_available = minutes_available.clamp(1, MAX_CATCHUP_MINUTES);
+    let candidates = state
+        .source_repository
+        .list_catchup_candidates(CATCHUP_BATCH_SIZE)
+        .await
+        .map_err(|error| error.to_string())?;
+    tokio::task::spawn_blocking(move || {
+        select_catchup(candidates, minutes_available, chrono::Utc::now())
+    })
+    .await
+    .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn skip_entries(
+    entry_ids: Vec<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .mark_entries_skipped(&entry_ids)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn reset_skipped_entries(state: tauri::State<'_, SharedState>) -> Result<u64, String> {
+    state
+        .source_repository
+        .clear_skipped_entries()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn mark_entry_read(
+    entry_id: i64,
+    is_read: bool,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    enforce_read_policy(&state.source_repository).await?;
+    state
+        .source_repository
+        .mark_entry_read(entry_id, is_read)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn archive_old_entries(
+    older_than_days: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .archive_entries_older_than(older_than_days)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn get_storage_breakdown(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<SourceStorageUsage>, String> {
+    state
+        .source_repository
+        .storage_breakdown()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn set_source_retention(
+    source_id: i64,
+    retention_days: Option<i64>,
+    max_entries: Option<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    let retention_days = retention_days
+        .filter(|days| *days > 0)
+        .map(|days| days.min(MAX_SOURCE_RETENTION_DAYS));
+    let max_entries = max_entries.filter(|count| *count > 0);
+    let affected = state
+        .source_repository
+        .set_source_retention(source_id, retention_days, max_entries)
+        .await
+        .map_err(|error| error.to_string())?;
+    if affected == 0 {
+        return Err(format!("source {source_id} not found"));
+    }
+    state
+        .source_repository
+        .apply_source_retention()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn compact_storage(state: tauri::State<'_, SharedState>) -> Result<usize, String> {
+    let compressed = state
+        .source_repository
+        .compress_legacy_content(COMPACT_STORAGE_BATCH_SIZE)
+        .await
+        .map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .vacuum()
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(compressed)
+}
+
+#[tauri::command]
+async fn get_sanitizer_allowlist(
+    state: tauri::State<'_, SharedState>,
+) -> Result<SanitizerAllowlist, String> {
+    state
+        .source_repository
+        .sanitizer_allowlist()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn save_sanitizer_allowlist(
+    allowlist: SanitizerAllowlist,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SanitizerAllowlist, String> {
+    let normalized = normalize_sanitizer_allowlist(allowlist);
+    state
+        .source_repository
+        .set_sanitizer_allowlist(&normalized)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+async fn sanitize_stored_entries(state: tauri::State<'_, SharedState>) -> Result<usize, String> {
+    state
+        .source_repository
+        .sanitize_stored_entries()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn sync_source(
+    source_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SyncSourceResponse, String> {
+    let source = state
+        .source_repository
+        .get_source_by_id(source_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("source {source_id} not found"))?;
+    if state.network.is_offline() {
+        state
+            .network
+            .enqueue(QueuedRequest::SyncSource { source_id });
+        return Ok(SyncSourceResponse {
+            source_id,
+            status: "queued".to_string(),
+            upserted_entries: 0,
+            inserted_entries: 0,
+            updated_entries: 0,
+            conflicted_entries: 0,
+        });
+    }
+    let settings = load_sync_settings(&state.source_repository).await?;
+    let rules = load_notification_rules(&state.source_repository).await?;
+    sync_single_source(
+        &state.source_repository,
+        source,
+        &settings,
+        &sync_http(&settings),
+        Some(&sync_budget(&settings)),
+        &rules,
+        &state.network,
+    )
+    .await
+    .map_err(|failure| failure.message)
+}
+
+#[tauri::command]
+async fn backfill_source(
+    source_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<BackfillResponse, String> {
+    ensure_online(&state.network)?;
+    let repository = &state.source_repository;
+    let source = repository
+        .get_source_by_id(source_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("source {source_id} not found"))?;
+    let settings = load_sync_settings(repository).await?;
+    let known = repository
+        .list_entry_links(source_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .collect::<HashSet<_>>();
+    let report = backfill_feed(
+        &sync_http(&settings),
+        settings.timeout_secs,
+        &source.feed_url,
+        WAYBACK_BASE_URL,
+        known,
+        BackfillLimits {
+            max_pages: BACKFILL_MAX_PAGES,
+            max_snapshots: BACKFILL_MAX_SNAPSHOTS,
+        },
+    )
+    .await
+    .map_err(|error| error.to_string())?;
+    let upserted_entries = repository
+        .upsert_backfill_entries(source_id, &report.entries)
+        .await
+        .map_err(|error| error.to_string())?
+        .total();
+    Ok(BackfillResponse {
+        source_id,
+        pages_fetched: report.pages_fetched,
+        snapshots_fetched: report.snapshots_fetched,
+        upserted_entries,
+    })
+}
+
+#[tauri::command]
+async fn sync_active_sources(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SyncRuntimeStatus, String> {
+    if state.network.is_offline() {
+        state.network.enqueue(QueuedRequest::SyncActiveSources);
+        return get_sync_runtime_status(state).await;
+    }
+    start_active_sync(
+        app,
+        state.source_repository.clone(),
+        state.sync_runtime.clone(),
+        state.network.clone(),
+    );
+    get_sync_runtime_status(state).await
+}
+
+fn start_active_sync(
+    app: tauri::AppHandle,
+    repository: SourceRepository,
+    runtime: Arc<SyncRuntime>,
+    network: Arc<NetworkRuntime>,
+) {
+    let Some(cancel) = runtime.try_begin() else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        let result = sync_active_sources_internal(&repository, &network, &cancel, false).await;
+        finish_active_sync(&app, &repository, &runtime, &network, result).await;
+    });
+}
+
+async fn finish_active_sync(
+    app: &tauri::AppHandle,
+    repository: &SourceRepository,
+    runtime: &SyncRuntime,
+    network: &NetworkRuntime,
+    result: Result<SyncBatchResponse, String>,
+) {
+    record_sync_result(runtime, &result).await;
+    let Ok(report) = result else {
+        runtime.running.store(false, Ordering::SeqCst);
+        return;
+    };
+    fire_digest_hooks(repository, network, &report).await;
+    emit_pending_notifications(app, repository).await;
+    let _ = apply_read_policy(repository).await;
+    let _ = repository.apply_source_retention().await;
+    runtime.running.store(false, Ordering::SeqCst);
+    let _ = translate_titles_within_budget(repository).await;
+    run_summary_backlog(repository, network).await;
+}
+
+async fn record_sync_result(runtime: &SyncRuntime, result: &Result<SyncBatchResponse, String>) {
+    match result {
+        Ok(report) => {
+            {
+                let mut guard = runtime.last_report.write().await;
+                *guard = Some(report.clone());
+            }
+            {
+                let mut guard = runtime.last_error.write().await;
+                *guard = None;
+            }
+        }
+        Err(error) => {
+            let mut guard = runtime.last_error.write().await;
+            *guard = Some(redact_secrets(error));
+        }
+    }
+}
+
+#[tauri::command]
+async fn cancel_sync(state: tauri::State<'_, SharedState>) -> Result<SyncRuntimeStatus, String> {
+    state.sync_runtime.cancel();
+    get_sync_runtime_status(state).await
+}
+
+#[tauri::command]
+async fn pause_background_sync(
+    state: tauri::State<'_, SharedState>,
+) -> Result<SyncRuntimeStatus, String> {
+    state.sync_runtime.paused.store(true, Ordering::SeqCst);
+    get_sync_runtime_status(state).await
+}
+
+#[tauri::command]
+async fn resume_background_sync(
+    state: tauri::State<'_, SharedState>,
+) -> Result<SyncRuntimeStatus, String> {
+    state.sync_runtime.paused.store(false, Ordering::SeqCst);
+    get_sync_runtime_status(state).await
+}
+
+#[tauri::command]
+async fn get_sync_runtime_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<SyncRuntimeStatus, String> {
+    Ok(snapshot_sync_runtime(&state.sync_runtime).await)
+}
+
+#[tauri::command]
+async fn get_sync_settings(state: tauri::State<'_, SharedState>) -> Result<SyncSettings, String> {
+    load_sync_settings(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn save_sync_settings(
+    settings: SyncSettings,
+    state: tauri::State<'_, SharedState>,
+) -> Result<SyncSettings, String> {
+    let advanced = load_advanced_settings(&state.source_repository).await?;
+    let normalized = normalize_sync_settings(settings, &advanced);
+    let serialized = serde_json::to_string(&normalized).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(SYNC_SETTINGS_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+async fn get_read_policy(
+    state: tauri::State<'_, SharedState>,
+) -> Result<ReadPolicySettings, String> {
+    load_read_policy(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn save_read_policy(
+    settings: ReadPolicySettings,
+    state: tauri::State<'_, SharedState>,
+) -> Result<ReadPolicySettings, String> {
+    let normalized = normalize_read_policy(settings);
+    let serialized = serde_json::to_string(&normalized).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(READ_POLICY_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    apply_read_policy(&state.source_repository).await?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+async fn get_llm_budget(state: tauri::State<'_, SharedState>) -> Result<LlmBudgetSettings, String> {
+    load_llm_budget(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn save_llm_budget(
+    settings: LlmBudgetSettings,
+    state: tauri::State<'_, SharedState>,
+) -> Result<LlmBudgetSettings, String> {
+    let normalized = normalize_llm_budget(settings);
+    let serialized = serde_json::to_string(&normalized).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(LLM_BUDGET_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+async fn get_llm_backlog(state: tauri::State<'_, SharedState>) -> Result<LlmBacklogStatus, String> {
+    let untranslated_titles = state
+        .source_repository
+        .count_entries_without_translated_title()
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(LlmBacklogStatus {
+        untranslated_titles,
+        queued_summaries: state.network.queued_summaries(),
+        budget: load_llm_budget(&state.source_repository).await?,
+    })
+}
+
+#[tauri::command]
+async fn translate_source_titles(
+    source_id: i64,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<TitleBackfillProgress, String> {
+    ensure_online(&state.network)?;
+    let repository = &state.source_repository;
+    repository
+        .get_source_by_id(source_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("source {source_id} not found"))?;
+    let config = resolve_llm_config(None, Some(LlmTask::Translation), repository).await?;
+
+    let total = repository
+        .count_source_entries_without_translated_title(source_id)
+        .await
+        .map_err(|error| error.to_string())?;
+    let mut progress = TitleBackfillProgress {
+        source_id,
+        translated: 0,
+        remaining: total,
+        total,
+        done: false,
+    };
+    let mut before_id = None;
+    loop {
+        let targets = repository
+            .list_source_entries_without_translated_title(
+                source_id,
+                before_id,
+                TITLE_BACKFILL_BATCH_SIZE,
+            )
+            .await
+            .map_err(|error| error.to_string())?;
+        let exhausted = (targets.len() as i64) < TITLE_BACKFILL_BATCH_SIZE;
+        before_id = targets.last().map(|target| target.id).or(before_id);
+        let updated = translate_title_targets(repository, &config, targets).await;
+        progress.translated += updated;
+        progress.remaining = repository
+            .count_source_entries_without_translated_title(source_id)
+            .await
+            .map_err(|error| error.to_string())?;
+        progress.done = exhausted || progress.remaining == 0;
+        let _ = app.emit(TITLE_BACKFILL_PROGRESS_EVENT, progress.clone());
+        if progress.done {
+            return Ok(progress);
+        }
+    }
+}
+
+#[tauri::command]
+async fn get_advanced_settings(
+    state: tauri::State<'_, SharedState>,
+) -> Result<AdvancedSettings, String> {
+    load_advanced_settings(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn save_advanced_settings(
+    settings: AdvancedSettings,
+    state: tauri::State<'_, SharedState>,
+) -> Result<AdvancedSettings, String> {
+    let normalized = normalize_advanced_settings(settings);
+    let serialized = serde_json::to_string(&normalized).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(ADVANCED_SETTINGS_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+async fn get_backup_settings(
+    state: tauri::State<'_, SharedState>,
+) -> Result<BackupSettings, String> {
+    load_backup_settings(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn save_backup_settings(
+    settings: BackupSettings,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<BackupSettings, String> {
+    let normalized = normalize_backup_settings(settings);
+    let serialized = serde_json::to_string(&normalized).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(BACKUP_SETTINGS_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    let data_dir = build_app_data_dir(&app).map_err(|error| error.to_string())?;
+    remember_backup_dir(&data_dir, normalized.directory.as_deref())
+        .map_err(|error| error.to_string())?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+async fn get_automation_hooks(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<AutomationHook>, String> {
+    load_automation_hooks(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn save_automation_hooks(
+    hooks: Vec<AutomationHook>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<AutomationHook>, String> {
+    for hook in &hooks {
+        validate_automation_hook(hook)?;
+    }
+    let serialized = serde_json::to_string(&hooks).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(AUTOMATION_HOOKS_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(hooks)
+}
+
+#[tauri::command]
+async fn test_automation_hook(hook: AutomationHook) -> Result<(), String> {
+    validate_automation_hook(&hook)?;
+    let payload = hook_payload(hook.event, serde_json::json!({ "test": true }));
+    run_hook(&reqwest::Client::new(), &hook, &payload)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn get_local_api_settings(
+    state: tauri::State<'_, SharedState>,
+) -> Result<LocalApiSettings, String> {
+    load_local_api_settings(&state.source_repository).await
+}
+
+#[tauri::command]
+async fn save_local_api_settings(
+    settings: LocalApiSettings,
+    state: tauri::State<'_, SharedState>,
+) -> Result<LocalApiSettings, String> {
+    let normalized = normalize_local_api_settings(settings);
+    let serialized = serde_json::to_string(&normalized).map_err(|error| error.to_string())?;
+    state
+        .source_repository
+        .set_setting(LOCAL_API_SETTINGS_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    restart_local_api(&state.source_repository, &normalized, &state.local_api).await?;
+    Ok(normalized)
+}
+
+#[tauri::command]
+async fn get_local_api_address(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Option<String>, String> {
+    let guard = state.local_api.lock().await;
+    Ok(guard
+        .as_ref()
+        .map(|server| format!("http://{}", server.local_addr())))
+}
+
+#[tauri::command]
+async fn run_backup_now(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+    let settings = load_backup_settings(&state.source_repository).await?;
+    let directory = settings
+        .directory
+        .clone()
+        .ok_or_else(|| "backup directory is not configured".to_string())?;
+    run_backup(&state.source_repository, &settings, &directory).await
+}
+
+#[tauri::command]
+async fn get_llm_config(state: tauri::State<'_, SharedState>) -> Result<Option<LlmConfig>, String> {
+    get_saved_or_env_llm_config(&state.source_repository, None).await
+}
+
+#[tauri::command]
+async fn save_llm_config(
+    config: LlmConfig,
+    state: tauri::State<'_, SharedState>,
+) -> Result<(), String> {
+    validate_config(&config).map_err(|error| error.to_string())?;
+    let repository = &state.source_repository;
+    let fallback = repository
+        .resolve_llm_profile(None)
+        .await
+        .map_err(|error| error.to_string())?;
+    match fallback {
+        Some(profile) => repository
+            .update_llm_profile(&LlmProfile { config, ..profile })
+            .await
+            .map(|_| ()),
+        None => repository
+            .create_llm_profile(&LlmProfile {
+                id: 0,
+                name: DEFAULT_LLM_PROFILE_NAME.to_string(),
+                config,
+                tasks: Vec::new(),
+                active: false,
+            })
+            .await
+            .map(|_| ()),
+    }
+    .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn list_llm_profiles(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<LlmProfile>, String> {
+    state
+        .source_repository
+        .list_llm_profiles()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn save_llm_profile(
+    profile: LlmProfile,
+    state: tauri::State<'_, SharedState>,
+) -> Result<LlmProfile, String> {
+    let mut profile = LlmProfile {
+        name: profile.name.trim().to_string(),
+        tasks: Vec::new(),
+        ..profile
+    };
+    if profile.name.is_empty() {
+        return Err("llm profile name cannot be empty".to_string());
+    }
+    validate_config(&profile.config).map_err(|error| error.to_string())?;
+    let repository = &state.source_repository;
+    if profile.id == 0 {
+        profile.id = repository
+            .create_llm_profile(&profile)
+            .await
+            .map_err(|error| error.to_string())?;
+        return Ok(profile);
+    }
+    let affected = repository
+        .update_llm_profile(&profile)
+        .await
+        .map_err(|error| error.to_string())?;
+    if affected == 0 {
+        return Err(format!("llm profile {} not found", profile.id));
+    }
+    Ok(profile)
+}
+
+#[tauri::command]
+async fn activate_llm_profile(
+    profile_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<(), String> {
+    let activated = state
+        .source_repository
+        .activate_llm_profile(profile_id)
+        .await
+        .map_err(|error| error.to_string())?;
+    if !activated {
+        return Err(format!("llm profile {profile_id} not found"));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn delete_llm_profile(
+    profile_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .delete_llm_profile(profile_id)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn list_glossary_terms(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<GlossaryTerm>, String> {
+    state
+        .source_repository
+        .list_glossary_terms()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn save_glossary_term(
+    term: GlossaryTerm,
+    state: tauri::State<'_, SharedState>,
+) -> Result<GlossaryTerm, String> {
+    let mut term = term.normalized();
+    if term.term.is_empty() || term.translation.is_empty() {
+        return Err("glossary term and translation cannot be empty".to_string());
+    }
+    term.id = state
+        .source_repository
+        .save_glossary_term(&term)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(term)
+}
+
+#[tauri::command]
+async fn delete_glossary_term(
+    term_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<u64, String> {
+    state
+        .source_repository
+        .delete_glossary_term(term_id)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn set_task_profile(
+    task: LlmTask,
+    profile_id: Option<i64>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<(), String> {
+    let repository = &state.source_repository;
+    if let Some(profile_id) = profile_id {
+        let profiles = repository
+            .list_llm_profiles()
+            .await
+            .map_err(|error| error.to_string())?;
+        if !profiles.iter().any(|profile| profile.id == profile_id) {
+            return Err(format!("llm profile {profile_id} not found"));
+        }
+    }
+    repository
+        .set_task_llm_profile(task, profile_id)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn test_llm_connection(
+    config: Option<LlmConfig>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<String, String> {
+    ensure_online(&state.network)?;
+    let resolved = resolve_llm_config(config, None, &state.source_repository).await?;
+    let response = call_chat_completion(
+        &resolved,
+        &GenerationParams::default(),
+        "You are a connectivity checker.",
+        "Reply with exactly: ok",
+    )
+    .await
+    .map_err(|error| error.to_string())?;
+    Ok(response)
+}
+
+#[tauri::command]
+async fn summarize_entry(
+    entry_id: i64,
+    state: tauri::State<'_, SharedState>,
+) -> Result<String, String> {
+    if state.network.is_offline() {
+        state
+            .network
+            .enqueue(QueuedRequest::SummarizeEntry { entry_id });
+        return Err("offline mode is on; summary request queued".to_string());
+    }
+    summarize_entry_internal(&state.source_repository, entry_id).await
+}
+
+#[tauri::command]
+async fn summarize_entry_stream(
+    entry_id: i64,
+    window: tauri::Window,
+    state: tauri::State<'_, SharedState>,
+) -> Result<String, String> {
+    let result = stream_entry_summary(&state, entry_id, &window).await;
+    if let Err(error) = &result {
+        let _ = window.emit(
+            SUMMARY_CHUNK_EVENT,
+            SummaryChunk {
+                entry_id,
+                delta: String::new(),
+                done: true,
+                error: Some(error.clone()),
+            },
+        );
+    }
+    result
+}
+
+async fn stream_entry_summary(
+    state: &SharedState,
+    entry_id: i64,
+    window: &tauri::Window,
+) -> Result<String, String> {
+    ensure_online(&state.network)?;
+    let repository = &state.source_repository;
+    let prepared = prepare_summary(repository, entry_id).await?;
+    if let Some(cached) = prepared.cached {
+        let _ = window.emit(
+            SUMMARY_CHUNK_EVENT,
+            SummaryChunk {
+                entry_id,
+                delta: cached.clone(),
+                done: true,
+                error: None,
+            },
+        );
+        return Ok(cached);
+    }
+
+    let output = stream_chat_completion(
+        &prepared.config,
+        &prepared.config.generation.summary,
+        SUMMARY_SYSTEM_PROMPT,
+        &summary_user_prompt(&prepared.input),
+        |delta| {
+            let _ = window.emit(
+                SUMMARY_CHUNK_EVENT,
+                SummaryChunk {
+                    entry_id,
+                    delta: delta.to_string(),
+                    done: false,
+                    error: None,
+                },
+            );
+        },
+    )
+    .await
+    .map_err(|error| error.to_string())?;
+    let _ = window.emit(
+        SUMMARY_CHUNK_EVENT,
+        SummaryChunk {
+            entry_id,
+            delta: String::new(),
+            done: true,
+            error: None,
+        },
+    );
+    repository
+        .set_llm_cache("summary", &prepared.config.model, &prepared.hash, &output)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(output)
+}
+
+struct PreparedSummary {
+    config: LlmConfig,
+    input: String,
+    hash: String,
+    cached: Option<String>,
+}
+
+async fn prepare_summary(
+    repository: &SourceRepository,
+    entry_id: i64,
+) -> Result<PreparedSummary, String> {
+    let config = resolve_llm_config(None, Some(LlmTask::Summary), repository).await?;
+    let entry = repository
+        .get_entry_by_id(entry_id)
+        .await
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| format!("entry {entry_id} not found"))?;
+    let article_text = fetch_webpage_text_for_summary(&entry.link, &config)
+        .await
+        .unwrap_or_else(|_| fallback_entry_text(&entry));
+    let input = build_summary_input(&entry, &article_text, config.max_input_chars);
+    let (hash, cached) = lookup_llm_cache(repository, "summary", &config.model, &input).await?;
+    Ok(PreparedSummary {
+        config,
+        input,
+        hash,
+        cached,
+    })
+}
+
+fn summary_user_prompt(input: &str) -> String {
+    format!("请总结下面这篇文章，输出 5 条以内要点：\n\n{input}")
+}
+
+async fn summarize_entry_internal(
+    repository: &SourceRepository,
+    entry_id: i64,
+) -> Result<String, String> {
+    let prepared = prepare_summary(repository, entry_id).await?;
+    if let Some(cached) = prepared.cached {
+        return Ok(cached);
+    }
+
+    let output = call_chat_completion(
+        &prepared.config,
+        &prepared.config.generation.summary,
+        SUMMARY_SYSTEM_PROMPT,
+        &summary_user_prompt(&prepared.input),
+    )
+    .await
+    .map_err(|error| error.to_string())?;
+    repository
+        .set_llm_cache("summary", &prepared.config.model, &prepared.hash, &output)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(output)
+}
+
+#[cfg(feature = "demo-data")]
+#[tauri::command]
+async fn seed_demo_data(state: tauri::State<'_, SharedState>) -> Result<serde_json::Value, String> {
+    let report = core::storage::demo::seed_demo_data(&state.source_repository)
+        .await
+        .map_err(|error| error.to_string())?;
+    serde_json::to_value(report).map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn get_migration_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<MigrationStatus, String> {
+    state
+        .source_repository
+        .migration_status()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn export_diagnostics_bundle(
+    path: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<String, String> {
+    let target = PathBuf::from(path.trim());
+    if target.as_os_str().is_empty() {
+        return Err("path cannot be empty".to_string());
+    }
+    let repository = &state.source_repository;
+    let stats = repository
+        .database_stats()
+        .await
+        .map_err(|error| error.to_string())?;
+    let sources = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?;
+    let sync_settings = load_sync_settings(repository).await?;
+    let llm_profiles = repository
+        .list_llm_profiles()
+        .await
+        .map_err(|error| error.to_string())?;
+    let runtime_status = snapshot_sync_runtime(&state.sync_runtime).await;
+    let mut sync_log = Vec::with_capacity(sources.len());
+    for source in &sources {
+        let attempts = repository
+            .list_sync_log(source.id, DIAGNOSTICS_SYNC_LOG_LIMIT)
+            .await
+            .map_err(|error| error.to_string())?;
+        sync_log.push(serde_json::json!({
+            "source_id": source.id,
+            "title": source.title,
+            "attempts": attempts,
+        }));
+    }
+    let failing_sources = repository
+        .list_failing_sources(DEFAULT_FAILING_SOURCES_LIMIT)
+        .await
+        .map_err(|error| error.to_string())?;
+    let sync_history = serde_json::json!({
+        "last_report": load_last_sync_report(repository).await,
+        "failing_sources": failing_sources,
+        "sources": sync_log,
+    });
+
+    let mut bundle = DiagnosticsBundle::default();
+    let app_info = serde_json::json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "schema_version": stats.schema_version,
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+    });
+    let settings = serde_json::json!({
+        "sync_settings": sync_settings,
+        "llm_profiles": llm_profiles,
+    });
+    let sources: Vec<SourceDto> = sources.into_iter().map(source_to_dto).collect();
+    bundle.add_text(
+        "README.txt",
+        "RSSR diagnostics bundle. API keys and tokens are redacted.\n",
+    );
+    bundle
+        .add_json("app.json", &app_info)
+        .map_err(|error| error.to_string())?;
+    bundle
+        .add_json("database_stats.json", &stats)
+        .map_err(|error| error.to_string())?;
+    bundle
+        .add_json("settings.json", &settings)
+        .map_err(|error| error.to_string())?;
+    bundle
+        .add_json("sources.json", &sources)
+        .map_err(|error| error.to_string())?;
+    bundle
+        .add_json("sync_runtime.json", &runtime_status)
+        .map_err(|error| error.to_string())?;
+    bundle
+        .add_json("sync_history.json", &sync_history)
+        .map_err(|error| error.to_string())?;
+    bundle
+        .write_zip(&target)
+        .map_err(|error| error.to_string())?;
+    Ok(target.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn export_llm_cache(
+    path: String,
+    state: tauri::State<'_, SharedState>,
+) -> Result<usize, String> {
+    let target = PathBuf::from(path.trim());
+    if target.as_os_str().is_empty() {
+        return Err("path cannot be empty".to_string());
+    }
+    let records = state
+        .source_repository
+        .list_llm_cache_records()
+        .await
+        .map_err(|error| error.to_string())?;
+    tokio::task::spawn_blocking(move || {
+        write_llm_cache_export(&target, records, chrono::Utc::now())
+    })
+    .await
+    .map_err(|error| error.to_string())?
+    .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+async fn import_llm_cache(
+    path: String,
+    model_map: Option<BTreeMap<String, String>>,
+    overwrite: Option<bool>,
+    state: tauri::State<'_, SharedState>,
+) -> Result<LlmCacheImport, String> {
+    let source = PathBuf::from(path.trim());
+    if source.as_os_str().is_empty() {
+        return Err("path cannot be empty".to_string());
+    }
+    let export = tokio::task::spawn_blocking(move || read_llm_cache_export(&source))
+        .await
+        .map_err(|error| error.to_string())?
+        .map_err(|error| error.to_string())?;
+    let records = remap_models(export.records, &model_map.unwrap_or_default());
+    state
+        .source_repository
+        .import_llm_cache_records(&records, overwrite.unwrap_or(false))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+async fn snapshot_sync_runtime(runtime: &SyncRuntime) -> SyncRuntimeStatus {
+    let last_report = runtime.last_report.read().await.clone();
+    let last_error = runtime.last_error.read().await.clone();
+    SyncRuntimeStatus {
+        running: runtime.running.load(Ordering::SeqCst),
+        paused: runtime.is_paused(),
+        last_report,
+        last_error,
+    }
+}
+
+async fn preview_import_candidates(
+    repository: &SourceRepository,
+    candidates: Vec<ImportSource>,
+) -> Result<ImportPreviewResponse, String> {
+    let existing_rows = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?;
+    let existing_sources = existing_rows
+        .into_iter()
+        .map(|row| ImportSource {
+            title: row.title,
+            feed_url: row.feed_url,
+            site_url: row.site_url,
+            category: row.category,
+            rssr: None,
+        })
+        .collect::<Vec<_>>();
+    let preview = build_preview_against(candidates, &existing_sources);
+    let category_suggestions = suggest_categories(&preview.new_sources, &existing_sources);
+
+    Ok(ImportPreviewResponse {
+        new_count: preview.new_sources.len(),
+        duplicate_count: preview.duplicate_sources.len(),
+        possible_duplicate_count: preview.possible_duplicates.len(),
+        new_sources: preview.new_sources,
+        duplicate_sources: preview.duplicate_sources,
+        possible_duplicates: preview.possible_duplicates,
+        category_suggestions,
+    })
+}
+
+#[derive(Debug, Clone)]
+struct ImportOptions {
+    default_category: Option<String>,
+    is_active: bool,
+    include_possible_duplicates: bool,
+    mark_existing_read: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            default_category: None,
+            is_active: true,
+            include_possible_duplicates: false,
+            mark_existing_read: false,
+        }
+    }
+}
+
+async fn import_candidates(
+    repository: &SourceRepository,
+    candidates: Vec<ImportSource>,
+    options: ImportOptions,
+) -> Result<ImportExecuteResponse, String> {
+    let existing_sources = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .map(|row| ImportSource {
+            title: row.title,
+            feed_url: row.feed_url,
+            site_url: row.site_url,
+            category: row.category,
+            rssr: None,
+        })
+        .collect::<Vec<_>>();
+    let mut preview = build_preview_against(candidates, &existing_sources);
+    if options.include_possible_duplicates {
+        preview.new_sources.append(&mut preview.possible_duplicates);
+    }
+    let sources_to_import: Vec<NewSource> = preview
+        .new_sources
+        .iter()
+        .map(|source| NewSource {
+            title: source.title.clone(),
+            site_url: source.site_url.clone(),
+            feed_url: source.feed_url.clone(),
+            category: source
+                .category
+                .clone()
+                .or_else(|| options.default_category.clone()),
+            is_active: source
+                .rssr
+                .as_ref()
+                .and_then(|rssr| rssr.is_active)
+                .unwrap_or(options.is_active),
+        })
+        .collect();
+
+    let imported = repository
+        .upsert_sources_batch(&sources_to_import)
+        .await
+        .map_err(|error| error.to_string())?;
+    apply_imported_appearance(repository, &preview.new_sources).await?;
+    if options.mark_existing_read {
+        let feed_urls = sources_to_import
+            .iter()
+            .map(|source| source.feed_url.clone())
+            .collect::<Vec<_>>();
+        repository
+            .mark_backlog_read_on_first_sync(&feed_urls)
+            .await
+            .map_err(|error| error.to_string())?;
+    }
+
+    Ok(ImportExecuteResponse {
+        imported_count: imported.inserted,
+        updated_count: imported.updated,
+        duplicate_count: preview.duplicate_sources.len(),
+        possible_duplicate_count: preview.possible_duplicates.len(),
+        possible_duplicates: preview.possible_duplicates,
+    })
+}
+
+fn build_preview_against(
+    candidates: Vec<ImportSource>,
+    existing_sources: &[ImportSource],
+) -> ImportPreview {
+    let existing_feed_urls: HashSet<String> = existing_sources
+        .iter()
+        .map(|source| normalize_url(&source.feed_url))
+        .collect();
+    let existing_site_hosts: HashSet<String> = existing_sources
+        .iter()
+        .filter_map(source_site_host)
+        .collect();
+    build_import_preview(candidates, &existing_feed_urls, &existing_site_hosts)
+}
+
+async fn apply_imported_appearance(
+    repository: &SourceRepository,
+    sources: &[ImportSource],
+) -> Result<(), String> {
+    let styled = sources
+        .iter()
+        .filter_map(|source| Some((normalize_url(&source.feed_url), source.rssr.as_ref()?)))
+        .filter(|(_, rssr)| rssr.color.is_some() || rssr.custom_icon.is_some())
+        .collect::<Vec<_>>();
+    if styled.is_empty() {
+        return Ok(());
+    }
+    let ids = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .map(|row| (normalize_url(&row.feed_url), row.id))
+        .collect::<HashMap<_, _>>();
+    for (feed_url, rssr) in styled {
+        let Some(source_id) = ids.get(&feed_url).copied() else {
+            continue;
+        };
+        if let Ok(Some(color)) = normalize_source_color(rssr.color.as_deref()) {
+            repository
+                .set_source_color(source_id, Some(&color))
+                .await
+                .map_err(|error| error.to_string())?;
+        }
+        if let Some(custom_icon) = rssr
+            .custom_icon
+            .as_deref()
+            .filter(|value| value.len() <= MAX_CUSTOM_ICON_BYTES)
+        {
+            repository
+                .set_source_custom_icon(source_id, Some(custom_icon))
+                .await
+                .map_err(|error| error.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+fn select_export_sources(
+    rows: Vec<SourceRecord>,
+    request: &OpmlExportRequest,
+) -> Vec<ImportSource> {
+    let categories = request
+        .categories
+        .iter()
+        .map(|category| category.trim().to_lowercase())
+        .collect::<HashSet<_>>();
+    let source_ids = request.source_ids.iter().copied().collect::<HashSet<_>>();
+    rows.into_iter()
+        .filter(|row| {
+            let category = row
+                .category
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase();
+            let category_match = categories.is_empty() || categories.contains(&category);
+            let id_match = source_ids.is_empty() || source_ids.contains(&row.id);
+            category_match && id_match
+        })
+        .map(|row| ImportSource {
+            rssr: request
+                .include_rssr_attributes
+                .then(|| RssrSourceAttributes {
+                    is_active: Some(row.is_active == 1),
+                    color: row.color.clone(),
+                    custom_icon: row.custom_icon.clone(),
+                }),
+            title: row.title,
+            feed_url: row.feed_url,
+            site_url: row.site_url,
+            category: row.category,
+        })
+        .collect()
+}
+
+async fn load_import_sources(
+    state: &SharedState,
+    request: &ImportRequest,
+) -> Result<Vec<ImportSource>, String> {
+    match request.format.to_lowercase().as_str() {
+        "html_links" | "html" => Ok(html_link_sources(
+            discover_html_link_feeds(state, &request.content).await?,
+            request.all_feeds_per_site,
+        )),
+        _ => parse_import_sources_blocking(request).await,
+    }
+}
+
+async fn discover_html_link_feeds(state: &SharedState, html: &str) -> Result<SiteFeeds, String> {
+    if let Some(discovered) = state.html_imports.get(html) {
+        return Ok(discovered);
+    }
+    let links = extract_html_links(html);
+    if links.is_empty() {
+        return Ok(Vec::new());
+    }
+    ensure_online(&state.network)?;
+    let settings = load_sync_settings(&state.source_repository).await?;
+    let http = Arc::new(sync_http(&settings));
+    let client = http
+        .client(settings.timeout_secs)
+        .map_err(|error| error.to_string())?;
+    let mut join_set = JoinSet::new();
+    for (index, link) in links.into_iter().enumerate() {
+        let client = client.clone();
+        let http = http.clone();
+        join_set.spawn(async move {
+            let _permit = http.acquire(&link).await.ok()?;
+            let candidates = discover_site_feeds(&client, &link).await.ok()?;
+            Some((index, link, candidates))
+        });
+    }
+    let mut discovered = Vec::new();
+    while let Some(result) = join_set.join_next().await {
+        if let Ok(Some(found)) = result {
+            discovered.push(found);
+        }
+    }
+    discovered.sort_by_key(|(index, _, _)| *index);
+    let discovered = discovered
+        .into_iter()
+        .map(|(_, link, candidates)| (link, candidates))
+        .collect::<SiteFeeds>();
+    state.html_imports.store(html, discovered.clone());
+    Ok(discovered)
+}
+
+fn html_link_sources(discovered: SiteFeeds, all_feeds_per_site: bool) -> Vec<ImportSource> {
+    let mut seen = HashSet::new();
+    discovered
+        .into_iter()
+        .flat_map(|(link, candidates)| {
+            let keep = if all_feeds_per_site {
+                candidates.len()
+            } else {
+                1
+            };
+            candidates
+                .into_iter()
+                .take(keep)
+                .map(move |candidate| ImportSource {
+                    title: candidate.title,
+                    feed_url: candidate.url,
+                    site_url: (candidate.discovered_by != "direct").then(|| link.clone()),
+                    category: None,
+                    rssr: None,
+                })
+        })
+        .filter(|source| seen.insert(normalize_url(&source.feed_url)))
+        .collect()
+}
+
+async fn parse_import_sources_blocking(
+    request: &ImportRequest,
+) -> Result<Vec<ImportSource>, String> {
+    let request = request.clone();
+    tokio::task::spawn_blocking(move || parse_import_sources(&request))
+        .await
+        .map_err(|error| error.to_string())?
+}
+
+fn parse_import_sources(request: &ImportRequest) -> Result<Vec<ImportSource>, String> {
+    match request.format.to_lowercase().as_str() {
+        "opml" | "xml" => parse_opml(&request.content).map_err(|error| error.to_string()),
+        "url_list" | "urls" | "txt" => Ok(parse_url_list(&request.content)),
         "json" | "json_list" => {
             parse_json_sources(&request.content).map_err(|error| error.to_string())
         }
-        unsupported => Err(format!("unsupported import format: {unsupported}")),
+        unsupported => Err(format!("unsupported import format: {unsupported}")),
+    }
+}
+
+fn parse_source_sort_field(sort_by: Option<&str>) -> Result<SourceSortField, String> {
+    match sort_by.map(str::trim).unwrap_or_default() {
+        "" | "newest" => Ok(SourceSortField::Newest),
+        "title" => Ok(SourceSortField::Title),
+        "last_synced_at" => Ok(SourceSortField::LastSyncedAt),
+        "failure_count" => Ok(SourceSortField::FailureCount),
+        unsupported => Err(format!("unsupported source sort field: {unsupported}")),
+    }
+}
+
+fn normalize_source_color(color: Option<&str>) -> Result<Option<String>, String> {
+    let Some(color) = color.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    if !matches!(hex.len(), 3 | 6) || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err(format!("invalid source color: {color}"));
+    }
+    Ok(Some(format!("#{}", hex.to_ascii_lowercase())))
+}
+
+fn source_to_dto(source: SourceRecord) -> SourceDto {
+    SourceDto {
+        id: source.id,
+        title: source.title,
+        site_url: source.site_url,
+        feed_url: source.feed_url,
+        category: source.category,
+        is_active: source.is_active == 1,
+        failure_count: source.failure_count,
+        etag: source.etag,
+        last_modified: source.last_modified,
+        last_synced_at: source.last_synced_at,
+        color: source.color,
+        custom_icon: source.custom_icon,
+        created_at: source.created_at,
+        updated_at: source.updated_at,
+    }
+}
+
+fn entry_to_dto(entry: EntryRecord) -> EntryDto {
+    EntryDto {
+        id: entry.id,
+        source_id: entry.source_id,
+        source_title: entry.source_title,
+        guid: entry.guid,
+        link: entry.link,
+        title: entry.title,
+        translated_title: entry.translated_title,
+        summary: entry.summary,
+        content: entry.content,
+        published_at: entry.published_at,
+        is_read: entry.is_read == 1,
+        is_starred: entry.is_starred == 1,
+        created_at: entry.created_at,
+        updated_at: entry.updated_at,
+        is_updated: entry.is_updated,
+    }
+}
+
+async fn sync_single_source(
+    repository: &SourceRepository,
+    source: SourceRecord,
+    settings: &SyncSettings,
+    http: &SyncHttp,
+    budget: Option<&ByteBudget>,
+    notification_rules: &[NotificationRule],
+    network: &NetworkRuntime,
+) -> Result<SyncSourceResponse, SyncFailure> {
+    let overrides = repository
+        .get_source_settings(source.id)
+        .await
+        .map_err(|error| error.to_string())?;
+    let settings = &apply_source_overrides(settings, &overrides);
+    let client = http
+        .client(settings.timeout_secs)
+        .map_err(|error| error.to_string())?;
+    let _permit = http
+        .acquire(&source.feed_url)
+        .await
+        .map_err(|error| error.to_string())?;
+    let started_at = chrono::Utc::now().to_rfc3339();
+
+    let fetched = fetch_feed_with_retry(
+        &client,
+        &source.feed_url,
+        source.etag.as_deref(),
+        source.last_modified.as_deref(),
+        settings.retry_count as usize,
+        budget,
+    )
+    .await;
+
+    let result = match fetched {
+        Ok(FetchStatus::NotModified) => {
+            repository
+                .update_source_sync_success(
+                    source.id,
+                    source.etag.as_deref(),
+                    source.last_modified.as_deref(),
+                )
+                .await
+                .map_err(|error| error.to_string())?;
+            repository
+                .schedule_source_poll(source.id, chrono::Utc::now())
+                .await
+                .map_err(|error| error.to_string())?;
+            SyncSourceResponse {
+                source_id: source.id,
+                status: "not_modified".to_string(),
+                upserted_entries: 0,
+                inserted_entries: 0,
+                updated_entries: 0,
+                conflicted_entries: 0,
+            }
+        }
+        Ok(FetchStatus::Updated(payload)) => {
+            let body = payload.body;
+            let content_type = payload.content_type;
+            let (parsed, parked_domain) = tokio::task::spawn_blocking(move || {
+                let parsed = parse_feed_with_content_type(&body, content_type.as_deref());
+                let parked_domain = parsed.is_err() && looks_like_parked_domain(&body);
+                (parsed, parked_domain)
+            })
+            .await
+            .map_err(|error| error.to_string())?;
+            let mut parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(error) => {
+                    record_sync_failure(
+                        repository,
+                        network,
+                        &source,
+                        SyncAttempt {
+                            source_id: source.id,
+                            started_at,
+                            status: "parse_error".to_string(),
+                            http_status: Some(200),
+                            entries_upserted: 0,
+                            error: Some(error.to_string()),
+                        },
+                        parked_domain,
+                        settings.failure_disable_threshold,
+                    )
+                    .await?;
+                    return Err(SyncFailure::new(
+                        SyncFailureClass::ParseError,
+                        error.to_string(),
+                    ));
+                }
+            };
+            if settings.max_pages_per_sync > 1
+                && (parsed.next_url.is_some() || parsed.prev_archive_url.is_some())
+            {
+                let known = repository
+                    .list_entry_links(source.id)
+                    .await
+                    .map_err(|error| error.to_string())?
+                    .into_iter()
+                    .collect::<HashSet<_>>();
+                let older = fetch_following_pages(
+                    &client,
+                    &source.feed_url,
+                    &parsed,
+                    &known,
+                    settings.max_pages_per_sync as usize,
+                )
+                .await;
+                parsed.entries.extend(older);
+            }
+            let notify_prefs = repository
+                .get_source_notification_prefs(source.id)
+                .await
+                .map_err(|error| error.to_string())?;
+            let notify_rules = rules_for_source(
+                notify_prefs.as_ref(),
+                notification_rules,
+                source.id,
+                source.category.as_deref(),
+            );
+            let notify_prefs = notify_prefs.filter(|prefs| prefs.mode != NotifyMode::Never);
+            let notify_after = if notify_prefs.is_some() || !notify_rules.is_empty() {
+                repository
+                    .max_entry_id(source.id)
+                    .await
+                    .map_err(|error| error.to_string())?
+            } else {
+                0
+            };
+            let upserted = repository
+                .upsert_entries(source.id, &parsed.entries)
+                .await
+                .map_err(|error| error.to_string())?;
+            if notify_prefs.is_some() || !notify_rules.is_empty() {
+                queue_source_notifications(
+                    repository,
+                    source.id,
+                    notify_prefs.as_ref(),
+                    &notify_rules,
+                    notify_after,
+                )
+                .await?;
+            }
+            repository
+                .update_source_sync_success(
+                    source.id,
+                    payload.etag.as_deref(),
+                    payload.last_modified.as_deref(),
+                )
+                .await
+                .map_err(|error| error.to_string())?;
+            repository
+                .set_source_feed_hints(source.id, parsed.ttl_minutes, &parsed.skip_hours)
+                .await
+                .map_err(|error| error.to_string())?;
+            repository
+                .schedule_source_poll(source.id, chrono::Utc::now())
+                .await
+                .map_err(|error| error.to_string())?;
+            drop(payload.budget_permit);
+            SyncSourceResponse {
+                source_id: source.id,
+                status: "updated".to_string(),
+                upserted_entries: upserted.total(),
+                inserted_entries: upserted.inserted,
+                updated_entries: upserted.updated,
+                conflicted_entries: upserted.conflicted,
+            }
+        }
+        Err(error) => {
+            let status = match &error {
+                FetchError::Request(inner) if inner.is_timeout() => "timeout",
+                FetchError::Unauthorized | FetchError::Forbidden => "auth_required",
+                FetchError::NotFound | FetchError::Gone => "not_found",
+                FetchError::RateLimited { .. } => "rate_limited",
+                _ => "failed",
+            };
+            let gone_count = record_sync_failure(
+                repository,
+                network,
+                &source,
+                SyncAttempt {
+                    source_id: source.id,
+                    started_at,
+                    status: status.to_string(),
+                    http_status: error.status_code(),
+                    entries_upserted: 0,
+                    error: Some(error.to_string()),
+                },
+                false,
+                settings.failure_disable_threshold,
+            )
+            .await?;
+            if let FetchError::RateLimited { retry_after_secs } = error {
+                repository
+                    .defer_source_sync(source.id, rate_limit_backoff_secs(retry_after_secs))
+                    .await
+                    .map_err(|error| error.to_string())?;
+            }
+            let should_rediscover = gone_count == STALE_GONE_STATUS_THRESHOLD
+                || (matches!(error, FetchError::Gone) && gone_count == 1);
+            if should_rediscover {
+                let _ = rediscover_moved_feed(
+                    repository,
+                    &client,
+                    &source,
+                    settings.auto_fix_moved_feeds,
+                )
+                .await;
+            }
+            return Err(SyncFailure::from_fetch(&error));
+        }
+    };
+
+    repository
+        .record_source_sync_result(
+            source.id,
+            &result.status,
+            result.upserted_entries as i64,
+            None,
+        )
+        .await
+        .map_err(|error| error.to_string())?;
+    repository
+        .record_source_health(source.id, Some(200), false)
+        .await
+        .map_err(|error| error.to_string())?;
+    repository
+        .log_sync_attempt(&SyncAttempt {
+            source_id: source.id,
+            started_at,
+            status: result.status.clone(),
+            http_status: Some(if result.status == "not_modified" {
+                304
+            } else {
+                200
+            }),
+            entries_upserted: result.upserted_entries as i64,
+            error: None,
+        })
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(result)
+}
+
+fn rate_limit_backoff_secs(retry_after_secs: Option<u64>) -> i64 {
+    retry_after_secs
+        .unwrap_or(RATE_LIMIT_DEFAULT_BACKOFF_SECS)
+        .clamp(RATE_LIMIT_MIN_BACKOFF_SECS, RATE_LIMIT_MAX_BACKOFF_SECS) as i64
+}
+
+async fn record_sync_failure(
+    repository: &SourceRepository,
+    network: &NetworkRuntime,
+    source: &SourceRecord,
+    mut attempt: SyncAttempt,
+    parked_domain: bool,
+    disable_threshold: u32,
+) -> Result<i64, String> {
+    let error = redact_secrets(attempt.error.as_deref().unwrap_or_default());
+    attempt.error = Some(error.clone());
+    let failure_count = repository
+        .increment_source_failure(source.id)
+        .await
+        .map_err(|inner| inner.to_string())?;
+    if disable_threshold > 0 && failure_count >= i64::from(disable_threshold) {
+        let reason = format!("disabled after {failure_count} consecutive failures: {error}");
+        repository
+            .auto_disable_source(source.id, &reason)
+            .await
+            .map_err(|inner| inner.to_string())?;
+    }
+    repository
+        .record_source_sync_result(source.id, &attempt.status, 0, Some(&error))
+        .await
+        .map_err(|inner| inner.to_string())?;
+    let gone_count = repository
+        .record_source_health(source.id, attempt.http_status, parked_domain)
+        .await
+        .map_err(|inner| inner.to_string())?;
+    repository
+        .log_sync_attempt(&attempt)
+        .await
+        .map_err(|inner| inner.to_string())?;
+    fire_hooks(
+        repository,
+        network,
+        HookEvent::SourceFailed,
+        serde_json::json!({
+            "source_id": source.id,
+            "title": source.title,
+            "feed_url": source.feed_url,
+            "error": error,
+            "http_status": attempt.http_status,
+            "failure_count": failure_count,
+        }),
+    )
+    .await;
+    Ok(gone_count)
+}
+
+async fn load_onboarding_record(repository: &SourceRepository) -> Result<OnboardingRecord, String> {
+    let Some(raw) = repository
+        .get_setting(ONBOARDING_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    else {
+        return Ok(OnboardingRecord::default());
+    };
+    serde_json::from_str::<OnboardingRecord>(&raw).map_err(|error| error.to_string())
+}
+
+async fn save_onboarding_record(
+    repository: &SourceRepository,
+    record: &OnboardingRecord,
+) -> Result<(), String> {
+    let serialized = serde_json::to_string(record).map_err(|error| error.to_string())?;
+    repository
+        .set_setting(ONBOARDING_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+async fn load_onboarding_state(repository: &SourceRepository) -> Result<OnboardingState, String> {
+    let record = load_onboarding_record(repository).await?;
+    let source_count = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?
+        .len();
+    let step = if record.completed_at.is_some() {
+        OnboardingStep::Completed
+    } else if source_count == 0 {
+        OnboardingStep::ImportSources
+    } else if record.first_sync_at.is_none() {
+        OnboardingStep::FirstSync
+    } else {
+        OnboardingStep::Completed
+    };
+    Ok(OnboardingState {
+        step,
+        source_count,
+        first_sync_at: record.first_sync_at,
+        completed_at: record.completed_at,
+    })
+}
+
+fn prioritize_first_sync(sources: Vec<SourceRecord>) -> Vec<SourceRecord> {
+    let mut groups = Vec::<(Option<String>, Vec<SourceRecord>)>::new();
+    for source in sources {
+        let category = source.category.clone();
+        match groups.iter_mut().find(|(name, _)| *name == category) {
+            Some((_, items)) => items.push(source),
+            None => groups.push((category, vec![source])),
+        }
+    }
+    for (_, items) in &mut groups {
+        items.reverse();
+    }
+    let mut ordered = Vec::new();
+    while groups.iter().any(|(_, items)| !items.is_empty()) {
+        for (_, items) in &mut groups {
+            if let Some(source) = items.pop() {
+                ordered.push(source);
+            }
+        }
+    }
+    ordered
+}
+
+fn ensure_online(network: &NetworkRuntime) -> Result<(), String> {
+    if network.is_offline() {
+        return Err("offline mode is on".to_string());
+    }
+    Ok(())
+}
+
+fn network_status(network: &NetworkRuntime) -> NetworkStatus {
+    NetworkStatus {
+        offline: network.is_offline(),
+        queued_requests: network.queued(),
+    }
+}
+
+async fn run_queued_requests(
+    app: tauri::AppHandle,
+    repository: SourceRepository,
+    runtime: Arc<SyncRuntime>,
+    network: Arc<NetworkRuntime>,
+    requests: Vec<QueuedRequest>,
+) {
+    let settings = load_sync_settings(&repository).await.unwrap_or_default();
+    let http = sync_http(&settings);
+    let budget = sync_budget(&settings);
+    let rules = load_notification_rules(&repository)
+        .await
+        .unwrap_or_default();
+    for request in requests {
+        match request {
+            QueuedRequest::SyncSource { source_id } => {
+                if let Ok(Some(source)) = repository.get_source_by_id(source_id).await {
+                    let _ = sync_single_source(
+                        &repository,
+                        source,
+                        &settings,
+                        &http,
+                        Some(&budget),
+                        &rules,
+                        &network,
+                    )
+                    .await;
+                }
+            }
+            QueuedRequest::SyncActiveSources => {
+                start_active_sync(
+                    app.clone(),
+                    repository.clone(),
+                    runtime.clone(),
+                    network.clone(),
+                );
+            }
+            QueuedRequest::SummarizeEntry { entry_id } => {
+                let result = summarize_entry_internal(&repository, entry_id).await;
+                network.settle_summary(entry_id, result.is_ok());
+            }
+        }
+    }
+}
+
+async fn load_automation_hooks(
+    repository: &SourceRepository,
+) -> Result<Vec<AutomationHook>, String> {
+    let Some(raw) = repository
+        .get_setting(AUTOMATION_HOOKS_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    else {
+        return Ok(Vec::new());
+    };
+    serde_json::from_str::<Vec<AutomationHook>>(&raw).map_err(|error| error.to_string())
+}
+
+fn validate_automation_hook(hook: &AutomationHook) -> Result<(), String> {
+    if hook.name.trim().is_empty() {
+        return Err("hook name cannot be empty".to_string());
+    }
+    match &hook.action {
+        HookAction::Command { program, .. } if program.trim().is_empty() => {
+            Err(format!("hook {} has no command", hook.name))
+        }
+        HookAction::Http { url } if reqwest::Url::parse(url.trim()).is_err() => {
+            Err(format!("hook {} has an invalid url", hook.name))
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn load_notification_rules(
+    repository: &SourceRepository,
+) -> Result<Arc<[NotificationRule]>, String> {
+    let rules = repository
+        .list_notification_rules()
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(rules.into_iter().filter(|rule| rule.is_enabled).collect())
+}
+
+async fn queue_source_notifications(
+    repository: &SourceRepository,
+    source_id: i64,
+    prefs: Option<&NotificationPrefs>,
+    rules: &[NotificationRule],
+    after_id: i64,
+) -> Result<(), String> {
+    let entries = repository
+        .list_entries_after(source_id, after_id)
+        .await
+        .map_err(|error| error.to_string())?;
+    for entry in entries {
+        let summary = entry.summary.as_deref();
+        let matched = prefs
+            .and_then(|prefs| notification_match(prefs, &entry.title, summary))
+            .or_else(|| rule_match(rules, &entry.title, summary));
+        if let Some(keyword) = matched {
+            repository
+                .queue_notification(source_id, entry.id, keyword.as_deref())
+                .await
+                .map_err(|error| error.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+async fn emit_pending_notifications(app: &tauri::AppHandle, repository: &SourceRepository) {
+    if let Ok(pending) = repository
+        .take_pending_notifications(NOTIFICATION_BATCH_LIMIT)
+        .await
+    {
+        if !pending.is_empty() {
+            show_native_notifications(app, &pending);
+            let _ = app.emit(NOTIFICATIONS_EVENT, pending);
+        }
+    }
+}
+
+fn show_native_notifications(app: &tauri::AppHandle, pending: &[PendingNotification]) {
+    for notification in pending.iter().take(MAX_NATIVE_NOTIFICATIONS) {
+        let title = match &notification.matched_keyword {
+            Some(keyword) => format!("{} · {keyword}", notification.source_title),
+            None => notification.source_title.clone(),
+        };
+        let _ = app
+            .notification()
+            .builder()
+            .title(title)
+            .body(notification.title.clone())
+            .show();
+    }
+    let remaining = pending.len().saturating_sub(MAX_NATIVE_NOTIFICATIONS);
+    if remaining > 0 {
+        let _ = app
+            .notification()
+            .builder()
+            .title("RSSR")
+            .body(format!("还有 {remaining} 篇匹配的新文章"))
+            .show();
+    }
+}
+
+async fn fire_hooks(
+    repository: &SourceRepository,
+    network: &NetworkRuntime,
+    event: HookEvent,
+    data: serde_json::Value,
+) {
+    let Ok(hooks) = load_automation_hooks(repository).await else {
+        return;
+    };
+    let offline = network.is_offline();
+    let matching = hooks_for(&hooks, event)
+        .into_iter()
+        .filter(|hook| !(offline && matches!(hook.action, HookAction::Http { .. })))
+        .collect::<Vec<_>>();
+    if matching.is_empty() {
+        return;
+    }
+    let payload = hook_payload(event, data);
+    tauri::async_runtime::spawn(async move {
+        let client = reqwest::Client::new();
+        for hook in matching {
+            let _ = run_hook(&client, &hook, &payload).await;
+        }
+    });
+}
+
+fn entry_hook_data(entry: &EntryRecord) -> serde_json::Value {
+    serde_json::json!({
+        "entry_id": entry.id,
+        "source_id": entry.source_id,
+        "source_title": entry.source_title,
+        "title": entry.title,
+        "link": entry.link,
+        "summary": entry.summary,
+        "published_at": entry.published_at,
+    })
+}
+
+async fn fire_digest_hooks(
+    repository: &SourceRepository,
+    network: &NetworkRuntime,
+    report: &SyncBatchResponse,
+) {
+    if report.total_upserted_entries == 0 {
+        return;
+    }
+    let Ok(entries) = repository
+        .list_entries(None, None, true, DIGEST_HOOK_MAX_ENTRIES)
+        .await
+    else {
+        return;
+    };
+    fire_hooks(
+        repository,
+        network,
+        HookEvent::DigestReady,
+        serde_json::json!({
+            "report": report,
+            "entries": entries.iter().map(entry_hook_data).collect::<Vec<_>>(),
+        }),
+    )
+    .await;
+}
+
+async fn rediscover_moved_feed(
+    repository: &SourceRepository,
+    client: &reqwest::Client,
+    source: &SourceRecord,
+    auto_fix: bool,
+) -> Result<Option<String>, String> {
+    let Some(site_url) = source.site_url.as_deref().filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let Some(replacement) = find_replacement_feed(client, site_url, &source.feed_url)
+        .await
+        .map_err(|error| error.to_string())?
+    else {
+        return Ok(None);
+    };
+    if auto_fix
+        && repository
+            .replace_source_feed_url(source.id, &replacement)
+            .await
+            .is_ok()
+    {
+        return Ok(Some(replacement));
+    }
+    repository
+        .set_source_replacement_feed(source.id, Some(&replacement))
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(Some(replacement))
+}
+
+async fn sync_active_sources_internal(
+    repository: &SourceRepository,
+    network: &Arc<NetworkRuntime>,
+    cancel: &CancellationToken,
+    respect_schedule: bool,
+) -> Result<SyncBatchResponse, String> {
+    let settings = load_sync_settings(repository).await?;
+    let sources = repository
+        .list_sync_candidates(settings.batch_limit as i64, respect_schedule)
+        .await
+        .map_err(|error| error.to_string())?;
+    run_sync_batch(repository, network, &settings, sources, cancel, |_, _| {}).await
+}
+
+async fn run_sync_batch(
+    repository: &SourceRepository,
+    network: &Arc<NetworkRuntime>,
+    settings: &SyncSettings,
+    sources: Vec<SourceRecord>,
+    cancel: &CancellationToken,
+    on_progress: impl Fn(&SyncBatchResponse, &SyncSourceOutcome),
+) -> Result<SyncBatchResponse, String> {
+    let budget = sync_budget(settings);
+    let http = Arc::new(sync_http(settings));
+    let rules = load_notification_rules(repository).await?;
+    let mut join_set: JoinSet<(i64, String, Result<SyncSourceResponse, SyncFailure>)> =
+        JoinSet::new();
+    for source in sources {
+        let repo = repository.clone();
+        let copied_settings = settings.clone();
+        let copied_budget = budget.clone();
+        let http = http.clone();
+        let rules = rules.clone();
+        let network = network.clone();
+        join_set.spawn(async move {
+            let id = source.id;
+            let title = source.title.clone();
+            let result = sync_single_source(
+                &repo,
+                source,
+                &copied_settings,
+                &http,
+                Some(&copied_budget),
+                &rules,
+                &network,
+            )
+            .await;
+            (id, title, result)
+        });
     }
+
+    let mut report = SyncBatchResponse::default();
+    loop {
+        let joined = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => {
+                join_set.shutdown().await;
+                report.cancelled = true;
+                break;
+            }
+            joined = join_set.join_next() => joined,
+        };
+        match joined {
+            Some(Ok((source_id, title, result))) => {
+                let outcome = report.record(source_id, title, result).clone();
+                on_progress(&report, &outcome);
+            }
+            Some(Err(_)) => report.record_aborted(),
+            None => break,
+        }
+    }
+    report.sources.sort_by_key(|outcome| outcome.source_id);
+    report.finished_at = Some(chrono::Utc::now().to_rfc3339());
+    save_last_sync_report(repository, &report).await;
+    Ok(report)
+}
+
+async fn save_last_sync_report(repository: &SourceRepository, report: &SyncBatchResponse) {
+    if let Ok(serialized) = serde_json::to_string(report) {
+        let _ = repository
+            .set_setting(LAST_SYNC_REPORT_KEY, &serialized)
+            .await;
+    }
+}
+
+async fn load_last_sync_report(repository: &SourceRepository) -> Option<SyncBatchResponse> {
+    let raw = repository
+        .get_setting(LAST_SYNC_REPORT_KEY)
+        .await
+        .ok()
+        .flatten()?;
+    serde_json::from_str(&raw).ok()
+}
+
+async fn run_source_discovery_internal(
+    repository: &SourceRepository,
+) -> Result<Vec<DiscoverySuggestion>, String> {
+    let settings = load_sync_settings(repository).await?;
+    let sources = repository
+        .list_sources()
+        .await
+        .map_err(|error| error.to_string())?;
+    let mut subscribed_urls = HashSet::new();
+    let mut site_urls = Vec::new();
+    for source in &sources {
+        subscribed_urls.insert(normalize_url(&source.feed_url));
+        if let Some(site_url) = source.site_url.as_deref().filter(|value| !value.is_empty()) {
+            subscribed_urls.insert(normalize_url(site_url));
+            site_urls.push(site_url.to_string());
+        }
+    }
+    site_urls.sort_by_key(|url| normalize_url(url));
+    site_urls.dedup_by_key(|url| normalize_url(url));
+
+    let http = Arc::new(sync_http(&settings));
+    let client = http
+        .client(settings.timeout_secs)
+        .map_err(|error| error.to_string())?;
+    let mut join_set = JoinSet::new();
+    for site_url in site_urls {
+        let client = client.clone();
+        let http = http.clone();
+        join_set.spawn(async move {
+            let _permit = http.acquire(&site_url).await.ok()?;
+            discover_site(&client, &site_url).await.ok()
+        });
+    }
+    let mut discoveries = Vec::new();
+    while let Some(result) = join_set.join_next().await {
+        if let Ok(Some(discovery)) = result {
+            discoveries.push(discovery);
+        }
+    }
+
+    let suggestions = aggregate_suggestions(&discoveries, &subscribed_urls);
+    let raw = serde_json::to_string(&suggestions).map_err(|error| error.to_string())?;
+    repository
+        .set_setting(DISCOVERY_SUGGESTIONS_KEY, &raw)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(suggestions)
 }
 
-fn source_to_dto(source: SourceRecord) -> SourceDto {
-    SourceDto {
-        id: source.id,
-        title: source.title,
-        site_url: source.site_url,
-        feed_url: source.feed_url,
-        category: source.category,
-        is_active: source.is_active == 1,
-        failure_count: source.failure_count,
-        etag: source.etag,
-        last_modified: source.last_modified,
-        last_synced_at: source.last_synced_at,
-        created_at: source.created_at,
-        updated_at: source.updated_at,
+async fn translate_titles_within_budget(repository: &SourceRepository) -> Result<usize, String> {
+    let budget = load_llm_budget(repository).await?;
+    if budget.translations_per_cycle == 0 {
+        return Ok(0);
     }
+    translate_titles_background(repository, i64::from(budget.translations_per_cycle)).await
 }
 
-fn entry_to_dto(entry: EntryRecord) -> EntryDto {
-    EntryDto {
-        id: entry.id,
-        source_id: entry.source_id,
-        source_title: entry.source_title,
-        guid: entry.guid,
-        link: entry.link,
-        title: entry.title,
-        translated_title: entry.translated_title,
-        summary: entry.summary,
-        content: entry.content,
-        published_at: entry.published_at,
-        is_read: entry.is_read == 1,
-        is_starred: entry.is_starred == 1,
-        created_at: entry.created_at,
+async fn run_summary_backlog(repository: &SourceRepository, network: &NetworkRuntime) {
+    let Ok(budget) = load_llm_budget(repository).await else {
+        return;
+    };
+    let summaries = network
+        .drain(budget.summaries_per_cycle as usize)
+        .into_iter()
+        .filter_map(|request| match request {
+            QueuedRequest::SummarizeEntry { entry_id } => Some(entry_id),
+            other => {
+                network.enqueue(other);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    for entry_id in summaries {
+        let result = summarize_entry_internal(repository, entry_id).await;
+        network.settle_summary(entry_id, result.is_ok());
     }
 }
 
-async fn sync_single_source(
+async fn translate_titles_background(
     repository: &SourceRepository,
-    source: SourceRecord,
-    settings: &SyncSettings,
-) -> Result<SyncSourceResponse, String> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(settings.timeout_secs))
-        .build()
+    limit: i64,
+) -> Result<usize, String> {
+    let config = match get_saved_or_env_llm_config(repository, Some(LlmTask::Translation)).await? {
+        Some(config) => config,
+        None => return Ok(0),
+    };
+    validate_config(&config).map_err(|error| error.to_string())?;
+    let targets = repository
+        .list_entries_without_translated_title(limit)
+        .await
         .map_err(|error| error.to_string())?;
+    Ok(translate_title_targets(repository, &config, targets).await)
+}
 
-    let fetched = fetch_feed_with_retry(
-        &client,
-        &source.feed_url,
-        source.etag.as_deref(),
-        source.last_modified.as_deref(),
-        settings.retry_count as usize,
-    )
-    .await;
+async fn translate_title_targets(
+    repository: &SourceRepository,
+    config: &LlmConfig,
+    targets: Vec<EntryTitleRecord>,
+) -> usize {
+    if targets.is_empty() {
+        return 0;
+    }
 
-    let result = match fetched {
-        Ok(FetchStatus::NotModified) => {
-            repository
-                .update_source_sync_success(
-                    source.id,
-                    source.etag.as_deref(),
-                    source.last_modified.as_deref(),
-                )
+    let glossary = Arc::new(repository.list_glossary_terms().await.unwrap_or_default());
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(
+        DEFAULT_TITLE_TRANSLATE_MAX_CONCURRENCY,
+    ));
+    let mut join_set: JoinSet<Result<bool, String>> = JoinSet::new();
+    for target in targets {
+        let repo = repository.clone();
+        let cfg = config.clone();
+        let sem = semaphore.clone();
+        let glossary = glossary.clone();
+        join_set.spawn(async move {
+            let _permit = sem
+                .acquire_owned()
                 .await
                 .map_err(|error| error.to_string())?;
-            SyncSourceResponse {
-                source_id: source.id,
-                status: "not_modified".to_string(),
-                upserted_entries: 0,
+            let input = target.title.trim().to_string();
+            if input.is_empty() {
+                return Ok(false);
             }
-        }
-        Ok(FetchStatus::Updated(payload)) => {
-            let parsed = parse_feed_bytes(&payload.body).map_err(|error| error.to_string())?;
-            let upserted_entries = repository
-                .upsert_entries(source.id, &parsed.entries)
-                .await
-                .map_err(|error| error.to_string())?;
-            repository
-                .update_source_sync_success(
-                    source.id,
-                    payload.etag.as_deref(),
-                    payload.last_modified.as_deref(),
+            let terms = matching_terms(&glossary, &input);
+            let mut user_prompt = format!(
+                "Translate this article title into Chinese and keep it concise. Output only Chinese title.\n\n{}",
+                input
+            );
+            let glossary_section = glossary_prompt(&terms);
+            let hash_input = if glossary_section.is_empty() {
+                input.clone()
+            } else {
+                user_prompt.push_str(&format!("\n\n{glossary_section}"));
+                format!("{input}\n\n{glossary_section}")
+            };
+            let (hash, cached) =
+                lookup_llm_cache(&repo, "title_translate_zh", &cfg.model, &hash_input).await?;
+            let translated = if let Some(cached) = cached {
+                cached
+            } else {
+                let result = call_chat_completion(
+                    &cfg,
+                    &cfg.generation.translation,
+                    "You translate English article titles into concise Chinese.",
+                    &user_prompt,
                 )
                 .await
                 .map_err(|error| error.to_string())?;
-            SyncSourceResponse {
-                source_id: source.id,
-                status: "updated".to_string(),
-                upserted_entries,
+                repo.set_llm_cache("title_translate_zh", &cfg.model, &hash, &result)
+                    .await
+                    .map_err(|error| error.to_string())?;
+                result
+            };
+            let normalized = apply_glossary(translated.trim(), &terms);
+            if normalized.is_empty() {
+                return Ok(false);
             }
-        }
-        Err(error) => {
-            repository
-                .increment_source_failure(source.id)
+            repo.set_entry_translated_title(target.id, &normalized)
                 .await
-                .map_err(|inner| inner.to_string())?;
-            return Err(error.to_string());
+                .map_err(|error| error.to_string())?;
+            Ok(true)
+        });
+    }
+
+    let mut updated = 0_usize;
+    while let Some(result) = join_set.join_next().await {
+        if let Ok(Ok(true)) = result {
+            updated += 1;
         }
+    }
+    updated
+}
+
+async fn load_sync_settings(repository: &SourceRepository) -> Result<SyncSettings, String> {
+    if let Some(raw) = repository
+        .get_setting(SYNC_SETTINGS_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    {
+        let parsed =
+            serde_json::from_str::<SyncSettings>(&raw).map_err(|error| error.to_string())?;
+        let advanced = load_advanced_settings(repository).await?;
+        return Ok(normalize_sync_settings(parsed, &advanced));
+    }
+    Ok(SyncSettings::default())
+}
+
+async fn load_read_policy(repository: &SourceRepository) -> Result<ReadPolicySettings, String> {
+    if let Some(raw) = repository
+        .get_setting(READ_POLICY_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    {
+        let parsed =
+            serde_json::from_str::<ReadPolicySettings>(&raw).map_err(|error| error.to_string())?;
+        return Ok(normalize_read_policy(parsed));
+    }
+    Ok(ReadPolicySettings::default())
+}
+
+fn normalize_sanitizer_allowlist(allowlist: SanitizerAllowlist) -> SanitizerAllowlist {
+    let normalize = |values: Vec<String>| {
+        let mut values = values
+            .into_iter()
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>();
+        values.sort();
+        values.dedup();
+        values
     };
+    SanitizerAllowlist {
+        tags: normalize(allowlist.tags),
+        attributes: normalize(allowlist.attributes),
+        url_schemes: normalize(allowlist.url_schemes),
+    }
+}
 
-    Ok(result)
+fn normalize_read_policy(settings: ReadPolicySettings) -> ReadPolicySettings {
+    ReadPolicySettings {
+        mode: settings.mode,
+        after_days: settings.after_days.clamp(1, MAX_READ_AFTER_DAYS),
+    }
 }
 
-async fn sync_active_sources_internal(
+async fn apply_read_policy(repository: &SourceRepository) -> Result<u64, String> {
+    let policy = load_read_policy(repository).await?;
+    if policy.mode != ReadMarkMode::AfterDays {
+        return Ok(0);
+    }
+    repository
+        .sweep_read_older_than(i64::from(policy.after_days))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+async fn enforce_read_policy(repository: &SourceRepository) -> Result<(), String> {
+    if !repository.is_read_only() {
+        apply_read_policy(repository).await?;
+    }
+    Ok(())
+}
+
+async fn load_llm_budget(repository: &SourceRepository) -> Result<LlmBudgetSettings, String> {
+    if let Some(raw) = repository
+        .get_setting(LLM_BUDGET_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    {
+        let parsed =
+            serde_json::from_str::<LlmBudgetSettings>(&raw).map_err(|error| error.to_string())?;
+        return Ok(normalize_llm_budget(parsed));
+    }
+    Ok(LlmBudgetSettings::default())
+}
+
+fn normalize_llm_budget(settings: LlmBudgetSettings) -> LlmBudgetSettings {
+    LlmBudgetSettings {
+        translations_per_cycle: settings
+            .translations_per_cycle
+            .min(MAX_TRANSLATIONS_PER_CYCLE),
+        summaries_per_cycle: settings.summaries_per_cycle.min(MAX_SUMMARIES_PER_CYCLE),
+    }
+}
+
+async fn load_advanced_settings(repository: &SourceRepository) -> Result<AdvancedSettings, String> {
+    if let Some(raw) = repository
+        .get_setting(ADVANCED_SETTINGS_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    {
+        let parsed =
+            serde_json::from_str::<AdvancedSettings>(&raw).map_err(|error| error.to_string())?;
+        return Ok(normalize_advanced_settings(parsed));
+    }
+    Ok(AdvancedSettings::default())
+}
+
+async fn load_backup_settings(repository: &SourceRepository) -> Result<BackupSettings, String> {
+    if let Some(raw) = repository
+        .get_setting(BACKUP_SETTINGS_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    {
+        let parsed =
+            serde_json::from_str::<BackupSettings>(&raw).map_err(|error| error.to_string())?;
+        return Ok(normalize_backup_settings(parsed));
+    }
+    Ok(BackupSettings::default())
+}
+
+fn normalize_backup_settings(settings: BackupSettings) -> BackupSettings {
+    BackupSettings {
+        enabled: settings.enabled,
+        directory: settings
+            .directory
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty()),
+        interval_hours: settings.interval_hours.clamp(1, 24 * 30),
+        retention_count: settings.retention_count.clamp(1, 365),
+    }
+}
+
+async fn run_backup(
     repository: &SourceRepository,
-) -> Result<SyncBatchResponse, String> {
-    let settings = load_sync_settings(repository).await?;
-    let sources = repository
-        .list_sync_candidates(settings.batch_limit as i64)
+    settings: &BackupSettings,
+    directory: &str,
+) -> Result<String, String> {
+    let rows = repository
+        .list_sources()
         .await
         .map_err(|error| error.to_string())?;
-    let semaphore = Arc::new(tokio::sync::Semaphore::new(
-        settings.max_concurrency as usize,
-    ));
-    let mut join_set: JoinSet<Result<SyncSourceResponse, String>> = JoinSet::new();
-    for source in sources {
-        let repo = repository.clone();
-        let sem = semaphore.clone();
-        let copied_settings = settings.clone();
-        join_set.spawn(async move {
-            let _permit = sem
-                .acquire_owned()
-                .await
-                .map_err(|error| error.to_string())?;
-            sync_single_source(&repo, source, &copied_settings).await
-        });
+    let sources = select_export_sources(
+        rows,
+        &OpmlExportRequest {
+            include_rssr_attributes: true,
+            ..OpmlExportRequest::default()
+        },
+    );
+    let opml = core::importer::export_opml("rssr backup", &sources);
+    let snapshot_settings = serde_json::json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "sync_settings": load_sync_settings(repository).await?,
+        "backup_settings": settings,
+        "llm_profiles": repository
+            .list_llm_profiles()
+            .await
+            .map_err(|error| error.to_string())?,
+    });
+    let directory = PathBuf::from(directory);
+    let snapshot = write_backup_snapshot(&directory, &opml, &snapshot_settings, chrono::Utc::now())
+        .map_err(|error| error.to_string())?;
+    prune_backups(&directory, settings.retention_count as usize)
+        .map_err(|error| error.to_string())?;
+    Ok(snapshot.opml_path.to_string_lossy().to_string())
+}
+
+async fn run_scheduled_backup(repository: &SourceRepository) -> Result<Option<String>, String> {
+    let settings = load_backup_settings(repository).await?;
+    let Some(directory) = settings.directory.clone().filter(|_| settings.enabled) else {
+        return Ok(None);
+    };
+    let latest = latest_backup_at(&PathBuf::from(&directory)).map_err(|error| error.to_string())?;
+    let due = latest.is_none_or(|latest| {
+        chrono::Utc::now() - latest >= chrono::Duration::hours(i64::from(settings.interval_hours))
+    });
+    if !due {
+        return Ok(None);
+    }
+    run_backup(repository, &settings, &directory)
+        .await
+        .map(Some)
+}
+
+async fn load_snapshot_target(
+    repository: &SourceRepository,
+) -> Result<Option<RemoteTarget>, String> {
+    let Some(raw) = repository
+        .get_setting(SNAPSHOT_TARGET_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    else {
+        return Ok(None);
+    };
+    serde_json::from_str::<RemoteTarget>(&raw)
+        .map(Some)
+        .map_err(|error| error.to_string())
+}
+
+fn normalize_snapshot_target(target: RemoteTarget) -> Result<RemoteTarget, String> {
+    let trimmed = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let url = target.url.trim().to_string();
+    let parsed = url::Url::parse(&url).map_err(|error| error.to_string())?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("snapshot target must be an http or https url".to_string());
+    }
+    let target = RemoteTarget {
+        kind: target.kind,
+        url,
+        username: trimmed(target.username),
+        password: target.password.filter(|value| !value.is_empty()),
+        region: trimmed(target.region),
+    };
+    if target.kind == RemoteKind::S3
+        && (target.username.is_none() || target.password.is_none() || target.region.is_none())
+    {
+        return Err("s3 targets need an access key, secret key and region".to_string());
+    }
+    Ok(target)
+}
+
+fn redact_snapshot_target(target: RemoteTarget) -> RemoteTarget {
+    RemoteTarget {
+        password: None,
+        ..target
+    }
+}
+
+fn keep_snapshot_password(target: RemoteTarget, stored: Option<&RemoteTarget>) -> RemoteTarget {
+    if target
+        .password
+        .as_deref()
+        .is_some_and(|value| !value.is_empty())
+    {
+        return target;
+    }
+    let password = stored
+        .filter(|stored| {
+            stored.kind == target.kind
+                && stored.url == target.url.trim()
+                && stored.username.as_deref() == target.username.as_deref().map(str::trim)
+        })
+        .and_then(|stored| stored.password.clone());
+    RemoteTarget { password, ..target }
+}
+
+fn snapshot_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(SNAPSHOT_TRANSFER_TIMEOUT_SECS))
+        .build()
+        .map_err(|error| error.to_string())
+}
+
+async fn load_local_api_settings(
+    repository: &SourceRepository,
+) -> Result<LocalApiSettings, String> {
+    if let Some(raw) = repository
+        .get_setting(LOCAL_API_SETTINGS_KEY)
+        .await
+        .map_err(|error| error.to_string())?
+    {
+        let parsed =
+            serde_json::from_str::<LocalApiSettings>(&raw).map_err(|error| error.to_string())?;
+        return Ok(normalize_local_api_settings(parsed));
     }
-    let mut synced_sources = 0_usize;
-    let mut failed_sources = 0_usize;
-    let mut total_upserted_entries = 0_usize;
+    Ok(LocalApiSettings::default())
+}
 
-    while let Some(result) = join_set.join_next().await {
-        match result {
-            Ok(Ok(report)) => {
-                synced_sources += 1;
-                total_upserted_entries += report.upserted_entries;
-            }
-            Ok(Err(_)) | Err(_) => failed_sources += 1,
-        }
+fn normalize_local_api_settings(settings: LocalApiSettings) -> LocalApiSettings {
+    LocalApiSettings {
+        enabled: settings.enabled,
+        port: settings.port.max(1024),
+        token: settings
+            .token
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .or_else(generate_api_token),
     }
-
-    Ok(SyncBatchResponse {
-        synced_sources,
-        failed_sources,
-        total_upserted_entries,
-    })
 }
 
-async fn translate_titles_background(
+async fn restart_local_api(
     repository: &SourceRepository,
-    limit: i64,
-) -> Result<usize, String> {
-    let config = match get_saved_or_env_llm_config(repository).await? {
-        Some(config) => config,
-        None => return Ok(0),
+    settings: &LocalApiSettings,
+    slot: &tokio::sync::Mutex<Option<LocalApiServer>>,
+) -> Result<(), String> {
+    let mut guard = slot.lock().await;
+    if let Some(server) = guard.take() {
+        server.stop();
+    }
+    let Some(token) = settings.token.as_deref().filter(|_| settings.enabled) else {
+        return Ok(());
     };
-    validate_config(&config).map_err(|error| error.to_string())?;
-    let targets = repository
-        .list_entries_without_translated_title(limit)
+    let server = start_local_api(repository.clone(), settings.port, token)
         .await
         .map_err(|error| error.to_string())?;
-    if targets.is_empty() {
-        return Ok(0);
-    }
+    *guard = Some(server);
+    Ok(())
+}
 
-    let semaphore = Arc::new(tokio::sync::Semaphore::new(
-        DEFAULT_TITLE_TRANSLATE_MAX_CONCURRENCY,
-    ));
-    let mut join_set: JoinSet<Result<bool, String>> = JoinSet::new();
-    for target in targets {
-        let repo = repository.clone();
-        let cfg = config.clone();
-        let sem = semaphore.clone();
-        join_set.spawn(async move {
-            let _permit = sem
-                .acquire_owned()
-                .await
-                .map_err(|error| error.to_string())?;
-            let input = target.title.trim().to_string();
-            if input.is_empty() {
-                return Ok(false);
-            }
-            let hash = hash_llm_input("title_translate_zh", &cfg.model, &input);
-            let translated = if let Some(cached) = repo
-                .get_llm_cache("title_translate_zh", &cfg.model, &hash)
-                .await
-                .map_err(|error| error.to_string())?
-            {
-                cached
-            } else {
-                let result = call_chat_completion(
-                    &cfg,
-                    "You translate English article titles into concise Chinese.",
-                    &format!(
-                        "Translate this article title into Chinese and keep it concise. Output only Chinese title.\n\n{}",
-                        input
-                    ),
-                )
-                .await
-                .map_err(|error| error.to_string())?;
-                repo.set_llm_cache("title_translate_zh", &cfg.model, &hash, &result)
-                    .await
-                    .map_err(|error| error.to_string())?;
-                result
-            };
-            let normalized = translated.trim().to_string();
-            if normalized.is_empty() {
-                return Ok(false);
-            }
-            repo.set_entry_translated_title(target.id, &normalized)
-                .await
-                .map_err(|error| error.to_string())?;
-            Ok(true)
-        });
+fn normalize_sync_settings(settings: SyncSettings, limits: &AdvancedSettings) -> SyncSettings {
+    let max_concurrency = settings
+        .max_concurrency
+        .clamp(1, limits.max_sync_concurrency);
+    SyncSettings {
+        interval_secs: settings
+            .interval_secs
+            .clamp(limits.min_sync_interval_secs, limits.max_sync_interval_secs),
+        max_concurrency,
+        batch_limit: settings.batch_limit.clamp(1, limits.max_sync_batch_limit),
+        timeout_secs: settings
+            .timeout_secs
+            .clamp(limits.min_sync_timeout_secs, limits.max_sync_timeout_secs),
+        retry_count: settings.retry_count.clamp(0, limits.max_sync_retry_count),
+        max_inflight_mb: settings
+            .max_inflight_mb
+            .clamp(4, limits.max_sync_inflight_mb),
+        auto_fix_moved_feeds: settings.auto_fix_moved_feeds,
+        max_pages_per_sync: settings
+            .max_pages_per_sync
+            .clamp(1, MAX_SYNC_PAGES_PER_SYNC),
+        failure_disable_threshold: settings
+            .failure_disable_threshold
+            .min(MAX_FAILURE_DISABLE_THRESHOLD),
+        per_host_concurrency: settings.per_host_concurrency.clamp(1, max_concurrency),
+        per_host_delay_ms: settings.per_host_delay_ms.min(MAX_SYNC_PER_HOST_DELAY_MS),
     }
+}
 
-    let mut updated = 0_usize;
-    while let Some(result) = join_set.join_next().await {
-        if let Ok(Ok(true)) = result {
-            updated += 1;
-        }
-    }
+fn sync_http(settings: &SyncSettings) -> SyncHttp {
+    SyncHttp::new(
+        settings.max_concurrency,
+        settings.per_host_concurrency,
+        Duration::from_millis(settings.per_host_delay_ms),
+    )
+}
 
-    Ok(updated)
+fn sync_budget(settings: &SyncSettings) -> ByteBudget {
+    ByteBudget::new(u64::from(settings.max_inflight_mb) * 1024 * 1024)
 }
 
-async fn load_sync_settings(repository: &SourceRepository) -> Result<SyncSettings, String> {
-    if let Some(raw) = repository
-        .get_setting(SYNC_SETTINGS_KEY)
-        .await
-        .map_err(|error| error.to_string())?
-    {
-        let parsed =
-            serde_json::from_str::<SyncSettings>(&raw).map_err(|error| error.to_string())?;
-        return Ok(normalize_sync_settings(parsed));
+fn normalize_source_settings(
+    settings: SourceSettings,
+    limits: &AdvancedSettings,
+) -> SourceSettings {
+    SourceSettings {
+        source_id: settings.source_id,
+        sync_interval_secs: settings.sync_interval_secs.map(|secs| {
+            secs.clamp(
+                limits.min_sync_interval_secs as i64,
+                MAX_SOURCE_SYNC_INTERVAL_SECS,
+            )
+        }),
+        timeout_secs: settings.timeout_secs.map(|secs| {
+            secs.clamp(
+                limits.min_sync_timeout_secs as i64,
+                limits.max_sync_timeout_secs as i64,
+            )
+        }),
+        retry_count: settings
+            .retry_count
+            .map(|count| count.clamp(0, limits.max_sync_retry_count as i64)),
+        translate_titles: settings.translate_titles,
+        open_externally: settings.open_externally,
+        first_sync_max_age_days: settings
+            .first_sync_max_age_days
+            .map(|days| days.clamp(0, MAX_FIRST_SYNC_MAX_AGE_DAYS)),
     }
-    Ok(SyncSettings::default())
 }
 
-fn normalize_sync_settings(settings: SyncSettings) -> SyncSettings {
+fn apply_source_overrides(settings: &SyncSettings, overrides: &SourceSettings) -> SyncSettings {
     SyncSettings {
-        interval_secs: settings.interval_secs.clamp(60, 3600),
-        max_concurrency: settings.max_concurrency.clamp(1, 16),
-        batch_limit: settings.batch_limit.clamp(1, 200),
-        timeout_secs: settings.timeout_secs.clamp(5, 60),
-        retry_count: settings.retry_count.clamp(0, 4),
+        timeout_secs: overrides
+            .timeout_secs
+            .map_or(settings.timeout_secs, |secs| secs.max(1) as u64),
+        retry_count: overrides
+            .retry_count
+            .map_or(settings.retry_count, |count| count.max(0) as u32),
+        ..settings.clone()
+    }
+}
+
+fn normalize_advanced_settings(settings: AdvancedSettings) -> AdvancedSettings {
+    let min_sync_interval_secs = settings
+        .min_sync_interval_secs
+        .max(ABSOLUTE_MIN_SYNC_INTERVAL_SECS);
+    let min_sync_timeout_secs = settings.min_sync_timeout_secs.clamp(
+        ABSOLUTE_MIN_SYNC_TIMEOUT_SECS,
+        ABSOLUTE_MAX_SYNC_TIMEOUT_SECS,
+    );
+    AdvancedSettings {
+        min_sync_interval_secs,
+        max_sync_interval_secs: settings.max_sync_interval_secs.max(min_sync_interval_secs),
+        max_sync_concurrency: settings
+            .max_sync_concurrency
+            .clamp(1, ABSOLUTE_MAX_SYNC_CONCURRENCY),
+        max_sync_batch_limit: settings
+            .max_sync_batch_limit
+            .clamp(1, ABSOLUTE_MAX_SYNC_BATCH_LIMIT),
+        min_sync_timeout_secs,
+        max_sync_timeout_secs: settings
+            .max_sync_timeout_secs
+            .clamp(min_sync_timeout_secs, ABSOLUTE_MAX_SYNC_TIMEOUT_SECS),
+        max_sync_retry_count: settings
+            .max_sync_retry_count
+            .min(ABSOLUTE_MAX_SYNC_RETRY_COUNT),
+        max_sync_inflight_mb: settings
+            .max_sync_inflight_mb
+            .clamp(4, ABSOLUTE_MAX_SYNC_INFLIGHT_MB),
     }
 }
 
 async fn resolve_llm_config(
     provided: Option<LlmConfig>,
+    task: Option<LlmTask>,
     repository: &SourceRepository,
 ) -> Result<LlmConfig, String> {
     if let Some(config) = provided {
         validate_config(&config).map_err(|error| error.to_string())?;
         return Ok(config);
     }
-    let config = get_saved_or_env_llm_config(repository)
+    let config = get_saved_or_env_llm_config(repository, task)
         .await?
         .ok_or_else(|| "llm config is missing".to_string())?;
     validate_config(&config).map_err(|error| error.to_string())?;
@@ -766,27 +5403,39 @@ async fn resolve_llm_config(
 
 async fn get_saved_or_env_llm_config(
     repository: &SourceRepository,
+    task: Option<LlmTask>,
 ) -> Result<Option<LlmConfig>, String> {
-    if let Some(raw) = repository
-        .get_setting(LLM_CONFIG_KEY)
+    if let Some(profile) = repository
+        .resolve_llm_profile(task)
         .await
         .map_err(|error| error.to_string())?
     {
-        let parsed = serde_json::from_str::<LlmConfig>(&raw).map_err(|error| error.to_string())?;
-        return Ok(Some(parsed));
+        return Ok(Some(profile.config));
     }
 
+    let provider = LlmProvider::parse(&std::env::var("RSSR_LLM_PROVIDER").unwrap_or_default());
     let base_url = std::env::var("RSSR_LLM_BASE_URL").unwrap_or_default();
     let api_key = std::env::var("RSSR_LLM_API_KEY").unwrap_or_default();
     let model = std::env::var("RSSR_LLM_MODEL").unwrap_or_default();
-    if base_url.trim().is_empty() || api_key.trim().is_empty() || model.trim().is_empty() {
+    if base_url.trim().is_empty()
+        || (provider.requires_api_key() && api_key.trim().is_empty())
+        || model.trim().is_empty()
+    {
         return Ok(None);
     }
     Ok(Some(LlmConfig {
+        provider,
         base_url,
         api_key,
         model,
         timeout_secs: 30,
+        max_input_lines: DEFAULT_MAX_INPUT_LINES,
+        max_input_chars: DEFAULT_MAX_INPUT_CHARS,
+        text_wrap_width: DEFAULT_TEXT_WRAP_WIDTH,
+        generation: TaskGenerationParams::default(),
+        endpoint_path: None,
+        extra_headers: BTreeMap::new(),
+        query_params: BTreeMap::new(),
     }))
 }
 
@@ -804,17 +5453,17 @@ fn fallback_entry_text(entry: &EntryRecord) -> String {
     blocks.join("\n\n")
 }
 
-fn build_summary_input(entry: &EntryRecord, article_text: &str) -> String {
-    let body = article_text.chars().take(12000).collect::<String>();
+fn build_summary_input(entry: &EntryRecord, article_text: &str, max_chars: usize) -> String {
+    let body = article_text.chars().take(max_chars).collect::<String>();
     format!(
         "Title: {}\nLink: {}\n\nArticle Text:\n{}",
         entry.title, entry.link, body
     )
 }
 
-async fn fetch_webpage_text_for_summary(link: &str, timeout_secs: u64) -> Result<String, String> {
+async fn fetch_webpage_text_for_summary(link: &str, config: &LlmConfig) -> Result<String, String> {
     let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(timeout_secs.max(6)))
+        .timeout(Duration::from_secs(config.timeout_secs.max(6)))
         .build()
         .map_err(|error| error.to_string())?;
     let response = client
@@ -829,12 +5478,12 @@ async fn fetch_webpage_text_for_summary(link: &str, timeout_secs: u64) -> Result
         ));
     }
     let html = response.text().await.map_err(|error| error.to_string())?;
-    let text = html2text::from_read(html.as_bytes(), 120);
+    let text = html2text::from_read(html.as_bytes(), config.text_wrap_width);
     let normalized = text
         .lines()
         .map(str::trim)
         .filter(|line| !line.is_empty())
-        .take(1200)
+        .take(config.max_input_lines)
         .collect::<Vec<_>>()
         .join("\n");
     if normalized.is_empty() {
@@ -843,116 +5492,415 @@ async fn fetch_webpage_text_for_summary(link: &str, timeout_secs: u64) -> Result
     Ok(normalized)
 }
 
-fn hash_llm_input(task_type: &str, model: &str, input: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(task_type.as_bytes());
-    hasher.update(b"::");
-    hasher.update(model.as_bytes());
-    hasher.update(b"::");
-    hasher.update(input.as_bytes());
-    let bytes = hasher.finalize();
-    format!("{bytes:x}")
+async fn lookup_llm_cache(
+    repository: &SourceRepository,
+    task_type: &str,
+    model: &str,
+    input: &str,
+) -> Result<(String, Option<String>), String> {
+    let hash = llm_cache_key(task_type, input);
+    if let Some(cached) = repository
+        .get_llm_cache(task_type, model, &hash)
+        .await
+        .map_err(|error| error.to_string())?
+    {
+        return Ok((hash, Some(cached)));
+    }
+    let legacy = legacy_llm_cache_key(task_type, model, input);
+    let rekeyed = repository
+        .rekey_legacy_llm_cache(task_type, model, &legacy, &hash)
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok((hash, rekeyed))
 }
 
-fn build_database_url(app_handle: &tauri::AppHandle) -> Result<String, std::io::Error> {
+fn build_app_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, std::io::Error> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
         .map_err(|error| std::io::Error::other(error.to_string()))?;
     std::fs::create_dir_all(&app_data_dir)?;
-    let database_path = app_data_dir.join("rssr.db");
-    Ok(to_sqlite_url(database_path))
+    Ok(app_data_dir)
+}
+
+fn build_database_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, std::io::Error> {
+    Ok(build_app_data_dir(app_handle)?.join("rssr.db"))
 }
 
 fn to_sqlite_url(path: PathBuf) -> String {
     format!("sqlite://{}?mode=rwc", path.to_string_lossy())
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DatabaseStartup {
+    status: &'static str,
+    detail: Option<String>,
+}
+
+impl DatabaseStartup {
+    fn new(status: &'static str, detail: Option<String>) -> Self {
+        Self { status, detail }
+    }
+}
+
+async fn open_repository(
+    database_path: &Path,
+) -> Result<(SourceRepository, DatabaseStartup), StorageError> {
+    let database_url = to_sqlite_url(database_path.to_path_buf());
+    let data_dir = database_path.parent().unwrap_or(Path::new("."));
+    let error = match SourceRepository::connect(&database_url).await {
+        Ok(repository) => {
+            if let Ok(settings) = load_backup_settings(&repository).await {
+                let _ = remember_backup_dir(data_dir, settings.directory.as_deref());
+            }
+            return Ok((repository, DatabaseStartup::new("ok", None)));
+        }
+        Err(error) => error,
+    };
+    let mut detail = error.to_string();
+    if error.fault() == Some(DatabaseFault::Corrupt) {
+        let mut found = false;
+        for directory in recovery_backup_dirs(data_dir) {
+            match recover_from_backup(database_path, &directory).await {
+                Ok(Some((repository, restored))) => {
+                    return Ok((
+                        repository,
+                        DatabaseStartup::new("recovered", Some(restored)),
+                    ))
+                }
+                Ok(None) => {}
+                Err(recovery_error) => {
+                    detail = format!("{detail}; backup recovery failed: {recovery_error}");
+                    found = true;
+                    break;
+                }
+            }
+        }
+        if !found {
+            detail = format!("{detail}; no auto-backup was found");
+        }
+    }
+    if let Ok(repository) = SourceRepository::connect_read_only(&database_url).await {
+        if repository.list_sources().await.is_ok() {
+            return Ok((repository, DatabaseStartup::new("read_only", Some(detail))));
+        }
+    }
+    let repository = SourceRepository::connect_degraded().await?;
+    Ok((repository, DatabaseStartup::new("degraded", Some(detail))))
+}
+
+async fn recover_from_backup(
+    database_path: &Path,
+    directory: &Path,
+) -> Result<Option<(SourceRepository, String)>, String> {
+    let Some(opml_path) = latest_backup_opml(directory).map_err(|error| error.to_string())? else {
+        return Ok(None);
+    };
+    let opml = std::fs::read_to_string(&opml_path).map_err(|error| error.to_string())?;
+    let sources = core::importer::parse_opml(&opml).map_err(|error| error.to_string())?;
+    let quarantined = quarantine_database(database_path).map_err(|error| error.to_string())?;
+    let repository = SourceRepository::connect(&to_sqlite_url(database_path.to_path_buf()))
+        .await
+        .map_err(|error| error.to_string())?;
+    let settings = BackupSettings {
+        enabled: true,
+        directory: Some(directory.to_string_lossy().to_string()),
+        ..BackupSettings::default()
+    };
+    let serialized = serde_json::to_string(&settings).map_err(|error| error.to_string())?;
+    repository
+        .set_setting(BACKUP_SETTINGS_KEY, &serialized)
+        .await
+        .map_err(|error| error.to_string())?;
+    let imported = import_candidates(
+        &repository,
+        sources,
+        ImportOptions {
+            include_possible_duplicates: true,
+            mark_existing_read: true,
+            ..ImportOptions::default()
+        },
+    )
+    .await?;
+    Ok(Some((
+        repository,
+        format!(
+            "restored {} sources from {}; damaged database moved to {}",
+            imported.imported_count,
+            opml_path.display(),
+            quarantined.display()
+        ),
+    )))
+}
+
+fn quarantine_database(database_path: &Path) -> Result<PathBuf, std::io::Error> {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let quarantined = PathBuf::from(format!("{}.corrupt-{stamp}", database_path.display()));
+    std::fs::rename(database_path, &quarantined)?;
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{suffix}", database_path.display()));
+        if sidecar.exists() {
+            std::fs::rename(
+                &sidecar,
+                PathBuf::from(format!("{}{suffix}", quarantined.display())),
+            )?;
+        }
+    }
+    Ok(quarantined)
+}
+
+fn spawn_background_tasks(
+    app: tauri::AppHandle,
+    repository: SourceRepository,
+    sync_runtime: Arc<SyncRuntime>,
+    network: Arc<NetworkRuntime>,
+) {
+    let title_translate_network = network.clone();
+    let background_repository = repository.clone();
+    let title_translate_repository = repository.clone();
+    let backfill_repository = repository.clone();
+    let backup_repository = repository;
+    let background_runtime = sync_runtime;
+    tauri::async_runtime::spawn(async move {
+        let _ = backfill_repository.backfill_sanitized_content().await;
+    });
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let started = if network.is_offline() || background_runtime.is_paused() {
+                None
+            } else {
+                background_runtime.try_begin()
+            };
+            if let Some(cancel) = started {
+                let result =
+                    sync_active_sources_internal(&background_repository, &network, &cancel, true)
+                        .await;
+                finish_active_sync(
+                    &app,
+                    &background_repository,
+                    &background_runtime,
+                    &network,
+                    result,
+                )
+                .await;
+            }
+
+            let settings = load_sync_settings(&background_repository)
+                .await
+                .unwrap_or_default();
+            tokio::time::sleep(Duration::from_secs(settings.interval_secs)).await;
+        }
+    });
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if !title_translate_network.is_offline() {
+                let _ = translate_titles_within_budget(&title_translate_repository).await;
+            }
+            tokio::time::sleep(Duration::from_secs(DEFAULT_TITLE_TRANSLATE_INTERVAL_SECS)).await;
+        }
+    });
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let _ = run_scheduled_backup(&backup_repository).await;
+            tokio::time::sleep(Duration::from_secs(BACKUP_CHECK_INTERVAL_SECS)).await;
+        }
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             let _ = dotenvy::from_filename(".env.local");
-            let database_url = build_database_url(app.handle())?;
-            let repository =
-                tauri::async_runtime::block_on(SourceRepository::connect(&database_url))
+            let database_path = build_database_path(app.handle())?;
+            let (repository, database) =
+                tauri::async_runtime::block_on(open_repository(&database_path))
                     .map_err(|error| std::io::Error::other(error.to_string()))?;
-            let background_repository = repository.clone();
-            let title_translate_repository = repository.clone();
             let sync_runtime = Arc::new(SyncRuntime::default());
-            let background_runtime = sync_runtime.clone();
-            tauri::async_runtime::spawn(async move {
-                loop {
-                    if !background_runtime.running.swap(true, Ordering::SeqCst) {
-                        let result = sync_active_sources_internal(&background_repository).await;
-                        match result {
-                            Ok(report) => {
-                                {
-                                    let mut guard = background_runtime.last_report.write().await;
-                                    *guard = Some(report);
-                                }
-                                {
-                                    let mut guard = background_runtime.last_error.write().await;
-                                    *guard = None;
-                                }
-                                let _ = translate_titles_background(
-                                    &background_repository,
-                                    DEFAULT_TITLE_TRANSLATE_BATCH_SIZE,
-                                )
-                                .await;
-                            }
-                            Err(error) => {
-                                let mut guard = background_runtime.last_error.write().await;
-                                *guard = Some(error);
-                            }
-                        }
-                        background_runtime.running.store(false, Ordering::SeqCst);
-                    }
-
-                    let settings = load_sync_settings(&background_repository)
-                        .await
-                        .unwrap_or_default();
-                    tokio::time::sleep(Duration::from_secs(settings.interval_secs)).await;
+            let network = Arc::new(NetworkRuntime::default());
+            let offline = tauri::async_runtime::block_on(repository.get_setting(OFFLINE_MODE_KEY))
+                .ok()
+                .flatten()
+                .is_some_and(|value| value == "true");
+            network.offline.store(offline, Ordering::SeqCst);
+            if let Some(report) = tauri::async_runtime::block_on(load_last_sync_report(&repository))
+            {
+                if let Ok(mut guard) = sync_runtime.last_report.try_write() {
+                    *guard = Some(report);
                 }
-            });
+            }
+            if !repository.is_read_only() {
+                spawn_background_tasks(
+                    app.handle().clone(),
+                    repository.clone(),
+                    sync_runtime.clone(),
+                    network.clone(),
+                );
+            }
+            let local_api = Arc::new(tokio::sync::Mutex::new(None));
+            let api_repository = repository.clone();
+            let api_slot = local_api.clone();
             tauri::async_runtime::spawn(async move {
-                loop {
-                    let _ = translate_titles_background(
-                        &title_translate_repository,
-                        DEFAULT_TITLE_TRANSLATE_BATCH_SIZE,
-                    )
-                    .await;
-                    tokio::time::sleep(Duration::from_secs(DEFAULT_TITLE_TRANSLATE_INTERVAL_SECS))
-                        .await;
+                if let Ok(settings) = load_local_api_settings(&api_repository).await {
+                    let _ = restart_local_api(&api_repository, &settings, &api_slot).await;
                 }
             });
             app.manage(SharedState {
                 services: AppServices::default(),
                 source_repository: repository,
+                database,
                 sync_runtime,
+                network,
+                local_api,
+                queries: QueryRuntime::default(),
+                triage: TriageSessions::default(),
+                html_imports: HtmlImportCache::default(),
             });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             app_health,
+            list_actions,
+            get_network_status,
+            set_offline_mode,
             list_sources,
+            search_sources,
+            get_source_details,
+            get_source_sync_history,
+            list_failing_sources,
+            list_problem_sources,
             upsert_source,
             delete_source,
             set_sources_active,
+            reset_source_failures,
+            reactivate_source,
+            get_stale_sources,
+            unsubscribe_sources,
+            list_orphaned_entries,
+            rehome_orphaned_entries,
+            discover_feeds,
+            probe_feed,
+            rediscover_source_feed,
+            suggest_source_metadata,
+            accept_feed_replacement,
+            dismiss_feed_replacement,
+            set_source_color,
+            set_source_custom_icon,
+            recommend_sources,
+            run_source_discovery,
+            get_discovery_suggestions,
             preview_import,
             import_sources,
+            export_opml,
+            get_snapshot_target,
+            save_snapshot_target,
+            push_snapshot,
+            pull_snapshot,
+            list_starter_packs,
+            preview_starter_pack,
+            import_starter_pack,
+            get_onboarding_state,
+            onboarding_import,
+            run_onboarding_sync,
+            complete_onboarding,
             list_entries,
+            list_view_entries,
+            get_entry,
+            get_search_pinyin,
+            set_search_pinyin,
+            cancel_query,
+            start_triage_session,
+            triage_next,
+            triage_action,
+            end_triage_session,
+            get_entry_date_histogram,
+            get_trending_terms,
+            get_catchup_selection,
+            skip_entries,
+            reset_skipped_entries,
             mark_entry_read,
+            mark_entries_read_up_to,
+            report_entries_scrolled_past,
+            mark_all_read,
+            mark_category_read,
+            mark_read_older_than,
+            list_recently_read,
+            undo_last_read_operation,
+            get_flagged_entry_protection,
+            set_flagged_entry_protection,
+            fetch_full_content,
+            open_entry_external,
+            set_entry_starred,
+            list_starred_entries,
+            get_counts,
+            get_source_notifications,
+            set_source_notifications,
+            list_notification_rules,
+            create_notification_rule,
+            update_notification_rule,
+            delete_notification_rule,
+            take_pending_notifications,
+            export_starred_as_feed,
+            render_entry,
+            archive_old_entries,
+            get_storage_breakdown,
+            set_source_retention,
+            get_source_settings,
+            list_source_settings,
+            set_source_settings,
+            get_first_sync_max_age_days,
+            set_first_sync_max_age_days,
+            compact_storage,
+            get_sanitizer_allowlist,
+            save_sanitizer_allowlist,
+            sanitize_stored_entries,
             sync_source,
+            backfill_source,
             sync_active_sources,
             get_sync_runtime_status,
+            cancel_sync,
+            pause_background_sync,
+            resume_background_sync,
             get_sync_settings,
             save_sync_settings,
+            get_read_policy,
+            save_read_policy,
+            get_llm_budget,
+            save_llm_budget,
+            get_llm_backlog,
+            translate_source_titles,
+            get_advanced_settings,
+            save_advanced_settings,
+            get_backup_settings,
+            save_backup_settings,
+            run_backup_now,
+            get_automation_hooks,
+            save_automation_hooks,
+            test_automation_hook,
+            get_local_api_settings,
+            save_local_api_settings,
+            get_local_api_address,
             get_llm_config,
             save_llm_config,
+            list_llm_profiles,
+            save_llm_profile,
+            activate_llm_profile,
+            delete_llm_profile,
+            list_glossary_terms,
+            save_glossary_term,
+            delete_glossary_term,
+            set_task_profile,
             test_llm_connection,
-            summarize_entry
+            summarize_entry,
+            summarize_entry_stream,
+            get_migration_status,
+            #[cfg(feature = "demo-data")]
+            seed_demo_data,
+            export_diagnostics_bundle,
+            export_llm_cache,
+            import_llm_cache
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -960,15 +5908,119 @@ pub fn run() {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use crate::core::actions::list_actions;
+    use crate::core::feed::types::FeedFormat;
     use crate::core::storage::models::EntryRecord;
 
+    use super::apply_source_overrides;
     use super::build_summary_input;
     use super::fallback_entry_text;
-    use super::hash_llm_input;
+    use super::html_link_sources;
+    use super::import_candidates;
+    use super::keep_snapshot_password;
+    use super::load_last_sync_report;
+    use super::load_onboarding_record;
+    use super::normalize_advanced_settings;
+    use super::normalize_backup_settings;
+    use super::normalize_read_policy;
+    use super::normalize_source_color;
+    use super::normalize_source_settings;
     use super::normalize_sync_settings;
+    use super::open_repository;
     use super::parse_import_sources;
+    use super::parse_import_sources_blocking;
+    use super::prioritize_first_sync;
+    use super::redact_snapshot_target;
+    use super::select_export_sources;
+    use super::sync_active_sources_internal;
+    use super::sync_onboarding_sources;
+    use super::AdvancedSettings;
+    use super::BackupSettings;
+    use super::FeedCandidate;
+    use super::ImportOptions;
     use super::ImportRequest;
+    use super::ImportSource;
+    use super::NetworkRuntime;
+    use super::NewSource;
+    use super::OpmlExportRequest;
+    use super::QueryRuntime;
+    use super::QueuedRequest;
+    use super::ReadMarkMode;
+    use super::ReadPolicySettings;
+    use super::RemoteTarget;
+    use super::SourceRecord;
+    use super::SourceRepository;
+    use super::SourceSettings;
+    use super::SyncBatchResponse;
+    use super::SyncFailure;
+    use super::SyncFailureClass;
+    use super::SyncRuntime;
     use super::SyncSettings;
+    use super::SyncSourceResponse;
+    use super::DEFAULT_READ_AFTER_DAYS;
+    use super::MAX_READ_AFTER_DAYS;
+    use super::MAX_SUMMARY_ATTEMPTS;
+
+    #[tokio::test]
+    async fn corrupt_database_recovers_from_backup_or_boots_degraded() {
+        let dir = tempfile::tempdir().expect("tempdir should exist");
+        let backups = dir.path().join("elsewhere");
+        let opml = crate::core::importer::export_opml(
+            "rssr backup",
+            &[crate::core::importer::ImportSource {
+                title: "Backed Up".to_string(),
+                feed_url: "https://backup.example.com/feed.xml".to_string(),
+                site_url: None,
+                category: Some("Tech".to_string()),
+                rssr: None,
+            }],
+        );
+        crate::core::backup::write_backup_snapshot(
+            &backups,
+            &opml,
+            &serde_json::json!({}),
+            chrono::Utc::now(),
+        )
+        .expect("snapshot should be written");
+        let database_path = dir.path().join("rssr.db");
+        std::fs::write(&database_path, vec![0x5a_u8; 8192]).expect("garbage file");
+
+        let (degraded, startup) = open_repository(&database_path)
+            .await
+            .expect("startup must not fail");
+        assert_eq!(startup.status, "degraded");
+        assert!(degraded.is_read_only());
+        assert!(startup
+            .detail
+            .is_some_and(|detail| detail.contains("no auto-backup")));
+
+        crate::core::backup::remember_backup_dir(dir.path(), backups.to_str())
+            .expect("remember backup dir");
+        let (repository, startup) = open_repository(&database_path)
+            .await
+            .expect("startup must not fail");
+        let detail = startup.detail.expect("recovery detail");
+        assert_eq!(startup.status, "recovered");
+        let sources = repository.list_sources().await.expect("list sources");
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].feed_url, "https://backup.example.com/feed.xml");
+        assert!(detail.starts_with("restored 1 sources"));
+        assert!(std::fs::read_dir(dir.path())
+            .expect("dir should be readable")
+            .filter_map(Result::ok)
+            .any(|entry| entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("rssr.db.corrupt-")));
+        assert!(repository
+            .get_setting("backup_settings")
+            .await
+            .expect("setting")
+            .is_some());
+    }
 
     #[test]
     fn import_format_parser_accepts_known_aliases() {
@@ -977,33 +6029,330 @@ mod tests {
             content: "https://example.com/feed.xml".to_string(),
             default_category: None,
             is_active: Some(true),
+            selected_feed_urls: None,
+            excluded_feed_urls: None,
+            category_mapping: None,
+            mark_existing_read: false,
+            all_feeds_per_site: false,
         };
         let parsed = parse_import_sources(&payload).expect("url alias should parse");
         assert_eq!(parsed.len(), 1);
     }
 
     #[test]
-    fn llm_input_hash_is_deterministic() {
-        let a = hash_llm_input("summary", "deepseek-chat", "hello");
-        let b = hash_llm_input("summary", "deepseek-chat", "hello");
-        assert_eq!(a, b);
+    fn html_link_import_keeps_one_feed_per_site_by_default() {
+        let candidate = |url: &str, discovered_by: &str| FeedCandidate {
+            url: url.to_string(),
+            title: url.to_string(),
+            format: FeedFormat::XmlFeed,
+            discovered_by: discovered_by.to_string(),
+        };
+        let discovered = vec![
+            (
+                "https://blog.example.com".to_string(),
+                vec![
+                    candidate("https://blog.example.com/feed.xml", "alternate"),
+                    candidate("https://blog.example.com/comments.xml", "alternate"),
+                ],
+            ),
+            (
+                "https://news.example.com/rss".to_string(),
+                vec![candidate("https://news.example.com/rss", "direct")],
+            ),
+        ];
+
+        let feeds = |all_feeds_per_site| {
+            html_link_sources(discovered.clone(), all_feeds_per_site)
+                .into_iter()
+                .map(|source| source.feed_url)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            feeds(false),
+            vec![
+                "https://blog.example.com/feed.xml",
+                "https://news.example.com/rss"
+            ]
+        );
+        assert_eq!(feeds(true).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn import_reports_skipped_possible_duplicates() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        repository
+            .upsert_source(&NewSource {
+                title: "Blog".to_string(),
+                site_url: Some("https://blog.example.com/".to_string()),
+                feed_url: "https://blog.example.com/feed.xml".to_string(),
+                category: None,
+                is_active: true,
+            })
+            .await
+            .expect("create source should succeed");
+        let candidate = |title: &str, feed_url: &str, site_url: Option<&str>| ImportSource {
+            title: title.to_string(),
+            feed_url: feed_url.to_string(),
+            site_url: site_url.map(ToString::to_string),
+            category: None,
+            rssr: None,
+        };
+
+        let imported = import_candidates(
+            &repository,
+            vec![
+                candidate(
+                    "Blog via Feedburner",
+                    "https://feeds.feedburner.com/blog",
+                    Some("https://blog.example.com/"),
+                ),
+                candidate("Fresh", "https://fresh.example.net/rss", None),
+            ],
+            ImportOptions::default(),
+        )
+        .await
+        .expect("import should succeed");
+        assert_eq!(imported.imported_count, 1);
+        assert_eq!(imported.possible_duplicate_count, 1);
+        assert_eq!(
+            imported.possible_duplicates[0].feed_url,
+            "https://feeds.feedburner.com/blog"
+        );
+    }
+
+    #[tokio::test]
+    async fn large_opml_import_parses_off_the_async_runtime() {
+        let payload = ImportRequest {
+            format: "opml".to_string(),
+            content: include_str!("../../fixtures/import-samples/hn-popular-blogs-2025.opml")
+                .to_string(),
+            default_category: None,
+            is_active: None,
+            selected_feed_urls: None,
+            excluded_feed_urls: None,
+            category_mapping: None,
+            mark_existing_read: false,
+            all_feeds_per_site: false,
+        };
+        let parsed = parse_import_sources_blocking(&payload)
+            .await
+            .expect("opml should parse on the blocking pool");
+        assert!(parsed.len() > 50);
+    }
+
+    #[test]
+    fn snapshot_target_password_stays_on_the_backend() {
+        let stored = RemoteTarget {
+            url: "https://dav.example.com/rssr".to_string(),
+            username: Some("reader".to_string()),
+            password: Some("secret".to_string()),
+            ..RemoteTarget::default()
+        };
+        let redacted = redact_snapshot_target(stored.clone());
+        assert_eq!(redacted.password, None);
+        assert_eq!(redacted.username.as_deref(), Some("reader"));
+
+        let kept = keep_snapshot_password(redacted.clone(), Some(&stored));
+        assert_eq!(kept.password.as_deref(), Some("secret"));
+        let moved = RemoteTarget {
+            url: "https://other.example.com/rssr".to_string(),
+            ..redacted
+        };
+        assert_eq!(keep_snapshot_password(moved, Some(&stored)).password, None);
+    }
+
+    #[test]
+    fn source_color_is_normalized_to_lowercase_hex() {
+        assert_eq!(
+            normalize_source_color(Some(" FF6600 ")),
+            Ok(Some("#ff6600".to_string()))
+        );
+        assert_eq!(
+            normalize_source_color(Some("#AbC")),
+            Ok(Some("#abc".to_string()))
+        );
+        assert_eq!(normalize_source_color(Some("  ")), Ok(None));
+        assert!(normalize_source_color(Some("orange")).is_err());
     }
 
     #[test]
-    fn sync_settings_are_normalized_to_safe_bounds() {
-        let normalized = normalize_sync_settings(SyncSettings {
-            interval_secs: 1,
-            max_concurrency: 100,
-            batch_limit: 9999,
-            timeout_secs: 1,
-            retry_count: 99,
+    fn export_selection_filters_by_category_and_ids() {
+        let row = |id: i64, category: Option<&str>| SourceRecord {
+            id,
+            title: format!("source {id}"),
+            site_url: None,
+            feed_url: format!("https://example.com/{id}.xml"),
+            category: category.map(ToString::to_string),
+            is_active: 0,
+            failure_count: 0,
+            etag: None,
+            last_modified: None,
+            last_synced_at: None,
+            color: Some("#abcdef".to_string()),
+            custom_icon: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+            updated_at: "2026-01-01 00:00:00".to_string(),
+        };
+        let rows = vec![row(1, Some("Tech")), row(2, Some("News")), row(3, None)];
+
+        let by_category = select_export_sources(
+            rows.clone(),
+            &OpmlExportRequest {
+                categories: vec!["tech".to_string()],
+                ..OpmlExportRequest::default()
+            },
+        );
+        let by_ids = select_export_sources(
+            rows,
+            &OpmlExportRequest {
+                source_ids: vec![2, 3],
+                include_rssr_attributes: true,
+                ..OpmlExportRequest::default()
+            },
+        );
+
+        assert_eq!(by_category.len(), 1);
+        assert_eq!(by_category[0].feed_url, "https://example.com/1.xml");
+        assert!(by_category[0].rssr.is_none());
+        assert_eq!(by_ids.len(), 2);
+        let rssr = by_ids[0].rssr.as_ref().expect("rssr attributes included");
+        assert_eq!(rssr.is_active, Some(false));
+        assert_eq!(rssr.color.as_deref(), Some("#abcdef"));
+    }
+
+    #[test]
+    fn backup_settings_are_normalized() {
+        let normalized = normalize_backup_settings(BackupSettings {
+            enabled: true,
+            directory: Some("   ".to_string()),
+            interval_hours: 0,
+            retention_count: 5000,
         });
 
+        assert!(normalized.directory.is_none());
+        assert_eq!(normalized.interval_hours, 1);
+        assert_eq!(normalized.retention_count, 365);
+    }
+
+    #[test]
+    fn sync_settings_are_normalized_to_safe_bounds() {
+        let normalized = normalize_sync_settings(
+            SyncSettings {
+                interval_secs: 1,
+                max_concurrency: 100,
+                batch_limit: 9999,
+                timeout_secs: 1,
+                retry_count: 99,
+                max_inflight_mb: 1,
+                auto_fix_moved_feeds: true,
+                max_pages_per_sync: 500,
+                failure_disable_threshold: 5000,
+                per_host_concurrency: 50,
+                per_host_delay_ms: 60_000,
+            },
+            &AdvancedSettings::default(),
+        );
+
         assert_eq!(normalized.interval_secs, 60);
         assert_eq!(normalized.max_concurrency, 16);
+        assert_eq!(normalized.max_pages_per_sync, 50);
         assert_eq!(normalized.batch_limit, 200);
         assert_eq!(normalized.timeout_secs, 5);
         assert_eq!(normalized.retry_count, 4);
+        assert_eq!(normalized.max_inflight_mb, 4);
+        assert_eq!(normalized.failure_disable_threshold, 1000);
+        assert_eq!(normalized.per_host_concurrency, 16);
+        assert_eq!(normalized.per_host_delay_ms, 10_000);
+        assert!(normalized.auto_fix_moved_feeds);
+    }
+
+    #[test]
+    fn read_policy_defaults_to_on_open_and_clamps_days() {
+        let parsed = serde_json::from_str::<ReadPolicySettings>(r#"{"mode":"after_days"}"#)
+            .expect("policy should parse");
+        assert_eq!(parsed.mode, ReadMarkMode::AfterDays);
+        assert_eq!(parsed.after_days, DEFAULT_READ_AFTER_DAYS);
+        assert_eq!(ReadPolicySettings::default().mode, ReadMarkMode::OnOpen);
+
+        let normalized = normalize_read_policy(ReadPolicySettings {
+            mode: ReadMarkMode::AfterDays,
+            after_days: 0,
+        });
+        assert_eq!(normalized.after_days, 1);
+        assert_eq!(
+            normalize_read_policy(ReadPolicySettings {
+                mode: ReadMarkMode::Never,
+                after_days: 9999,
+            })
+            .after_days,
+            MAX_READ_AFTER_DAYS
+        );
+    }
+
+    #[test]
+    fn source_overrides_are_clamped_and_applied() {
+        let overrides = normalize_source_settings(
+            SourceSettings {
+                source_id: 7,
+                sync_interval_secs: Some(5),
+                timeout_secs: Some(999),
+                retry_count: Some(-3),
+                translate_titles: Some(false),
+                open_externally: None,
+                first_sync_max_age_days: Some(-1),
+            },
+            &AdvancedSettings::default(),
+        );
+        assert_eq!(overrides.sync_interval_secs, Some(60));
+        assert_eq!(overrides.timeout_secs, Some(60));
+        assert_eq!(overrides.retry_count, Some(0));
+        assert_eq!(overrides.first_sync_max_age_days, Some(0));
+
+        let effective = apply_source_overrides(&SyncSettings::default(), &overrides);
+        assert_eq!(effective.timeout_secs, 60);
+        assert_eq!(effective.retry_count, 0);
+        assert_eq!(
+            effective.interval_secs,
+            SyncSettings::default().interval_secs
+        );
+    }
+
+    #[test]
+    fn advanced_settings_widen_sync_bounds_above_absolute_floors() {
+        let limits = normalize_advanced_settings(AdvancedSettings {
+            min_sync_interval_secs: 0,
+            max_sync_concurrency: 500,
+            max_sync_batch_limit: 1000,
+            min_sync_timeout_secs: 0,
+            ..AdvancedSettings::default()
+        });
+        let normalized = normalize_sync_settings(
+            SyncSettings {
+                interval_secs: 1,
+                max_concurrency: 100,
+                batch_limit: 1000,
+                timeout_secs: 0,
+                ..SyncSettings::default()
+            },
+            &limits,
+        );
+
+        assert_eq!(normalized.interval_secs, 5);
+        assert_eq!(normalized.max_concurrency, 64);
+        assert_eq!(normalized.batch_limit, 1000);
+        assert_eq!(normalized.timeout_secs, 1);
+    }
+
+    #[test]
+    fn sync_settings_fill_missing_fields_from_defaults() {
+        let parsed: SyncSettings =
+            serde_json::from_str(r#"{"interval_secs":900}"#).expect("partial settings parse");
+
+        assert_eq!(parsed.interval_secs, 900);
+        assert_eq!(parsed.max_inflight_mb, 64);
+        assert!(!parsed.auto_fix_moved_feeds);
     }
 
     #[test]
@@ -1022,6 +6371,8 @@ mod tests {
             is_read: 0,
             is_starred: 0,
             created_at: "2026-02-24T00:00:00Z".to_string(),
+            updated_at: None,
+            is_updated: false,
         };
         assert_eq!(fallback_entry_text(&entry), "summary\n\ncontent");
     }
@@ -1042,11 +6393,236 @@ mod tests {
             is_read: 0,
             is_starred: 0,
             created_at: "2026-02-24T00:00:00Z".to_string(),
+            updated_at: None,
+            is_updated: false,
         };
         let huge = "a".repeat(13000);
-        let input = build_summary_input(&entry, &huge);
+        let input = build_summary_input(&entry, &huge, 12000);
         assert!(input.starts_with("Title: Post title"));
         assert!(input.contains("Article Text:"));
         assert!(input.len() < 12200);
+        let small = build_summary_input(&entry, &huge, 500);
+        assert!(small.len() < 700);
+    }
+
+    #[test]
+    fn action_registry_matches_registered_commands() {
+        let source = include_str!("lib.rs");
+        let start = source
+            .find("generate_handler![")
+            .expect("handler list should exist");
+        let end = start
+            + source[start..]
+                .find("])")
+                .expect("handler list should close");
+        let mut registered = source[start + "generate_handler![".len()..end]
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .filter_map(
+                |name| match name.strip_prefix("#[cfg(feature = \"demo-data\")]") {
+                    Some(gated) => cfg!(feature = "demo-data").then(|| gated.trim()),
+                    None => Some(name),
+                },
+            )
+            .collect::<Vec<_>>();
+        let mut listed = list_actions()
+            .iter()
+            .map(|action| action.name)
+            .collect::<Vec<_>>();
+        registered.sort_unstable();
+        listed.sort_unstable();
+        assert_eq!(registered, listed);
+    }
+
+    #[test]
+    fn offline_queue_deduplicates_and_drains_requests() {
+        let network = NetworkRuntime::default();
+        network.enqueue(QueuedRequest::SyncSource { source_id: 3 });
+        network.enqueue(QueuedRequest::SyncSource { source_id: 3 });
+        network.enqueue(QueuedRequest::SyncActiveSources);
+
+        assert_eq!(network.queued().len(), 2);
+        assert_eq!(
+            network.drain(usize::MAX),
+            vec![
+                QueuedRequest::SyncSource { source_id: 3 },
+                QueuedRequest::SyncActiveSources
+            ]
+        );
+        assert!(network.queued().is_empty());
+    }
+
+    #[test]
+    fn offline_queue_carries_summaries_over_the_budget() {
+        let network = NetworkRuntime::default();
+        for entry_id in 1..=3 {
+            network.enqueue(QueuedRequest::SummarizeEntry { entry_id });
+        }
+        network.enqueue(QueuedRequest::SyncActiveSources);
+
+        assert_eq!(
+            network.drain(2),
+            vec![
+                QueuedRequest::SummarizeEntry { entry_id: 1 },
+                QueuedRequest::SummarizeEntry { entry_id: 2 },
+                QueuedRequest::SyncActiveSources
+            ]
+        );
+        assert_eq!(network.queued_summaries(), 1);
+        assert_eq!(network.drain(0), Vec::<QueuedRequest>::new());
+        assert_eq!(network.queued_summaries(), 1);
+    }
+
+    #[test]
+    fn failed_summaries_are_requeued_until_the_retry_cap() {
+        let network = NetworkRuntime::default();
+        for _ in 1..MAX_SUMMARY_ATTEMPTS {
+            assert!(network.settle_summary(7, false));
+            assert_eq!(
+                network.drain(usize::MAX),
+                vec![QueuedRequest::SummarizeEntry { entry_id: 7 }]
+            );
+        }
+        assert!(!network.settle_summary(7, false));
+        assert_eq!(network.queued_summaries(), 0);
+
+        assert!(network.settle_summary(8, false));
+        network.drain(usize::MAX);
+        assert!(!network.settle_summary(8, true));
+        assert!(network.settle_summary(8, false));
+    }
+
+    #[test]
+    fn first_sync_round_robins_across_categories() {
+        let source = |id: i64, category: &str| SourceRecord {
+            id,
+            title: format!("source {id}"),
+            site_url: None,
+            feed_url: format!("https://example.com/{id}.xml"),
+            category: Some(category.to_string()),
+            is_active: 1,
+            failure_count: 0,
+            etag: None,
+            last_modified: None,
+            last_synced_at: None,
+            color: None,
+            custom_icon: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let ordered = prioritize_first_sync(vec![
+            source(1, "rust"),
+            source(2, "rust"),
+            source(3, "rust"),
+            source(4, "design"),
+            source(5, "security"),
+        ]);
+
+        let ids = ordered.iter().map(|source| source.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 4, 5, 2, 3]);
+    }
+
+    #[test]
+    fn sync_batch_report_tracks_outcomes_by_failure_class() {
+        let mut report = SyncBatchResponse::default();
+        report.record(
+            1,
+            "Updated".to_string(),
+            Ok(SyncSourceResponse {
+                source_id: 1,
+                status: "updated".to_string(),
+                upserted_entries: 3,
+                inserted_entries: 2,
+                updated_entries: 1,
+                conflicted_entries: 0,
+            }),
+        );
+        report.record(
+            2,
+            "Broken".to_string(),
+            Err(SyncFailure::new(SyncFailureClass::ParseError, "bad xml")),
+        );
+        report.record(
+            3,
+            "Missing".to_string(),
+            Err(SyncFailure::new(
+                SyncFailureClass::ClientError,
+                "unexpected status code: 404",
+            )),
+        );
+        report.record_aborted();
+
+        assert_eq!(report.synced_sources, 1);
+        assert_eq!(report.failed_sources, 3);
+        assert_eq!(report.total_upserted_entries, 3);
+        assert_eq!(report.total_inserted_entries, 2);
+        assert_eq!(report.failure_counts.parse_error, 1);
+        assert_eq!(report.failure_counts.client_error, 1);
+        assert_eq!(report.failure_counts.other, 1);
+        assert_eq!(report.sources.len(), 3);
+        assert_eq!(report.sources[1].error.as_deref(), Some("bad xml"));
+        assert_eq!(
+            report.sources[2].failure_class,
+            Some(SyncFailureClass::ClientError)
+        );
+    }
+
+    #[test]
+    fn query_runtime_supersedes_and_cancels_by_key() {
+        let runtime = QueryRuntime::default();
+        let first = runtime.begin("reader", std::time::Duration::from_secs(5));
+        let second = runtime.begin("reader", std::time::Duration::from_secs(5));
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+
+        runtime.finish("reader", &first);
+        assert!(runtime.cancel("reader"));
+        assert!(second.is_cancelled());
+        assert!(!runtime.cancel("reader"));
+    }
+
+    #[tokio::test]
+    async fn cancelled_sync_stops_the_batch() {
+        let repository = SourceRepository::connect("sqlite::memory:")
+            .await
+            .expect("connect must succeed");
+        repository
+            .upsert_source(&NewSource {
+                title: "Unreachable".to_string(),
+                site_url: None,
+                feed_url: "http://127.0.0.1:9/feed.xml".to_string(),
+                category: None,
+                is_active: true,
+            })
+            .await
+            .expect("create source should succeed");
+        let runtime = SyncRuntime::default();
+        let network = Arc::new(NetworkRuntime::default());
+        assert!(!runtime.cancel());
+        let cancel = runtime.try_begin().expect("first sync should start");
+        assert!(runtime.try_begin().is_none());
+        assert!(runtime.cancel());
+
+        let report = sync_active_sources_internal(&repository, &network, &cancel, false)
+            .await
+            .expect("cancelled sync should still report");
+        assert!(report.cancelled);
+        assert!(report.sources.is_empty());
+
+        let report = sync_onboarding_sources(&repository, &network, &cancel, |_| {
+            panic!("cancelled onboarding sync should not report progress")
+        })
+        .await
+        .expect("cancelled onboarding sync should still report");
+        assert!(report.cancelled);
+        assert!(load_last_sync_report(&repository)
+            .await
+            .is_some_and(|saved| saved.cancelled));
+        assert!(load_onboarding_record(&repository)
+            .await
+            .expect("onboarding record should load")
+            .first_sync_at
+            .is_none());
     }
 }
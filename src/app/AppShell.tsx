@@ -1,12 +1,31 @@
-import { useMemo, useState } from "react";
+import { useEffect, useMemo, useState } from "react";
 
 import { ReaderPage } from "../pages/ReaderPage";
 import { SettingsPage } from "../pages/SettingsPage";
+import { getAppHealth, isTauriRuntime, type HealthReport } from "../services/tauriApi";
 
 type ShellView = "reader" | "settings";
 
+const DATABASE_STATUS_LABELS: Record<string, string> = {
+  recovered: "数据库已损坏，已从最近的自动备份恢复订阅源",
+  read_only: "数据库当前只读，修改不会被保存",
+  degraded: "数据库无法打开，应用正以只读降级模式运行",
+};
+
 export function AppShell() {
   const [activeView, setActiveView] = useState<ShellView>("reader");
+  const [health, setHealth] = useState<HealthReport>({});
+
+  useEffect(() => {
+    if (!isTauriRuntime()) {
+      return;
+    }
+    void getAppHealth()
+      .then(setHealth)
+      .catch(() => setHealth({}));
+  }, []);
+
+  const databaseNotice = DATABASE_STATUS_LABELS[health.database ?? ""];
 
   const page = useMemo(() => {
     if (activeView === "reader") {
@@ -17,6 +36,12 @@ export function AppShell() {
 
   return (
     <main className="workspace-shell" data-testid="app-shell">
+      {databaseNotice ? (
+        <div className="database-banner" role="alert">
+          <strong>{databaseNotice}</strong>
+          {health.database_detail ? <span>{health.database_detail}</span> : null}
+        </div>
+      ) : null}
       <section className="workspace-content">{page}</section>
     </main>
   );
@@ -1,17 +1,21 @@
 import { FormEvent, useEffect, useState } from "react";
 
 import {
+  activateLlmProfile,
   getLlmConfig,
   getAppHealth,
   getSyncRuntimeStatus,
   getSyncSettings,
   isTauriRuntime,
+  listLlmProfiles,
   saveLlmConfig,
   saveSyncSettings,
   syncActiveSources,
   testLlmConnection,
   type HealthReport,
   type LlmConfig,
+  type LlmProfile,
+  type LlmProvider,
   type SyncRuntimeStatus,
   type SyncSettings,
 } from "../services/tauriApi";
@@ -21,6 +25,9 @@ const DEFAULT_LLM_CONFIG: LlmConfig = {
   api_key: "",
   model: "deepseek-chat",
   timeout_secs: 30,
+  max_input_lines: 1200,
+  max_input_chars: 12000,
+  text_wrap_width: 120,
 };
 
 const DEFAULT_SYNC_SETTINGS: SyncSettings = {
@@ -33,6 +40,7 @@ const DEFAULT_SYNC_SETTINGS: SyncSettings = {
 
 export function AiSettingsPage() {
   const [llmConfig, setLlmConfig] = useState<LlmConfig>(DEFAULT_LLM_CONFIG);
+  const [llmProfiles, setLlmProfiles] = useState<LlmProfile[]>([]);
   const [syncSettings, setSyncSettings] = useState<SyncSettings>(DEFAULT_SYNC_SETTINGS);
   const [syncStatus, setSyncStatus] = useState<SyncRuntimeStatus | null>(null);
   const [health, setHealth] = useState<HealthReport>({});
@@ -58,11 +66,18 @@ export function AiSettingsPage() {
     if (!canOperate) {
       return;
     }
-    Promise.all([getLlmConfig(), getSyncSettings(), getSyncRuntimeStatus(), getAppHealth()])
-      .then(([savedLlm, savedSync, runtime, healthReport]) => {
+    Promise.all([
+      getLlmConfig(),
+      listLlmProfiles(),
+      getSyncSettings(),
+      getSyncRuntimeStatus(),
+      getAppHealth(),
+    ])
+      .then(([savedLlm, profiles, savedSync, runtime, healthReport]) => {
         if (savedLlm) {
           setLlmConfig(savedLlm);
         }
+        setLlmProfiles(profiles);
         setSyncSettings(savedSync);
         setSyncStatus(runtime);
         setHealth(healthReport);
@@ -97,6 +112,24 @@ export function AiSettingsPage() {
     }
   }
 
+  async function onActivateProfile(profileId: number) {
+    if (!canOperate) {
+      return;
+    }
+    setError("");
+    setTestResult("");
+    try {
+      await activateLlmProfile(profileId);
+      const [savedLlm, profiles] = await Promise.all([getLlmConfig(), listLlmProfiles()]);
+      if (savedLlm) {
+        setLlmConfig(savedLlm);
+      }
+      setLlmProfiles(profiles);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "切换 LLM 配置失败");
+    }
+  }
+
   async function onSaveSync(event: FormEvent<HTMLFormElement>) {
     event.preventDefault();
     if (!canOperate) {
@@ -243,6 +276,17 @@ export function AiSettingsPage() {
             }
             placeholder="重试次数"
           />
+          <input
+            type="number"
+            value={syncSettings.failure_disable_threshold ?? 10}
+            onChange={(event) =>
+              setSyncSettings((current) => ({
+                ...current,
+                failure_disable_threshold: Number(event.target.value || 0),
+              }))
+            }
+            placeholder="连续失败自动停用阈值（0 为不停用）"
+          />
           <button type="submit" disabled={saving}>
             保存同步配置
           </button>
@@ -251,7 +295,32 @@ export function AiSettingsPage() {
 
       <article className="page-card page-wide">
         <h2>LLM Provider（OpenAI Compatible）</h2>
+        {llmProfiles.length > 1 ? (
+          <select
+            value={llmProfiles.find((profile) => profile.active)?.id ?? llmProfiles[0].id}
+            onChange={(event) => onActivateProfile(Number(event.target.value))}
+          >
+            {llmProfiles.map((profile) => (
+              <option key={profile.id} value={profile.id}>
+                {profile.name}
+              </option>
+            ))}
+          </select>
+        ) : null}
         <form className="form-grid" onSubmit={onSaveLlm}>
+          <select
+            value={llmConfig.provider ?? "openai_compatible"}
+            onChange={(event) =>
+              setLlmConfig((current) => ({
+                ...current,
+                provider: event.target.value as LlmProvider,
+              }))
+            }
+          >
+            <option value="openai_compatible">OpenAI 兼容接口</option>
+            <option value="ollama">Ollama（本地，无需 API Key）</option>
+            <option value="anthropic">Anthropic Messages API</option>
+          </select>
           <input
             placeholder="Base URL"
             value={llmConfig.base_url}
@@ -284,6 +353,39 @@ export function AiSettingsPage() {
               }))
             }
           />
+          <input
+            placeholder="正文最大行数"
+            type="number"
+            value={llmConfig.max_input_lines}
+            onChange={(event) =>
+              setLlmConfig((current) => ({
+                ...current,
+                max_input_lines: Number(event.target.value || 1200),
+              }))
+            }
+          />
+          <input
+            placeholder="正文最大字符数"
+            type="number"
+            value={llmConfig.max_input_chars}
+            onChange={(event) =>
+              setLlmConfig((current) => ({
+                ...current,
+                max_input_chars: Number(event.target.value || 12000),
+              }))
+            }
+          />
+          <input
+            placeholder="正文换行宽度"
+            type="number"
+            value={llmConfig.text_wrap_width}
+            onChange={(event) =>
+              setLlmConfig((current) => ({
+                ...current,
+                text_wrap_width: Number(event.target.value || 120),
+              }))
+            }
+          />
           <div className="button-row">
             <button type="submit" disabled={saving}>
               保存 LLM 配置
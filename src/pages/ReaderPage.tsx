@@ -2,19 +2,32 @@ import { useEffect, useMemo, useRef, useState } from "react";
 
 import {
   deleteSource,
+  getCounts,
+  getEntry,
   getSyncRuntimeStatus,
+  isQueryCancelled,
   isTauriRuntime,
   listEntries,
   listSources,
+  listViewEntries,
   markEntryRead,
   summarizeEntry,
   syncActiveSources,
   type Entry,
+  type EntriesPage,
+  type EntryView,
   type Source,
   type SyncRuntimeStatus,
 } from "../services/tauriApi";
 import { toPlainText, toSafeHtml } from "../utils/richText";
 
+const SYSTEM_VIEWS: { view: EntryView; label: string }[] = [
+  { view: "all", label: "全部文章" },
+  { view: "today", label: "今天" },
+  { view: "unread", label: "未读" },
+  { view: "starred", label: "星标" },
+];
+
 const ENTRY_TIME_FORMATTER = new Intl.DateTimeFormat("zh-CN", {
   year: "numeric",
   month: "2-digit",
@@ -62,9 +75,15 @@ type ReaderPageProps = {
 
 export function ReaderPage({ onOpenSettings }: ReaderPageProps = {}) {
   const [sources, setSources] = useState<Source[]>([]);
+  const [unreadBySource, setUnreadBySource] = useState<Record<number, number>>({});
   const [entries, setEntries] = useState<Entry[]>([]);
+  const [nextCursor, setNextCursor] = useState<string | null>(null);
   const [selectedSourceId, setSelectedSourceId] = useState<number | undefined>(undefined);
+  const [systemView, setSystemView] = useState<EntryView>("all");
   const [activeEntryId, setActiveEntryId] = useState<number | null>(null);
+  const [activeContent, setActiveContent] = useState<{ id: number; content: string | null } | null>(
+    null,
+  );
   const [search, setSearch] = useState("");
   const [unreadOnly, setUnreadOnly] = useState(false);
   const [loading, setLoading] = useState(false);
@@ -92,34 +111,76 @@ export function ReaderPage({ onOpenSettings }: ReaderPageProps = {}) {
     [activeEntry],
   );
   const summaryHtml = useMemo(() => toSafeHtml(activeEntry?.summary), [activeEntry?.summary]);
-  const contentHtml = useMemo(() => toSafeHtml(activeEntry?.content), [activeEntry?.content]);
+  const contentHtml = useMemo(
+    () => (activeContent?.id === activeEntryId ? toSafeHtml(activeContent?.content) : ""),
+    [activeContent, activeEntryId],
+  );
+
+  useEffect(() => {
+    if (!canOperate || activeEntryId === null) {
+      return;
+    }
+    let cancelled = false;
+    void getEntry(activeEntryId)
+      .then((entry) => {
+        if (!cancelled) {
+          setActiveContent({ id: entry.id, content: entry.content ?? null });
+        }
+      })
+      .catch((err) => {
+        if (!cancelled) {
+          setError(err instanceof Error ? err.message : "加载文章内容失败");
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [activeEntryId, canOperate]);
 
   async function refreshSources() {
     if (!canOperate) {
       return;
     }
     try {
-      const response = await listSources();
+      const [response, counts] = await Promise.all([listSources(), getCounts()]);
       setSources(response);
+      setUnreadBySource(
+        Object.fromEntries(counts.sources.map((row) => [row.source_id, row.unread_count])),
+      );
     } catch (err) {
       setError(err instanceof Error ? err.message : "加载订阅源失败");
     }
   }
 
+  function fetchEntriesPage(cursor?: string | null): Promise<EntriesPage> {
+    const query = search.trim();
+    if (selectedSourceId === undefined && !query) {
+      const view = unreadOnly && systemView === "all" ? "unread" : systemView;
+      if (!unreadOnly || view === "unread") {
+        return listViewEntries(view, cursor, 300);
+      }
+    }
+    return listEntries({
+      source_id: selectedSourceId,
+      search: query || undefined,
+      unread_only: unreadOnly,
+      limit: 300,
+      query_key: cursor ? undefined : "reader-entries",
+      cursor,
+    });
+  }
+
   async function refreshEntries() {
     if (!canOperate) {
       return;
     }
     setLoading(true);
     setError("");
+    let superseded = false;
     try {
-      const items = await listEntries({
-        source_id: selectedSourceId,
-        search: search.trim() || undefined,
-        unread_only: unreadOnly,
-        limit: 300,
-      });
+      const { entries: items, next_cursor } = await fetchEntriesPage();
       setEntries(items);
+      setNextCursor(next_cursor ?? null);
       if (items.length > 0 && !activeEntryId) {
         setActiveEntryId(items[0].id);
       }
@@ -127,9 +188,28 @@ export function ReaderPage({ onOpenSettings }: ReaderPageProps = {}) {
         setActiveEntryId(null);
       }
     } catch (err) {
+      if (isQueryCancelled(err)) {
+        superseded = true;
+        return;
+      }
       setError(err instanceof Error ? err.message : "加载文章失败");
     } finally {
-      setLoading(false);
+      if (!superseded) {
+        setLoading(false);
+      }
+    }
+  }
+
+  async function loadMoreEntries() {
+    if (!canOperate || !nextCursor) {
+      return;
+    }
+    try {
+      const page = await fetchEntriesPage(nextCursor);
+      setEntries((current) => [...current, ...page.entries]);
+      setNextCursor(page.next_cursor ?? null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "加载文章失败");
     }
   }
 
@@ -153,7 +233,7 @@ export function ReaderPage({ onOpenSettings }: ReaderPageProps = {}) {
 
   useEffect(() => {
     refreshEntries();
-  }, [selectedSourceId, search, unreadOnly]);
+  }, [selectedSourceId, systemView, search, unreadOnly]);
 
   useEffect(() => {
     if (!canOperate) {
@@ -301,13 +381,23 @@ export function ReaderPage({ onOpenSettings }: ReaderPageProps = {}) {
             : "暂无同步记录"}
         </p>
         <div className="source-tree">
-          <button
-            type="button"
-            className={selectedSourceId === undefined ? "source-item source-item-active" : "source-item"}
-            onClick={() => setSelectedSourceId(undefined)}
-          >
-            全部文章
-          </button>
+          {SYSTEM_VIEWS.map(({ view, label }) => (
+            <button
+              key={view}
+              type="button"
+              className={
+                selectedSourceId === undefined && systemView === view
+                  ? "source-item source-item-active"
+                  : "source-item"
+              }
+              onClick={() => {
+                setSelectedSourceId(undefined);
+                setSystemView(view);
+              }}
+            >
+              {label}
+            </button>
+          ))}
           {sources.map((source) => {
             const iconUrl = buildSourceIconUrl(source);
             const titleText = source.title.trim() || source.feed_url;
@@ -346,7 +436,11 @@ export function ReaderPage({ onOpenSettings }: ReaderPageProps = {}) {
                     {titleText}
                   </span>
                 </span>
-                <span className="tiny-muted">{source.failure_count > 0 ? `失败 ${source.failure_count}` : ""}</span>
+                <span className="tiny-muted">
+                  {source.failure_count > 0
+                    ? `失败 ${source.failure_count}`
+                    : unreadBySource[source.id] || ""}
+                </span>
               </button>
             );
           })}
@@ -414,10 +508,16 @@ export function ReaderPage({ onOpenSettings }: ReaderPageProps = {}) {
                 <span>{entry.source_title}</span>
                 <span className="reader-item-meta">
                   {formatEntryTime(entry.published_at ?? entry.created_at)} · {entry.is_read ? "已读" : "未读"}
+                  {entry.is_updated ? " · 已更新" : ""}
                 </span>
               </button>
             );
           })}
+          {nextCursor ? (
+            <button type="button" onClick={loadMoreEntries}>
+              加载更多
+            </button>
+          ) : null}
         </div>
       </section>
 
@@ -7,6 +7,7 @@ import {
   listSources,
   previewImport,
   setSourcesActive,
+  suggestSourceMetadata,
   type ImportPreviewResponse,
   type Source,
   upsertSource,
@@ -30,13 +31,17 @@ export function SubscriptionsPage() {
   const [sources, setSources] = useState<Source[]>([]);
   const [loading, setLoading] = useState(false);
   const [saving, setSaving] = useState(false);
+  const [suggesting, setSuggesting] = useState(false);
   const [error, setError] = useState("");
   const [form, setForm] = useState<FeedFormState>(DEFAULT_FORM);
   const [selectedIds, setSelectedIds] = useState<number[]>([]);
-  const [importFormat, setImportFormat] = useState<"opml" | "xml" | "url_list" | "json">("opml");
+  const [importFormat, setImportFormat] = useState<
+    "opml" | "xml" | "url_list" | "json" | "html_links"
+  >("opml");
   const [importContent, setImportContent] = useState("");
   const [importPreviewResult, setImportPreviewResult] = useState<ImportPreviewResponse | null>(null);
   const [importMessage, setImportMessage] = useState("");
+  const [markExistingRead, setMarkExistingRead] = useState(false);
 
   const canOperate = isTauriRuntime();
 
@@ -80,6 +85,7 @@ export function SubscriptionsPage() {
         site_url: form.siteUrl.trim() || null,
         category: form.category.trim() || null,
         is_active: true,
+        mark_existing_read: markExistingRead,
       });
       setForm(DEFAULT_FORM);
       await refreshSources();
@@ -90,6 +96,26 @@ export function SubscriptionsPage() {
     }
   }
 
+  async function onSuggestMetadata() {
+    if (!canOperate || !form.feedUrl.trim()) {
+      return;
+    }
+    setSuggesting(true);
+    setError("");
+    try {
+      const suggestion = await suggestSourceMetadata(form.feedUrl.trim());
+      setForm((current) => ({
+        ...current,
+        title: suggestion.title ?? current.title,
+        category: suggestion.category ?? current.category,
+      }));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "AI 建议失败");
+    } finally {
+      setSuggesting(false);
+    }
+  }
+
   async function onDeleteSource(id: number) {
     if (!canOperate) {
       return;
@@ -144,9 +170,10 @@ export function SubscriptionsPage() {
         format: importFormat,
         content: importContent,
         is_active: true,
+        mark_existing_read: markExistingRead,
       });
       setImportMessage(
-        `导入完成：新增 ${result.imported_count}，跳过重复 ${result.duplicate_count}`,
+        `导入完成：新增 ${result.imported_count}，跳过重复 ${result.duplicate_count}，疑似重复未导入 ${result.possible_duplicate_count}`,
       );
       setImportPreviewResult(null);
       await refreshSources();
@@ -206,6 +233,21 @@ export function SubscriptionsPage() {
               setForm((current) => ({ ...current, category: event.target.value }))
             }
           />
+          <label className="checkbox-line">
+            <input
+              checked={markExistingRead}
+              onChange={(event) => setMarkExistingRead(event.target.checked)}
+              type="checkbox"
+            />
+            <span>现有文章标记为已读</span>
+          </label>
+          <button
+            disabled={suggesting || !form.feedUrl.trim()}
+            onClick={onSuggestMetadata}
+            type="button"
+          >
+            {suggesting ? "生成中..." : "AI 建议标题和分组"}
+          </button>
           <button disabled={saving} type="submit">
             {saving ? "保存中..." : "新增订阅"}
           </button>
@@ -218,19 +260,30 @@ export function SubscriptionsPage() {
           <select
             value={importFormat}
             onChange={(event) =>
-              setImportFormat(event.target.value as "opml" | "xml" | "url_list" | "json")
+              setImportFormat(
+                event.target.value as "opml" | "xml" | "url_list" | "json" | "html_links",
+              )
             }
           >
             <option value="opml">OPML/XML</option>
             <option value="url_list">URL 列表</option>
+            <option value="html_links">HTML 链接页</option>
             <option value="json">JSON</option>
           </select>
           <textarea
             rows={8}
-            placeholder="粘贴 OPML、URL 列表或 JSON 内容"
+            placeholder="粘贴 OPML、URL 列表、JSON 或包含链接的 HTML"
             value={importContent}
             onChange={(event) => setImportContent(event.target.value)}
           />
+          <label className="checkbox-line">
+            <input
+              checked={markExistingRead}
+              onChange={(event) => setMarkExistingRead(event.target.checked)}
+              type="checkbox"
+            />
+            <span>现有文章标记为已读</span>
+          </label>
           <div className="button-row">
             <button onClick={onPreviewImport} type="button">
               预览导入
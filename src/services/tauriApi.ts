@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 
 export type HealthReport = Record<string, string>;
 export type Source = {
@@ -19,13 +20,44 @@ export type UpsertSourceRequest = {
   feed_url: string;
   category?: string | null;
   is_active: boolean;
+  mark_existing_read?: boolean;
+};
+
+export type EntryCountFields = {
+  unread_count: number;
+  starred_count: number;
+  total_count: number;
+};
+
+export type EntryCounts = {
+  sources: Array<
+    EntryCountFields & { source_id: number; title: string; category?: string | null }
+  >;
+  categories: Array<EntryCountFields & { category?: string | null }>;
+};
+
+export type SourceDeletion = {
+  sources: number;
+  entries: number;
+  preserved_starred: number;
+};
+
+export type OrphanedEntry = {
+  id: number;
+  title: string;
+  link: string;
+  published_at?: string | null;
+  orphaned_from_feed_url: string;
+  resubscribed_source_id?: number | null;
 };
 
 export type ImportRequest = {
-  format: "opml" | "xml" | "url_list" | "json";
+  format: "opml" | "xml" | "url_list" | "json" | "html_links";
   content: string;
   default_category?: string;
   is_active?: boolean;
+  mark_existing_read?: boolean;
+  all_feeds_per_site?: boolean;
 };
 
 export type ImportPreviewResponse = {
@@ -47,7 +79,15 @@ export type ImportPreviewResponse = {
 
 export type ImportExecuteResponse = {
   imported_count: number;
+  updated_count: number;
   duplicate_count: number;
+  possible_duplicate_count: number;
+  possible_duplicates: Array<{
+    title: string;
+    feed_url: string;
+    site_url: string | null;
+    category: string | null;
+  }>;
 };
 
 export type Entry = {
@@ -64,6 +104,29 @@ export type Entry = {
   is_read: boolean;
   is_starred: boolean;
   created_at: string;
+  updated_at: string | null;
+  is_updated: boolean;
+};
+
+export type EntryFulltext = {
+  entry_id: number;
+  url: string;
+  title: string | null;
+  content_html: string;
+  text_length: number;
+  fetched_at: string;
+};
+
+export type OpenedEntry = {
+  entry_id: number;
+  link: string;
+  opened_externally: boolean;
+  open_count: number;
+};
+
+export type StarredEntriesPage = {
+  entries: Entry[];
+  next_offset: number | null;
 };
 
 export type ListEntriesRequest = {
@@ -71,18 +134,50 @@ export type ListEntriesRequest = {
   search?: string;
   unread_only: boolean;
   limit?: number;
+  query_key?: string;
+  cursor?: string | null;
+};
+
+export type EntryView = "all" | "today" | "unread" | "starred";
+
+export type EntriesPage = {
+  entries: Entry[];
+  next_cursor?: string | null;
+};
+
+export type CatchupItem = {
+  entry_id: number;
+  source_id: number;
+  source_title: string;
+  title: string;
+  link: string;
+  published_at?: string | null;
+  estimated_minutes: number;
+  importance: number;
+};
+
+export type CatchupSelection = {
+  minutes_available: number;
+  estimated_minutes: number;
+  entries: CatchupItem[];
+  skipped_entry_ids: number[];
 };
 
 export type SyncSourceResponse = {
   source_id: number;
   status: "updated" | "not_modified";
   upserted_entries: number;
+  inserted_entries: number;
+  updated_entries: number;
+  conflicted_entries: number;
 };
 
 export type SyncBatchResponse = {
   synced_sources: number;
   failed_sources: number;
   total_upserted_entries: number;
+  total_inserted_entries: number;
+  cancelled?: boolean;
 };
 
 export type SyncSettings = {
@@ -91,19 +186,177 @@ export type SyncSettings = {
   batch_limit: number;
   timeout_secs: number;
   retry_count: number;
+  failure_disable_threshold?: number;
+  per_host_concurrency?: number;
+  per_host_delay_ms?: number;
+};
+
+export type SourceSuggestion = {
+  title?: string | null;
+  category?: string | null;
+};
+
+export type ProbeReport = {
+  requested_url: string;
+  final_url: string | null;
+  redirects: { url: string; status: number; location: string | null }[];
+  status: number | null;
+  headers: { name: string; value: string }[];
+  content_type: string | null;
+  detected_format: string | null;
+  body_bytes: number;
+  elapsed_ms: number;
+  parsed: boolean;
+  feed_title: string | null;
+  entry_count: number;
+  entries: { title: string; link: string; published_at: string | null }[];
+  alternate_links: { url: string; title: string | null }[];
+  body_preview: string | null;
+  error: string | null;
+};
+
+export type SyncLogEntry = {
+  id: number;
+  source_id: number;
+  started_at: string;
+  finished_at: string;
+  status: string;
+  http_status: number | null;
+  entries_upserted: number;
+  error: string | null;
+};
+
+export type FailingSource = {
+  id: number;
+  title: string;
+  feed_url: string;
+  is_active: boolean;
+  failure_count: number;
+  last_http_status: number | null;
+  last_error: string | null;
+  last_failed_at: string | null;
+  auto_disabled_at: string | null;
+  auto_disabled_reason: string | null;
+};
+
+export type ProblemClass = "auth_required" | "gone" | "parse_errors" | "repeated_timeouts" | "other";
+
+export type ProblemSource = {
+  id: number;
+  title: string;
+  feed_url: string;
+  site_url: string | null;
+  is_active: boolean;
+  failure_count: number;
+  last_http_status: number | null;
+  last_sync_status: string | null;
+  last_error: string | null;
+  gone_count: number;
+  parked_domain: boolean;
+  replacement_feed_url: string | null;
+  auto_disabled_reason: string | null;
+  recent_timeouts: number;
+};
+
+export type ProblemGroup = {
+  class: ProblemClass;
+  remediation: string;
+  sources: ProblemSource[];
+};
+
+export type NotificationRule = {
+  id: number;
+  name: string;
+  keywords: string[];
+  source_id: number | null;
+  category: string | null;
+  is_enabled: boolean;
+};
+
+export type ReadMarkMode = "on_open" | "on_scroll" | "after_days" | "never";
+
+export type LlmCacheImport = {
+  imported: number;
+  skipped: number;
+};
+
+export type SanitizerAllowlist = {
+  tags: string[];
+  attributes: string[];
+  url_schemes: string[];
+};
+
+export type ReadPolicySettings = {
+  mode: ReadMarkMode;
+  after_days: number;
+};
+
+export type LlmBudgetSettings = {
+  translations_per_cycle: number;
+  summaries_per_cycle: number;
+};
+
+export type LlmBacklogStatus = {
+  untranslated_titles: number;
+  queued_summaries: number;
+  budget: LlmBudgetSettings;
+};
+
+export type TitleBackfillProgress = {
+  source_id: number;
+  translated: number;
+  remaining: number;
+  total: number;
+  done: boolean;
 };
 
 export type SyncRuntimeStatus = {
   running: boolean;
+  paused: boolean;
   last_report: SyncBatchResponse | null;
   last_error: string | null;
 };
 
+export type LlmProvider = "openai_compatible" | "ollama" | "anthropic";
+
 export type LlmConfig = {
+  provider?: LlmProvider;
   base_url: string;
   api_key: string;
   model: string;
   timeout_secs: number;
+  max_input_lines: number;
+  max_input_chars: number;
+  text_wrap_width: number;
+  generation?: {
+    summary: LlmGenerationParams;
+    translation: LlmGenerationParams;
+  };
+  endpoint_path?: string | null;
+  extra_headers?: Record<string, string>;
+  query_params?: Record<string, string>;
+};
+
+export type LlmTask = "summary" | "translation";
+
+export type GlossaryTerm = {
+  id: number;
+  term: string;
+  translation: string;
+};
+
+export type LlmProfile = {
+  id: number;
+  name: string;
+  config: LlmConfig;
+  tasks: LlmTask[];
+  active?: boolean;
+};
+
+export type LlmGenerationParams = {
+  temperature: number;
+  max_tokens: number | null;
+  top_p: number | null;
 };
 
 declare global {
@@ -128,14 +381,40 @@ export async function upsertSource(payload: UpsertSourceRequest): Promise<Source
   return invoke<Source>("upsert_source", { request: payload });
 }
 
-export async function deleteSource(id: number): Promise<number> {
-  return invoke<number>("delete_source", { id });
+export async function deleteSource(id: number): Promise<SourceDeletion> {
+  return invoke<SourceDeletion>("delete_source", { id });
+}
+
+export type DemoSeedReport = {
+  sources: number;
+  entries: number;
+  translated: number;
+};
+
+export async function seedDemoData(): Promise<DemoSeedReport> {
+  return invoke<DemoSeedReport>("seed_demo_data");
+}
+
+export async function listOrphanedEntries(): Promise<OrphanedEntry[]> {
+  return invoke<OrphanedEntry[]>("list_orphaned_entries");
+}
+
+export async function rehomeOrphanedEntries(sourceId: number): Promise<number> {
+  return invoke<number>("rehome_orphaned_entries", { sourceId });
 }
 
 export async function setSourcesActive(sourceIds: number[], isActive: boolean): Promise<number> {
   return invoke<number>("set_sources_active", { sourceIds, isActive });
 }
 
+export async function reactivateSource(sourceId: number): Promise<number> {
+  return invoke<number>("reactivate_source", { sourceId });
+}
+
+export async function resetSourceFailures(sourceIds: number[]): Promise<number> {
+  return invoke<number>("reset_source_failures", { sourceIds });
+}
+
 export async function previewImport(payload: ImportRequest): Promise<ImportPreviewResponse> {
   return invoke<ImportPreviewResponse>("preview_import", { request: payload });
 }
@@ -144,14 +423,103 @@ export async function importSources(payload: ImportRequest): Promise<ImportExecu
   return invoke<ImportExecuteResponse>("import_sources", { request: payload });
 }
 
-export async function listEntries(payload: ListEntriesRequest): Promise<Entry[]> {
-  return invoke<Entry[]>("list_entries", { request: payload });
+export async function listEntries(payload: ListEntriesRequest): Promise<EntriesPage> {
+  return invoke<EntriesPage>("list_entries", { request: payload });
+}
+
+export async function listViewEntries(
+  view: EntryView,
+  cursor?: string | null,
+  limit?: number,
+): Promise<EntriesPage> {
+  return invoke<EntriesPage>("list_view_entries", { view, cursor, limit });
+}
+
+export async function getSearchPinyin(): Promise<boolean> {
+  return invoke<boolean>("get_search_pinyin");
+}
+
+export async function setSearchPinyin(enabled: boolean): Promise<boolean> {
+  return invoke<boolean>("set_search_pinyin", { enabled });
+}
+
+export async function getCatchupSelection(minutesAvailable: number): Promise<CatchupSelection> {
+  return invoke<CatchupSelection>("get_catchup_selection", { minutesAvailable });
+}
+
+export async function skipEntries(entryIds: number[]): Promise<number> {
+  return invoke<number>("skip_entries", { entryIds });
+}
+
+export async function resetSkippedEntries(): Promise<number> {
+  return invoke<number>("reset_skipped_entries");
+}
+
+export async function getEntry(entryId: number): Promise<Entry> {
+  return invoke<Entry>("get_entry", { entryId });
+}
+
+export async function fetchFullContent(
+  entryId: number,
+  refresh?: boolean,
+): Promise<EntryFulltext> {
+  return invoke<EntryFulltext>("fetch_full_content", { entryId, refresh });
+}
+
+export async function openEntryExternal(entryId: number): Promise<OpenedEntry> {
+  return invoke<OpenedEntry>("open_entry_external", { entryId });
+}
+
+export async function setEntryStarred(entryId: number, isStarred: boolean): Promise<number> {
+  return invoke<number>("set_entry_starred", { entryId, isStarred });
+}
+
+export async function getCounts(): Promise<EntryCounts> {
+  return invoke<EntryCounts>("get_counts");
+}
+
+export async function listStarredEntries(
+  limit?: number,
+  offset?: number,
+): Promise<StarredEntriesPage> {
+  return invoke<StarredEntriesPage>("list_starred_entries", { limit, offset });
+}
+
+export async function cancelQuery(queryKey: string): Promise<boolean> {
+  return invoke<boolean>("cancel_query", { queryKey });
+}
+
+export function isQueryCancelled(error: unknown): boolean {
+  return String(error) === "query cancelled";
 }
 
 export async function markEntryRead(entryId: number, isRead: boolean): Promise<number> {
   return invoke<number>("mark_entry_read", { entryId, isRead });
 }
 
+export async function getSourceSyncHistory(
+  sourceId: number,
+  limit?: number,
+): Promise<SyncLogEntry[]> {
+  return invoke<SyncLogEntry[]>("get_source_sync_history", { sourceId, limit });
+}
+
+export async function listProblemSources(): Promise<ProblemGroup[]> {
+  return invoke<ProblemGroup[]>("list_problem_sources");
+}
+
+export async function listFailingSources(limit?: number): Promise<FailingSource[]> {
+  return invoke<FailingSource[]>("list_failing_sources", { limit });
+}
+
+export async function probeFeed(url: string): Promise<ProbeReport> {
+  return invoke<ProbeReport>("probe_feed", { url });
+}
+
+export async function suggestSourceMetadata(url: string): Promise<SourceSuggestion> {
+  return invoke<SourceSuggestion>("suggest_source_metadata", { url });
+}
+
 export async function syncSource(sourceId: number): Promise<SyncSourceResponse> {
   return invoke<SyncSourceResponse>("sync_source", { sourceId });
 }
@@ -164,6 +532,18 @@ export async function getSyncRuntimeStatus(): Promise<SyncRuntimeStatus> {
   return invoke<SyncRuntimeStatus>("get_sync_runtime_status");
 }
 
+export async function cancelSync(): Promise<SyncRuntimeStatus> {
+  return invoke<SyncRuntimeStatus>("cancel_sync");
+}
+
+export async function pauseBackgroundSync(): Promise<SyncRuntimeStatus> {
+  return invoke<SyncRuntimeStatus>("pause_background_sync");
+}
+
+export async function resumeBackgroundSync(): Promise<SyncRuntimeStatus> {
+  return invoke<SyncRuntimeStatus>("resume_background_sync");
+}
+
 export async function getSyncSettings(): Promise<SyncSettings> {
   return invoke<SyncSettings>("get_sync_settings");
 }
@@ -172,6 +552,87 @@ export async function saveSyncSettings(settings: SyncSettings): Promise<SyncSett
   return invoke<SyncSettings>("save_sync_settings", { settings });
 }
 
+export async function getFirstSyncMaxAgeDays(): Promise<number> {
+  return invoke<number>("get_first_sync_max_age_days");
+}
+
+export async function setFirstSyncMaxAgeDays(days: number): Promise<number> {
+  return invoke<number>("set_first_sync_max_age_days", { days });
+}
+
+export async function getSanitizerAllowlist(): Promise<SanitizerAllowlist> {
+  return invoke<SanitizerAllowlist>("get_sanitizer_allowlist");
+}
+
+export async function saveSanitizerAllowlist(
+  allowlist: SanitizerAllowlist,
+): Promise<SanitizerAllowlist> {
+  return invoke<SanitizerAllowlist>("save_sanitizer_allowlist", { allowlist });
+}
+
+export async function sanitizeStoredEntries(): Promise<number> {
+  return invoke<number>("sanitize_stored_entries");
+}
+
+export async function exportLlmCache(path: string): Promise<number> {
+  return invoke<number>("export_llm_cache", { path });
+}
+
+export async function importLlmCache(
+  path: string,
+  modelMap?: Record<string, string>,
+  overwrite?: boolean,
+): Promise<LlmCacheImport> {
+  return invoke<LlmCacheImport>("import_llm_cache", { path, modelMap, overwrite });
+}
+
+export async function listNotificationRules(): Promise<NotificationRule[]> {
+  return invoke<NotificationRule[]>("list_notification_rules");
+}
+
+export async function createNotificationRule(rule: NotificationRule): Promise<NotificationRule> {
+  return invoke<NotificationRule>("create_notification_rule", { rule });
+}
+
+export async function updateNotificationRule(rule: NotificationRule): Promise<NotificationRule> {
+  return invoke<NotificationRule>("update_notification_rule", { rule });
+}
+
+export async function deleteNotificationRule(ruleId: number): Promise<number> {
+  return invoke<number>("delete_notification_rule", { ruleId });
+}
+
+export async function getReadPolicy(): Promise<ReadPolicySettings> {
+  return invoke<ReadPolicySettings>("get_read_policy");
+}
+
+export async function saveReadPolicy(settings: ReadPolicySettings): Promise<ReadPolicySettings> {
+  return invoke<ReadPolicySettings>("save_read_policy", { settings });
+}
+
+export async function reportEntriesScrolledPast(
+  lastSeenEntryId: number,
+  filter?: { source_id?: number; search?: string },
+): Promise<number> {
+  return invoke<number>("report_entries_scrolled_past", { filter, lastSeenEntryId });
+}
+
+export async function getLlmBudget(): Promise<LlmBudgetSettings> {
+  return invoke<LlmBudgetSettings>("get_llm_budget");
+}
+
+export async function saveLlmBudget(settings: LlmBudgetSettings): Promise<LlmBudgetSettings> {
+  return invoke<LlmBudgetSettings>("save_llm_budget", { settings });
+}
+
+export async function getLlmBacklog(): Promise<LlmBacklogStatus> {
+  return invoke<LlmBacklogStatus>("get_llm_backlog");
+}
+
+export async function translateSourceTitles(sourceId: number): Promise<TitleBackfillProgress> {
+  return invoke<TitleBackfillProgress>("translate_source_titles", { sourceId });
+}
+
 export async function getLlmConfig(): Promise<LlmConfig | null> {
   return invoke<LlmConfig | null>("get_llm_config");
 }
@@ -180,6 +641,38 @@ export async function saveLlmConfig(config: LlmConfig): Promise<void> {
   return invoke<void>("save_llm_config", { config });
 }
 
+export async function listLlmProfiles(): Promise<LlmProfile[]> {
+  return invoke<LlmProfile[]>("list_llm_profiles");
+}
+
+export async function saveLlmProfile(profile: LlmProfile): Promise<LlmProfile> {
+  return invoke<LlmProfile>("save_llm_profile", { profile });
+}
+
+export async function activateLlmProfile(profileId: number): Promise<void> {
+  return invoke<void>("activate_llm_profile", { profileId });
+}
+
+export async function deleteLlmProfile(profileId: number): Promise<number> {
+  return invoke<number>("delete_llm_profile", { profileId });
+}
+
+export async function listGlossaryTerms(): Promise<GlossaryTerm[]> {
+  return invoke<GlossaryTerm[]>("list_glossary_terms");
+}
+
+export async function saveGlossaryTerm(term: GlossaryTerm): Promise<GlossaryTerm> {
+  return invoke<GlossaryTerm>("save_glossary_term", { term });
+}
+
+export async function deleteGlossaryTerm(termId: number): Promise<number> {
+  return invoke<number>("delete_glossary_term", { termId });
+}
+
+export async function setTaskProfile(task: LlmTask, profileId: number | null): Promise<void> {
+  return invoke<void>("set_task_profile", { task, profileId });
+}
+
 export async function testLlmConnection(config?: LlmConfig): Promise<string> {
   return invoke<string>("test_llm_connection", { config: config ?? null });
 }
@@ -187,3 +680,26 @@ export async function testLlmConnection(config?: LlmConfig): Promise<string> {
 export async function summarizeEntry(entryId: number): Promise<string> {
   return invoke<string>("summarize_entry", { entryId });
 }
+
+export type SummaryChunk = {
+  entry_id: number;
+  delta: string;
+  done: boolean;
+  error?: string;
+};
+
+export async function summarizeEntryStream(
+  entryId: number,
+  onChunk: (chunk: SummaryChunk) => void,
+): Promise<string> {
+  const unlisten = await listen<SummaryChunk>("summary-chunk", (event) => {
+    if (event.payload.entry_id === entryId) {
+      onChunk(event.payload);
+    }
+  });
+  try {
+    return await invoke<string>("summarize_entry_stream", { entryId });
+  } finally {
+    unlisten();
+  }
+}